@@ -20,19 +20,41 @@ pub fn criterion_benchmark(c: &mut Criterion) {
         let mut buf = String::new();
 
         // Read in as much as we can at once
-        let bytes_read = reader.read_to_string(&mut buf).unwrap(); // TODO: There is some edge
-                                                                   // case behaviour here when
-                                                                   // a file is too long to
-                                                                   // store in memory. This is
-                                                                   // currently unhandled.
+        let _bytes_read = reader.read_to_string(&mut buf).unwrap(); // TODO: There is some edge
+                                                                    // case behaviour here when
+                                                                    // a file is too long to
+                                                                    // store in memory. This is
+                                                                    // currently unhandled.
 
         b.iter(|| {
-            let tokens: Vec<Token<'_>> = lexer.tokenize(&buf);
+            let tokens: Vec<Token<'_>> = lexer.tokenize(&buf).unwrap();
             black_box(tokens);
             buf.clear();
         });
     });
 
+    // Single-threaded throughput over inputs of different sizes is measured by `lexer_bench`
+
+    #[cfg(feature = "parallel")]
+    {
+        let mut group = c.benchmark_group("parallel lexing throughput");
+        let mut buf = std::fs::read_to_string("./examples/example_1000_lines.src").unwrap();
+        buf = buf.repeat((50_000_000 / buf.len()).max(1));
+
+        group.throughput(criterion::Throughput::Bytes(buf.len() as u64));
+        group.sample_size(10);
+        group.bench_function("50MB", |b| {
+            let mut lexer = AsciiLexer::new();
+            let threads = std::thread::available_parallelism().map_or(4, |n| n.get());
+
+            b.iter(|| {
+                let tokens = lexer.tokenize_parallel(black_box(&buf), threads).unwrap();
+                black_box(tokens);
+            });
+        });
+        group.finish();
+    }
+
     c.bench_function("parsing", |b| {
         const READER_CAPACITY: usize = 100_000_000;
         let file = File::open("./examples/infix.src").unwrap();
@@ -42,13 +64,13 @@ pub fn criterion_benchmark(c: &mut Criterion) {
         let mut buf = String::new();
 
         // Read in as much as we can at once
-        let bytes_read = reader.read_to_string(&mut buf).unwrap(); // TODO: There is some edge
-                                                                   // case behaviour here when
-                                                                   // a file is too long to
-                                                                   // store in memory. This is
-                                                                   // currently unhandled.
+        let _bytes_read = reader.read_to_string(&mut buf).unwrap(); // TODO: There is some edge
+                                                                    // case behaviour here when
+                                                                    // a file is too long to
+                                                                    // store in memory. This is
+                                                                    // currently unhandled.
 
-        let tokens: Vec<Token<'_>> = lexer.tokenize(&buf);
+        let tokens: Vec<Token<'_>> = lexer.tokenize(&buf).unwrap();
 
         b.iter(|| {
             let mut parser = Parser::new(0, &tokens);
@@ -56,7 +78,7 @@ pub fn criterion_benchmark(c: &mut Criterion) {
             let source = buf.clone();
             let parsed = parse(&mut parser).map_err(|report| report.with_source_code(source));
 
-            black_box(parsed);
+            let _ = black_box(parsed);
         });
     });
 }
@@ -0,0 +1,106 @@
+//! Lexer benchmarks over inputs from a few lines up to a whole module, reported in MB/s.
+//!
+//! Changes within 10% are treated as noise. To compare against an earlier run, save a baseline
+//! with `cargo bench --bench lexer_bench -- --save-baseline main`, then check a change against it
+//! with `cargo bench --bench lexer_bench -- --baseline main`.
+
+use std::time::Duration;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use llvm_compiler::{lexer::AsciiLexer, token::Token};
+
+const EXAMPLE: &str = "./examples/example_1000_lines.src";
+
+/// The start of `text`, cut at the last line ending within `len` bytes.
+fn lines_up_to(text: &str, len: usize) -> &str {
+    let end = text[..len.min(text.len())]
+        .rfind('\n')
+        .map_or(0, |idx| idx + 1);
+    &text[..end]
+}
+
+/// Benchmark lexing each of `inputs`, named by their size.
+fn bench_lexing(c: &mut Criterion, group: &str, inputs: &[(&str, &str)]) {
+    let mut group = c.benchmark_group(group);
+
+    for (name, input) in inputs {
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_function(*name, |b| {
+            let mut lexer = AsciiLexer::new();
+
+            b.iter(|| {
+                let tokens = lexer.tokenize(black_box(input)).unwrap();
+                black_box(tokens);
+            });
+        });
+    }
+
+    group.finish();
+}
+
+pub fn tokenizer_benchmark(c: &mut Criterion) {
+    let example = std::fs::read_to_string(EXAMPLE).unwrap();
+    let large = example.repeat((1_000_000 / example.len()).max(1));
+
+    let short = "func add(a: i32, b: i32) -> i32 {\n    let sum = a + b;\n    return sum * 2;\n}\n\
+                 func main() { add(1, 2); }\n";
+    let short = &short[..100];
+
+    bench_lexing(
+        c,
+        "lexing",
+        &[
+            ("100B", short),
+            ("10KB", lines_up_to(&example, 10_000)),
+            ("1MB", &large),
+        ],
+    );
+
+    // Every word is looked up as a keyword, so this is the worst case for that lookup
+    let keywords = "while ".repeat(100_000);
+    bench_lexing(c, "lexing keywords", &[("100K", &keywords)]);
+
+    let mut group = c.benchmark_group("packing");
+    let tokens: Vec<Token> = AsciiLexer::new()
+        .tokenize(&large)
+        .unwrap()
+        .into_iter()
+        .take(10_000)
+        .collect();
+    let packed: Vec<u64> = tokens
+        .iter()
+        .map(|token| u64::try_from(*token).unwrap())
+        .collect();
+
+    group.throughput(Throughput::Bytes(
+        (packed.len() * std::mem::size_of::<u64>()) as u64,
+    ));
+    group.bench_function("pack 10K", |b| {
+        b.iter(|| {
+            let packed: Vec<u64> = black_box(&tokens)
+                .iter()
+                .map(|token| u64::try_from(*token).unwrap())
+                .collect();
+            black_box(packed);
+        });
+    });
+    group.bench_function("unpack 10K", |b| {
+        b.iter(|| {
+            let tokens: Vec<Token> = black_box(&packed)
+                .iter()
+                .map(|packed| Token::from_packed(*packed, &large).unwrap())
+                .collect();
+            black_box(tokens);
+        });
+    });
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default()
+        .noise_threshold(0.10)
+        .measurement_time(Duration::from_secs(5));
+    targets = tokenizer_benchmark
+}
+criterion_main!(benches);
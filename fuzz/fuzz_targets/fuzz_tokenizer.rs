@@ -0,0 +1,20 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use llvm_compiler::lexer::AsciiLexer;
+
+fuzz_target!(|data: &[u8]| {
+    // The lexer only takes valid UTF-8, so mangle anything else into it
+    let source = String::from_utf8_lossy(data);
+
+    let mut lexer = AsciiLexer::new();
+    let tokens = lexer.tokenize(&source).expect("the lexer only fails in strict mode");
+    let errors = lexer.metrics().errors_encountered;
+
+    if !source.is_empty() {
+        assert!(tokens.len() + errors > 0, "nothing was emitted for {source:?}");
+    }
+
+    // Failing is fine in strict mode, panicking isn't
+    let _ = AsciiLexer::new().strict_mode().tokenize(&source);
+});
@@ -0,0 +1,699 @@
+pub mod mutator;
+pub mod visitor;
+pub mod walk;
+
+use std::fmt;
+use std::path::PathBuf;
+
+use crate::builtins::BuiltinFn;
+use crate::span::Span;
+use crate::token::Token;
+
+/// A single compilation unit and the top-level items declared in it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Module {
+    pub path: PathBuf,
+    pub items: Vec<Item>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Item {
+    FnDecl(FnDecl),
+    StructDecl(StructDecl),
+    EnumDecl(EnumDecl),
+    TraitDecl(TraitDecl),
+    ImplBlock(ImplBlock),
+    UseDecl(UseDecl),
+    GlobalDecl(GlobalDecl),
+    TypeAlias(TypeAlias),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FnDecl {
+    pub visibility: Visibility,
+    /// Set by `extern "ABI"`, for a function defined elsewhere. Its body is left empty.
+    pub abi: Option<String>,
+    pub is_unsafe: bool,
+    pub name: String,
+    pub generic_params: GenericParams,
+    pub params: Vec<Param>,
+    pub ret: Option<TypeExpr>,
+    pub body: Block,
+    pub span: Span,
+}
+
+impl FnDecl {
+    /// Whether the function is only declared here, by `extern`.
+    pub fn is_extern(&self) -> bool {
+        self.abi.is_some()
+    }
+}
+
+/// Whether an item can be used from outside its module, which takes `pub`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Visibility {
+    Public,
+    #[default]
+    Private,
+}
+
+/// A function declaration without its body, as required by a trait.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FnSig {
+    pub name: String,
+    pub generic_params: GenericParams,
+    pub params: Vec<Param>,
+    pub ret: Option<TypeExpr>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Param {
+    pub name: String,
+    pub ty: TypeExpr,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructDecl {
+    pub name: String,
+    pub generic_params: GenericParams,
+    pub fields: Vec<FieldDecl>,
+    pub span: Span,
+}
+
+/// The `<T: Bound, U>` declared by a generic function or struct, empty when it isn't generic.
+/// The arguments given to a generic type are the `generics` of [`TypeExpr::Named`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GenericParams(pub Vec<GenericParam>);
+
+impl GenericParams {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, GenericParam> {
+        self.0.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, GenericParam> {
+        self.0.iter_mut()
+    }
+}
+
+/// `T: A + B`, where `bounds` are the traits `T` must implement.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenericParam {
+    pub name: String,
+    pub bounds: Vec<TypeExpr>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldDecl {
+    pub name: String,
+    pub ty: TypeExpr,
+    pub span: Span,
+}
+
+/// `enum Name<T> { A, B(T), C { field: T } }`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumDecl {
+    pub name: String,
+    pub generic_params: GenericParams,
+    pub variants: Vec<EnumVariant>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumVariant {
+    pub name: String,
+    pub fields: EnumVariantFields,
+    pub span: Span,
+}
+
+/// The data an enum variant carries, if any.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EnumVariantFields {
+    Unit,
+    Tuple(Vec<TypeExpr>),
+    Struct(Vec<FieldDecl>),
+}
+
+/// `trait Name<T> { ... }`, declaring the functions its implementations must provide.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraitDecl {
+    pub name: String,
+    pub generic_params: GenericParams,
+    pub items: Vec<TraitItem>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TraitItem {
+    /// `func name(...) -> T;`, which every implementation must define.
+    FnSignature(FnSig),
+    /// A function with a body, used by implementations that don't define their own.
+    DefaultFn(FnDecl),
+}
+
+/// `impl<T> Trait for Type { ... }`, or `impl Type { ... }` for an inherent impl, which has no
+/// `trait_path`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImplBlock {
+    pub generic_params: GenericParams,
+    pub trait_path: Option<Vec<String>>,
+    /// The arguments given to a generic trait, like the `T` of `impl Show<T> for ...`.
+    pub trait_generics: Vec<TypeExpr>,
+    pub for_type: TypeExpr,
+    pub items: Vec<ImplItem>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImplItem {
+    Fn(FnDecl),
+}
+
+/// `use a::b::c as d`. A glob import ends its path with `"*"`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UseDecl {
+    pub path: Vec<String>,
+    pub alias: Option<String>,
+    pub span: Span,
+}
+
+/// `type Name<T> = Target;`, another name for `target`. Type checking replaces every use of the
+/// name with `target`, so aliases never reach code generation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeAlias {
+    pub name: String,
+    pub generic_params: GenericParams,
+    pub target: TypeExpr,
+    pub span: Span,
+}
+
+/// `let name: Type = value;`, or `const name: Type = value;`, outside of any function. The value
+/// must be a constant, and a `let` without one starts out as zero.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GlobalDecl {
+    pub name: String,
+    pub ty: TypeExpr,
+    pub init: Option<ExprNode>,
+    pub mutable: bool,
+    pub is_const: bool,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TypeExpr {
+    /// A named type, with any generic arguments given to it.
+    Named {
+        name: String,
+        generics: Vec<TypeExpr>,
+    },
+    /// `*T` or `*mut T`.
+    Pointer {
+        inner: Box<TypeExpr>,
+        mutable: bool,
+    },
+    /// `[T; N]`, or `[T]` when the size isn't known.
+    Array {
+        element: Box<TypeExpr>,
+        size: Option<usize>,
+    },
+    /// `func(A, B) -> C`. Without a return type, `C` is `void`.
+    FnType {
+        params: Vec<TypeExpr>,
+        ret: Box<TypeExpr>,
+    },
+    Tuple(Vec<TypeExpr>),
+    /// `!`, the type of expressions that never finish.
+    Never,
+}
+
+impl TypeExpr {
+    /// A named type without generic arguments, like `i32` or `Point`.
+    pub fn named(name: impl Into<String>) -> Self {
+        TypeExpr::Named {
+            name: name.into(),
+            generics: vec![],
+        }
+    }
+
+    pub fn void() -> Self {
+        TypeExpr::named("void")
+    }
+
+    pub fn is_void(&self) -> bool {
+        matches!(self, TypeExpr::Named { name, .. } if name == "void")
+    }
+
+    pub fn is_float(&self) -> bool {
+        matches!(self, TypeExpr::Named { name, .. } if name == "f32" || name == "f64")
+    }
+
+    pub fn is_integer(&self) -> bool {
+        matches!(
+            self,
+            TypeExpr::Named { name, .. } if matches!(
+                name.as_str(),
+                "i8" | "i16" | "i32" | "i64" | "i128" | "u8" | "u16" | "u32" | "u64"
+            )
+        )
+    }
+
+    /// This type with each of the generic `params` replaced by the argument at the same position
+    /// in `args`. Parameters without an argument are left as they are.
+    pub fn substitute(&self, params: &GenericParams, args: &[TypeExpr]) -> TypeExpr {
+        let substitute = |ty: &TypeExpr| ty.substitute(params, args);
+
+        match self {
+            TypeExpr::Named { name, generics } if generics.is_empty() => params
+                .iter()
+                .zip(args)
+                .find(|(param, _)| param.name == *name)
+                .map_or_else(|| self.clone(), |(_, arg)| arg.clone()),
+            TypeExpr::Named { name, generics } => TypeExpr::Named {
+                name: name.clone(),
+                generics: generics.iter().map(substitute).collect(),
+            },
+            TypeExpr::Pointer { inner, mutable } => TypeExpr::Pointer {
+                inner: Box::new(substitute(inner)),
+                mutable: *mutable,
+            },
+            TypeExpr::Array { element, size } => TypeExpr::Array {
+                element: Box::new(substitute(element)),
+                size: *size,
+            },
+            TypeExpr::FnType {
+                params: fn_params,
+                ret,
+            } => TypeExpr::FnType {
+                params: fn_params.iter().map(substitute).collect(),
+                ret: Box::new(substitute(ret)),
+            },
+            TypeExpr::Tuple(types) => TypeExpr::Tuple(types.iter().map(substitute).collect()),
+            TypeExpr::Never => TypeExpr::Never,
+        }
+    }
+}
+
+impl fmt::Display for TypeExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let list = |f: &mut fmt::Formatter<'_>, types: &[TypeExpr]| {
+            for (idx, ty) in types.iter().enumerate() {
+                if idx > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{ty}")?;
+            }
+            Ok(())
+        };
+
+        match self {
+            TypeExpr::Named { name, generics } if generics.is_empty() => write!(f, "{name}"),
+            TypeExpr::Named { name, generics } => {
+                write!(f, "{name}<")?;
+                list(f, generics)?;
+                write!(f, ">")
+            }
+            TypeExpr::Pointer { inner, mutable } if *mutable => write!(f, "*mut {inner}"),
+            TypeExpr::Pointer { inner, .. } => write!(f, "*{inner}"),
+            TypeExpr::Array {
+                element,
+                size: Some(size),
+            } => write!(f, "[{element}; {size}]"),
+            TypeExpr::Array {
+                element,
+                size: None,
+            } => write!(f, "[{element}]"),
+            TypeExpr::FnType { params, ret } => {
+                write!(f, "func(")?;
+                list(f, params)?;
+                write!(f, ") -> {ret}")
+            }
+            TypeExpr::Tuple(types) => {
+                write!(f, "(")?;
+                list(f, types)?;
+                write!(f, ")")
+            }
+            TypeExpr::Never => write!(f, "!"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExprNode {
+    Literal {
+        value: LiteralValue,
+        span: Span,
+    },
+    Ident {
+        name: String,
+        span: Span,
+    },
+    BinOp {
+        op: BinOpKind,
+        lhs: Box<ExprNode>,
+        rhs: Box<ExprNode>,
+        span: Span,
+    },
+    UnaryOp {
+        op: UnOpKind,
+        operand: Box<ExprNode>,
+        span: Span,
+    },
+    Call {
+        callee: Box<ExprNode>,
+        args: Vec<ExprNode>,
+        span: Span,
+    },
+    /// A call to a function built into the compiler, like `sizeof(i32)` or `memset(p, 0, n)`.
+    Builtin {
+        builtin: BuiltinFn,
+        args: Vec<ExprNode>,
+        span: Span,
+    },
+    Index {
+        base: Box<ExprNode>,
+        index: Box<ExprNode>,
+        span: Span,
+    },
+    Field {
+        base: Box<ExprNode>,
+        name: String,
+        span: Span,
+    },
+    If(IfExpr),
+    Match(MatchExpr),
+    Closure(ClosureExpr),
+    Block(Block),
+}
+
+impl ExprNode {
+    pub fn span(&self) -> Span {
+        match self {
+            ExprNode::Literal { span, .. }
+            | ExprNode::Ident { span, .. }
+            | ExprNode::BinOp { span, .. }
+            | ExprNode::UnaryOp { span, .. }
+            | ExprNode::Call { span, .. }
+            | ExprNode::Builtin { span, .. }
+            | ExprNode::Index { span, .. }
+            | ExprNode::Field { span, .. } => *span,
+            ExprNode::If(if_expr) => if_expr.span,
+            ExprNode::Match(match_expr) => match_expr.span,
+            ExprNode::Closure(closure) => closure.span,
+            ExprNode::Block(block) => block.span,
+        }
+    }
+
+    /// Expressions ending in a block don't need a `;` to be used as a statement.
+    pub fn is_block_like(&self) -> bool {
+        matches!(
+            self,
+            ExprNode::If(_) | ExprNode::Match(_) | ExprNode::Block(_)
+        )
+    }
+
+    /// Whether this names a place that can be assigned to.
+    pub fn is_lvalue(&self) -> bool {
+        matches!(
+            self,
+            ExprNode::Ident { .. } | ExprNode::Field { .. } | ExprNode::Index { .. }
+        )
+    }
+}
+
+/// `|a, b: i32| body`, or `|| body` without parameters. Parameter types can be left off where
+/// they can be inferred from how the closure is used.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClosureExpr {
+    pub params: Vec<ClosureParam>,
+    pub body: ClosureBody,
+    pub capture_mode: CaptureMode,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClosureParam {
+    pub name: String,
+    pub ty: Option<TypeExpr>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClosureBody {
+    Expr(Box<ExprNode>),
+    Block(Block),
+}
+
+/// How a closure holds on to the variables it uses from its environment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureMode {
+    /// `move |x| ...` takes its captures by value.
+    Move,
+    /// Captures by reference.
+    Borrow,
+    /// Not written in the source, so left to be decided from how captures are used.
+    Infer,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IfExpr {
+    pub cond: Box<ExprNode>,
+    pub then_block: Block,
+    pub else_block: Option<Box<IfOrBlock>>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum IfOrBlock {
+    If(IfExpr),
+    Block(Block),
+}
+
+/// `match scrutinee { pattern => body, ... }`, running the body of the first arm that matches.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchExpr {
+    pub scrutinee: Box<ExprNode>,
+    pub arms: Vec<MatchArm>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchArm {
+    pub pattern: Pattern,
+    /// Set by writing `pattern if guard => body`, so the arm only matches when `guard` holds.
+    pub guard: Option<ExprNode>,
+    pub body: ExprNode,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Pattern {
+    Literal(LiteralValue),
+    /// Matches anything, binding it to the name.
+    Ident(String),
+    /// `_`, which matches anything without binding it.
+    Wildcard,
+    /// `a | b`, which matches if either side does.
+    Or(Box<Pattern>, Box<Pattern>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Block {
+    /// Set by writing `'label: { ... }`.
+    pub label: Option<String>,
+    pub stmts: Vec<StmtNode>,
+    /// The trailing expression without a `;`, which gives the block its value.
+    pub final_expr: Option<Box<ExprNode>>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StmtNode {
+    Expr(ExprNode),
+    While(WhileStmt),
+    For(ForStmt),
+    ForIn(ForInStmt),
+    Let(LetStmt),
+    Assign(AssignStmt),
+    Return(ReturnStmt),
+    Break(BreakStmt),
+    Continue(ContinueStmt),
+}
+
+impl StmtNode {
+    pub fn span(&self) -> Span {
+        match self {
+            StmtNode::Expr(expr) => expr.span(),
+            StmtNode::While(stmt) => stmt.span,
+            StmtNode::For(stmt) => stmt.span,
+            StmtNode::ForIn(stmt) => stmt.span,
+            StmtNode::Let(stmt) => stmt.span,
+            StmtNode::Assign(stmt) => stmt.span,
+            StmtNode::Return(stmt) => stmt.span,
+            StmtNode::Break(stmt) => stmt.span,
+            StmtNode::Continue(stmt) => stmt.span,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReturnStmt {
+    pub value: Option<ExprNode>,
+    pub span: Span,
+}
+
+/// `break`, optionally naming the loop or block to leave and the value to leave it with.
+///
+/// The parser accepts `break` and `continue` anywhere. Whether they are inside a loop, and whether
+/// their label exists, is checked along with the other names during semantic analysis, so that
+/// the parser doesn't need to track scopes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BreakStmt {
+    pub label: Option<String>,
+    pub value: Option<ExprNode>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContinueStmt {
+    pub label: Option<String>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LetStmt {
+    pub name: String,
+    pub ty: Option<TypeExpr>,
+    pub init: Option<ExprNode>,
+    pub mutable: bool,
+    pub span: Span,
+}
+
+/// `target = value`, or `target op= value` for compound assignments.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssignStmt {
+    pub target: ExprNode,
+    pub op: Option<BinOpKind>,
+    pub value: ExprNode,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WhileStmt {
+    pub label: Option<String>,
+    pub cond: Box<ExprNode>,
+    pub body: Block,
+    pub span: Span,
+}
+
+/// `for (init; cond; post) { ... }`, where the parentheses and each of the clauses are optional.
+///
+/// `init` and `post` are statements rather than expressions, so that they can be a `let` and an
+/// assignment like `i += 1`. Without `cond` the loop runs until it's broken out of.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForStmt {
+    pub label: Option<String>,
+    pub init: Option<Box<StmtNode>>,
+    pub cond: Option<ExprNode>,
+    pub post: Option<Box<StmtNode>>,
+    pub body: Block,
+    pub span: Span,
+}
+
+/// `for x in iterable { ... }`, or `for mut x in ...` to be able to assign to `x`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForInStmt {
+    pub label: Option<String>,
+    pub binding: String,
+    pub mutable: bool,
+    pub iterable: ExprNode,
+    pub body: Block,
+    pub span: Span,
+}
+
+/// The value of a literal, with escapes and digit separators removed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LiteralValue {
+    Int(i128),
+    /// An integer too large for `i128`.
+    UInt(u128),
+    Float(f64),
+    String(String),
+    Char(char),
+    Bool(bool),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BinOpKind {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    BitAnd,
+    BitXor,
+    BitOr,
+    Or,
+    Lt,
+    Gt,
+    /// `a |> f`, passing `a` to `f`.
+    Pipe,
+}
+
+impl BinOpKind {
+    pub fn from_token(token: &Token) -> Option<Self> {
+        match token {
+            Token::Plus(_) => Some(BinOpKind::Add),
+            Token::Minus(_) => Some(BinOpKind::Sub),
+            Token::Star(_) => Some(BinOpKind::Mul),
+            Token::Slash(_) => Some(BinOpKind::Div),
+            Token::Percent(_) => Some(BinOpKind::Rem),
+            Token::Ampersand(_) => Some(BinOpKind::BitAnd),
+            Token::Caret(_) => Some(BinOpKind::BitXor),
+            Token::Pipe(_) => Some(BinOpKind::BitOr),
+            Token::PipePipe(_) => Some(BinOpKind::Or),
+            Token::LessThan(_) => Some(BinOpKind::Lt),
+            Token::GreaterThan(_) => Some(BinOpKind::Gt),
+            Token::PipeGt(_) => Some(BinOpKind::Pipe),
+            _ => None,
+        }
+    }
+
+    /// The operator applied by a compound assignment like `+=`.
+    pub fn from_compound_assignment(token: &Token) -> Option<Self> {
+        match token {
+            Token::PlusEquals(_) => Some(BinOpKind::Add),
+            Token::MinusEquals(_) => Some(BinOpKind::Sub),
+            Token::StarEquals(_) => Some(BinOpKind::Mul),
+            Token::SlashEquals(_) => Some(BinOpKind::Div),
+            Token::PercentEquals(_) => Some(BinOpKind::Rem),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UnOpKind {
+    /// `-x`
+    Neg,
+    /// `!x`
+    Not,
+    /// `~x`
+    BitNot,
+}
+
+impl UnOpKind {
+    pub fn from_token(token: &Token) -> Option<Self> {
+        match token {
+            Token::Minus(_) => Some(UnOpKind::Neg),
+            Token::Bang(_) => Some(UnOpKind::Not),
+            Token::Tilde(_) => Some(UnOpKind::BitNot),
+            _ => None,
+        }
+    }
+}
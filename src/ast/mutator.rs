@@ -0,0 +1,466 @@
+use std::mem;
+
+use super::{
+    BinOpKind, Block, ClosureBody, EnumDecl, EnumVariantFields, ExprNode, FnDecl, FnSig,
+    GlobalDecl, IfOrBlock, ImplBlock, ImplItem, Item, LiteralValue, MatchArm, Module, Param,
+    Pattern, StmtNode, StructDecl, TraitDecl, TraitItem, TypeAlias, TypeExpr,
+};
+use crate::builtins::BuiltinFn;
+use crate::span::Span;
+
+/// A pass changing the AST in place, the counterpart of [`AstVisitor`].
+///
+/// Every method visits its children through the `walk_*_mut` function of the same name, so an
+/// implementation only overrides the methods for the nodes it changes. Expressions can also be
+/// replaced through [`fold_expr`], which sees each one after its subexpressions have been visited.
+///
+/// [`AstVisitor`]: super::visitor::AstVisitor
+/// [`fold_expr`]: AstMutator::fold_expr
+pub trait AstMutator: Sized {
+    fn visit_module_mut(&mut self, n: &mut Module) {
+        walk_module_mut(self, n);
+    }
+
+    fn visit_item_mut(&mut self, n: &mut Item) {
+        walk_item_mut(self, n);
+    }
+
+    fn visit_fn_decl_mut(&mut self, n: &mut FnDecl) {
+        walk_fn_decl_mut(self, n);
+    }
+
+    fn visit_fn_sig_mut(&mut self, n: &mut FnSig) {
+        walk_fn_sig_mut(self, n);
+    }
+
+    fn visit_param_mut(&mut self, n: &mut Param) {
+        walk_param_mut(self, n);
+    }
+
+    fn visit_struct_decl_mut(&mut self, n: &mut StructDecl) {
+        walk_struct_decl_mut(self, n);
+    }
+
+    fn visit_enum_decl_mut(&mut self, n: &mut EnumDecl) {
+        walk_enum_decl_mut(self, n);
+    }
+
+    fn visit_trait_decl_mut(&mut self, n: &mut TraitDecl) {
+        walk_trait_decl_mut(self, n);
+    }
+
+    fn visit_impl_block_mut(&mut self, n: &mut ImplBlock) {
+        walk_impl_block_mut(self, n);
+    }
+
+    fn visit_global_decl_mut(&mut self, n: &mut GlobalDecl) {
+        walk_global_decl_mut(self, n);
+    }
+
+    fn visit_type_alias_mut(&mut self, n: &mut TypeAlias) {
+        walk_type_alias_mut(self, n);
+    }
+
+    fn visit_type_mut(&mut self, n: &mut TypeExpr) {
+        walk_type_mut(self, n);
+    }
+
+    fn visit_block_mut(&mut self, n: &mut Block) {
+        walk_block_mut(self, n);
+    }
+
+    fn visit_stmt_mut(&mut self, n: &mut StmtNode) {
+        walk_stmt_mut(self, n);
+    }
+
+    fn visit_expr_mut(&mut self, n: &mut ExprNode) {
+        walk_expr_mut(self, n);
+
+        // Only left in place until the folded expression replaces it
+        let placeholder = ExprNode::Literal {
+            value: LiteralValue::Bool(false),
+            span: Span::default(),
+        };
+        let expr = mem::replace(n, placeholder);
+        *n = self.fold_expr(expr);
+    }
+
+    /// The expression to replace `e` with, which is `e` itself unless overridden.
+    fn fold_expr(&mut self, e: ExprNode) -> ExprNode {
+        e
+    }
+
+    fn visit_match_arm_mut(&mut self, n: &mut MatchArm) {
+        walk_match_arm_mut(self, n);
+    }
+
+    fn visit_pattern_mut(&mut self, n: &mut Pattern) {
+        walk_pattern_mut(self, n);
+    }
+}
+
+pub fn walk_module_mut<V: AstMutator>(v: &mut V, n: &mut Module) {
+    for item in &mut n.items {
+        v.visit_item_mut(item);
+    }
+}
+
+/// Visit the declaration inside an item. `use` declarations have nothing to visit.
+pub fn walk_item_mut<V: AstMutator>(v: &mut V, n: &mut Item) {
+    match n {
+        Item::FnDecl(decl) => v.visit_fn_decl_mut(decl),
+        Item::StructDecl(decl) => v.visit_struct_decl_mut(decl),
+        Item::EnumDecl(decl) => v.visit_enum_decl_mut(decl),
+        Item::TraitDecl(decl) => v.visit_trait_decl_mut(decl),
+        Item::ImplBlock(block) => v.visit_impl_block_mut(block),
+        Item::UseDecl(_) => {}
+        Item::GlobalDecl(decl) => v.visit_global_decl_mut(decl),
+        Item::TypeAlias(alias) => v.visit_type_alias_mut(alias),
+    }
+}
+
+/// Visit the parameters, return type and body of a function. The bounds of its generic
+/// parameters are visited as types.
+pub fn walk_fn_decl_mut<V: AstMutator>(v: &mut V, n: &mut FnDecl) {
+    for bound in n
+        .generic_params
+        .iter_mut()
+        .flat_map(|param| &mut param.bounds)
+    {
+        v.visit_type_mut(bound);
+    }
+    for param in &mut n.params {
+        v.visit_param_mut(param);
+    }
+    if let Some(ret) = &mut n.ret {
+        v.visit_type_mut(ret);
+    }
+    v.visit_block_mut(&mut n.body);
+}
+
+pub fn walk_fn_sig_mut<V: AstMutator>(v: &mut V, n: &mut FnSig) {
+    for bound in n
+        .generic_params
+        .iter_mut()
+        .flat_map(|param| &mut param.bounds)
+    {
+        v.visit_type_mut(bound);
+    }
+    for param in &mut n.params {
+        v.visit_param_mut(param);
+    }
+    if let Some(ret) = &mut n.ret {
+        v.visit_type_mut(ret);
+    }
+}
+
+pub fn walk_param_mut<V: AstMutator>(v: &mut V, n: &mut Param) {
+    v.visit_type_mut(&mut n.ty);
+}
+
+pub fn walk_struct_decl_mut<V: AstMutator>(v: &mut V, n: &mut StructDecl) {
+    for bound in n
+        .generic_params
+        .iter_mut()
+        .flat_map(|param| &mut param.bounds)
+    {
+        v.visit_type_mut(bound);
+    }
+    for field in &mut n.fields {
+        v.visit_type_mut(&mut field.ty);
+    }
+}
+
+pub fn walk_enum_decl_mut<V: AstMutator>(v: &mut V, n: &mut EnumDecl) {
+    for bound in n
+        .generic_params
+        .iter_mut()
+        .flat_map(|param| &mut param.bounds)
+    {
+        v.visit_type_mut(bound);
+    }
+    for variant in &mut n.variants {
+        match &mut variant.fields {
+            EnumVariantFields::Unit => {}
+            EnumVariantFields::Tuple(types) => {
+                for ty in types {
+                    v.visit_type_mut(ty);
+                }
+            }
+            EnumVariantFields::Struct(fields) => {
+                for field in fields {
+                    v.visit_type_mut(&mut field.ty);
+                }
+            }
+        }
+    }
+}
+
+pub fn walk_trait_decl_mut<V: AstMutator>(v: &mut V, n: &mut TraitDecl) {
+    for bound in n
+        .generic_params
+        .iter_mut()
+        .flat_map(|param| &mut param.bounds)
+    {
+        v.visit_type_mut(bound);
+    }
+    for item in &mut n.items {
+        match item {
+            TraitItem::FnSignature(sig) => v.visit_fn_sig_mut(sig),
+            TraitItem::DefaultFn(decl) => v.visit_fn_decl_mut(decl),
+        }
+    }
+}
+
+pub fn walk_impl_block_mut<V: AstMutator>(v: &mut V, n: &mut ImplBlock) {
+    for bound in n
+        .generic_params
+        .iter_mut()
+        .flat_map(|param| &mut param.bounds)
+    {
+        v.visit_type_mut(bound);
+    }
+    for ty in &mut n.trait_generics {
+        v.visit_type_mut(ty);
+    }
+    v.visit_type_mut(&mut n.for_type);
+    for ImplItem::Fn(decl) in &mut n.items {
+        v.visit_fn_decl_mut(decl);
+    }
+}
+
+pub fn walk_type_alias_mut<V: AstMutator>(v: &mut V, n: &mut TypeAlias) {
+    for bound in n
+        .generic_params
+        .iter_mut()
+        .flat_map(|param| &mut param.bounds)
+    {
+        v.visit_type_mut(bound);
+    }
+    v.visit_type_mut(&mut n.target);
+}
+
+pub fn walk_global_decl_mut<V: AstMutator>(v: &mut V, n: &mut GlobalDecl) {
+    v.visit_type_mut(&mut n.ty);
+    if let Some(init) = &mut n.init {
+        v.visit_expr_mut(init);
+    }
+}
+
+/// Visit the types a type is made of, like the `T` of `*T` or the generic arguments of a named
+/// type.
+pub fn walk_type_mut<V: AstMutator>(v: &mut V, n: &mut TypeExpr) {
+    match n {
+        TypeExpr::Named { generics, .. } => {
+            for ty in generics {
+                v.visit_type_mut(ty);
+            }
+        }
+        TypeExpr::Pointer { inner, .. } => v.visit_type_mut(inner),
+        TypeExpr::Array { element, .. } => v.visit_type_mut(element),
+        TypeExpr::FnType { params, ret } => {
+            for ty in params {
+                v.visit_type_mut(ty);
+            }
+            v.visit_type_mut(ret);
+        }
+        TypeExpr::Tuple(types) => {
+            for ty in types {
+                v.visit_type_mut(ty);
+            }
+        }
+        TypeExpr::Never => {}
+    }
+}
+
+pub fn walk_block_mut<V: AstMutator>(v: &mut V, n: &mut Block) {
+    for stmt in &mut n.stmts {
+        v.visit_stmt_mut(stmt);
+    }
+    if let Some(expr) = &mut n.final_expr {
+        v.visit_expr_mut(expr);
+    }
+}
+
+/// Visit the expressions, types and blocks inside a statement, in the order they're written.
+pub fn walk_stmt_mut<V: AstMutator>(v: &mut V, n: &mut StmtNode) {
+    match n {
+        StmtNode::Expr(expr) => v.visit_expr_mut(expr),
+        StmtNode::While(stmt) => {
+            v.visit_expr_mut(&mut stmt.cond);
+            v.visit_block_mut(&mut stmt.body);
+        }
+        StmtNode::For(stmt) => {
+            if let Some(init) = &mut stmt.init {
+                v.visit_stmt_mut(init);
+            }
+            if let Some(cond) = &mut stmt.cond {
+                v.visit_expr_mut(cond);
+            }
+            if let Some(post) = &mut stmt.post {
+                v.visit_stmt_mut(post);
+            }
+            v.visit_block_mut(&mut stmt.body);
+        }
+        StmtNode::ForIn(stmt) => {
+            v.visit_expr_mut(&mut stmt.iterable);
+            v.visit_block_mut(&mut stmt.body);
+        }
+        StmtNode::Let(stmt) => {
+            if let Some(ty) = &mut stmt.ty {
+                v.visit_type_mut(ty);
+            }
+            if let Some(init) = &mut stmt.init {
+                v.visit_expr_mut(init);
+            }
+        }
+        StmtNode::Assign(stmt) => {
+            v.visit_expr_mut(&mut stmt.target);
+            v.visit_expr_mut(&mut stmt.value);
+        }
+        StmtNode::Return(stmt) => {
+            if let Some(value) = &mut stmt.value {
+                v.visit_expr_mut(value);
+            }
+        }
+        StmtNode::Break(stmt) => {
+            if let Some(value) = &mut stmt.value {
+                v.visit_expr_mut(value);
+            }
+        }
+        StmtNode::Continue(_) => {}
+    }
+}
+
+/// Visit the subexpressions of an expression, along with the types of closure parameters and
+/// the type given to `sizeof` or `alignof`.
+pub fn walk_expr_mut<V: AstMutator>(v: &mut V, n: &mut ExprNode) {
+    match n {
+        ExprNode::Literal { .. } | ExprNode::Ident { .. } => {}
+        ExprNode::BinOp { lhs, rhs, .. } => {
+            v.visit_expr_mut(lhs);
+            v.visit_expr_mut(rhs);
+        }
+        ExprNode::UnaryOp { operand, .. } => v.visit_expr_mut(operand),
+        ExprNode::Call { callee, args, .. } => {
+            v.visit_expr_mut(callee);
+            for arg in args {
+                v.visit_expr_mut(arg);
+            }
+        }
+        ExprNode::Builtin { builtin, args, .. } => {
+            if let BuiltinFn::SizeOf(ty) | BuiltinFn::AlignOf(ty) = builtin {
+                v.visit_type_mut(ty);
+            }
+            for arg in args {
+                v.visit_expr_mut(arg);
+            }
+        }
+        ExprNode::Index { base, index, .. } => {
+            v.visit_expr_mut(base);
+            v.visit_expr_mut(index);
+        }
+        ExprNode::Field { base, .. } => v.visit_expr_mut(base),
+        ExprNode::If(if_expr) => {
+            let mut if_expr = if_expr;
+
+            loop {
+                v.visit_expr_mut(&mut if_expr.cond);
+                v.visit_block_mut(&mut if_expr.then_block);
+
+                match if_expr.else_block.as_deref_mut() {
+                    Some(IfOrBlock::If(else_if)) => if_expr = else_if,
+                    Some(IfOrBlock::Block(block)) => {
+                        v.visit_block_mut(block);
+                        break;
+                    }
+                    None => break,
+                }
+            }
+        }
+        ExprNode::Match(match_expr) => {
+            v.visit_expr_mut(&mut match_expr.scrutinee);
+            for arm in &mut match_expr.arms {
+                v.visit_match_arm_mut(arm);
+            }
+        }
+        ExprNode::Closure(closure) => {
+            for ty in closure
+                .params
+                .iter_mut()
+                .filter_map(|param| param.ty.as_mut())
+            {
+                v.visit_type_mut(ty);
+            }
+            match &mut closure.body {
+                ClosureBody::Expr(expr) => v.visit_expr_mut(expr),
+                ClosureBody::Block(block) => v.visit_block_mut(block),
+            }
+        }
+        ExprNode::Block(block) => v.visit_block_mut(block),
+    }
+}
+
+pub fn walk_match_arm_mut<V: AstMutator>(v: &mut V, n: &mut MatchArm) {
+    v.visit_pattern_mut(&mut n.pattern);
+    if let Some(guard) = &mut n.guard {
+        v.visit_expr_mut(guard);
+    }
+    v.visit_expr_mut(&mut n.body);
+}
+
+pub fn walk_pattern_mut<V: AstMutator>(v: &mut V, n: &mut Pattern) {
+    if let Pattern::Or(lhs, rhs) = n {
+        v.visit_pattern_mut(lhs);
+        v.visit_pattern_mut(rhs);
+    }
+}
+
+/// Replaces operations on integer and `bool` literals with their result, as an example of an
+/// [`AstMutator`].
+///
+/// Types aren't known yet, so integers are folded as `i128` without checking that the result fits
+/// the type they end up with. Operations that would overflow `i128` or divide by zero are left for
+/// the [`ConstantFolder`] of the typed module to report.
+///
+/// [`ConstantFolder`]: crate::codegen::ConstantFolder
+#[derive(Debug, Default)]
+pub struct ConstantFoldMutator;
+
+impl AstMutator for ConstantFoldMutator {
+    fn fold_expr(&mut self, e: ExprNode) -> ExprNode {
+        let ExprNode::BinOp { op, lhs, rhs, span } = &e else {
+            return e;
+        };
+        let (ExprNode::Literal { value: lhs, .. }, ExprNode::Literal { value: rhs, .. }) =
+            (&**lhs, &**rhs)
+        else {
+            return e;
+        };
+
+        let value = match (lhs, rhs) {
+            (LiteralValue::Int(lhs), LiteralValue::Int(rhs)) => match op {
+                BinOpKind::Add => lhs.checked_add(*rhs).map(LiteralValue::Int),
+                BinOpKind::Sub => lhs.checked_sub(*rhs).map(LiteralValue::Int),
+                BinOpKind::Mul => lhs.checked_mul(*rhs).map(LiteralValue::Int),
+                BinOpKind::Div => lhs.checked_div(*rhs).map(LiteralValue::Int),
+                BinOpKind::Rem => lhs.checked_rem(*rhs).map(LiteralValue::Int),
+                BinOpKind::BitAnd => Some(LiteralValue::Int(lhs & rhs)),
+                BinOpKind::BitXor => Some(LiteralValue::Int(lhs ^ rhs)),
+                BinOpKind::BitOr => Some(LiteralValue::Int(lhs | rhs)),
+                BinOpKind::Lt => Some(LiteralValue::Bool(lhs < rhs)),
+                BinOpKind::Gt => Some(LiteralValue::Bool(lhs > rhs)),
+                BinOpKind::Or | BinOpKind::Pipe => None,
+            },
+            (LiteralValue::Bool(lhs), LiteralValue::Bool(rhs)) if *op == BinOpKind::Or => {
+                Some(LiteralValue::Bool(*lhs || *rhs))
+            }
+            _ => None,
+        };
+
+        match value {
+            Some(value) => ExprNode::Literal { value, span: *span },
+            None => e,
+        }
+    }
+}
@@ -0,0 +1,413 @@
+use std::collections::HashSet;
+
+use super::{
+    Block, ClosureBody, EnumDecl, EnumVariantFields, ExprNode, FnDecl, FnSig, GlobalDecl,
+    IfOrBlock, ImplBlock, ImplItem, Item, MatchArm, Module, Param, Pattern, StmtNode, StructDecl,
+    TraitDecl, TraitItem, TypeAlias, TypeExpr,
+};
+use crate::span::Span;
+
+/// A read-only pass over the AST.
+///
+/// Every method visits its children through the `walk_*` function of the same name, so an
+/// implementation only overrides the methods for the nodes it's interested in. An override that
+/// still needs the children visited calls the `walk_*` function itself.
+pub trait AstVisitor: Sized {
+    fn visit_module(&mut self, n: &Module) {
+        walk_module(self, n);
+    }
+
+    fn visit_item(&mut self, n: &Item) {
+        walk_item(self, n);
+    }
+
+    fn visit_fn_decl(&mut self, n: &FnDecl) {
+        walk_fn_decl(self, n);
+    }
+
+    fn visit_fn_sig(&mut self, n: &FnSig) {
+        walk_fn_sig(self, n);
+    }
+
+    fn visit_param(&mut self, n: &Param) {
+        walk_param(self, n);
+    }
+
+    fn visit_struct_decl(&mut self, n: &StructDecl) {
+        walk_struct_decl(self, n);
+    }
+
+    fn visit_enum_decl(&mut self, n: &EnumDecl) {
+        walk_enum_decl(self, n);
+    }
+
+    fn visit_trait_decl(&mut self, n: &TraitDecl) {
+        walk_trait_decl(self, n);
+    }
+
+    fn visit_impl_block(&mut self, n: &ImplBlock) {
+        walk_impl_block(self, n);
+    }
+
+    fn visit_global_decl(&mut self, n: &GlobalDecl) {
+        walk_global_decl(self, n);
+    }
+
+    fn visit_type_alias(&mut self, n: &TypeAlias) {
+        walk_type_alias(self, n);
+    }
+
+    fn visit_type(&mut self, n: &TypeExpr) {
+        walk_type(self, n);
+    }
+
+    fn visit_block(&mut self, n: &Block) {
+        walk_block(self, n);
+    }
+
+    fn visit_stmt(&mut self, n: &StmtNode) {
+        walk_stmt(self, n);
+    }
+
+    fn visit_expr(&mut self, n: &ExprNode) {
+        walk_expr(self, n);
+    }
+
+    fn visit_match_arm(&mut self, n: &MatchArm) {
+        walk_match_arm(self, n);
+    }
+
+    fn visit_pattern(&mut self, n: &Pattern) {
+        walk_pattern(self, n);
+    }
+}
+
+pub fn walk_module<V: AstVisitor>(v: &mut V, n: &Module) {
+    for item in &n.items {
+        v.visit_item(item);
+    }
+}
+
+/// Visit the declaration inside an item. `use` declarations have nothing to visit.
+pub fn walk_item<V: AstVisitor>(v: &mut V, n: &Item) {
+    match n {
+        Item::FnDecl(decl) => v.visit_fn_decl(decl),
+        Item::StructDecl(decl) => v.visit_struct_decl(decl),
+        Item::EnumDecl(decl) => v.visit_enum_decl(decl),
+        Item::TraitDecl(decl) => v.visit_trait_decl(decl),
+        Item::ImplBlock(block) => v.visit_impl_block(block),
+        Item::UseDecl(_) => {}
+        Item::GlobalDecl(decl) => v.visit_global_decl(decl),
+        Item::TypeAlias(alias) => v.visit_type_alias(alias),
+    }
+}
+
+/// Visit the parameters, return type and body of a function. The bounds of its generic
+/// parameters are visited as types.
+pub fn walk_fn_decl<V: AstVisitor>(v: &mut V, n: &FnDecl) {
+    for bound in n.generic_params.iter().flat_map(|param| &param.bounds) {
+        v.visit_type(bound);
+    }
+    for param in &n.params {
+        v.visit_param(param);
+    }
+    if let Some(ret) = &n.ret {
+        v.visit_type(ret);
+    }
+    v.visit_block(&n.body);
+}
+
+pub fn walk_fn_sig<V: AstVisitor>(v: &mut V, n: &FnSig) {
+    for bound in n.generic_params.iter().flat_map(|param| &param.bounds) {
+        v.visit_type(bound);
+    }
+    for param in &n.params {
+        v.visit_param(param);
+    }
+    if let Some(ret) = &n.ret {
+        v.visit_type(ret);
+    }
+}
+
+pub fn walk_param<V: AstVisitor>(v: &mut V, n: &Param) {
+    v.visit_type(&n.ty);
+}
+
+pub fn walk_struct_decl<V: AstVisitor>(v: &mut V, n: &StructDecl) {
+    for bound in n.generic_params.iter().flat_map(|param| &param.bounds) {
+        v.visit_type(bound);
+    }
+    for field in &n.fields {
+        v.visit_type(&field.ty);
+    }
+}
+
+pub fn walk_enum_decl<V: AstVisitor>(v: &mut V, n: &EnumDecl) {
+    for bound in n.generic_params.iter().flat_map(|param| &param.bounds) {
+        v.visit_type(bound);
+    }
+    for variant in &n.variants {
+        match &variant.fields {
+            EnumVariantFields::Unit => {}
+            EnumVariantFields::Tuple(types) => {
+                for ty in types {
+                    v.visit_type(ty);
+                }
+            }
+            EnumVariantFields::Struct(fields) => {
+                for field in fields {
+                    v.visit_type(&field.ty);
+                }
+            }
+        }
+    }
+}
+
+pub fn walk_trait_decl<V: AstVisitor>(v: &mut V, n: &TraitDecl) {
+    for bound in n.generic_params.iter().flat_map(|param| &param.bounds) {
+        v.visit_type(bound);
+    }
+    for item in &n.items {
+        match item {
+            TraitItem::FnSignature(sig) => v.visit_fn_sig(sig),
+            TraitItem::DefaultFn(decl) => v.visit_fn_decl(decl),
+        }
+    }
+}
+
+pub fn walk_impl_block<V: AstVisitor>(v: &mut V, n: &ImplBlock) {
+    for bound in n.generic_params.iter().flat_map(|param| &param.bounds) {
+        v.visit_type(bound);
+    }
+    for ty in &n.trait_generics {
+        v.visit_type(ty);
+    }
+    v.visit_type(&n.for_type);
+    for ImplItem::Fn(decl) in &n.items {
+        v.visit_fn_decl(decl);
+    }
+}
+
+pub fn walk_type_alias<V: AstVisitor>(v: &mut V, n: &TypeAlias) {
+    for bound in n.generic_params.iter().flat_map(|param| &param.bounds) {
+        v.visit_type(bound);
+    }
+    v.visit_type(&n.target);
+}
+
+pub fn walk_global_decl<V: AstVisitor>(v: &mut V, n: &GlobalDecl) {
+    v.visit_type(&n.ty);
+    if let Some(init) = &n.init {
+        v.visit_expr(init);
+    }
+}
+
+/// Visit the types a type is made of, like the `T` of `*T` or the generic arguments of a named
+/// type.
+pub fn walk_type<V: AstVisitor>(v: &mut V, n: &TypeExpr) {
+    match n {
+        TypeExpr::Named { generics, .. } => {
+            for ty in generics {
+                v.visit_type(ty);
+            }
+        }
+        TypeExpr::Pointer { inner, .. } => v.visit_type(inner),
+        TypeExpr::Array { element, .. } => v.visit_type(element),
+        TypeExpr::FnType { params, ret } => {
+            for ty in params {
+                v.visit_type(ty);
+            }
+            v.visit_type(ret);
+        }
+        TypeExpr::Tuple(types) => {
+            for ty in types {
+                v.visit_type(ty);
+            }
+        }
+        TypeExpr::Never => {}
+    }
+}
+
+pub fn walk_block<V: AstVisitor>(v: &mut V, n: &Block) {
+    for stmt in &n.stmts {
+        v.visit_stmt(stmt);
+    }
+    if let Some(expr) = &n.final_expr {
+        v.visit_expr(expr);
+    }
+}
+
+/// Visit the expressions, types and blocks inside a statement, in the order they're written.
+pub fn walk_stmt<V: AstVisitor>(v: &mut V, n: &StmtNode) {
+    match n {
+        StmtNode::Expr(expr) => v.visit_expr(expr),
+        StmtNode::While(stmt) => {
+            v.visit_expr(&stmt.cond);
+            v.visit_block(&stmt.body);
+        }
+        StmtNode::For(stmt) => {
+            if let Some(init) = &stmt.init {
+                v.visit_stmt(init);
+            }
+            if let Some(cond) = &stmt.cond {
+                v.visit_expr(cond);
+            }
+            if let Some(post) = &stmt.post {
+                v.visit_stmt(post);
+            }
+            v.visit_block(&stmt.body);
+        }
+        StmtNode::ForIn(stmt) => {
+            v.visit_expr(&stmt.iterable);
+            v.visit_block(&stmt.body);
+        }
+        StmtNode::Let(stmt) => {
+            if let Some(ty) = &stmt.ty {
+                v.visit_type(ty);
+            }
+            if let Some(init) = &stmt.init {
+                v.visit_expr(init);
+            }
+        }
+        StmtNode::Assign(stmt) => {
+            v.visit_expr(&stmt.target);
+            v.visit_expr(&stmt.value);
+        }
+        StmtNode::Return(stmt) => {
+            if let Some(value) = &stmt.value {
+                v.visit_expr(value);
+            }
+        }
+        StmtNode::Break(stmt) => {
+            if let Some(value) = &stmt.value {
+                v.visit_expr(value);
+            }
+        }
+        StmtNode::Continue(_) => {}
+    }
+}
+
+/// Visit the subexpressions of an expression, along with the types of closure parameters and
+/// the type given to `sizeof` or `alignof`.
+pub fn walk_expr<V: AstVisitor>(v: &mut V, n: &ExprNode) {
+    match n {
+        ExprNode::Literal { .. } | ExprNode::Ident { .. } => {}
+        ExprNode::BinOp { lhs, rhs, .. } => {
+            v.visit_expr(lhs);
+            v.visit_expr(rhs);
+        }
+        ExprNode::UnaryOp { operand, .. } => v.visit_expr(operand),
+        ExprNode::Call { callee, args, .. } => {
+            v.visit_expr(callee);
+            for arg in args {
+                v.visit_expr(arg);
+            }
+        }
+        ExprNode::Builtin { builtin, args, .. } => {
+            if let Some(ty) = builtin.type_arg() {
+                v.visit_type(ty);
+            }
+            for arg in args {
+                v.visit_expr(arg);
+            }
+        }
+        ExprNode::Index { base, index, .. } => {
+            v.visit_expr(base);
+            v.visit_expr(index);
+        }
+        ExprNode::Field { base, .. } => v.visit_expr(base),
+        ExprNode::If(if_expr) => {
+            let mut if_expr = if_expr;
+
+            loop {
+                v.visit_expr(&if_expr.cond);
+                v.visit_block(&if_expr.then_block);
+
+                match if_expr.else_block.as_deref() {
+                    Some(IfOrBlock::If(else_if)) => if_expr = else_if,
+                    Some(IfOrBlock::Block(block)) => {
+                        v.visit_block(block);
+                        break;
+                    }
+                    None => break,
+                }
+            }
+        }
+        ExprNode::Match(match_expr) => {
+            v.visit_expr(&match_expr.scrutinee);
+            for arm in &match_expr.arms {
+                v.visit_match_arm(arm);
+            }
+        }
+        ExprNode::Closure(closure) => {
+            for ty in closure.params.iter().filter_map(|param| param.ty.as_ref()) {
+                v.visit_type(ty);
+            }
+            match &closure.body {
+                ClosureBody::Expr(expr) => v.visit_expr(expr),
+                ClosureBody::Block(block) => v.visit_block(block),
+            }
+        }
+        ExprNode::Block(block) => v.visit_block(block),
+    }
+}
+
+pub fn walk_match_arm<V: AstVisitor>(v: &mut V, n: &MatchArm) {
+    v.visit_pattern(&n.pattern);
+    if let Some(guard) = &n.guard {
+        v.visit_expr(guard);
+    }
+    v.visit_expr(&n.body);
+}
+
+pub fn walk_pattern<V: AstVisitor>(v: &mut V, n: &Pattern) {
+    if let Pattern::Or(lhs, rhs) = n {
+        v.visit_pattern(lhs);
+        v.visit_pattern(rhs);
+    }
+}
+
+/// Finds the `let` bindings whose name is never read, as an example of an [`AstVisitor`].
+///
+/// Names aren't resolved, so a binding counts as used when any variable of the same name appears
+/// anywhere in what's visited. See [`UnusedVariable`] for a lint that follows scopes.
+///
+/// [`UnusedVariable`]: crate::linter::UnusedVariable
+#[derive(Debug, Default)]
+pub struct UnusedVarVisitor {
+    declared: Vec<(String, Span)>,
+    used: HashSet<String>,
+}
+
+impl UnusedVarVisitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The name and span of each unused binding, in the order the bindings were visited.
+    pub fn unused(&self) -> Vec<(&str, Span)> {
+        self.declared
+            .iter()
+            .filter(|(name, _)| !name.starts_with('_') && !self.used.contains(name))
+            .map(|(name, span)| (name.as_str(), *span))
+            .collect()
+    }
+}
+
+impl AstVisitor for UnusedVarVisitor {
+    fn visit_stmt(&mut self, n: &StmtNode) {
+        if let StmtNode::Let(stmt) = n {
+            self.declared.push((stmt.name.clone(), stmt.span));
+        }
+
+        walk_stmt(self, n);
+    }
+
+    fn visit_expr(&mut self, n: &ExprNode) {
+        if let ExprNode::Ident { name, .. } = n {
+            self.used.insert(name.clone());
+        }
+
+        walk_expr(self, n);
+    }
+}
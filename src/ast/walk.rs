@@ -0,0 +1,150 @@
+//! Traversals calling a closure on each node, for one-off passes that don't need their own
+//! [`AstVisitor`].
+
+use super::visitor::{self, AstVisitor};
+use super::{
+    Block, EnumDecl, ExprNode, FnDecl, FnSig, GlobalDecl, ImplBlock, Item, MatchArm, Module, Param,
+    Pattern, StmtNode, StructDecl, TraitDecl, TypeAlias, TypeExpr,
+};
+use crate::ast_printer::AstNode;
+
+/// Call `f` on `module` and every node in it, each before its children, in the order an
+/// [`AstVisitor`] visits them.
+pub fn walk_module<F: FnMut(&dyn AstNode)>(module: &Module, f: &mut F) {
+    NodeWalker(f).visit_module(module);
+}
+
+/// Call `f` on every expression in `module`, each before its subexpressions.
+///
+/// ```
+/// use llvm_compiler::ast::walk::walk_exprs;
+/// use llvm_compiler::ast::ExprNode;
+/// use llvm_compiler::compiler::parse_source;
+/// use llvm_compiler::source::SourceFile;
+///
+/// let source = SourceFile::new("calls.src", "func main() -> i32 { return f(g(1)); }");
+/// let module = parse_source(&source).unwrap();
+///
+/// let mut calls = 0;
+/// walk_exprs(&module, &mut |expr| {
+///     if let ExprNode::Call { .. } = expr {
+///         calls += 1;
+///     }
+/// });
+/// assert_eq!(calls, 2);
+/// ```
+pub fn walk_exprs<F: FnMut(&ExprNode)>(module: &Module, f: &mut F) {
+    ExprWalker(f).visit_module(module);
+}
+
+/// Call `f` on every statement in `module`, including those nested in the blocks of others.
+pub fn walk_stmts<F: FnMut(&StmtNode)>(module: &Module, f: &mut F) {
+    StmtWalker(f).visit_module(module);
+}
+
+struct NodeWalker<'f, F>(&'f mut F);
+
+impl<F: FnMut(&dyn AstNode)> AstVisitor for NodeWalker<'_, F> {
+    fn visit_module(&mut self, n: &Module) {
+        (self.0)(n);
+        visitor::walk_module(self, n);
+    }
+
+    fn visit_item(&mut self, n: &Item) {
+        (self.0)(n);
+        visitor::walk_item(self, n);
+    }
+
+    fn visit_fn_decl(&mut self, n: &FnDecl) {
+        (self.0)(n);
+        visitor::walk_fn_decl(self, n);
+    }
+
+    fn visit_fn_sig(&mut self, n: &FnSig) {
+        (self.0)(n);
+        visitor::walk_fn_sig(self, n);
+    }
+
+    fn visit_param(&mut self, n: &Param) {
+        (self.0)(n);
+        visitor::walk_param(self, n);
+    }
+
+    fn visit_struct_decl(&mut self, n: &StructDecl) {
+        (self.0)(n);
+        visitor::walk_struct_decl(self, n);
+    }
+
+    fn visit_enum_decl(&mut self, n: &EnumDecl) {
+        (self.0)(n);
+        visitor::walk_enum_decl(self, n);
+    }
+
+    fn visit_trait_decl(&mut self, n: &TraitDecl) {
+        (self.0)(n);
+        visitor::walk_trait_decl(self, n);
+    }
+
+    fn visit_impl_block(&mut self, n: &ImplBlock) {
+        (self.0)(n);
+        visitor::walk_impl_block(self, n);
+    }
+
+    fn visit_global_decl(&mut self, n: &GlobalDecl) {
+        (self.0)(n);
+        visitor::walk_global_decl(self, n);
+    }
+
+    fn visit_type_alias(&mut self, n: &TypeAlias) {
+        (self.0)(n);
+        visitor::walk_type_alias(self, n);
+    }
+
+    fn visit_type(&mut self, n: &TypeExpr) {
+        (self.0)(n);
+        visitor::walk_type(self, n);
+    }
+
+    fn visit_block(&mut self, n: &Block) {
+        (self.0)(n);
+        visitor::walk_block(self, n);
+    }
+
+    fn visit_stmt(&mut self, n: &StmtNode) {
+        (self.0)(n);
+        visitor::walk_stmt(self, n);
+    }
+
+    fn visit_expr(&mut self, n: &ExprNode) {
+        (self.0)(n);
+        visitor::walk_expr(self, n);
+    }
+
+    fn visit_match_arm(&mut self, n: &MatchArm) {
+        (self.0)(n);
+        visitor::walk_match_arm(self, n);
+    }
+
+    fn visit_pattern(&mut self, n: &Pattern) {
+        (self.0)(n);
+        visitor::walk_pattern(self, n);
+    }
+}
+
+struct ExprWalker<'f, F>(&'f mut F);
+
+impl<F: FnMut(&ExprNode)> AstVisitor for ExprWalker<'_, F> {
+    fn visit_expr(&mut self, n: &ExprNode) {
+        (self.0)(n);
+        visitor::walk_expr(self, n);
+    }
+}
+
+struct StmtWalker<'f, F>(&'f mut F);
+
+impl<F: FnMut(&StmtNode)> AstVisitor for StmtWalker<'_, F> {
+    fn visit_stmt(&mut self, n: &StmtNode) {
+        (self.0)(n);
+        visitor::walk_stmt(self, n);
+    }
+}
@@ -0,0 +1,831 @@
+use std::fmt;
+
+use crate::ast::{
+    AssignStmt, BinOpKind, Block, BreakStmt, CaptureMode, ClosureBody, ClosureExpr, ClosureParam,
+    ContinueStmt, EnumDecl, EnumVariant, EnumVariantFields, ExprNode, FieldDecl, FnDecl, FnSig,
+    ForInStmt, ForStmt, GenericParam, GenericParams, GlobalDecl, IfExpr, IfOrBlock, ImplBlock,
+    ImplItem, Item, LetStmt, LiteralValue, MatchArm, MatchExpr, Module, Param, Pattern, ReturnStmt,
+    StmtNode, StructDecl, TraitDecl, TraitItem, TypeAlias, TypeExpr, UnOpKind, UseDecl, Visibility,
+    WhileStmt,
+};
+use crate::span::Span;
+
+/// A part of the AST, which can be printed with [`print_sexp`] and knows where it was written.
+pub trait AstNode {
+    fn to_sexp(&self) -> Sexp;
+
+    /// The source covered by the node, for pointing errors at it.
+    fn span(&self) -> Span;
+}
+
+impl<T: AstNode + ?Sized> AstNode for Box<T> {
+    fn to_sexp(&self) -> Sexp {
+        (**self).to_sexp()
+    }
+
+    fn span(&self) -> Span {
+        (**self).span()
+    }
+}
+
+/// Print `node` on one line as an S-expression, like `(fn add (params (a i32)) (ret i32) ...)`,
+/// for comparing parses while debugging.
+///
+/// Every node is a list starting with a symbol naming what it is, apart from literals, types
+/// without generic arguments and patterns binding a name, which are printed as a single atom.
+/// Operators and paths are written so that the output reads as plain Lisp, with `a::b` becoming
+/// `(path a b)` and the `|` operators spelled out.
+pub fn print_sexp(node: &dyn AstNode) -> String {
+    node.to_sexp().to_string()
+}
+
+/// An atom, or a list printed as `(a b c)`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Sexp {
+    Atom(String),
+    List(Vec<Sexp>),
+}
+
+impl Sexp {
+    fn atom(text: impl Into<String>) -> Self {
+        Sexp::Atom(text.into())
+    }
+
+    /// `(head items...)`.
+    fn list(head: &str, items: impl IntoIterator<Item = Sexp>) -> Self {
+        Sexp::List(
+            std::iter::once(Sexp::atom(head))
+                .chain(items)
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    fn nodes<'a, T: AstNode + 'a>(head: &str, nodes: impl IntoIterator<Item = &'a T>) -> Self {
+        Sexp::list(head, nodes.into_iter().map(AstNode::to_sexp))
+    }
+}
+
+impl fmt::Display for Sexp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Sexp::Atom(text) => write!(f, "{text}"),
+            Sexp::List(items) => {
+                write!(f, "(")?;
+                for (idx, item) in items.iter().enumerate() {
+                    if idx > 0 {
+                        write!(f, " ")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+impl AstNode for Module {
+    fn to_sexp(&self) -> Sexp {
+        Sexp::nodes("module", &self.items)
+    }
+
+    fn span(&self) -> Span {
+        merged(self.items.iter().map(AstNode::span))
+    }
+}
+
+impl AstNode for Item {
+    fn to_sexp(&self) -> Sexp {
+        match self {
+            Item::FnDecl(decl) => decl.to_sexp(),
+            Item::StructDecl(decl) => decl.to_sexp(),
+            Item::EnumDecl(decl) => decl.to_sexp(),
+            Item::TraitDecl(decl) => decl.to_sexp(),
+            Item::ImplBlock(block) => block.to_sexp(),
+            Item::UseDecl(decl) => decl.to_sexp(),
+            Item::GlobalDecl(decl) => decl.to_sexp(),
+            Item::TypeAlias(alias) => alias.to_sexp(),
+        }
+    }
+
+    fn span(&self) -> Span {
+        match self {
+            Item::FnDecl(decl) => decl.span,
+            Item::StructDecl(decl) => decl.span,
+            Item::EnumDecl(decl) => decl.span,
+            Item::TraitDecl(decl) => decl.span,
+            Item::ImplBlock(block) => block.span,
+            Item::UseDecl(decl) => decl.span,
+            Item::GlobalDecl(decl) => decl.span,
+            Item::TypeAlias(alias) => alias.span,
+        }
+    }
+}
+
+/// Modifiers come before the name, like `(fn pub (extern "C") unsafe name ...)`, and an `extern`
+/// function has no body.
+impl AstNode for FnDecl {
+    fn to_sexp(&self) -> Sexp {
+        let mut items = vec![];
+        if self.visibility == Visibility::Public {
+            items.push(Sexp::atom("pub"));
+        }
+        if let Some(abi) = &self.abi {
+            items.push(Sexp::list("extern", [Sexp::atom(quote(abi))]));
+        }
+        if self.is_unsafe {
+            items.push(Sexp::atom("unsafe"));
+        }
+        items.push(Sexp::atom(&self.name));
+
+        items.extend(signature(
+            &self.generic_params,
+            &self.params,
+            self.ret.as_ref(),
+        ));
+        if !self.is_extern() {
+            items.extend(body(&self.body));
+        }
+
+        Sexp::list("fn", items)
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl AstNode for FnSig {
+    fn to_sexp(&self) -> Sexp {
+        let items = signature(&self.generic_params, &self.params, self.ret.as_ref());
+
+        Sexp::list(
+            "fn-sig",
+            std::iter::once(Sexp::atom(&self.name)).chain(items),
+        )
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// The generics, `(params ...)` and `(ret ...)` of a function, leaving out what isn't written.
+fn signature(generics: &GenericParams, params: &[Param], ret: Option<&TypeExpr>) -> Vec<Sexp> {
+    let mut items = vec![];
+
+    if !generics.is_empty() {
+        items.push(generics.to_sexp());
+    }
+    items.push(Sexp::nodes("params", params));
+    if let Some(ret) = ret {
+        items.push(Sexp::list("ret", [ret.to_sexp()]));
+    }
+
+    items
+}
+
+impl AstNode for Param {
+    fn to_sexp(&self) -> Sexp {
+        Sexp::List(vec![Sexp::atom(&self.name), self.ty.to_sexp()])
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl AstNode for GenericParams {
+    fn to_sexp(&self) -> Sexp {
+        Sexp::nodes("generics", self.iter())
+    }
+
+    fn span(&self) -> Span {
+        merged(self.iter().map(|param| param.span))
+    }
+}
+
+impl AstNode for GenericParam {
+    fn to_sexp(&self) -> Sexp {
+        if self.bounds.is_empty() {
+            return Sexp::atom(&self.name);
+        }
+
+        Sexp::nodes(&self.name, &self.bounds)
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl AstNode for StructDecl {
+    fn to_sexp(&self) -> Sexp {
+        let mut items = vec![Sexp::atom(&self.name)];
+        if !self.generic_params.is_empty() {
+            items.push(self.generic_params.to_sexp());
+        }
+        items.extend(self.fields.iter().map(AstNode::to_sexp));
+
+        Sexp::list("struct", items)
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl AstNode for FieldDecl {
+    fn to_sexp(&self) -> Sexp {
+        Sexp::List(vec![Sexp::atom(&self.name), self.ty.to_sexp()])
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl AstNode for EnumDecl {
+    fn to_sexp(&self) -> Sexp {
+        let mut items = vec![Sexp::atom(&self.name)];
+        if !self.generic_params.is_empty() {
+            items.push(self.generic_params.to_sexp());
+        }
+        items.extend(self.variants.iter().map(AstNode::to_sexp));
+
+        Sexp::list("enum", items)
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl AstNode for EnumVariant {
+    fn to_sexp(&self) -> Sexp {
+        match &self.fields {
+            EnumVariantFields::Unit => Sexp::atom(&self.name),
+            EnumVariantFields::Tuple(types) => {
+                Sexp::list(&self.name, [Sexp::nodes("tuple", types)])
+            }
+            EnumVariantFields::Struct(fields) => Sexp::nodes(&self.name, fields),
+        }
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl AstNode for TraitDecl {
+    fn to_sexp(&self) -> Sexp {
+        let mut items = vec![Sexp::atom(&self.name)];
+        if !self.generic_params.is_empty() {
+            items.push(self.generic_params.to_sexp());
+        }
+        items.extend(self.items.iter().map(AstNode::to_sexp));
+
+        Sexp::list("trait", items)
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl AstNode for TraitItem {
+    fn to_sexp(&self) -> Sexp {
+        match self {
+            TraitItem::FnSignature(sig) => sig.to_sexp(),
+            TraitItem::DefaultFn(decl) => decl.to_sexp(),
+        }
+    }
+
+    fn span(&self) -> Span {
+        match self {
+            TraitItem::FnSignature(sig) => sig.span,
+            TraitItem::DefaultFn(decl) => decl.span,
+        }
+    }
+}
+
+impl AstNode for ImplBlock {
+    fn to_sexp(&self) -> Sexp {
+        let mut items = vec![];
+
+        if !self.generic_params.is_empty() {
+            items.push(self.generic_params.to_sexp());
+        }
+        if let Some(trait_path) = &self.trait_path {
+            let mut trait_items = vec![path(trait_path)];
+            trait_items.extend(self.trait_generics.iter().map(AstNode::to_sexp));
+            items.push(Sexp::list("trait", trait_items));
+        }
+        items.push(Sexp::list("for", [self.for_type.to_sexp()]));
+        items.extend(self.items.iter().map(AstNode::to_sexp));
+
+        Sexp::list("impl", items)
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl AstNode for ImplItem {
+    fn to_sexp(&self) -> Sexp {
+        match self {
+            ImplItem::Fn(decl) => decl.to_sexp(),
+        }
+    }
+
+    fn span(&self) -> Span {
+        match self {
+            ImplItem::Fn(decl) => decl.span,
+        }
+    }
+}
+
+impl AstNode for UseDecl {
+    fn to_sexp(&self) -> Sexp {
+        let mut items = vec![path(&self.path)];
+        if let Some(alias) = &self.alias {
+            items.push(Sexp::list("as", [Sexp::atom(alias)]));
+        }
+
+        Sexp::list("use", items)
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl AstNode for TypeAlias {
+    fn to_sexp(&self) -> Sexp {
+        let mut items = vec![Sexp::atom(&self.name)];
+        if !self.generic_params.is_empty() {
+            items.push(self.generic_params.to_sexp());
+        }
+        items.push(self.target.to_sexp());
+
+        Sexp::list("type-alias", items)
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// Printed like a `let` statement, with `const` in place of `let` for a constant.
+impl AstNode for GlobalDecl {
+    fn to_sexp(&self) -> Sexp {
+        let mut items = vec![
+            binding(&self.name, self.mutable),
+            Sexp::list("type", [self.ty.to_sexp()]),
+        ];
+        items.extend(self.init.as_ref().map(AstNode::to_sexp));
+
+        Sexp::list(if self.is_const { "const" } else { "let" }, items)
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// `(path a b c)` for `a::b::c`, as `:` means something else to Lisp.
+fn path(segments: &[String]) -> Sexp {
+    Sexp::list("path", segments.iter().map(Sexp::atom))
+}
+
+impl AstNode for TypeExpr {
+    fn to_sexp(&self) -> Sexp {
+        match self {
+            TypeExpr::Named { name, generics } if generics.is_empty() => Sexp::atom(name),
+            TypeExpr::Named { name, generics } => Sexp::nodes(name, generics),
+            TypeExpr::Pointer { inner, mutable } => {
+                Sexp::list(if *mutable { "ptr-mut" } else { "ptr" }, [inner.to_sexp()])
+            }
+            TypeExpr::Array { element, size } => {
+                let mut items = vec![element.to_sexp()];
+                items.extend(size.map(|size| Sexp::atom(size.to_string())));
+                Sexp::list("array", items)
+            }
+            TypeExpr::FnType { params, ret } => Sexp::list(
+                "func",
+                [
+                    Sexp::List(params.iter().map(AstNode::to_sexp).collect()),
+                    ret.to_sexp(),
+                ],
+            ),
+            TypeExpr::Tuple(types) => Sexp::nodes("tuple", types),
+            TypeExpr::Never => Sexp::atom("!"),
+        }
+    }
+
+    /// Always empty, as a type is stored without where it was written.
+    fn span(&self) -> Span {
+        Span::default()
+    }
+}
+
+impl AstNode for ExprNode {
+    fn to_sexp(&self) -> Sexp {
+        match self {
+            ExprNode::Literal { value, .. } => value.to_sexp(),
+            ExprNode::Ident { name, .. } => Sexp::list("ident", [Sexp::atom(name)]),
+            ExprNode::BinOp { op, lhs, rhs, .. } => {
+                Sexp::list(bin_op(*op), [lhs.to_sexp(), rhs.to_sexp()])
+            }
+            ExprNode::UnaryOp { op, operand, .. } => {
+                let op = match op {
+                    UnOpKind::Neg => "-",
+                    UnOpKind::Not => "not",
+                    UnOpKind::BitNot => "~",
+                };
+                Sexp::list(op, [operand.to_sexp()])
+            }
+            ExprNode::Call { callee, args, .. } => Sexp::list(
+                "call",
+                std::iter::once(callee.to_sexp()).chain(args.iter().map(AstNode::to_sexp)),
+            ),
+            ExprNode::Builtin { builtin, args, .. } => Sexp::list(
+                builtin.name(),
+                builtin
+                    .type_arg()
+                    .map(AstNode::to_sexp)
+                    .into_iter()
+                    .chain(args.iter().map(AstNode::to_sexp)),
+            ),
+            ExprNode::Index { base, index, .. } => {
+                Sexp::list("index", [base.to_sexp(), index.to_sexp()])
+            }
+            ExprNode::Field { base, name, .. } => {
+                Sexp::list("field", [base.to_sexp(), Sexp::atom(name)])
+            }
+            ExprNode::If(if_expr) => if_expr.to_sexp(),
+            ExprNode::Match(match_expr) => match_expr.to_sexp(),
+            ExprNode::Closure(closure) => closure.to_sexp(),
+            ExprNode::Block(block) => block.to_sexp(),
+        }
+    }
+
+    fn span(&self) -> Span {
+        ExprNode::span(self)
+    }
+}
+
+fn bin_op(op: BinOpKind) -> &'static str {
+    match op {
+        BinOpKind::Add => "+",
+        BinOpKind::Sub => "-",
+        BinOpKind::Mul => "*",
+        BinOpKind::Div => "/",
+        BinOpKind::Rem => "%",
+        BinOpKind::BitAnd => "&",
+        BinOpKind::BitXor => "^",
+        BinOpKind::BitOr => "bit-or",
+        BinOpKind::Or => "or",
+        BinOpKind::Lt => "<",
+        BinOpKind::Gt => ">",
+        BinOpKind::Pipe => "pipe",
+    }
+}
+
+impl AstNode for LiteralValue {
+    fn to_sexp(&self) -> Sexp {
+        match self {
+            LiteralValue::Int(value) => Sexp::atom(value.to_string()),
+            LiteralValue::UInt(value) => Sexp::atom(value.to_string()),
+            // Debug formatting keeps the `.0` that marks whole numbers as floats
+            LiteralValue::Float(value) => Sexp::atom(format!("{value:?}")),
+            LiteralValue::String(value) => Sexp::atom(quote(value)),
+            LiteralValue::Char(value) => {
+                Sexp::list("char", [Sexp::atom(quote(&value.to_string()))])
+            }
+            LiteralValue::Bool(value) => Sexp::atom(value.to_string()),
+        }
+    }
+
+    /// Always empty, as a literal value is stored without where it was written.
+    fn span(&self) -> Span {
+        Span::default()
+    }
+}
+
+/// `text` as a Lisp string, where only `"` and `\` need escaping.
+fn quote(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+
+    quoted.push('"');
+    for char in text.chars() {
+        if matches!(char, '"' | '\\') {
+            quoted.push('\\');
+        }
+        quoted.push(char);
+    }
+    quoted.push('"');
+
+    quoted
+}
+
+/// The span from the start of the first of `spans` to the end of the last, or an empty span if
+/// there are none.
+fn merged(mut spans: impl Iterator<Item = Span>) -> Span {
+    let first = spans.next().unwrap_or_default();
+    spans.fold(first, |span, next| span.merge(&next))
+}
+
+impl AstNode for ClosureExpr {
+    fn to_sexp(&self) -> Sexp {
+        let mut items = vec![];
+
+        match self.capture_mode {
+            CaptureMode::Move => items.push(Sexp::atom("move")),
+            CaptureMode::Borrow => items.push(Sexp::atom("borrow")),
+            CaptureMode::Infer => {}
+        }
+        items.push(Sexp::nodes("params", &self.params));
+        items.push(self.body.to_sexp());
+
+        Sexp::list("closure", items)
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl AstNode for ClosureParam {
+    fn to_sexp(&self) -> Sexp {
+        match &self.ty {
+            Some(ty) => Sexp::List(vec![Sexp::atom(&self.name), ty.to_sexp()]),
+            None => Sexp::atom(&self.name),
+        }
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl AstNode for ClosureBody {
+    fn to_sexp(&self) -> Sexp {
+        match self {
+            ClosureBody::Expr(expr) => expr.to_sexp(),
+            ClosureBody::Block(block) => block.to_sexp(),
+        }
+    }
+
+    fn span(&self) -> Span {
+        match self {
+            ClosureBody::Expr(expr) => expr.span(),
+            ClosureBody::Block(block) => block.span,
+        }
+    }
+}
+
+impl AstNode for IfExpr {
+    fn to_sexp(&self) -> Sexp {
+        let mut items = vec![self.cond.to_sexp(), self.then_block.to_sexp()];
+        items.extend(self.else_block.as_deref().map(AstNode::to_sexp));
+
+        Sexp::list("if", items)
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl AstNode for IfOrBlock {
+    fn to_sexp(&self) -> Sexp {
+        match self {
+            IfOrBlock::If(if_expr) => if_expr.to_sexp(),
+            IfOrBlock::Block(block) => block.to_sexp(),
+        }
+    }
+
+    fn span(&self) -> Span {
+        match self {
+            IfOrBlock::If(if_expr) => if_expr.span,
+            IfOrBlock::Block(block) => block.span,
+        }
+    }
+}
+
+impl AstNode for MatchExpr {
+    fn to_sexp(&self) -> Sexp {
+        Sexp::list(
+            "match",
+            std::iter::once(self.scrutinee.to_sexp()).chain(self.arms.iter().map(AstNode::to_sexp)),
+        )
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl AstNode for MatchArm {
+    fn to_sexp(&self) -> Sexp {
+        let mut items = vec![self.pattern.to_sexp()];
+        if let Some(guard) = &self.guard {
+            items.push(Sexp::list("guard", [guard.to_sexp()]));
+        }
+        items.push(self.body.to_sexp());
+
+        Sexp::list("arm", items)
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl AstNode for Pattern {
+    fn to_sexp(&self) -> Sexp {
+        match self {
+            Pattern::Literal(value) => value.to_sexp(),
+            Pattern::Ident(name) => Sexp::atom(name),
+            Pattern::Wildcard => Sexp::atom("_"),
+            Pattern::Or(lhs, rhs) => Sexp::list("or", [lhs.to_sexp(), rhs.to_sexp()]),
+        }
+    }
+
+    /// Always empty, as a pattern is stored without where it was written.
+    fn span(&self) -> Span {
+        Span::default()
+    }
+}
+
+impl AstNode for Block {
+    fn to_sexp(&self) -> Sexp {
+        Sexp::list(
+            "block",
+            label(self.label.as_ref()).into_iter().chain(body(self)),
+        )
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// The statements of `block` followed by its final expression, without the `(block ...)`
+/// around them. Expression statements are wrapped in `(expr ...)`, so that they can be told apart
+/// from the final expression giving the block its value.
+fn body(block: &Block) -> Vec<Sexp> {
+    let mut items: Vec<_> = block.stmts.iter().map(AstNode::to_sexp).collect();
+    items.extend(block.final_expr.as_deref().map(AstNode::to_sexp));
+
+    items
+}
+
+fn label(label: Option<&String>) -> Option<Sexp> {
+    label.map(|label| Sexp::list("label", [Sexp::atom(label)]))
+}
+
+/// `name`, or `(mut name)` when it's declared `mut`.
+fn binding(name: &str, mutable: bool) -> Sexp {
+    match mutable {
+        true => Sexp::list("mut", [Sexp::atom(name)]),
+        false => Sexp::atom(name),
+    }
+}
+
+impl AstNode for StmtNode {
+    fn to_sexp(&self) -> Sexp {
+        match self {
+            StmtNode::Expr(expr) => Sexp::list("expr", [expr.to_sexp()]),
+            StmtNode::While(stmt) => stmt.to_sexp(),
+            StmtNode::For(stmt) => stmt.to_sexp(),
+            StmtNode::ForIn(stmt) => stmt.to_sexp(),
+            StmtNode::Let(stmt) => stmt.to_sexp(),
+            StmtNode::Assign(stmt) => stmt.to_sexp(),
+            StmtNode::Return(stmt) => stmt.to_sexp(),
+            StmtNode::Break(stmt) => stmt.to_sexp(),
+            StmtNode::Continue(stmt) => stmt.to_sexp(),
+        }
+    }
+
+    fn span(&self) -> Span {
+        StmtNode::span(self)
+    }
+}
+
+impl AstNode for WhileStmt {
+    fn to_sexp(&self) -> Sexp {
+        let mut items: Vec<_> = label(self.label.as_ref()).into_iter().collect();
+        items.push(self.cond.to_sexp());
+        items.extend(body(&self.body));
+
+        Sexp::list("while", items)
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl AstNode for ForStmt {
+    fn to_sexp(&self) -> Sexp {
+        let mut items: Vec<_> = label(self.label.as_ref()).into_iter().collect();
+        if let Some(init) = &self.init {
+            items.push(Sexp::list("init", [init.to_sexp()]));
+        }
+        if let Some(cond) = &self.cond {
+            items.push(Sexp::list("cond", [cond.to_sexp()]));
+        }
+        if let Some(post) = &self.post {
+            items.push(Sexp::list("post", [post.to_sexp()]));
+        }
+        items.extend(body(&self.body));
+
+        Sexp::list("for", items)
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl AstNode for ForInStmt {
+    fn to_sexp(&self) -> Sexp {
+        let mut items: Vec<_> = label(self.label.as_ref()).into_iter().collect();
+        items.push(binding(&self.binding, self.mutable));
+        items.push(self.iterable.to_sexp());
+        items.extend(body(&self.body));
+
+        Sexp::list("for-in", items)
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl AstNode for LetStmt {
+    fn to_sexp(&self) -> Sexp {
+        let mut items = vec![binding(&self.name, self.mutable)];
+        if let Some(ty) = &self.ty {
+            items.push(Sexp::list("type", [ty.to_sexp()]));
+        }
+        items.extend(self.init.as_ref().map(AstNode::to_sexp));
+
+        Sexp::list("let", items)
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl AstNode for AssignStmt {
+    fn to_sexp(&self) -> Sexp {
+        let op = match self.op {
+            Some(op) => format!("{}=", bin_op(op)),
+            None => "=".to_owned(),
+        };
+
+        Sexp::list(&op, [self.target.to_sexp(), self.value.to_sexp()])
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl AstNode for ReturnStmt {
+    fn to_sexp(&self) -> Sexp {
+        Sexp::list("return", self.value.as_ref().map(AstNode::to_sexp))
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl AstNode for BreakStmt {
+    fn to_sexp(&self) -> Sexp {
+        let items = label(self.label.as_ref())
+            .into_iter()
+            .chain(self.value.as_ref().map(AstNode::to_sexp));
+
+        Sexp::list("break", items)
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl AstNode for ContinueStmt {
+    fn to_sexp(&self) -> Sexp {
+        Sexp::list("continue", label(self.label.as_ref()))
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
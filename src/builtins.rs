@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+use std::mem::{self, Discriminant};
+
+use crate::ast::TypeExpr;
+
+/// A function built into the compiler, called like any other function but emitted as an LLVM
+/// intrinsic, constant or instruction.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BuiltinFn {
+    /// `sizeof(T)`, the size of `T` in bytes.
+    SizeOf(TypeExpr),
+    /// `alignof(T)`, the alignment of `T` in bytes.
+    AlignOf(TypeExpr),
+    /// `unreachable()`, promising that control never gets there.
+    Unreachable,
+    /// `memcpy(dst, src, len)`, copying `len` bytes from `src` to `dst`, which mustn't overlap.
+    Memcpy,
+    /// `memset(dst, byte, len)`, setting `len` bytes from `dst` onwards to `byte`.
+    Memset,
+}
+
+impl BuiltinFn {
+    /// The keyword calling this builtin.
+    pub fn name(&self) -> &'static str {
+        match self {
+            BuiltinFn::SizeOf(_) => "sizeof",
+            BuiltinFn::AlignOf(_) => "alignof",
+            BuiltinFn::Unreachable => "unreachable",
+            BuiltinFn::Memcpy => "memcpy",
+            BuiltinFn::Memset => "memset",
+        }
+    }
+
+    /// The type given in place of arguments, for `sizeof` and `alignof`.
+    pub fn type_arg(&self) -> Option<&TypeExpr> {
+        match self {
+            BuiltinFn::SizeOf(ty) | BuiltinFn::AlignOf(ty) => Some(ty),
+            _ => None,
+        }
+    }
+}
+
+/// What a call to a builtin takes and gives.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BuiltinSignature {
+    pub params: Vec<TypeExpr>,
+    pub ret: TypeExpr,
+    /// The LLVM intrinsic called, or `None` for builtins emitted as a constant or an instruction.
+    pub intrinsic: Option<&'static str>,
+}
+
+/// The signature of each builtin, which its arguments are checked against.
+#[derive(Debug, Clone)]
+pub struct BuiltinRegistry {
+    signatures: HashMap<Discriminant<BuiltinFn>, BuiltinSignature>,
+}
+
+impl BuiltinRegistry {
+    pub fn new() -> Self {
+        let bytes = || TypeExpr::Pointer {
+            inner: Box::new(TypeExpr::named("u8")),
+            mutable: false,
+        };
+        let bytes_mut = || TypeExpr::Pointer {
+            inner: Box::new(TypeExpr::named("u8")),
+            mutable: true,
+        };
+        let u64 = || TypeExpr::named("u64");
+
+        let signatures = [
+            (
+                BuiltinFn::SizeOf(TypeExpr::void()),
+                BuiltinSignature {
+                    params: vec![],
+                    ret: u64(),
+                    intrinsic: None,
+                },
+            ),
+            (
+                BuiltinFn::AlignOf(TypeExpr::void()),
+                BuiltinSignature {
+                    params: vec![],
+                    ret: u64(),
+                    intrinsic: None,
+                },
+            ),
+            (
+                BuiltinFn::Unreachable,
+                BuiltinSignature {
+                    params: vec![],
+                    ret: TypeExpr::Never,
+                    intrinsic: None,
+                },
+            ),
+            (
+                BuiltinFn::Memcpy,
+                BuiltinSignature {
+                    params: vec![bytes_mut(), bytes(), u64()],
+                    ret: TypeExpr::void(),
+                    intrinsic: Some("llvm.memcpy.p0i8.p0i8.i64"),
+                },
+            ),
+            (
+                BuiltinFn::Memset,
+                BuiltinSignature {
+                    params: vec![bytes_mut(), TypeExpr::named("u8"), u64()],
+                    ret: TypeExpr::void(),
+                    intrinsic: Some("llvm.memset.p0i8.i64"),
+                },
+            ),
+        ];
+
+        BuiltinRegistry {
+            signatures: signatures
+                .into_iter()
+                .map(|(builtin, signature)| (mem::discriminant(&builtin), signature))
+                .collect(),
+        }
+    }
+
+    /// The signature of `builtin`, which is the same whatever type `sizeof` or `alignof` is given.
+    pub fn signature(&self, builtin: &BuiltinFn) -> &BuiltinSignature {
+        &self.signatures[&mem::discriminant(builtin)]
+    }
+}
+
+impl Default for BuiltinRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,281 @@
+use crate::ast::{Block, ExprNode, FnDecl, IfExpr, IfOrBlock, StmtNode};
+
+/// A straight run of statements, left through its `terminator`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BasicBlock {
+    pub id: u32,
+    pub stmts: Vec<StmtNode>,
+    pub terminator: Terminator,
+}
+
+impl BasicBlock {
+    /// The blocks that can run right after this one.
+    pub fn successors(&self) -> Vec<u32> {
+        match &self.terminator {
+            Terminator::Return(_) => vec![],
+            Terminator::Jump(target) => vec![*target],
+            Terminator::ConditionalJump { then, else_, .. } => vec![*then, *else_],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Terminator {
+    Return(Option<ExprNode>),
+    Jump(u32),
+    ConditionalJump {
+        cond: ExprNode,
+        then: u32,
+        else_: u32,
+    },
+}
+
+/// The basic blocks of a function body, where each block's id is its index in `blocks`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ControlFlowGraph {
+    pub entry: u32,
+    pub blocks: Vec<BasicBlock>,
+}
+
+impl ControlFlowGraph {
+    pub fn block(&self, id: u32) -> &BasicBlock {
+        &self.blocks[id as usize]
+    }
+}
+
+/// Split the body of `fn_decl` into basic blocks at each `if`, `while`, `for`, `return`, `break`
+/// and `continue` statement.
+///
+/// Only statements are split, so an `if` used as a value, a `match` or a `for ... in` loop stays
+/// inside the statement it's part of. Statements after a jump end up in a block nothing jumps to.
+/// The value given to a `break` isn't kept, as the graph only records where it jumps to.
+pub fn build_cfg(fn_decl: &FnDecl) -> ControlFlowGraph {
+    let mut builder = CfgBuilder {
+        blocks: vec![],
+        current: None,
+        targets: vec![],
+    };
+
+    let entry = builder.new_block();
+    builder.current = Some(entry);
+
+    for stmt in &fn_decl.body.stmts {
+        builder.lower_stmt(stmt);
+    }
+    builder.terminate(Terminator::Return(
+        fn_decl.body.final_expr.as_deref().cloned(),
+    ));
+
+    let blocks = builder
+        .blocks
+        .into_iter()
+        .enumerate()
+        .map(|(id, (stmts, terminator))| BasicBlock {
+            id: id as u32,
+            stmts,
+            terminator: terminator.unwrap_or(Terminator::Return(None)),
+        })
+        .collect();
+
+    ControlFlowGraph { entry, blocks }
+}
+
+/// Where `break` and `continue` go inside a loop or labelled block.
+struct JumpTarget {
+    label: Option<String>,
+    break_to: u32,
+    /// `None` for blocks, which can't be continued.
+    continue_to: Option<u32>,
+}
+
+struct CfgBuilder {
+    blocks: Vec<(Vec<StmtNode>, Option<Terminator>)>,
+    /// The block statements are added to, or `None` right after a jump.
+    current: Option<u32>,
+    targets: Vec<JumpTarget>,
+}
+
+impl CfgBuilder {
+    fn new_block(&mut self) -> u32 {
+        self.blocks.push((vec![], None));
+        (self.blocks.len() - 1) as u32
+    }
+
+    fn push(&mut self, stmt: StmtNode) {
+        // Code after a jump still gets a block, even though it can never run
+        let current = match self.current {
+            Some(current) => current,
+            None => {
+                let block = self.new_block();
+                self.current = Some(block);
+                block
+            }
+        };
+
+        self.blocks[current as usize].0.push(stmt);
+    }
+
+    /// End the current block, if there is one, with `terminator`.
+    fn terminate(&mut self, terminator: Terminator) {
+        if let Some(current) = self.current.take() {
+            self.blocks[current as usize].1 = Some(terminator);
+        }
+    }
+
+    fn jump_into(&mut self, block: u32) {
+        self.terminate(Terminator::Jump(block));
+        self.current = Some(block);
+    }
+
+    fn lower_stmt(&mut self, stmt: &StmtNode) {
+        match stmt {
+            StmtNode::Expr(ExprNode::If(if_expr)) => self.lower_if(if_expr),
+            StmtNode::Expr(ExprNode::Block(block)) => {
+                let exit = self.new_block();
+
+                self.targets.push(JumpTarget {
+                    label: block.label.clone(),
+                    break_to: exit,
+                    continue_to: None,
+                });
+                self.lower_block(block);
+                self.targets.pop();
+
+                self.jump_into(exit);
+            }
+            StmtNode::While(stmt) => {
+                let header = self.new_block();
+                let body = self.new_block();
+                let exit = self.new_block();
+
+                self.jump_into(header);
+                self.terminate(Terminator::ConditionalJump {
+                    cond: (*stmt.cond).clone(),
+                    then: body,
+                    else_: exit,
+                });
+
+                self.current = Some(body);
+                self.lower_loop_body(stmt.label.as_ref(), &stmt.body, header, exit);
+
+                self.current = Some(exit);
+            }
+            StmtNode::For(stmt) => {
+                if let Some(init) = &stmt.init {
+                    self.lower_stmt(init);
+                }
+
+                let header = self.new_block();
+                let body = self.new_block();
+                let step = self.new_block();
+                let exit = self.new_block();
+
+                self.jump_into(header);
+                self.terminate(match &stmt.cond {
+                    Some(cond) => Terminator::ConditionalJump {
+                        cond: cond.clone(),
+                        then: body,
+                        else_: exit,
+                    },
+                    None => Terminator::Jump(body),
+                });
+
+                self.current = Some(body);
+                self.lower_loop_body(stmt.label.as_ref(), &stmt.body, step, exit);
+
+                self.current = Some(step);
+                if let Some(post) = &stmt.post {
+                    self.lower_stmt(post);
+                }
+                self.terminate(Terminator::Jump(header));
+
+                self.current = Some(exit);
+            }
+            StmtNode::Return(stmt) => self.terminate(Terminator::Return(stmt.value.clone())),
+            StmtNode::Break(stmt) => {
+                let target = self.find_target(stmt.label.as_deref(), false);
+
+                if let Some(target) = target.map(|target| target.break_to) {
+                    self.terminate(Terminator::Jump(target));
+                }
+            }
+            StmtNode::Continue(stmt) => {
+                let target = self.find_target(stmt.label.as_deref(), true);
+
+                if let Some(target) = target.and_then(|target| target.continue_to) {
+                    self.terminate(Terminator::Jump(target));
+                }
+            }
+            _ => self.push(stmt.clone()),
+        }
+    }
+
+    /// Lower a loop's body, where `continue` goes to `continue_to`, and jump back there after.
+    fn lower_loop_body(
+        &mut self,
+        label: Option<&String>,
+        body: &Block,
+        continue_to: u32,
+        break_to: u32,
+    ) {
+        self.targets.push(JumpTarget {
+            label: label.cloned(),
+            break_to,
+            continue_to: Some(continue_to),
+        });
+        self.lower_block(body);
+        self.targets.pop();
+
+        self.terminate(Terminator::Jump(continue_to));
+    }
+
+    fn lower_if(&mut self, if_expr: &IfExpr) {
+        let then = self.new_block();
+        let merge = self.new_block();
+        let else_ = match if_expr.else_block {
+            Some(_) => self.new_block(),
+            None => merge,
+        };
+
+        self.terminate(Terminator::ConditionalJump {
+            cond: (*if_expr.cond).clone(),
+            then,
+            else_,
+        });
+
+        self.current = Some(then);
+        self.lower_block(&if_expr.then_block);
+        self.terminate(Terminator::Jump(merge));
+
+        if let Some(else_block) = if_expr.else_block.as_deref() {
+            self.current = Some(else_);
+
+            match else_block {
+                IfOrBlock::If(else_if) => self.lower_if(else_if),
+                IfOrBlock::Block(block) => self.lower_block(block),
+            }
+            self.terminate(Terminator::Jump(merge));
+        }
+
+        self.current = Some(merge);
+    }
+
+    /// Lower the statements of a block whose value isn't used.
+    fn lower_block(&mut self, block: &Block) {
+        for stmt in &block.stmts {
+            self.lower_stmt(stmt);
+        }
+
+        if let Some(expr) = &block.final_expr {
+            self.lower_stmt(&StmtNode::Expr((**expr).clone()));
+        }
+    }
+
+    /// The innermost loop, or block too when breaking to a label, that a jump with `label` leaves.
+    fn find_target(&self, label: Option<&str>, is_continue: bool) -> Option<&JumpTarget> {
+        self.targets.iter().rev().find(|target| match label {
+            Some(label) => target.label.as_deref() == Some(label),
+            None => target.continue_to.is_some(),
+        } && (!is_continue || target.continue_to.is_some()))
+    }
+}
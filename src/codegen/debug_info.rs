@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::path::Path;
+
+use crate::ast::TypeExpr;
+use crate::source::SourceFile;
+use crate::span::Span;
+
+use super::string_table::escape;
+
+/// Builds the DWARF metadata describing a module for debuggers, for [`LlvmEmitter`] to attach to
+/// what it emits.
+///
+/// Every function gets a `DISubprogram`, every local a `DILocalVariable`, and every instruction a
+/// `DILocation` pointing at the statement it came from.
+///
+/// [`LlvmEmitter`]: super::emitter::LlvmEmitter
+pub struct DebugInfoEmitter {
+    source: SourceFile,
+    /// The metadata nodes, each numbered by its index.
+    nodes: Vec<String>,
+    file: u32,
+    compile_unit: u32,
+    empty_tuple: u32,
+    /// Shared by every function, as parameter and return types aren't described.
+    subroutine_type: u32,
+    basic_types: HashMap<String, u32>,
+    locations: HashMap<(usize, usize, u32), u32>,
+    /// The function being emitted.
+    subprogram: Option<u32>,
+    location: Span,
+    /// Set once a variable has been declared, which needs `@llvm.dbg.declare` declaring.
+    uses_declare: bool,
+}
+
+impl DebugInfoEmitter {
+    pub fn new(source: &SourceFile) -> Self {
+        let mut emitter = DebugInfoEmitter {
+            source: source.clone(),
+            nodes: vec![],
+            file: 0,
+            compile_unit: 0,
+            empty_tuple: 0,
+            subroutine_type: 0,
+            basic_types: HashMap::new(),
+            locations: HashMap::new(),
+            subprogram: None,
+            location: Span::default(),
+            uses_declare: false,
+        };
+
+        let path = Path::new(source.name());
+        let filename = path.file_name().map_or(source.name().into(), |name| {
+            name.to_string_lossy().into_owned()
+        });
+        let directory = path
+            .parent()
+            .map_or(String::new(), |dir| dir.to_string_lossy().into_owned());
+
+        emitter.file = emitter.node(format!(
+            "!DIFile(filename: \"{}\", directory: \"{}\")",
+            escape(&filename),
+            escape(&directory)
+        ));
+        emitter.compile_unit = emitter.node(format!(
+            "distinct !DICompileUnit(language: DW_LANG_C, file: !{}, producer: \"llvm-compiler\", \
+             isOptimized: false, runtimeVersion: 0, emissionKind: FullDebug)",
+            emitter.file
+        ));
+        emitter.empty_tuple = emitter.node("!{}".into());
+        emitter.subroutine_type = emitter.node(format!(
+            "!DISubroutineType(types: !{})",
+            emitter.empty_tuple
+        ));
+
+        emitter
+    }
+
+    /// Start describing the function `name` declared at `span`, returning its `DISubprogram`.
+    pub fn begin_function(&mut self, name: &str, span: Span) -> u32 {
+        let (line, _) = self.source.line_col(span.start);
+
+        let subprogram = self.node(format!(
+            "distinct !DISubprogram(name: \"{}\", scope: !{file}, file: !{file}, line: {line}, \
+             type: !{}, scopeLine: {line}, spFlags: DISPFlagDefinition, unit: !{}, \
+             retainedNodes: !{})",
+            escape(name),
+            self.subroutine_type,
+            self.compile_unit,
+            self.empty_tuple,
+            file = self.file,
+        ));
+
+        self.subprogram = Some(subprogram);
+        self.location = span;
+
+        subprogram
+    }
+
+    /// Attribute the instructions emitted from now on to the code at `span`.
+    pub fn set_location(&mut self, span: Span) {
+        self.location = span;
+    }
+
+    /// The `DILocation` of the current location in the current function.
+    pub fn current_location(&mut self) -> Option<u32> {
+        let subprogram = self.subprogram?;
+        let (line, col) = self.source.line_col(self.location.start);
+
+        if let Some(location) = self.locations.get(&(line, col, subprogram)) {
+            return Some(*location);
+        }
+
+        let location = self.node(format!(
+            "!DILocation(line: {line}, column: {col}, scope: !{subprogram})"
+        ));
+        self.locations.insert((line, col, subprogram), location);
+
+        Some(location)
+    }
+
+    /// Describe the local variable `name` declared at `span`, returning its `DILocalVariable`.
+    /// Parameters give their 1-based position as `arg`.
+    pub fn local_variable(
+        &mut self,
+        name: &str,
+        ty: &TypeExpr,
+        span: Span,
+        arg: Option<usize>,
+    ) -> Option<u32> {
+        let subprogram = self.subprogram?;
+        let (line, _) = self.source.line_col(span.start);
+        self.uses_declare = true;
+
+        let mut variable = format!(
+            "!DILocalVariable(name: \"{}\", scope: !{subprogram}, file: !{}, line: {line}",
+            escape(name),
+            self.file
+        );
+        if let Some(arg) = arg {
+            write!(variable, ", arg: {arg}").unwrap();
+        }
+        // Types without a description are still shown by name, just not their value
+        if let Some(ty) = self.basic_type(ty) {
+            write!(variable, ", type: !{ty}").unwrap();
+        }
+        variable.push(')');
+
+        Some(self.node(variable))
+    }
+
+    /// Append the metadata to the module in `output`.
+    pub fn finish(self, output: &mut String) {
+        if self.uses_declare {
+            writeln!(
+                output,
+                "\ndeclare void @llvm.dbg.declare(metadata, metadata, metadata)"
+            )
+            .unwrap();
+        }
+
+        writeln!(output, "\n!llvm.dbg.cu = !{{!{}}}", self.compile_unit).unwrap();
+
+        let flags = self.nodes.len();
+        writeln!(
+            output,
+            "!llvm.module.flags = !{{!{}, !{}}}",
+            flags,
+            flags + 1
+        )
+        .unwrap();
+
+        for (id, node) in self.nodes.iter().enumerate() {
+            writeln!(output, "!{id} = {node}").unwrap();
+        }
+        writeln!(output, "!{flags} = !{{i32 7, !\"Dwarf Version\", i32 4}}").unwrap();
+        writeln!(
+            output,
+            "!{} = !{{i32 2, !\"Debug Info Version\", i32 3}}",
+            flags + 1
+        )
+        .unwrap();
+    }
+
+    /// The `DIBasicType` of a primitive type, or `None` for any other type.
+    fn basic_type(&mut self, ty: &TypeExpr) -> Option<u32> {
+        let TypeExpr::Named { name, .. } = ty else {
+            return None;
+        };
+
+        let (size, encoding) = match name.as_str() {
+            "i8" => (8, "DW_ATE_signed"),
+            "i16" => (16, "DW_ATE_signed"),
+            "i32" => (32, "DW_ATE_signed"),
+            "i64" => (64, "DW_ATE_signed"),
+            "i128" => (128, "DW_ATE_signed"),
+            "u8" => (8, "DW_ATE_unsigned"),
+            "u16" => (16, "DW_ATE_unsigned"),
+            "u32" => (32, "DW_ATE_unsigned"),
+            "u64" => (64, "DW_ATE_unsigned"),
+            "f32" => (32, "DW_ATE_float"),
+            "f64" => (64, "DW_ATE_float"),
+            "bool" => (8, "DW_ATE_boolean"),
+            _ => return None,
+        };
+
+        if let Some(id) = self.basic_types.get(name) {
+            return Some(*id);
+        }
+
+        let id = self.node(format!(
+            "!DIBasicType(name: \"{name}\", size: {size}, encoding: {encoding})"
+        ));
+        self.basic_types.insert(name.clone(), id);
+
+        Some(id)
+    }
+
+    fn node(&mut self, node: String) -> u32 {
+        self.nodes.push(node);
+        (self.nodes.len() - 1) as u32
+    }
+}
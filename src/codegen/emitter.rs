@@ -0,0 +1,1269 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::ast::{BinOpKind, LiteralValue, StructDecl, TypeExpr, UnOpKind};
+use crate::builtins::{BuiltinFn, BuiltinRegistry};
+#[cfg(feature = "debug-info")]
+use crate::source::SourceFile;
+use crate::span::Span;
+use crate::typeck::typed::{TypedBlock, TypedExpr, TypedExprKind, TypedFn, TypedModule, TypedStmt};
+
+#[cfg(feature = "debug-info")]
+use super::debug_info::DebugInfoEmitter;
+use super::error::CodegenError;
+use super::global::GlobalVarEmitter;
+use super::phi::PhiNode;
+use super::signature::{FnSignature, SignatureRegistry};
+use super::string_table::{self, StringTable};
+use super::target::{DataLayout, Target};
+
+/// Writes textual LLVM IR for a type checked module.
+pub struct LlvmEmitter {
+    output: String,
+    /// Instructions of the function being emitted.
+    body: String,
+    /// Stack slots of the function being emitted, kept apart so they all end up in the entry block.
+    allocas: String,
+    tmp_counter: u32,
+    label_counter: u32,
+    /// Where `break` and `continue` jump to, innermost last.
+    break_targets: Vec<BreakTarget>,
+    /// The stack slot and type of each local in scope.
+    locals: HashMap<String, (String, TypeExpr)>,
+    /// How many slots have been made for each name, to keep shadowed locals apart.
+    slot_counts: HashMap<String, u32>,
+    structs: HashMap<String, StructDecl>,
+    strings: StringTable,
+    globals: GlobalVarEmitter,
+    bounds_checking: bool,
+    /// Set once a bounds check has been emitted, which needs `@panic_out_of_bounds` declaring.
+    uses_bounds_panic: bool,
+    builtins: BuiltinRegistry,
+    /// The signatures of the LLVM intrinsics called by builtins so far, which need declaring.
+    intrinsics: Vec<String>,
+    /// The functions of the module being emitted, and those called so far without being in it.
+    signatures: SignatureRegistry,
+    /// The functions called without being defined or declared in the module, which need declaring.
+    undeclared: Vec<FnSignature>,
+    /// Set once the current basic block has ended, after which nothing more can be added to it.
+    terminated: bool,
+    /// The label of the basic block being emitted.
+    current_block: String,
+    target_triple: Option<String>,
+    /// Used to give `sizeof` and `alignof` as constants, which otherwise LLVM works out.
+    data_layout: Option<DataLayout>,
+    #[cfg(feature = "debug-info")]
+    debug_info: Option<DebugInfoEmitter>,
+}
+
+impl LlvmEmitter {
+    pub fn new() -> Self {
+        LlvmEmitter {
+            output: String::new(),
+            body: String::new(),
+            allocas: String::new(),
+            tmp_counter: 0,
+            label_counter: 0,
+            break_targets: Vec::new(),
+            locals: HashMap::new(),
+            slot_counts: HashMap::new(),
+            structs: HashMap::new(),
+            strings: StringTable::new(),
+            globals: GlobalVarEmitter::new(),
+            bounds_checking: false,
+            uses_bounds_panic: false,
+            builtins: BuiltinRegistry::new(),
+            intrinsics: Vec::new(),
+            signatures: SignatureRegistry::new(),
+            undeclared: Vec::new(),
+            terminated: false,
+            current_block: String::new(),
+            target_triple: None,
+            data_layout: None,
+            #[cfg(feature = "debug-info")]
+            debug_info: None,
+        }
+    }
+
+    /// Give the module a `target datalayout`, which describes how types are stored in memory,
+    /// like `e-p:32:32-i64:64`.
+    pub fn with_target_data_layout(mut self, layout: &str) -> Self {
+        self.data_layout = Some(DataLayout::new(layout));
+        self
+    }
+
+    /// Give the module a `target triple`, like `x86_64-unknown-linux-gnu`.
+    pub fn with_target_triple(mut self, triple: &str) -> Self {
+        self.target_triple = Some(triple.to_owned());
+        self
+    }
+
+    /// Give the module both the triple and data layout of `target`.
+    pub fn with_target(self, target: Target) -> Self {
+        self.with_target_triple(target.triple)
+            .with_target_data_layout(target.data_layout)
+    }
+
+    /// Check that indices into fixed size arrays are in bounds when the program runs, calling
+    /// `panic_out_of_bounds` when they aren't.
+    pub fn with_bounds_checking(mut self, enabled: bool) -> Self {
+        self.bounds_checking = enabled;
+        self
+    }
+
+    /// Describe the emitted code to debuggers, using `source` to find the lines it came from.
+    #[cfg(feature = "debug-info")]
+    pub fn with_debug_info(mut self, source: &SourceFile) -> Self {
+        self.debug_info = Some(DebugInfoEmitter::new(source));
+        self
+    }
+
+    /// The IR written so far.
+    #[cfg(not(feature = "debug-info"))]
+    pub fn finish(self) -> String {
+        self.output
+    }
+
+    /// The IR written so far, followed by its debug info metadata.
+    #[cfg(feature = "debug-info")]
+    pub fn finish(mut self) -> String {
+        if let Some(debug_info) = self.debug_info.take() {
+            debug_info.finish(&mut self.output);
+        }
+
+        self.output
+    }
+
+    pub fn emit_module(&mut self, module: &TypedModule) -> Result<(), CodegenError> {
+        let path = module.path.display();
+        writeln!(self.output, "; ModuleID = '{path}'").unwrap();
+        writeln!(self.output, "source_filename = \"{path}\"").unwrap();
+        if let Some(layout) = &self.data_layout {
+            writeln!(
+                self.output,
+                "target datalayout = \"{}\"",
+                string_table::escape(layout.as_str())
+            )
+            .unwrap();
+        }
+        if let Some(triple) = &self.target_triple {
+            writeln!(
+                self.output,
+                "target triple = \"{}\"",
+                string_table::escape(triple)
+            )
+            .unwrap();
+        }
+        // The string literals are only known once the functions using them have been emitted
+        let globals_at = self.output.len();
+        self.signatures = SignatureRegistry::from_module(module);
+
+        for decl in &module.structs {
+            let fields: Vec<_> = decl
+                .fields
+                .iter()
+                .map(|field| llvm_type(&field.ty))
+                .collect();
+            writeln!(
+                self.output,
+                "\n%{} = type {{ {} }}",
+                decl.name,
+                fields.join(", ")
+            )
+            .unwrap();
+
+            self.structs.insert(decl.name.clone(), decl.clone());
+        }
+
+        for global in &module.globals {
+            self.globals.add(global.clone());
+        }
+        if !self.globals.is_empty() {
+            self.output.push('\n');
+            self.globals.emit(
+                self.data_layout.as_ref(),
+                &self.structs,
+                &mut self.strings,
+                &mut self.output,
+            );
+        }
+
+        for function in &module.functions {
+            self.output.push('\n');
+            self.emit_fn(function)?;
+        }
+
+        if self.uses_bounds_panic {
+            writeln!(self.output, "\ndeclare void @panic_out_of_bounds()").unwrap();
+        }
+        for signature in &self.intrinsics {
+            writeln!(self.output, "\ndeclare {signature}").unwrap();
+        }
+        for signature in &self.undeclared {
+            writeln!(self.output, "\n{}", signature.declaration()).unwrap();
+        }
+
+        if !self.strings.is_empty() {
+            let mut globals = String::from("\n");
+            self.strings.emit(&mut globals);
+            self.output.insert_str(globals_at, &globals);
+        }
+
+        Ok(())
+    }
+
+    pub fn emit_fn(&mut self, function: &TypedFn) -> Result<(), CodegenError> {
+        match function.abi.as_deref() {
+            Some("C") => {
+                self.emit_fn_declaration(function);
+                return Ok(());
+            }
+            Some(abi) => return Err(unsupported(&format!("the {abi:?} ABI"), function.span)),
+            None => {}
+        }
+
+        self.tmp_counter = 0;
+        self.label_counter = 0;
+        self.locals.clear();
+        self.slot_counts.clear();
+        self.terminated = false;
+        self.current_block = "entry".into();
+
+        // Globals are used through their address like locals are, until a local shadows them
+        for global in self.globals.iter() {
+            self.locals.insert(
+                global.name.clone(),
+                (format!("@{}", global.name), global.ty.clone()),
+            );
+        }
+
+        let params: Vec<_> = function
+            .params
+            .iter()
+            .map(|param| format!("{} %{}", llvm_type(&param.ty), param.name))
+            .collect();
+
+        let subprogram = self.begin_debug_function(function);
+
+        writeln!(
+            self.output,
+            "define {} @{}({}){subprogram} {{",
+            llvm_type(&function.ret),
+            function.name,
+            params.join(", ")
+        )
+        .unwrap();
+
+        // Parameters get stack slots like any other local so they can be assigned to
+        for (idx, param) in function.params.iter().enumerate() {
+            let ptr = self.emit_local_var(&param.name, &param.ty);
+            self.emit_store(&ptr, &format!("%{}", param.name), &param.ty);
+            self.declare_debug_variable(&param.name, &param.ty, &ptr, param.span, Some(idx + 1));
+        }
+
+        let value = self.emit_block(&function.body)?;
+
+        if !self.terminated {
+            match &function.body.final_expr {
+                _ if function.ret.is_void() => self.instruction("ret void"),
+                Some(_) if !value.is_empty() => {
+                    self.instruction(&format!("ret {} {value}", llvm_type(&function.ret)))
+                }
+                // Type checking doesn't yet make sure every path returns
+                _ => self.instruction("unreachable"),
+            }
+        }
+
+        writeln!(self.output, "entry:").unwrap();
+        self.output.push_str(&std::mem::take(&mut self.allocas));
+        self.output.push_str(&std::mem::take(&mut self.body));
+        writeln!(self.output, "}}").unwrap();
+
+        Ok(())
+    }
+
+    /// Declare the `extern` function `function`, which is defined outside the module.
+    fn emit_fn_declaration(&mut self, function: &TypedFn) {
+        writeln!(self.output, "{}", FnSignature::from(function).declaration()).unwrap();
+    }
+
+    /// Make a stack slot for the local `name`, returning a pointer to it.
+    pub fn emit_local_var(&mut self, name: &str, ty: &TypeExpr) -> String {
+        let count = self.slot_counts.entry(name.to_owned()).or_default();
+        let ptr = match *count {
+            0 => format!("%{name}.addr"),
+            n => format!("%{name}.addr{n}"),
+        };
+        *count += 1;
+
+        writeln!(self.allocas, "  {ptr} = alloca {}", llvm_type(ty)).unwrap();
+        self.locals
+            .insert(name.to_owned(), (ptr.clone(), ty.clone()));
+
+        ptr
+    }
+
+    pub fn emit_store(&mut self, ptr: &str, val: &str, ty: &TypeExpr) {
+        let ty = llvm_type(ty);
+        self.instruction(&format!("store {ty} {val}, {ty}* {ptr}"));
+    }
+
+    /// Load the value behind `ptr` into a new temporary, which is returned.
+    pub fn emit_load(&mut self, ptr: &str, ty: &TypeExpr) -> String {
+        let ty = llvm_type(ty);
+        self.assign(&format!("load {ty}, {ty}* {ptr}"))
+    }
+
+    /// Emit the statements of `block`, returning the value of its final expression.
+    fn emit_block(&mut self, block: &TypedBlock) -> Result<String, CodegenError> {
+        // Only labelled blocks can be broken out of
+        let Some(label) = &block.label else {
+            return self.emit_scope(block);
+        };
+
+        let exit = self.fresh_label("block_exit");
+        self.break_targets.push(BreakTarget {
+            label: Some(label.clone()),
+            continue_to: None,
+            break_to: exit.clone(),
+        });
+
+        let value = self.emit_scope(block);
+        self.break_targets.pop();
+        let value = value?;
+
+        // A `break` leaves the block without a value, so there's nothing to return from it
+        self.emit_br(&exit);
+        self.start_block(&exit);
+
+        Ok(value)
+    }
+
+    fn emit_scope(&mut self, block: &TypedBlock) -> Result<String, CodegenError> {
+        // Locals declared in the block go out of scope at its end
+        let outer = self.locals.clone();
+        let value = self.emit_block_contents(block);
+        self.locals = outer;
+
+        value
+    }
+
+    fn emit_block_contents(&mut self, block: &TypedBlock) -> Result<String, CodegenError> {
+        for stmt in &block.stmts {
+            // Anything after a `return` can never run
+            if self.terminated {
+                return Ok(String::new());
+            }
+
+            self.emit_stmt(stmt)?;
+        }
+
+        match &block.final_expr {
+            Some(expr) if !self.terminated => {
+                self.set_debug_location(expr.span);
+                self.emit_expr(expr)
+            }
+            _ => Ok(String::new()),
+        }
+    }
+
+    pub fn emit_stmt(&mut self, stmt: &TypedStmt) -> Result<(), CodegenError> {
+        self.set_debug_location(stmt_span(stmt));
+
+        match stmt {
+            TypedStmt::Expr(expr) => {
+                self.emit_expr(expr)?;
+            }
+            TypedStmt::Let {
+                name,
+                ty,
+                init,
+                span,
+                ..
+            } => {
+                // The initializer can't see the new local
+                let value = init.as_ref().map(|init| self.emit_expr(init)).transpose()?;
+                let ptr = self.emit_local_var(name, ty);
+                self.declare_debug_variable(name, ty, &ptr, *span, None);
+
+                if let Some(value) = value {
+                    self.emit_store(&ptr, &value, ty);
+                }
+            }
+            TypedStmt::Assign {
+                target, op, value, ..
+            } => {
+                let mut value = self.emit_expr(value)?;
+                let ptr = self.emit_place(target)?;
+                let ty = &target.ty;
+
+                if let Some(op) = op {
+                    let current = self.emit_load(&ptr, ty);
+                    value = self.emit_binary(*op, ty, &current, &value);
+                }
+
+                self.emit_store(&ptr, &value, ty);
+            }
+            TypedStmt::Return { value, .. } => {
+                match value {
+                    Some(value) => {
+                        let result = self.emit_expr(value)?;
+                        self.instruction(&format!("ret {} {result}", llvm_type(&value.ty)));
+                    }
+                    None => self.instruction("ret void"),
+                }
+                self.terminated = true;
+            }
+            TypedStmt::While {
+                label, cond, body, ..
+            } => self.emit_while(label.as_deref(), cond, body)?,
+            TypedStmt::For {
+                label,
+                init,
+                cond,
+                post,
+                body,
+                ..
+            } => self.emit_for(
+                label.as_deref(),
+                init.as_deref(),
+                cond.as_ref(),
+                post.as_deref(),
+                body,
+            )?,
+            TypedStmt::ForIn {
+                label,
+                binding,
+                iterable,
+                body,
+                ..
+            } => self.emit_for_in(label.as_deref(), binding, iterable, body)?,
+            TypedStmt::Break {
+                label, value: None, ..
+            } => {
+                let target = self.break_target(label.as_deref(), false);
+                self.emit_br(&target);
+            }
+            TypedStmt::Continue { label, .. } => {
+                let target = self.break_target(label.as_deref(), true);
+                self.emit_br(&target);
+            }
+            _ => return Err(unsupported("this statement", stmt_span(stmt))),
+        }
+
+        Ok(())
+    }
+
+    /// Branch on `cond_val` to the `then` and `else` blocks, which both continue in a merge block.
+    /// Returns the value of whichever branch ran, if the `if` has one.
+    pub fn emit_if(
+        &mut self,
+        cond_val: &str,
+        then_block: &TypedBlock,
+        else_block: Option<&TypedExpr>,
+        ty: &TypeExpr,
+    ) -> Result<String, CodegenError> {
+        let then_label = self.fresh_label("then");
+        let merge_label = self.fresh_label("merge");
+        let else_label = match else_block {
+            Some(_) => self.fresh_label("else"),
+            None => merge_label.clone(),
+        };
+
+        // The value is picked by a `phi` once the branches meet again, from each that gets there
+        let mut result = match ty {
+            TypeExpr::Never => None,
+            _ if ty.is_void() => None,
+            _ => Some(PhiNode::new(String::new(), ty.clone())),
+        };
+        // Without an `else`, nothing was given to pick when the condition doesn't hold
+        if let Some(phi) = result.as_mut().filter(|_| else_block.is_none()) {
+            phi.add_incoming("undef", self.current_block.clone());
+        }
+
+        self.instruction(&format!(
+            "br i1 {cond_val}, label %{then_label}, label %{else_label}"
+        ));
+
+        self.start_block(&then_label);
+        let value = self.emit_block(then_block)?;
+        self.emit_branch_result(result.as_mut(), value);
+        // Without an `else`, the merge block is where a false condition jumps to
+        let mut merges = else_block.is_none() || !self.terminated;
+        self.emit_br(&merge_label);
+
+        if let Some(else_block) = else_block {
+            self.start_block(&else_label);
+            let value = self.emit_expr(else_block)?;
+            self.emit_branch_result(result.as_mut(), value);
+            merges |= !self.terminated;
+            self.emit_br(&merge_label);
+        }
+
+        self.start_block(&merge_label);
+
+        // Nothing gets here when both branches return, so the block can't be left open
+        if !merges {
+            self.instruction("unreachable");
+            self.terminated = true;
+
+            return Ok(match result {
+                Some(_) => "undef".into(),
+                None => String::new(),
+            });
+        }
+
+        match result {
+            Some(mut phi) => {
+                phi.result = self.fresh();
+                Ok(self.emit_phi(&phi))
+            }
+            None => Ok(String::new()),
+        }
+    }
+
+    /// Emit `phi` at the start of the current block, returning its result.
+    pub fn emit_phi(&mut self, phi: &PhiNode) -> String {
+        self.instruction(&phi.instruction());
+        phi.result.clone()
+    }
+
+    /// Loop over `body` for as long as `cond` holds, checking it before every iteration.
+    pub fn emit_while(
+        &mut self,
+        label: Option<&str>,
+        cond: &TypedExpr,
+        body: &TypedBlock,
+    ) -> Result<(), CodegenError> {
+        let header = self.fresh_label("loop_header");
+        let body_label = self.fresh_label("loop_body");
+        let exit = self.fresh_label("loop_exit");
+
+        self.emit_br(&header);
+        self.start_block(&header);
+        let cond_val = self.emit_expr(cond)?;
+        self.instruction(&format!(
+            "br i1 {cond_val}, label %{body_label}, label %{exit}"
+        ));
+
+        self.start_block(&body_label);
+        self.emit_loop_body(label, body, &header, &exit)?;
+        self.start_block(&exit);
+
+        Ok(())
+    }
+
+    /// Emit a C-style `for` loop. `continue` jumps to `post`, which then checks `cond` again.
+    pub fn emit_for(
+        &mut self,
+        label: Option<&str>,
+        init: Option<&TypedStmt>,
+        cond: Option<&TypedExpr>,
+        post: Option<&TypedStmt>,
+        body: &TypedBlock,
+    ) -> Result<(), CodegenError> {
+        // Locals declared by `init` go out of scope after the loop
+        let outer = self.locals.clone();
+
+        if let Some(init) = init {
+            self.emit_stmt(init)?;
+        }
+
+        let header = self.fresh_label("loop_header");
+        let body_label = self.fresh_label("loop_body");
+        let step = self.fresh_label("loop_step");
+        let exit = self.fresh_label("loop_exit");
+
+        self.emit_br(&header);
+        self.start_block(&header);
+        match cond {
+            Some(cond) => {
+                let cond_val = self.emit_expr(cond)?;
+                self.instruction(&format!(
+                    "br i1 {cond_val}, label %{body_label}, label %{exit}"
+                ));
+            }
+            None => self.emit_br(&body_label),
+        }
+
+        self.start_block(&body_label);
+        self.emit_loop_body(label, body, &step, &exit)?;
+
+        self.start_block(&step);
+        if let Some(post) = post {
+            self.emit_stmt(post)?;
+        }
+        self.emit_br(&header);
+        self.start_block(&exit);
+
+        self.locals = outer;
+
+        Ok(())
+    }
+
+    /// Emit a `for x in array` loop, which counts up an index into a copy of the array.
+    pub fn emit_for_in(
+        &mut self,
+        label: Option<&str>,
+        binding: &str,
+        iterable: &TypedExpr,
+        body: &TypedBlock,
+    ) -> Result<(), CodegenError> {
+        let TypeExpr::Array {
+            element,
+            size: Some(size),
+        } = &iterable.ty
+        else {
+            unreachable!("type checking only allows iterating over arrays of known length");
+        };
+        let index_ty = TypeExpr::named("i64");
+
+        let array = self.emit_expr(iterable)?;
+        let outer = self.locals.clone();
+
+        let array_ptr = self.emit_local_var("for.array", &iterable.ty);
+        self.emit_store(&array_ptr, &array, &iterable.ty);
+        let index_ptr = self.emit_local_var("for.index", &index_ty);
+        self.emit_store(&index_ptr, "0", &index_ty);
+
+        let header = self.fresh_label("loop_header");
+        let body_label = self.fresh_label("loop_body");
+        let step = self.fresh_label("loop_step");
+        let exit = self.fresh_label("loop_exit");
+
+        self.emit_br(&header);
+        self.start_block(&header);
+        let index = self.emit_load(&index_ptr, &index_ty);
+        let in_range = self.assign(&format!("icmp ult i64 {index}, {size}"));
+        self.instruction(&format!(
+            "br i1 {in_range}, label %{body_label}, label %{exit}"
+        ));
+
+        self.start_block(&body_label);
+        let array_llvm_ty = llvm_type(&iterable.ty);
+        let element_ptr = self.assign(&format!(
+            "getelementptr inbounds {array_llvm_ty}, {array_llvm_ty}* {array_ptr}, i64 0, i64 {index}"
+        ));
+        let value = self.emit_load(&element_ptr, element);
+        let binding_ptr = self.emit_local_var(binding, element);
+        self.emit_store(&binding_ptr, &value, element);
+
+        self.emit_loop_body(label, body, &step, &exit)?;
+
+        self.start_block(&step);
+        let index = self.emit_load(&index_ptr, &index_ty);
+        let next = self.assign(&format!("add i64 {index}, 1"));
+        self.emit_store(&index_ptr, &next, &index_ty);
+        self.emit_br(&header);
+        self.start_block(&exit);
+
+        self.locals = outer;
+
+        Ok(())
+    }
+
+    /// Emit the body of a loop whose `continue` jumps to `step`, ending with a jump there.
+    fn emit_loop_body(
+        &mut self,
+        label: Option<&str>,
+        body: &TypedBlock,
+        step: &str,
+        exit: &str,
+    ) -> Result<(), CodegenError> {
+        self.break_targets.push(BreakTarget {
+            label: label.map(str::to_owned),
+            continue_to: Some(step.to_owned()),
+            break_to: exit.to_owned(),
+        });
+        let result = self.emit_block(body);
+        self.break_targets.pop();
+        result?;
+
+        self.emit_br(step);
+
+        Ok(())
+    }
+
+    /// The block a `break` or `continue` with `label` jumps to.
+    fn break_target(&self, label: Option<&str>, is_continue: bool) -> String {
+        self.break_targets
+            .iter()
+            .rev()
+            // Blocks can't be continued, and an unlabelled `break` only leaves loops
+            .filter(|target| target.continue_to.is_some() || label.is_some())
+            .find(|target| label.is_none() || target.label.as_deref() == label)
+            .and_then(|target| match is_continue {
+                true => target.continue_to.clone(),
+                false => Some(target.break_to.clone()),
+            })
+            // Name resolution has made sure every `break` has somewhere to go
+            .expect("break or continue outside of a loop")
+    }
+
+    /// Have `result` take `value` when coming from the current block, unless it has already
+    /// jumped somewhere else.
+    fn emit_branch_result(&mut self, result: Option<&mut PhiNode>, value: String) {
+        if let Some(phi) = result.filter(|_| !self.terminated) {
+            phi.add_incoming(value, self.current_block.clone());
+        }
+    }
+
+    /// Jump to `label`, unless the current block has already ended.
+    fn emit_br(&mut self, label: &str) {
+        if !self.terminated {
+            self.instruction(&format!("br label %{label}"));
+            self.terminated = true;
+        }
+    }
+
+    fn start_block(&mut self, label: &str) {
+        writeln!(self.body, "{label}:").unwrap();
+        self.terminated = false;
+        self.current_block = label.to_owned();
+    }
+
+    /// Emit the instructions computing `expr`, returning the LLVM value holding the result. Calls
+    /// to `void` functions return an empty string.
+    pub fn emit_expr(&mut self, expr: &TypedExpr) -> Result<String, CodegenError> {
+        match &expr.kind {
+            TypedExprKind::Literal(value) => {
+                Ok(literal_constant(value, &expr.ty, &mut self.strings))
+            }
+            TypedExprKind::Ident(name) => match self.locals.get(name).cloned() {
+                Some((ptr, ty)) => Ok(self.emit_load(&ptr, &ty)),
+                None => Err(unsupported("functions used as values", expr.span)),
+            },
+            TypedExprKind::BinOp { op, lhs, rhs } => {
+                let lhs_value = self.emit_expr(lhs)?;
+                let rhs_value = self.emit_expr(rhs)?;
+
+                Ok(self.emit_binary(*op, &lhs.ty, &lhs_value, &rhs_value))
+            }
+            TypedExprKind::UnaryOp { op, operand } => {
+                let value = self.emit_expr(operand)?;
+                let ty = llvm_type(&operand.ty);
+
+                let instruction = match op {
+                    UnOpKind::Neg if operand.ty.is_float() => format!("fneg {ty} {value}"),
+                    UnOpKind::Neg => format!("sub {ty} 0, {value}"),
+                    UnOpKind::Not => format!("xor i1 {value}, true"),
+                    UnOpKind::BitNot => format!("xor {ty} {value}, -1"),
+                };
+
+                Ok(self.assign(&instruction))
+            }
+            TypedExprKind::Call {
+                callee,
+                args,
+                type_args,
+            } => {
+                if !type_args.is_empty() {
+                    return Err(unsupported(
+                        "generic calls before monomorphization",
+                        expr.span,
+                    ));
+                }
+
+                let mut values = Vec::with_capacity(args.len());
+
+                for arg in args {
+                    values.push((self.emit_expr(arg)?, arg.ty.clone()));
+                }
+
+                Ok(self.emit_call(callee, &values, &expr.ty))
+            }
+            TypedExprKind::Builtin { builtin, args } => {
+                let mut values = Vec::with_capacity(args.len());
+
+                for arg in args {
+                    values.push(self.emit_expr(arg)?);
+                }
+
+                Ok(self.emit_builtin_call(builtin, &values))
+            }
+            TypedExprKind::Block(block) => self.emit_block(block),
+            TypedExprKind::Field { .. } | TypedExprKind::Index { .. } => {
+                let ptr = self.emit_place(expr)?;
+                Ok(self.emit_load(&ptr, &expr.ty))
+            }
+            TypedExprKind::Match { .. } => Err(unsupported("match expressions", expr.span)),
+            TypedExprKind::Closure { .. } => Err(unsupported("closures", expr.span)),
+            TypedExprKind::If {
+                cond,
+                then_block,
+                else_block,
+            } => {
+                let cond_val = self.emit_expr(cond)?;
+                self.emit_if(&cond_val, then_block, else_block.as_deref(), &expr.ty)
+            }
+        }
+    }
+
+    /// Get a pointer to the memory `expr` refers to, so it can be loaded from or stored to.
+    fn emit_place(&mut self, expr: &TypedExpr) -> Result<String, CodegenError> {
+        match &expr.kind {
+            TypedExprKind::Ident(name) => match self.locals.get(name) {
+                Some((ptr, _)) => Ok(ptr.clone()),
+                None => Err(unsupported("functions used as values", expr.span)),
+            },
+            TypedExprKind::Field { base, name, index } => {
+                let base_ptr = self.emit_place(base)?;
+
+                match &base.ty {
+                    TypeExpr::Named { name: ty, .. } => {
+                        let decl = self.structs[ty].clone();
+                        self.emit_field_access(&base_ptr, &decl, name, expr.span)
+                    }
+                    // Tuples have no declaration, type checking already found the index
+                    ty => {
+                        let ty = llvm_type(ty);
+                        Ok(self.assign(&format!(
+                            "getelementptr inbounds {ty}, {ty}* {base_ptr}, i32 0, i32 {index}"
+                        )))
+                    }
+                }
+            }
+            TypedExprKind::Index { base, index } => {
+                // Unsized arrays are already pointers to their first element
+                let array_ptr = match &base.ty {
+                    TypeExpr::Array { size: None, .. } => self.emit_expr(base)?,
+                    _ => self.emit_place(base)?,
+                };
+
+                let index_val = self.emit_expr(index)?;
+                let index_val = self.emit_index_cast(&index_val, &index.ty);
+
+                Ok(self.emit_array_index(&array_ptr, &base.ty, &index_val))
+            }
+            // Anything else is a temporary, which needs a slot of its own to point to
+            _ => {
+                let value = self.emit_expr(expr)?;
+                let ptr = self.emit_local_var("tmp", &expr.ty);
+                self.emit_store(&ptr, &value, &expr.ty);
+
+                Ok(ptr)
+            }
+        }
+    }
+
+    /// Get a pointer to the field `field_name` of the struct behind `struct_ptr`.
+    pub fn emit_field_access(
+        &mut self,
+        struct_ptr: &str,
+        struct_ty: &StructDecl,
+        field_name: &str,
+        span: Span,
+    ) -> Result<String, CodegenError> {
+        let index = struct_ty
+            .fields
+            .iter()
+            .position(|field| field.name == field_name)
+            .ok_or_else(|| CodegenError::UnknownField {
+                struct_name: struct_ty.name.clone(),
+                field_name: field_name.to_owned(),
+                span: span.into(),
+            })?;
+
+        let ty = format!("%{}", struct_ty.name);
+        Ok(self.assign(&format!(
+            "getelementptr inbounds {ty}, {ty}* {struct_ptr}, i32 0, i32 {index}"
+        )))
+    }
+
+    /// Get a pointer to the element at `index_val`, an `i64`, of the array `array_ty` behind
+    /// `array_ptr`.
+    pub fn emit_array_index(
+        &mut self,
+        array_ptr: &str,
+        array_ty: &TypeExpr,
+        index_val: &str,
+    ) -> String {
+        let TypeExpr::Array { element, size } = array_ty else {
+            unreachable!("type checking only allows indexing arrays");
+        };
+        let element_ty = llvm_type(element);
+
+        let Some(size) = size else {
+            return self.assign(&format!(
+                "getelementptr inbounds {element_ty}, {element_ty}* {array_ptr}, i64 {index_val}"
+            ));
+        };
+
+        if self.bounds_checking {
+            let in_bounds = self.assign(&format!("icmp ult i64 {index_val}, {size}"));
+            let ok_label = self.fresh_label("in_bounds");
+            let panic_label = self.fresh_label("out_of_bounds");
+
+            self.instruction(&format!(
+                "br i1 {in_bounds}, label %{ok_label}, label %{panic_label}"
+            ));
+            self.start_block(&panic_label);
+            self.instruction("call void @panic_out_of_bounds()");
+            self.instruction("unreachable");
+            self.start_block(&ok_label);
+
+            self.uses_bounds_panic = true;
+        }
+
+        let ty = llvm_type(array_ty);
+        self.assign(&format!(
+            "getelementptr inbounds {ty}, {ty}* {array_ptr}, i64 0, i64 {index_val}"
+        ))
+    }
+
+    /// Convert an index of integer type `ty` to the `i64` that `getelementptr` expects.
+    fn emit_index_cast(&mut self, index_val: &str, ty: &TypeExpr) -> String {
+        let from = llvm_type(ty);
+
+        match from.as_str() {
+            "i64" => index_val.to_owned(),
+            "i128" => self.assign(&format!("trunc i128 {index_val} to i64")),
+            _ if is_unsigned(ty) => self.assign(&format!("zext {from} {index_val} to i64")),
+            _ => self.assign(&format!("sext {from} {index_val} to i64")),
+        }
+    }
+
+    /// Call the function `callee` directly with `args`, given as values and their types. Returns
+    /// the result, or an empty string when `ret_ty` is `void`.
+    pub fn emit_call(
+        &mut self,
+        callee: &str,
+        args: &[(String, TypeExpr)],
+        ret_ty: &TypeExpr,
+    ) -> String {
+        // Anything else is declared from the types it's first called with
+        if !self.signatures.contains(callee) {
+            let signature = FnSignature {
+                name: callee.to_owned(),
+                params: args.iter().map(|(_, ty)| ty.clone()).collect(),
+                ret: ret_ty.clone(),
+                variadic: false,
+            };
+            self.signatures.insert(signature.clone());
+            self.undeclared.push(signature);
+        }
+
+        let args: Vec<_> = args
+            .iter()
+            .map(|(value, ty)| format!("{} {value}", llvm_type(ty)))
+            .collect();
+
+        let call = format!("call {} @{callee}({})", llvm_type(ret_ty), args.join(", "));
+
+        if ret_ty.is_void() {
+            self.instruction(&call);
+            String::new()
+        } else {
+            self.assign(&call)
+        }
+    }
+
+    /// Call `builtin` with the values of its arguments, which have the types of its signature.
+    /// Returns the result, or an empty string when there's none.
+    pub fn emit_builtin_call(&mut self, builtin: &BuiltinFn, args: &[String]) -> String {
+        let intrinsic = self
+            .builtins
+            .signature(builtin)
+            .intrinsic
+            .unwrap_or_default();
+
+        let layout = self.data_layout.as_ref();
+        let known = match builtin {
+            BuiltinFn::SizeOf(ty) => layout.and_then(|layout| layout.size_of(ty, &self.structs)),
+            BuiltinFn::AlignOf(ty) => layout.and_then(|layout| layout.align_of(ty, &self.structs)),
+            _ => None,
+        };
+        if let Some(known) = known {
+            return known.to_string();
+        }
+
+        match builtin {
+            // The offset of the element after the first in an array starting at null
+            BuiltinFn::SizeOf(ty) => {
+                let ty = llvm_type(ty);
+                format!("ptrtoint ({ty}* getelementptr ({ty}, {ty}* null, i32 1) to i64)")
+            }
+            // The offset of a field placed after a single byte, which is padded to its alignment
+            BuiltinFn::AlignOf(ty) => {
+                let ty = llvm_type(ty);
+                let pair = format!("{{ i8, {ty} }}");
+                format!(
+                    "ptrtoint ({ty}* getelementptr ({pair}, {pair}* null, i32 0, i32 1) to i64)"
+                )
+            }
+            BuiltinFn::Unreachable => {
+                self.instruction("unreachable");
+                self.terminated = true;
+                String::new()
+            }
+            // The last argument makes the access volatile
+            BuiltinFn::Memcpy => {
+                self.declare_intrinsic(format!("void @{intrinsic}(i8*, i8*, i64, i1 immarg)"));
+                self.instruction(&format!(
+                    "call void @{intrinsic}(i8* {}, i8* {}, i64 {}, i1 false)",
+                    args[0], args[1], args[2]
+                ));
+                String::new()
+            }
+            BuiltinFn::Memset => {
+                self.declare_intrinsic(format!("void @{intrinsic}(i8*, i8, i64, i1 immarg)"));
+                self.instruction(&format!(
+                    "call void @{intrinsic}(i8* {}, i8 {}, i64 {}, i1 false)",
+                    args[0], args[1], args[2]
+                ));
+                String::new()
+            }
+        }
+    }
+
+    /// Declare an intrinsic by its signature, once however many times it's called.
+    fn declare_intrinsic(&mut self, signature: String) {
+        if !self.intrinsics.contains(&signature) {
+            self.intrinsics.push(signature);
+        }
+    }
+
+    fn emit_binary(&mut self, op: BinOpKind, ty: &TypeExpr, lhs: &str, rhs: &str) -> String {
+        let float = ty.is_float();
+        let unsigned = is_unsigned(ty);
+
+        let instruction = match op {
+            BinOpKind::Add if float => "fadd",
+            BinOpKind::Sub if float => "fsub",
+            BinOpKind::Mul if float => "fmul",
+            BinOpKind::Div if float => "fdiv",
+            BinOpKind::Rem if float => "frem",
+            BinOpKind::Lt if float => "fcmp olt",
+            BinOpKind::Gt if float => "fcmp ogt",
+            BinOpKind::Add => "add",
+            BinOpKind::Sub => "sub",
+            BinOpKind::Mul => "mul",
+            BinOpKind::Div if unsigned => "udiv",
+            BinOpKind::Div => "sdiv",
+            BinOpKind::Rem if unsigned => "urem",
+            BinOpKind::Rem => "srem",
+            BinOpKind::Lt if unsigned => "icmp ult",
+            BinOpKind::Lt => "icmp slt",
+            BinOpKind::Gt if unsigned => "icmp ugt",
+            BinOpKind::Gt => "icmp sgt",
+            BinOpKind::BitAnd => "and",
+            BinOpKind::BitXor => "xor",
+            // TODO: `||` should only evaluate its right side when needed
+            BinOpKind::BitOr | BinOpKind::Or => "or",
+            // Type checking turns pipes into calls
+            BinOpKind::Pipe => unreachable!("pipes are checked as calls"),
+        };
+
+        self.assign(&format!("{instruction} {} {lhs}, {rhs}", llvm_type(ty)))
+    }
+
+    /// A new temporary name, unique within the current function.
+    fn fresh(&mut self) -> String {
+        let name = format!("%tmp{}", self.tmp_counter);
+        self.tmp_counter += 1;
+        name
+    }
+
+    /// A new basic block label, unique within the current function.
+    fn fresh_label(&mut self, name: &str) -> String {
+        let label = format!("{name}{}", self.label_counter);
+        self.label_counter += 1;
+        label
+    }
+
+    /// Emit `instruction`, storing its result in a new temporary which is returned.
+    fn assign(&mut self, instruction: &str) -> String {
+        let result = self.fresh();
+        self.instruction(&format!("{result} = {instruction}"));
+        result
+    }
+
+    fn instruction(&mut self, instruction: &str) {
+        write!(self.body, "  {instruction}").unwrap();
+
+        #[cfg(feature = "debug-info")]
+        if let Some(location) = self
+            .debug_info
+            .as_mut()
+            .and_then(DebugInfoEmitter::current_location)
+        {
+            write!(self.body, ", !dbg !{location}").unwrap();
+        }
+
+        self.body.push('\n');
+    }
+
+    /// Start the debug info for `function`, returning what to add to its `define` line.
+    #[cfg(feature = "debug-info")]
+    fn begin_debug_function(&mut self, function: &TypedFn) -> String {
+        match &mut self.debug_info {
+            Some(debug_info) => {
+                let subprogram = debug_info.begin_function(&function.name, function.span);
+                format!(" !dbg !{subprogram}")
+            }
+            None => String::new(),
+        }
+    }
+
+    #[cfg(not(feature = "debug-info"))]
+    fn begin_debug_function(&mut self, _function: &TypedFn) -> String {
+        String::new()
+    }
+
+    /// Attribute the instructions emitted from now on to the code at `span`.
+    #[cfg(feature = "debug-info")]
+    fn set_debug_location(&mut self, span: Span) {
+        if let Some(debug_info) = &mut self.debug_info {
+            debug_info.set_location(span);
+        }
+    }
+
+    #[cfg(not(feature = "debug-info"))]
+    fn set_debug_location(&mut self, _span: Span) {}
+
+    /// Tell debuggers that the local `name` declared at `span` lives at `ptr`.
+    #[cfg(feature = "debug-info")]
+    fn declare_debug_variable(
+        &mut self,
+        name: &str,
+        ty: &TypeExpr,
+        ptr: &str,
+        span: Span,
+        arg: Option<usize>,
+    ) {
+        let Some(variable) = self
+            .debug_info
+            .as_mut()
+            .and_then(|debug_info| debug_info.local_variable(name, ty, span, arg))
+        else {
+            return;
+        };
+
+        self.instruction(&format!(
+            "call void @llvm.dbg.declare(metadata {}* {ptr}, metadata !{variable}, metadata !DIExpression())",
+            llvm_type(ty)
+        ));
+    }
+
+    #[cfg(not(feature = "debug-info"))]
+    fn declare_debug_variable(
+        &mut self,
+        _name: &str,
+        _ty: &TypeExpr,
+        _ptr: &str,
+        _span: Span,
+        _arg: Option<usize>,
+    ) {
+    }
+}
+
+struct BreakTarget {
+    label: Option<String>,
+    /// Only loops can be continued.
+    continue_to: Option<String>,
+    break_to: String,
+}
+
+impl Default for LlvmEmitter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The LLVM spelling of `ty`.
+pub fn llvm_type(ty: &TypeExpr) -> String {
+    match ty {
+        TypeExpr::Named { name, .. } => match name.as_str() {
+            // LLVM integers have no signedness, that lives in the instructions instead
+            "i8" | "u8" => "i8".into(),
+            "i16" | "u16" => "i16".into(),
+            "i32" | "u32" => "i32".into(),
+            "i64" | "u64" => "i64".into(),
+            "i128" => "i128".into(),
+            "f32" => "float".into(),
+            "f64" => "double".into(),
+            "bool" => "i1".into(),
+            "void" => "void".into(),
+            _ => format!("%{name}"),
+        },
+        // LLVM has no `void*`
+        TypeExpr::Pointer { inner, .. } if inner.is_void() => "i8*".into(),
+        TypeExpr::Pointer { inner, .. } => format!("{}*", llvm_type(inner)),
+        TypeExpr::Array {
+            element,
+            size: Some(size),
+        } => format!("[{size} x {}]", llvm_type(element)),
+        TypeExpr::Array {
+            element,
+            size: None,
+        } => format!("{}*", llvm_type(element)),
+        TypeExpr::FnType { params, ret } => {
+            let params: Vec<_> = params.iter().map(llvm_type).collect();
+            format!("{} ({})*", llvm_type(ret), params.join(", "))
+        }
+        TypeExpr::Tuple(types) if types.is_empty() => "{}".into(),
+        TypeExpr::Tuple(types) => {
+            let types: Vec<_> = types.iter().map(llvm_type).collect();
+            format!("{{ {} }}", types.join(", "))
+        }
+        TypeExpr::Never => "void".into(),
+    }
+}
+
+/// The LLVM constant for a literal of type `ty`. Strings are interned in `strings`, and become a
+/// pointer to their first byte.
+pub(super) fn literal_constant(
+    value: &LiteralValue,
+    ty: &TypeExpr,
+    strings: &mut StringTable,
+) -> String {
+    match value {
+        LiteralValue::Int(value) => value.to_string(),
+        LiteralValue::UInt(value) => value.to_string(),
+        LiteralValue::Char(value) => u32::from(*value).to_string(),
+        LiteralValue::Bool(value) => value.to_string(),
+        LiteralValue::Float(value) => float_constant(*value, ty),
+        LiteralValue::String(value) => {
+            let global = strings.intern(value);
+            let ty = string_table::array_type(value);
+
+            format!("getelementptr inbounds ({ty}, {ty}* {global}, i64 0, i64 0)")
+        }
+    }
+}
+
+/// LLVM writes float constants as the hex bits of an `f64`, even for `float`, which can only hold
+/// the ones that are exactly representable as an `f32`.
+fn float_constant(value: f64, ty: &TypeExpr) -> String {
+    let value = match ty {
+        TypeExpr::Named { name, .. } if name == "f32" => value as f32 as f64,
+        _ => value,
+    };
+
+    format!("0x{:016X}", value.to_bits())
+}
+
+fn is_unsigned(ty: &TypeExpr) -> bool {
+    matches!(ty, TypeExpr::Named { name, .. } if name.starts_with('u'))
+}
+
+fn stmt_span(stmt: &TypedStmt) -> Span {
+    match stmt {
+        TypedStmt::Expr(expr) => expr.span,
+        TypedStmt::Let { span, .. }
+        | TypedStmt::Assign { span, .. }
+        | TypedStmt::While { span, .. }
+        | TypedStmt::For { span, .. }
+        | TypedStmt::ForIn { span, .. }
+        | TypedStmt::Return { span, .. }
+        | TypedStmt::Break { span, .. }
+        | TypedStmt::Continue { span, .. } => *span,
+    }
+}
+
+fn unsupported(what: &str, span: Span) -> CodegenError {
+    CodegenError::Unsupported {
+        what: what.into(),
+        span: span.into(),
+    }
+}
@@ -0,0 +1,45 @@
+use std::io;
+
+use miette::{Diagnostic, SourceSpan};
+use thiserror::Error;
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum CodegenError {
+    #[error("Code generation doesn't support {what} yet")]
+    Unsupported {
+        what: String,
+
+        #[label("not supported")]
+        span: SourceSpan,
+    },
+
+    #[error("Struct {struct_name} has no field {field_name}")]
+    UnknownField {
+        struct_name: String,
+        field_name: String,
+
+        #[label("unknown field")]
+        span: SourceSpan,
+    },
+
+    #[error("LLVM can't generate code for target {0}")]
+    #[diagnostic(help("run `llc --version` to see the supported targets"))]
+    UnsupportedTarget(String),
+
+    #[error("LLVM failed to generate code: {0}")]
+    Llvm(String),
+
+    #[error("Couldn't run the LLVM code generator")]
+    #[diagnostic(help("make sure LLVM's `llc` is installed and on PATH"))]
+    Io(#[from] io::Error),
+}
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum OptimizationError {
+    #[error("Couldn't run the LLVM optimizer")]
+    #[diagnostic(help("make sure LLVM's `opt` is installed and on PATH"))]
+    Io(#[from] io::Error),
+
+    #[error("LLVM rejected the module: {0}")]
+    Llvm(String),
+}
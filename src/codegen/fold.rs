@@ -0,0 +1,304 @@
+use crate::ast::{BinOpKind, LiteralValue, TypeExpr, UnOpKind};
+use crate::compiler::error::CompilerWarning;
+use crate::span::Span;
+use crate::typeck::typed::{TypedBlock, TypedExpr, TypedExprKind, TypedModule, TypedStmt};
+
+/// Evaluates the operators in a type checked module whose operands are all literals, replacing
+/// them with the literal they evaluate to.
+///
+/// Integer arithmetic that overflows its type is left alone, to wrap around at run time as it
+/// would have without folding, and a warning is recorded for it.
+#[derive(Debug, Default)]
+pub struct ConstantFolder {
+    warnings: Vec<CompilerWarning>,
+}
+
+impl ConstantFolder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The warnings found by everything folded so far.
+    pub fn warnings(&self) -> &[CompilerWarning] {
+        &self.warnings
+    }
+
+    pub fn fold_module(&mut self, module: &mut TypedModule) {
+        for function in &mut module.functions {
+            self.fold_block(&mut function.body);
+        }
+    }
+
+    /// The value of `expr`, or `None` if it can't be known at compile time.
+    pub fn fold_expr(&mut self, expr: &TypedExpr) -> Option<LiteralValue> {
+        match &expr.kind {
+            TypedExprKind::Literal(value) => Some(value.clone()),
+            TypedExprKind::BinOp { op, lhs, rhs } => {
+                let lhs_value = self.fold_expr(lhs)?;
+                let rhs_value = self.fold_expr(rhs)?;
+
+                self.binary(*op, lhs_value, rhs_value, &lhs.ty, expr.span)
+            }
+            TypedExprKind::UnaryOp { op, operand } => {
+                let value = self.fold_expr(operand)?;
+
+                self.unary(*op, value, &expr.ty, expr.span)
+            }
+            _ => None,
+        }
+    }
+
+    fn fold_block(&mut self, block: &mut TypedBlock) {
+        for stmt in &mut block.stmts {
+            self.fold_stmt(stmt);
+        }
+
+        if let Some(expr) = &mut block.final_expr {
+            self.fold_in_place(expr);
+        }
+    }
+
+    fn fold_stmt(&mut self, stmt: &mut TypedStmt) {
+        match stmt {
+            TypedStmt::Expr(expr) => self.fold_in_place(expr),
+            TypedStmt::Let { init, .. } => {
+                if let Some(init) = init {
+                    self.fold_in_place(init);
+                }
+            }
+            TypedStmt::Assign { target, value, .. } => {
+                self.fold_in_place(target);
+                self.fold_in_place(value);
+            }
+            TypedStmt::While { cond, body, .. } => {
+                self.fold_in_place(cond);
+                self.fold_block(body);
+            }
+            TypedStmt::For {
+                init,
+                cond,
+                post,
+                body,
+                ..
+            } => {
+                if let Some(init) = init {
+                    self.fold_stmt(init);
+                }
+                if let Some(cond) = cond {
+                    self.fold_in_place(cond);
+                }
+                if let Some(post) = post {
+                    self.fold_stmt(post);
+                }
+                self.fold_block(body);
+            }
+            TypedStmt::ForIn { iterable, body, .. } => {
+                self.fold_in_place(iterable);
+                self.fold_block(body);
+            }
+            TypedStmt::Return { value, .. } | TypedStmt::Break { value, .. } => {
+                if let Some(value) = value {
+                    self.fold_in_place(value);
+                }
+            }
+            TypedStmt::Continue { .. } => {}
+        }
+    }
+
+    /// Fold the operands of `expr` before `expr` itself, so that an operand that couldn't be
+    /// folded is only evaluated, and warned about, once.
+    fn fold_in_place(&mut self, expr: &mut TypedExpr) {
+        let value = match &mut expr.kind {
+            TypedExprKind::Literal(_) | TypedExprKind::Ident(_) => None,
+            TypedExprKind::BinOp { op, lhs, rhs } => {
+                self.fold_in_place(lhs);
+                self.fold_in_place(rhs);
+
+                match (&lhs.kind, &rhs.kind) {
+                    (TypedExprKind::Literal(lhs_value), TypedExprKind::Literal(rhs_value)) => {
+                        let (lhs_value, rhs_value) = (lhs_value.clone(), rhs_value.clone());
+                        self.binary(*op, lhs_value, rhs_value, &lhs.ty, expr.span)
+                    }
+                    _ => None,
+                }
+            }
+            TypedExprKind::UnaryOp { op, operand } => {
+                self.fold_in_place(operand);
+
+                match &operand.kind {
+                    TypedExprKind::Literal(value) => {
+                        self.unary(*op, value.clone(), &expr.ty, expr.span)
+                    }
+                    _ => None,
+                }
+            }
+            TypedExprKind::Call { args, .. } | TypedExprKind::Builtin { args, .. } => {
+                for arg in args {
+                    self.fold_in_place(arg);
+                }
+                None
+            }
+            TypedExprKind::Index { base, index } => {
+                self.fold_in_place(base);
+                self.fold_in_place(index);
+                None
+            }
+            TypedExprKind::Field { base, .. } => {
+                self.fold_in_place(base);
+                None
+            }
+            TypedExprKind::If {
+                cond,
+                then_block,
+                else_block,
+            } => {
+                self.fold_in_place(cond);
+                self.fold_block(then_block);
+                if let Some(else_block) = else_block {
+                    self.fold_in_place(else_block);
+                }
+                None
+            }
+            TypedExprKind::Match { scrutinee, arms } => {
+                self.fold_in_place(scrutinee);
+
+                for arm in arms {
+                    if let Some(guard) = &mut arm.guard {
+                        self.fold_in_place(guard);
+                    }
+                    self.fold_in_place(&mut arm.body);
+                }
+                None
+            }
+            TypedExprKind::Closure { body, .. } => {
+                self.fold_in_place(body);
+                None
+            }
+            TypedExprKind::Block(block) => {
+                self.fold_block(block);
+                None
+            }
+        };
+
+        if let Some(value) = value {
+            expr.kind = TypedExprKind::Literal(value);
+        }
+    }
+
+    /// Apply `op` to two literals, where `ty` is the type of the operands.
+    fn binary(
+        &mut self,
+        op: BinOpKind,
+        lhs: LiteralValue,
+        rhs: LiteralValue,
+        ty: &TypeExpr,
+        span: Span,
+    ) -> Option<LiteralValue> {
+        match (lhs, rhs) {
+            (LiteralValue::Int(lhs), LiteralValue::Int(rhs)) => {
+                let value = match op {
+                    BinOpKind::Lt => return Some(LiteralValue::Bool(lhs < rhs)),
+                    BinOpKind::Gt => return Some(LiteralValue::Bool(lhs > rhs)),
+                    // Dividing by zero is left to fail at run time
+                    BinOpKind::Div | BinOpKind::Rem if rhs == 0 => return None,
+                    BinOpKind::Add => lhs.checked_add(rhs),
+                    BinOpKind::Sub => lhs.checked_sub(rhs),
+                    BinOpKind::Mul => lhs.checked_mul(rhs),
+                    BinOpKind::Div => lhs.checked_div(rhs),
+                    BinOpKind::Rem => lhs.checked_rem(rhs),
+                    BinOpKind::BitAnd => Some(lhs & rhs),
+                    BinOpKind::BitXor => Some(lhs ^ rhs),
+                    BinOpKind::BitOr => Some(lhs | rhs),
+                    BinOpKind::Or | BinOpKind::Pipe => return None,
+                };
+
+                self.check_int(value, ty, span)
+            }
+            (LiteralValue::Bool(lhs), LiteralValue::Bool(rhs)) => match op {
+                BinOpKind::Or => Some(LiteralValue::Bool(lhs || rhs)),
+                _ => None,
+            },
+            (LiteralValue::String(lhs), LiteralValue::String(rhs)) => match op {
+                BinOpKind::Add => Some(LiteralValue::String(lhs + &rhs)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn unary(
+        &mut self,
+        op: UnOpKind,
+        operand: LiteralValue,
+        ty: &TypeExpr,
+        span: Span,
+    ) -> Option<LiteralValue> {
+        match (op, operand) {
+            (UnOpKind::Not, LiteralValue::Bool(value)) => Some(LiteralValue::Bool(!value)),
+            (UnOpKind::Neg, LiteralValue::Int(value)) => {
+                self.check_int(value.checked_neg(), ty, span)
+            }
+            (UnOpKind::BitNot, LiteralValue::Int(value)) => {
+                // Only the bits of the type are flipped, which matters for unsigned types
+                let (_, max) = int_range(ty)?;
+                let value = if is_unsigned(ty) {
+                    !value & max
+                } else {
+                    !value
+                };
+
+                Some(LiteralValue::Int(value))
+            }
+            _ => None,
+        }
+    }
+
+    /// Keep the result of an integer operation if it fits in `ty`, otherwise warn about it.
+    fn check_int(
+        &mut self,
+        value: Option<i128>,
+        ty: &TypeExpr,
+        span: Span,
+    ) -> Option<LiteralValue> {
+        let (min, max) = int_range(ty)?;
+
+        match value {
+            Some(value) if (min..=max).contains(&value) => Some(LiteralValue::Int(value)),
+            _ => {
+                self.warnings
+                    .push(CompilerWarning::IntegerOverflowInConstant {
+                        ty: ty.clone(),
+                        span: span.into(),
+                    });
+
+                None
+            }
+        }
+    }
+}
+
+/// The smallest and largest values of an integer type.
+fn int_range(ty: &TypeExpr) -> Option<(i128, i128)> {
+    let TypeExpr::Named { name, .. } = ty else {
+        return None;
+    };
+
+    let range = match name.as_str() {
+        "i8" => (i8::MIN.into(), i8::MAX.into()),
+        "i16" => (i16::MIN.into(), i16::MAX.into()),
+        "i32" => (i32::MIN.into(), i32::MAX.into()),
+        "i64" => (i64::MIN.into(), i64::MAX.into()),
+        "i128" => (i128::MIN, i128::MAX),
+        "u8" => (0, u8::MAX.into()),
+        "u16" => (0, u16::MAX.into()),
+        "u32" => (0, u32::MAX.into()),
+        "u64" => (0, u64::MAX.into()),
+        _ => return None,
+    };
+
+    Some(range)
+}
+
+fn is_unsigned(ty: &TypeExpr) -> bool {
+    matches!(ty, TypeExpr::Named { name, .. } if name.starts_with('u'))
+}
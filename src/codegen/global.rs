@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::ast::{LiteralValue, StructDecl, TypeExpr};
+
+use super::emitter::{literal_constant, llvm_type};
+use super::string_table::StringTable;
+use super::target::DataLayout;
+
+/// A variable declared outside of any function, which lives for the whole program.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GlobalVar {
+    pub name: String,
+    pub ty: TypeExpr,
+    /// The value it starts out with, or `None` to start out as zero.
+    pub init: Option<LiteralValue>,
+    /// Whether it can be assigned to. Anything else is emitted as an LLVM `constant`.
+    pub mutable: bool,
+}
+
+/// The globals of a module, written before any of its functions.
+#[derive(Debug, Default)]
+pub struct GlobalVarEmitter {
+    globals: Vec<GlobalVar>,
+}
+
+impl GlobalVarEmitter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, global: GlobalVar) {
+        self.globals.push(global);
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, GlobalVar> {
+        self.globals.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.globals.is_empty()
+    }
+
+    /// Write a definition for each global in the order they were added, like
+    /// `@limit = constant i32 10`. Strings they start out as are added to `strings`.
+    ///
+    /// With a `layout`, each is given the alignment of its type, like `@limit = constant i32 10,
+    /// align 4`.
+    pub fn emit(
+        &self,
+        layout: Option<&DataLayout>,
+        structs: &HashMap<String, StructDecl>,
+        strings: &mut StringTable,
+        output: &mut String,
+    ) {
+        for global in &self.globals {
+            let value = match &global.init {
+                Some(value) => literal_constant(value, &global.ty, strings),
+                None => "zeroinitializer".to_owned(),
+            };
+
+            write!(
+                output,
+                "@{} = {} {} {value}",
+                global.name,
+                if global.mutable { "global" } else { "constant" },
+                llvm_type(&global.ty)
+            )
+            .unwrap();
+            if let Some(align) = layout.and_then(|layout| layout.align_of(&global.ty, structs)) {
+                write!(output, ", align {align}").unwrap();
+            }
+            output.push('\n');
+        }
+    }
+}
@@ -0,0 +1,211 @@
+use std::fmt;
+
+use crate::ast::{FnDecl, TypeExpr};
+
+/// How the instances of a generic function are named, so that each gets its own symbol.
+/// Functions without type arguments keep their own name in the built-in styles, so that `main`
+/// and anything called from outside the module can still be found.
+#[derive(Debug)]
+pub struct MangleScheme {
+    pub style: MangleStyle,
+}
+
+/// Names a function given its declaration and type arguments.
+pub type MangleFn = dyn Fn(&FnDecl, &[TypeExpr]) -> String;
+
+pub enum MangleStyle {
+    /// The name followed by `__` and each type argument, like `max__i32` or `wrap__ptr_u8`.
+    Simple,
+    /// The Itanium C++ ABI's mangling for function templates, like `_Z3maxIiEiii` for `max<i32>`,
+    /// so that tools like `c++filt` can demangle them. Only types C++ also has are encoded as C++
+    /// encodes them, without the substitutions that shorten repeated types.
+    Itanium,
+    /// Names given by a function of the declaration and its type arguments, also used for
+    /// functions without any.
+    Custom(Box<MangleFn>),
+}
+
+impl MangleScheme {
+    pub fn new(style: MangleStyle) -> Self {
+        MangleScheme { style }
+    }
+
+    /// The symbol for `fn_decl` with its generic parameters replaced by `type_args`.
+    pub fn mangle(&self, fn_decl: &FnDecl, type_args: &[TypeExpr]) -> String {
+        match &self.style {
+            MangleStyle::Custom(mangle) => mangle(fn_decl, type_args),
+            _ if type_args.is_empty() => fn_decl.name.clone(),
+            MangleStyle::Simple => {
+                let mut name = fn_decl.name.clone();
+                for ty in type_args {
+                    name.push_str("__");
+                    simple_type(ty, &mut name);
+                }
+                name
+            }
+            MangleStyle::Itanium => {
+                let mut name = format!("_Z{}{}I", fn_decl.name.len(), fn_decl.name);
+                for ty in type_args {
+                    itanium_type(ty, &mut name);
+                }
+                name.push('E');
+
+                // Function templates also encode their return type, before the parameters
+                let substitute = |ty: &TypeExpr| ty.substitute(&fn_decl.generic_params, type_args);
+                match &fn_decl.ret {
+                    Some(ret) => itanium_type(&substitute(ret), &mut name),
+                    None => name.push('v'),
+                }
+                if fn_decl.params.is_empty() {
+                    name.push('v');
+                }
+                for param in &fn_decl.params {
+                    itanium_type(&substitute(&param.ty), &mut name);
+                }
+
+                name
+            }
+        }
+    }
+}
+
+impl Default for MangleScheme {
+    fn default() -> Self {
+        Self::new(MangleStyle::Simple)
+    }
+}
+
+impl fmt::Debug for MangleStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MangleStyle::Simple => write!(f, "Simple"),
+            MangleStyle::Itanium => write!(f, "Itanium"),
+            MangleStyle::Custom(_) => write!(f, "Custom(..)"),
+        }
+    }
+}
+
+/// Write `ty` using only characters that can appear in an unquoted LLVM name.
+fn simple_type(ty: &TypeExpr, out: &mut String) {
+    let list = |types: &[TypeExpr], out: &mut String| {
+        for ty in types {
+            out.push('_');
+            simple_type(ty, out);
+        }
+    };
+
+    match ty {
+        TypeExpr::Named { name, generics } => {
+            out.push_str(name);
+            list(generics, out);
+        }
+        TypeExpr::Pointer { inner, mutable } => {
+            out.push_str(if *mutable { "ptrmut_" } else { "ptr_" });
+            simple_type(inner, out);
+        }
+        TypeExpr::Array {
+            element,
+            size: Some(size),
+        } => {
+            out.push_str(&format!("arr{size}_"));
+            simple_type(element, out);
+        }
+        TypeExpr::Array {
+            element,
+            size: None,
+        } => {
+            out.push_str("slice_");
+            simple_type(element, out);
+        }
+        TypeExpr::FnType { params, ret } => {
+            out.push_str(&format!("fn{}", params.len()));
+            list(params, out);
+            out.push('_');
+            simple_type(ret, out);
+        }
+        TypeExpr::Tuple(types) => {
+            out.push_str(&format!("tup{}", types.len()));
+            list(types, out);
+        }
+        TypeExpr::Never => out.push_str("never"),
+    }
+}
+
+/// Write the Itanium encoding of `ty`, where the integers are given the C++ type of the same
+/// size on LP64 targets.
+fn itanium_type(ty: &TypeExpr, out: &mut String) {
+    match ty {
+        TypeExpr::Named { name, generics } => {
+            let builtin = match name.as_str() {
+                "i8" => "a",
+                "u8" => "h",
+                "i16" => "s",
+                "u16" => "t",
+                "i32" => "i",
+                "u32" => "j",
+                "i64" => "l",
+                "u64" => "m",
+                "i128" => "n",
+                "f32" => "f",
+                "f64" => "d",
+                "bool" => "b",
+                "void" => "v",
+                _ => "",
+            };
+
+            if builtin.is_empty() {
+                out.push_str(&format!("{}{name}", name.len()));
+                if !generics.is_empty() {
+                    itanium_args(generics, out);
+                }
+            } else {
+                out.push_str(builtin);
+            }
+        }
+        // A `*T` can't change what it points to, like a `const T*`
+        TypeExpr::Pointer { inner, mutable } => {
+            out.push_str(if *mutable { "P" } else { "PK" });
+            itanium_type(inner, out);
+        }
+        TypeExpr::Array {
+            element,
+            size: Some(size),
+        } => {
+            out.push_str(&format!("A{size}_"));
+            itanium_type(element, out);
+        }
+        TypeExpr::Array {
+            element,
+            size: None,
+        } => {
+            out.push('P');
+            itanium_type(element, out);
+        }
+        TypeExpr::FnType { params, ret } => {
+            out.push_str("PF");
+            itanium_type(ret, out);
+            if params.is_empty() {
+                out.push('v');
+            }
+            for param in params {
+                itanium_type(param, out);
+            }
+            out.push('E');
+        }
+        // As a `std::tuple`, the closest C++ has
+        TypeExpr::Tuple(types) => {
+            out.push_str("St5tuple");
+            itanium_args(types, out);
+        }
+        // A vendor extended type, as C++ has no equivalent
+        TypeExpr::Never => out.push_str("u5never"),
+    }
+}
+
+fn itanium_args(types: &[TypeExpr], out: &mut String) {
+    out.push('I');
+    for ty in types {
+        itanium_type(ty, out);
+    }
+    out.push('E');
+}
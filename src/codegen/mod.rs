@@ -0,0 +1,51 @@
+pub mod cfg;
+#[cfg(feature = "debug-info")]
+pub mod debug_info;
+pub mod emitter;
+pub mod error;
+mod fold;
+mod global;
+mod mangle;
+#[cfg(feature = "llvm")]
+mod object;
+#[cfg(feature = "llvm")]
+pub mod optimize;
+mod phi;
+mod signature;
+pub mod string_table;
+mod target;
+
+#[cfg(feature = "debug-info")]
+use crate::source::SourceFile;
+use crate::typeck::typed::TypedModule;
+
+use self::emitter::LlvmEmitter;
+use self::error::CodegenError;
+pub use self::fold::ConstantFolder;
+pub use self::global::{GlobalVar, GlobalVarEmitter};
+pub use self::mangle::{MangleFn, MangleScheme, MangleStyle};
+#[cfg(feature = "llvm")]
+pub use self::object::emit_object_file;
+pub use self::phi::PhiNode;
+pub use self::signature::{FnSignature, SignatureRegistry};
+pub use self::target::{DataLayout, Target};
+
+/// Lowers a type checked `module` to textual LLVM IR.
+pub fn emit_llvm_ir(module: &TypedModule) -> Result<String, CodegenError> {
+    let mut emitter = LlvmEmitter::new();
+    emitter.emit_module(module)?;
+
+    Ok(emitter.finish())
+}
+
+/// Lowers a type checked `module` to textual LLVM IR, with debug info pointing into `source`.
+#[cfg(feature = "debug-info")]
+pub fn emit_llvm_ir_with_debug_info(
+    module: &TypedModule,
+    source: &SourceFile,
+) -> Result<String, CodegenError> {
+    let mut emitter = LlvmEmitter::new().with_debug_info(source);
+    emitter.emit_module(module)?;
+
+    Ok(emitter.finish())
+}
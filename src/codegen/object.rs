@@ -0,0 +1,54 @@
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use crate::typeck::typed::TypedModule;
+
+use super::emit_llvm_ir;
+use super::error::CodegenError;
+
+/// Compile `module` to a native object file at `output_path`, for `target_triple` or the host
+/// when it's `None`.
+///
+/// Like [`optimize_llvm_ir`](super::optimize::optimize_llvm_ir) this drives the LLVM tools on
+/// `PATH`, `llc` here.
+pub fn emit_object_file(
+    module: &TypedModule,
+    output_path: &Path,
+    target_triple: Option<&str>,
+) -> Result<(), CodegenError> {
+    let ir = emit_llvm_ir(module)?;
+
+    let mut llc = Command::new("llc");
+    llc.arg("-filetype=obj").arg("-o").arg(output_path);
+
+    if let Some(triple) = target_triple {
+        llc.arg(format!("-mtriple={triple}"));
+    }
+
+    let mut llc = llc
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    llc.stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(ir.as_bytes())?;
+
+    let output = llc.wait_with_output()?;
+
+    if !output.status.success() {
+        let message = String::from_utf8_lossy(&output.stderr).trim().to_owned();
+
+        return Err(match target_triple {
+            Some(triple) if message.contains("unable to get target") => {
+                CodegenError::UnsupportedTarget(triple.to_owned())
+            }
+            _ => CodegenError::Llvm(message),
+        });
+    }
+
+    Ok(())
+}
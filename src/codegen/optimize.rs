@@ -0,0 +1,58 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use super::error::OptimizationError;
+
+/// How hard LLVM should try to optimize, matching the levels of `clang -O`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OptLevel {
+    O0,
+    O1,
+    #[default]
+    O2,
+    O3,
+    /// Optimize for size.
+    Os,
+}
+
+impl OptLevel {
+    fn pipeline(self) -> &'static str {
+        match self {
+            OptLevel::O0 => "default<O0>",
+            OptLevel::O1 => "default<O1>",
+            OptLevel::O2 => "default<O2>",
+            OptLevel::O3 => "default<O3>",
+            OptLevel::Os => "default<Os>",
+        }
+    }
+}
+
+/// Run LLVM's standard optimization pipeline for `level` over the textual IR in `ir`, returning
+/// the optimized IR.
+///
+/// This drives the `opt` tool from the LLVM installation on `PATH`.
+pub fn optimize_llvm_ir(ir: &str, level: OptLevel) -> Result<String, OptimizationError> {
+    let mut opt = Command::new("opt")
+        .arg("-S")
+        .arg(format!("-passes={}", level.pipeline()))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // Dropping stdin closes it, so `opt` knows the module is complete
+    opt.stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(ir.as_bytes())?;
+
+    let output = opt.wait_with_output()?;
+
+    if !output.status.success() {
+        return Err(OptimizationError::Llvm(
+            String::from_utf8_lossy(&output.stderr).trim().to_owned(),
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
@@ -0,0 +1,45 @@
+use crate::ast::TypeExpr;
+
+use super::emitter::llvm_type;
+
+/// A `phi` instruction, which picks its value by the block that jumped to it, so a value
+/// computed differently on each branch needs no stack slot once they meet again.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhiNode {
+    /// The temporary it's assigned to, like `%tmp3`.
+    pub result: String,
+    pub ty: TypeExpr,
+    /// Each value it can take and the label of the block it comes from.
+    pub incoming: Vec<(String, String)>,
+}
+
+impl PhiNode {
+    pub fn new(result: impl Into<String>, ty: TypeExpr) -> Self {
+        PhiNode {
+            result: result.into(),
+            ty,
+            incoming: vec![],
+        }
+    }
+
+    /// Take `value` when coming from the block `label`.
+    pub fn add_incoming(&mut self, value: impl Into<String>, label: impl Into<String>) {
+        self.incoming.push((value.into(), label.into()));
+    }
+
+    /// The instruction, like `%tmp3 = phi i32 [ 1, %then0 ], [ %tmp2, %else2 ]`.
+    pub fn instruction(&self) -> String {
+        let incoming: Vec<_> = self
+            .incoming
+            .iter()
+            .map(|(value, label)| format!("[ {value}, %{label} ]"))
+            .collect();
+
+        format!(
+            "{} = phi {} {}",
+            self.result,
+            llvm_type(&self.ty),
+            incoming.join(", ")
+        )
+    }
+}
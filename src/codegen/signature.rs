@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+
+use crate::ast::TypeExpr;
+use crate::typeck::typed::{TypedFn, TypedModule};
+
+use super::emitter::llvm_type;
+
+/// The types a function is called with, which is all LLVM needs to declare it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FnSignature {
+    pub name: String,
+    pub params: Vec<TypeExpr>,
+    pub ret: TypeExpr,
+    /// Whether it takes more arguments after `params`, like C's `printf`.
+    pub variadic: bool,
+}
+
+impl FnSignature {
+    /// The `declare` line for the function, without a line ending.
+    pub fn declaration(&self) -> String {
+        let mut params: Vec<_> = self.params.iter().map(llvm_type).collect();
+        if self.variadic {
+            params.push("...".into());
+        }
+
+        format!(
+            "declare {} @{}({})",
+            llvm_type(&self.ret),
+            self.name,
+            params.join(", ")
+        )
+    }
+}
+
+impl From<&TypedFn> for FnSignature {
+    fn from(function: &TypedFn) -> Self {
+        FnSignature {
+            name: function.name.clone(),
+            params: function
+                .params
+                .iter()
+                .map(|param| param.ty.clone())
+                .collect(),
+            ret: function.ret.clone(),
+            variadic: false,
+        }
+    }
+}
+
+/// The signature of every function a module defines or declares, by name, so that the functions
+/// it calls without doing either can be declared before LLVM rejects the calls.
+#[derive(Debug, Default)]
+pub struct SignatureRegistry {
+    sigs: HashMap<String, FnSignature>,
+}
+
+impl SignatureRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The signatures of the functions in `module`, including the `extern` ones.
+    pub fn from_module(module: &TypedModule) -> Self {
+        let mut registry = Self::new();
+        for function in &module.functions {
+            registry.insert(function.into());
+        }
+
+        registry
+    }
+
+    /// Add `sig`, replacing any signature of the same name.
+    pub fn insert(&mut self, sig: FnSignature) {
+        self.sigs.insert(sig.name.clone(), sig);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&FnSignature> {
+        self.sigs.get(name)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.sigs.contains_key(name)
+    }
+
+    pub fn len(&self) -> usize {
+        self.sigs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sigs.is_empty()
+    }
+}
@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// The string literals of a module, each stored once as a private global no matter how many
+/// times it's used.
+#[derive(Debug, Default)]
+pub struct StringTable {
+    /// The global holding each string.
+    entries: HashMap<String, String>,
+    /// The strings in the order they were interned, so the globals are emitted in that order.
+    strings: Vec<String>,
+}
+
+impl StringTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The name of the global holding `s`, like `@str.0`, adding one if `s` hasn't been seen.
+    pub fn intern(&mut self, s: &str) -> String {
+        if let Some(global) = self.entries.get(s) {
+            return global.clone();
+        }
+
+        let global = format!("@str.{}", self.strings.len());
+        self.entries.insert(s.to_owned(), global.clone());
+        self.strings.push(s.to_owned());
+
+        global
+    }
+
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    /// Write a global for each string, as an array of its bytes followed by a nul terminator.
+    pub fn emit(&self, output: &mut String) {
+        for s in &self.strings {
+            writeln!(
+                output,
+                "{} = private unnamed_addr constant {} c\"{}\\00\"",
+                self.entries[s],
+                array_type(s),
+                escape(s)
+            )
+            .unwrap();
+        }
+    }
+}
+
+/// The LLVM type of the global holding `s`.
+pub fn array_type(s: &str) -> String {
+    format!("[{} x i8]", s.len() + 1)
+}
+
+/// Escape `text` for an LLVM string, which only takes printable ASCII as it is and hex escapes
+/// for the rest of the bytes.
+pub(super) fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for byte in text.bytes() {
+        match byte {
+            b'"' | b'\\' | 0..=0x1F | 0x7F.. => write!(escaped, "\\{byte:02X}").unwrap(),
+            _ => escaped.push(byte as char),
+        }
+    }
+
+    escaped
+}
@@ -0,0 +1,213 @@
+use std::collections::HashMap;
+
+use crate::ast::{StructDecl, TypeExpr};
+
+/// A platform to compile for, given by its target triple and the data layout LLVM uses for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Target {
+    pub triple: &'static str,
+    pub data_layout: &'static str,
+}
+
+impl Target {
+    pub const X86_64_LINUX_GNU: Target = Target {
+        triple: "x86_64-unknown-linux-gnu",
+        data_layout: "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128",
+    };
+
+    pub const AARCH64_APPLE_DARWIN: Target = Target {
+        triple: "aarch64-apple-darwin",
+        data_layout: "e-m:o-i64:64-i128:128-n32:64-S128",
+    };
+
+    pub const WASM32_UNKNOWN_UNKNOWN: Target = Target {
+        triple: "wasm32-unknown-unknown",
+        data_layout: "e-m:e-p:32:32-i64:64-n32:64-S128",
+    };
+
+    /// Every target with a data layout known ahead of time.
+    pub const PREDEFINED: [Target; 3] = [
+        Target::X86_64_LINUX_GNU,
+        Target::AARCH64_APPLE_DARWIN,
+        Target::WASM32_UNKNOWN_UNKNOWN,
+    ];
+
+    /// The predefined target with the triple `triple`.
+    pub fn from_triple(triple: &str) -> Option<Target> {
+        Self::PREDEFINED
+            .into_iter()
+            .find(|target| target.triple == triple)
+    }
+}
+
+/// The sizes and alignments of types given by an LLVM data layout string, like
+/// `e-p:32:32-i64:64`, so `sizeof` and `alignof` can be worked out without LLVM.
+///
+/// Anything not in the string has LLVM's default, and specifications that can't be read are
+/// skipped, leaving LLVM to reject them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataLayout {
+    text: String,
+    pointer_size: u64,
+    pointer_align: u64,
+    /// The ABI alignment in bytes of integers by their width in bits, sorted by width.
+    int_aligns: Vec<(u64, u64)>,
+    float_aligns: Vec<(u64, u64)>,
+}
+
+impl DataLayout {
+    pub fn new(layout: &str) -> Self {
+        let mut data_layout = DataLayout {
+            text: layout.to_owned(),
+            pointer_size: 8,
+            pointer_align: 8,
+            int_aligns: vec![(1, 1), (8, 1), (16, 2), (32, 4), (64, 4)],
+            float_aligns: vec![(16, 2), (32, 4), (64, 8), (128, 16)],
+        };
+
+        for spec in layout.split('-') {
+            let mut parts = spec.split(':');
+            let Some(kind) = parts.next() else {
+                continue;
+            };
+            let bits: Vec<u64> = parts.map_while(|part| part.parse().ok()).collect();
+
+            // Pointers in address spaces other than the default aren't used
+            match (kind, bits.as_slice()) {
+                ("p" | "p0", [size, align, ..]) => {
+                    data_layout.pointer_size = size / 8;
+                    data_layout.pointer_align = align / 8;
+                }
+                (_, [align, ..]) if kind.starts_with('i') => {
+                    if let Ok(width) = kind[1..].parse() {
+                        set_align(&mut data_layout.int_aligns, width, align / 8);
+                    }
+                }
+                (_, [align, ..]) if kind.starts_with('f') => {
+                    if let Ok(width) = kind[1..].parse() {
+                        set_align(&mut data_layout.float_aligns, width, align / 8);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        data_layout
+    }
+
+    /// The layout string this was read from.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// The size in bytes of `ty`, including the padding after it in an array, or `None` for a
+    /// type only LLVM knows the size of.
+    pub fn size_of(&self, ty: &TypeExpr, structs: &HashMap<String, StructDecl>) -> Option<u64> {
+        let size = match ty {
+            TypeExpr::Named { name, .. } => match name.as_str() {
+                "i8" | "u8" | "bool" => 1,
+                "i16" | "u16" => 2,
+                "i32" | "u32" | "f32" => 4,
+                "i64" | "u64" | "f64" => 8,
+                "i128" => 16,
+                "void" => 0,
+                _ => {
+                    let decl = structs.get(name)?;
+                    let fields: Vec<_> = decl.fields.iter().map(|field| &field.ty).collect();
+                    return self.aggregate_size(&fields, structs);
+                }
+            },
+            TypeExpr::Pointer { .. } | TypeExpr::FnType { .. } => self.pointer_size,
+            // Slices are passed around as a pointer to their first element
+            TypeExpr::Array { size: None, .. } => self.pointer_size,
+            TypeExpr::Array {
+                element,
+                size: Some(size),
+            } => self.size_of(element, structs)? * *size as u64,
+            TypeExpr::Tuple(types) => {
+                let types: Vec<_> = types.iter().collect();
+                return self.aggregate_size(&types, structs);
+            }
+            TypeExpr::Never => 0,
+        };
+
+        Some(size.next_multiple_of(self.align_of(ty, structs)?))
+    }
+
+    /// The ABI alignment in bytes of `ty`, or `None` for a type only LLVM knows the alignment of.
+    pub fn align_of(&self, ty: &TypeExpr, structs: &HashMap<String, StructDecl>) -> Option<u64> {
+        let align = match ty {
+            TypeExpr::Named { name, .. } => match name.as_str() {
+                "i8" | "u8" => lookup_align(&self.int_aligns, 8),
+                "bool" => lookup_align(&self.int_aligns, 1),
+                "i16" | "u16" => lookup_align(&self.int_aligns, 16),
+                "i32" | "u32" => lookup_align(&self.int_aligns, 32),
+                "i64" | "u64" => lookup_align(&self.int_aligns, 64),
+                "i128" => lookup_align(&self.int_aligns, 128),
+                "f32" => lookup_align(&self.float_aligns, 32),
+                "f64" => lookup_align(&self.float_aligns, 64),
+                "void" => 1,
+                _ => {
+                    let decl = structs.get(name)?;
+                    let mut align = 1;
+                    for field in &decl.fields {
+                        align = align.max(self.align_of(&field.ty, structs)?);
+                    }
+                    align
+                }
+            },
+            TypeExpr::Pointer { .. } | TypeExpr::FnType { .. } => self.pointer_align,
+            TypeExpr::Array { size: None, .. } => self.pointer_align,
+            TypeExpr::Array {
+                element,
+                size: Some(_),
+            } => self.align_of(element, structs)?,
+            TypeExpr::Tuple(types) => {
+                let mut align = 1;
+                for ty in types {
+                    align = align.max(self.align_of(ty, structs)?);
+                }
+                align
+            }
+            TypeExpr::Never => 1,
+        };
+
+        Some(align)
+    }
+
+    /// The size of a struct with fields of `types`, each placed at the next offset that's a
+    /// multiple of its alignment.
+    fn aggregate_size(
+        &self,
+        types: &[&TypeExpr],
+        structs: &HashMap<String, StructDecl>,
+    ) -> Option<u64> {
+        let mut offset = 0u64;
+        let mut align = 1;
+
+        for ty in types {
+            let field_align = self.align_of(ty, structs)?;
+            offset = offset.next_multiple_of(field_align) + self.size_of(ty, structs)?;
+            align = align.max(field_align);
+        }
+
+        Some(offset.next_multiple_of(align))
+    }
+}
+
+fn set_align(aligns: &mut Vec<(u64, u64)>, width: u64, align: u64) {
+    match aligns.binary_search_by_key(&width, |(width, _)| *width) {
+        Ok(idx) => aligns[idx].1 = align,
+        Err(idx) => aligns.insert(idx, (width, align)),
+    }
+}
+
+/// The alignment of a type `width` bits wide. Like LLVM, a width without its own alignment takes
+/// that of the next wider one, or the widest if there's none.
+fn lookup_align(aligns: &[(u64, u64)], width: u64) -> u64 {
+    aligns
+        .iter()
+        .find(|(other, _)| *other >= width)
+        .or(aligns.last())
+        .map_or(1, |(_, align)| *align)
+}
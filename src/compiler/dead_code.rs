@@ -0,0 +1,98 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::ast::{FnDecl, ImplItem, Item, Module, TraitItem};
+use crate::linter::walk::{walk_fn, Event};
+
+use super::error::CompilerWarning;
+
+/// A copy of `module` without the functions that can't be reached by calls starting from the
+/// function named `entry`, along with a warning for each one removed. If there's no such
+/// function, nothing is known to be reachable and the module is returned unchanged.
+///
+/// Methods in impl blocks and traits are always kept, along with the functions they call. Any
+/// use of a function's name keeps it, not just calls, as does a local variable sharing its name.
+pub fn eliminate_dead_code(module: &Module, entry: &str) -> (Module, Vec<CompilerWarning>) {
+    let functions: HashMap<&str, &FnDecl> = module
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            Item::FnDecl(decl) => Some((decl.name.as_str(), decl)),
+            _ => None,
+        })
+        .collect();
+
+    if !functions.contains_key(entry) {
+        return (module.clone(), vec![]);
+    }
+
+    let mut reachable = HashSet::new();
+    let mut pending = vec![entry];
+
+    for item in &module.items {
+        let methods: Vec<&FnDecl> = match item {
+            Item::TraitDecl(decl) => decl
+                .items
+                .iter()
+                .filter_map(|item| match item {
+                    TraitItem::DefaultFn(decl) => Some(decl),
+                    TraitItem::FnSignature(_) => None,
+                })
+                .collect(),
+            Item::ImplBlock(block) => block.items.iter().map(|ImplItem::Fn(decl)| decl).collect(),
+            _ => continue,
+        };
+
+        for method in methods {
+            pending.extend(used_names(method));
+        }
+    }
+
+    // Checking `reachable` before following a function's calls handles recursion
+    while let Some(name) = pending.pop() {
+        let Some(decl) = functions.get(name) else {
+            continue;
+        };
+
+        if reachable.insert(name) {
+            pending.extend(used_names(decl));
+        }
+    }
+
+    let mut warnings = vec![];
+    let items = module
+        .items
+        .iter()
+        .filter(|item| match item {
+            Item::FnDecl(decl) if !reachable.contains(decl.name.as_str()) => {
+                warnings.push(CompilerWarning::UnusedFunction {
+                    name: decl.name.clone(),
+                    span: decl.span.into(),
+                });
+
+                false
+            }
+            _ => true,
+        })
+        .cloned()
+        .collect();
+
+    let module = Module {
+        path: module.path.clone(),
+        items,
+    };
+
+    (module, warnings)
+}
+
+/// Every identifier read in the body of `decl`.
+fn used_names(decl: &FnDecl) -> Vec<&str> {
+    let mut names = vec![];
+
+    walk_fn(decl, &mut |event| {
+        if let Event::Use { name } = event {
+            names.push(name);
+        }
+    });
+
+    names
+}
@@ -0,0 +1,60 @@
+use miette::{Diagnostic, SourceSpan};
+use thiserror::Error;
+
+use crate::ast::TypeExpr;
+use crate::codegen::error::CodegenError;
+use crate::lexer::error::TokenizationError;
+use crate::parse::error::ParseError;
+use crate::typeck::error::{NameError, TypeError};
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum CompilerError {
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Tokenization(#[from] TokenizationError),
+
+    #[error("Found {} syntax error(s)", .errors.len())]
+    Parse {
+        #[related]
+        errors: Vec<ParseError>,
+    },
+
+    #[error("Found {} undefined or repeated name(s)", .errors.len())]
+    Names {
+        #[related]
+        errors: Vec<NameError>,
+    },
+
+    #[error("Found {} type error(s)", .errors.len())]
+    Types {
+        #[related]
+        errors: Vec<TypeError>,
+    },
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Codegen(#[from] CodegenError),
+}
+
+/// Problems that don't stop compilation, but probably aren't what was meant.
+#[derive(Error, Diagnostic, Debug, Clone)]
+#[diagnostic(severity(Warning))]
+pub enum CompilerWarning {
+    #[error("Constant expression overflows {ty}")]
+    #[diagnostic(help("it will wrap around when the program runs"))]
+    IntegerOverflowInConstant {
+        ty: TypeExpr,
+
+        #[label("overflows")]
+        span: SourceSpan,
+    },
+
+    #[error("Function {name:?} is never called")]
+    #[diagnostic(help("it's left out of the compiled module"))]
+    UnusedFunction {
+        name: String,
+
+        #[label("never called")]
+        span: SourceSpan,
+    },
+}
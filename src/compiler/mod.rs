@@ -0,0 +1,93 @@
+mod dead_code;
+pub mod error;
+
+#[cfg(feature = "llvm")]
+use std::path::Path;
+
+use crate::ast::Module;
+use crate::codegen::{emit_llvm_ir, ConstantFolder};
+use crate::lexer::AsciiLexer;
+use crate::parse::parser::Parser;
+use crate::source::SourceFile;
+use crate::typeck::symbols::SymbolTable;
+use crate::typeck::typed::TypedModule;
+use crate::typeck::{expand_type_aliases, monomorphize, resolve_names, type_check};
+
+pub use self::dead_code::eliminate_dead_code;
+use self::error::CompilerError;
+
+/// Lexes and parses `source` into an untyped module.
+pub fn parse_source(source: &SourceFile) -> Result<Module, CompilerError> {
+    let mut tokens = AsciiLexer::new().tokenize(source.text())?;
+    tokens.retain(|token| !token.is_trivia());
+
+    let mut parser = Parser::new(0, &tokens);
+    let mut module = parser
+        .parse_module()
+        .map_err(|errors| CompilerError::Parse { errors })?;
+    module.path = source.name().into();
+
+    Ok(module)
+}
+
+/// Runs every stage of the compiler before code generation over `source`, returning a module
+/// without generic functions.
+pub fn check_source(source: &SourceFile) -> Result<TypedModule, CompilerError> {
+    let module = parse_source(source)?;
+    let module = expand_type_aliases(&module).map_err(|errors| CompilerError::Types { errors })?;
+
+    let mut syms = SymbolTable::new();
+    resolve_names(&module, &mut syms).map_err(|errors| CompilerError::Names { errors })?;
+
+    let typed = type_check(&module, &syms).map_err(|errors| CompilerError::Types { errors })?;
+    let mono = monomorphize(&typed).map_err(|errors| CompilerError::Types { errors })?;
+
+    Ok(mono.into())
+}
+
+/// Runs every stage of the compiler over `source`, returning the textual LLVM IR for it.
+pub fn compile_to_llvm_ir(source: &SourceFile) -> Result<String, CompilerError> {
+    let mut typed = check_source(source)?;
+    fold_constants(&mut typed);
+
+    Ok(emit_llvm_ir(&typed)?)
+}
+
+/// Like [`compile_to_llvm_ir`], but with debug info describing the IR in terms of `source`.
+#[cfg(feature = "debug-info")]
+pub fn compile_to_llvm_ir_with_debug_info(source: &SourceFile) -> Result<String, CompilerError> {
+    let mut typed = check_source(source)?;
+    fold_constants(&mut typed);
+
+    Ok(crate::codegen::emit_llvm_ir_with_debug_info(
+        &typed, source,
+    )?)
+}
+
+/// Compiles `source` to an object file at `output_path`, for the host unless a `target_triple`
+/// is given.
+#[cfg(feature = "llvm")]
+pub fn compile_to_object_file(
+    source: &SourceFile,
+    output_path: &Path,
+    target_triple: Option<&str>,
+) -> Result<(), CompilerError> {
+    let mut typed = check_source(source)?;
+    fold_constants(&mut typed);
+
+    Ok(crate::codegen::emit_object_file(
+        &typed,
+        output_path,
+        target_triple,
+    )?)
+}
+
+/// Folds the constant expressions in `module` before it's emitted, logging any warnings.
+fn fold_constants(module: &mut TypedModule) {
+    let mut folder = ConstantFolder::new();
+    folder.fold_module(module);
+
+    for warning in folder.warnings() {
+        log::warn!("{warning}");
+    }
+}
@@ -0,0 +1,95 @@
+use crate::span::Span;
+use crate::token::Token;
+
+#[derive(Debug, Clone)]
+pub enum TokenOrAttr<'a> {
+    Token(Token<'a>),
+    DocAttr(DocAttr),
+}
+
+/// The text of the doc comments attached to a declaration, with comment markers removed.
+#[derive(Debug, Clone)]
+pub struct DocAttr {
+    pub text: String,
+    pub span: Span,
+}
+
+/// Folds runs of doc comments that precede a declaration into a single [`DocAttr`].
+///
+/// Doc comments that aren't followed by a declaration are left in the stream as tokens.
+pub fn comments_to_doc_attrs<'a>(tokens: &[Token<'a>]) -> Vec<TokenOrAttr<'a>> {
+    let mut output = Vec::with_capacity(tokens.len());
+    let mut idx = 0;
+
+    while idx < tokens.len() {
+        if !is_doc_comment(&tokens[idx]) {
+            output.push(TokenOrAttr::Token(tokens[idx]));
+            idx += 1;
+            continue;
+        }
+
+        // Find the last doc comment in this run, allowing whitespace in between
+        let mut last_doc = idx;
+        let mut next = idx + 1;
+
+        while let Some(token) = tokens.get(next) {
+            if is_doc_comment(token) {
+                last_doc = next;
+            } else if !matches!(token, Token::Space(_) | Token::Newline(_)) {
+                break;
+            }
+            next += 1;
+        }
+
+        let documents_declaration = tokens
+            .get(next)
+            .is_some_and(|token| token.is_declaration_keyword());
+
+        if documents_declaration {
+            let lines: Vec<String> = tokens[idx..=last_doc]
+                .iter()
+                .filter(|token| is_doc_comment(token))
+                .flat_map(doc_lines)
+                .collect();
+
+            output.push(TokenOrAttr::DocAttr(DocAttr {
+                text: lines.join("\n").trim().to_owned(),
+                span: Span::new(tokens[idx].loc(), tokens[last_doc].end()),
+            }));
+        } else {
+            output.extend(
+                tokens[idx..=last_doc]
+                    .iter()
+                    .copied()
+                    .map(TokenOrAttr::Token),
+            );
+        }
+
+        idx = last_doc + 1;
+    }
+
+    output
+}
+
+fn is_doc_comment(token: &Token) -> bool {
+    matches!(token, Token::DocLineComment(_) | Token::DocBlockComment(_))
+}
+
+fn doc_lines(token: &Token) -> Vec<String> {
+    match token {
+        Token::DocLineComment(inner) => vec![inner.slice["///".len()..].trim().to_owned()],
+        Token::DocBlockComment(inner) => {
+            let body = &inner.slice["/**".len()..];
+            let body = body.strip_suffix("*/").unwrap_or(body);
+
+            // Block comments conventionally start each line with a `*`
+            body.lines()
+                .map(|line| {
+                    let line = line.trim();
+                    line.strip_prefix('*').unwrap_or(line).trim().to_owned()
+                })
+                .collect()
+        }
+        _ => vec![],
+    }
+}
@@ -0,0 +1,111 @@
+use std::io;
+use std::path::PathBuf;
+
+use miette::{Diagnostic, SourceSpan};
+use thiserror::Error;
+
+use crate::source::SourceFile;
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum TokenizationError {
+    #[error("Unrecognized character {ch:?} ({})", .ch.escape_unicode())]
+    #[diagnostic(help(
+        "only identifiers, keywords and known operators are accepted in strict mode"
+    ))]
+    UnrecognizedToken {
+        #[label("unrecognized character")]
+        span: SourceSpan,
+        ch: char,
+    },
+
+    #[error("Giving up after {limit} unrecognized token(s)")]
+    TooManyErrors {
+        limit: usize,
+
+        /// Where `last_error` is, so the report points at it.
+        #[label("stopped at this error")]
+        span: SourceSpan,
+
+        #[source]
+        last_error: Box<TokenizationError>,
+    },
+
+    #[error("Source is not covered by any token")]
+    Gap {
+        #[label("no token here")]
+        span: SourceSpan,
+    },
+
+    #[error("Token is too long or too far into the source to pack into a u64")]
+    #[diagnostic(help(
+        "packed tokens start in the first 4 GiB of the source and are under 16 MiB long"
+    ))]
+    TooLargeToPack {
+        #[label("this token")]
+        span: SourceSpan,
+    },
+
+    #[error("Couldn't read {}", .path.display())]
+    ReadFile {
+        path: PathBuf,
+
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("Couldn't start the lexer's threads: {0}")]
+    ThreadPool(String),
+}
+
+impl TokenizationError {
+    /// The part of the source this error is about.
+    pub fn span(&self) -> SourceSpan {
+        match self {
+            TokenizationError::UnrecognizedToken { span, .. }
+            | TokenizationError::Gap { span }
+            | TokenizationError::TooManyErrors { span, .. }
+            | TokenizationError::TooLargeToPack { span } => *span,
+            // Nothing has been lexed yet
+            TokenizationError::ReadFile { .. } | TokenizationError::ThreadPool(_) => (0, 0).into(),
+        }
+    }
+
+    /// Formats the error like clang does, followed by the offending line of `src` with the span
+    /// underlined:
+    ///
+    /// ```text
+    /// main.src:2:13: error: Unrecognized character '@' (\u{40})
+    ///     let x = @;
+    ///             ^
+    /// ```
+    pub fn display_with_source(&self, src: &SourceFile) -> String {
+        let span = self.span();
+        let (line, col) = src.line_col(span.offset());
+        let text = src.line_text(line - 1);
+
+        // Keep any tabs so the caret lines up however wide they are shown
+        let indent: String = text
+            .chars()
+            .take(col - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+
+        // Spans reaching past the end of the line are only underlined up to it
+        let start = text
+            .char_indices()
+            .nth(col - 1)
+            .map_or(text.len(), |(idx, _)| idx);
+        let rest = &text[start..];
+        let underlined = rest
+            .char_indices()
+            .take_while(|(idx, _)| *idx < span.len())
+            .count()
+            .max(1);
+
+        format!(
+            "{}:{line}:{col}: error: {self}\n{text}\n{indent}^{}",
+            src.name(),
+            "~".repeat(underlined - 1)
+        )
+    }
+}
@@ -0,0 +1,859 @@
+pub mod doc;
+pub mod error;
+#[cfg(feature = "parallel")]
+mod parallel;
+pub mod validate;
+
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::time::Instant;
+
+use crate::source::SourceFile;
+use crate::token::{Inner, Token, TokenCategory};
+
+use self::error::TokenizationError;
+
+pub struct AsciiLexer {
+    strict: bool,
+    error_limit: usize,
+    errors: usize,
+    line: u32,
+    after_newline: bool,
+    metrics: LexerMetrics,
+}
+
+/// Timing and throughput of the most recent call to [`AsciiLexer::tokenize`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LexerMetrics {
+    pub elapsed_ns: u64,
+    pub bytes_processed: usize,
+    pub tokens_emitted: usize,
+    /// Number of `Unknown` tokens found, including the one that stops a strict lexer.
+    pub errors_encountered: usize,
+}
+
+impl LexerMetrics {
+    pub fn bytes_per_second(&self) -> f64 {
+        self.bytes_processed as f64 / (self.elapsed_ns.max(1) as f64 / 1e9)
+    }
+}
+
+impl<'a> AsciiLexer {
+    pub fn new() -> Self {
+        AsciiLexer {
+            strict: false,
+            error_limit: usize::MAX,
+            errors: 0,
+            line: 1,
+            after_newline: false,
+            metrics: LexerMetrics::default(),
+        }
+    }
+
+    /// Fail on the first `Unknown` token instead of emitting it.
+    pub fn strict_mode(mut self) -> Self {
+        self.strict = true;
+        self
+    }
+
+    /// Give up once `limit` `Unknown` tokens have been found, instead of lexing the whole input.
+    pub fn with_error_limit(mut self, limit: usize) -> Self {
+        self.error_limit = limit;
+        self
+    }
+
+    /// The 1-based line of the most recently lexed token.
+    pub fn current_line(&self) -> u32 {
+        self.line
+    }
+
+    pub fn metrics(&self) -> LexerMetrics {
+        self.metrics
+    }
+
+    pub fn tokenize(&mut self, buf: &'a str) -> Result<Vec<Token<'a>>, TokenizationError> {
+        let start = Instant::now();
+        self.errors = 0;
+        self.line = 1;
+        self.after_newline = false;
+
+        let result = self.tokenize_buf(buf, 0..buf.len());
+
+        self.metrics = LexerMetrics {
+            elapsed_ns: start.elapsed().as_nanos() as u64,
+            bytes_processed: buf.len(),
+            tokens_emitted: result.as_ref().map_or(0, Vec::len),
+            errors_encountered: self.errors,
+        };
+
+        #[cfg(debug_assertions)]
+        if let Ok(tokens) = &result {
+            for violation in validate::validation_pass(tokens, self.strict) {
+                log::debug!(
+                    "Token stream violation at {:?}: {}",
+                    violation.span,
+                    violation.message
+                );
+            }
+        }
+
+        result
+    }
+
+    /// Lex the tokens in `range`, which must start and end on token boundaries.
+    fn tokenize_buf(
+        &mut self,
+        buf: &'a str,
+        range: Range<usize>,
+    ) -> Result<Vec<Token<'a>>, TokenizationError> {
+        let mut tokens = Vec::new();
+        let bytes = &buf.as_bytes()[..range.end];
+
+        let mut last_token = Token::Unknown(Inner { loc: 0, slice: "" });
+        let mut last_idx = range.start;
+        let mut skip_until = range.start;
+        // Inside `${…}` the lexer is back in its normal mode, until the `}` that returns to the
+        // string. Each interpolation still open keeps how many `{` inside it are yet to close,
+        // with the innermost last.
+        let mut interpolations: Vec<u32> = Vec::new();
+
+        for (idx, byte) in bytes.iter().enumerate().skip(range.start) {
+            // Already consumed as part of a comment
+            if idx < skip_until {
+                continue;
+            }
+
+            // Convert to char unsafely to avoid slow validity checks
+            let c: char = unsafe { char::from_u32_unchecked((*byte).into()) };
+
+            if is_word_boundary(c) {
+                // There might be tokens between us and the previous boundary
+                if last_idx != idx {
+                    // Get the str between both indices
+                    let word: &str = unsafe { buf.get_unchecked(last_idx..idx) };
+
+                    let word_token = self.check(join_float(&mut tokens, buf, last_idx, word))?;
+
+                    last_token = word_token;
+                    tokens.push(word_token);
+                }
+
+                if c == '"' {
+                    if let Some(end) =
+                        self.string_piece(buf, idx, &mut tokens, &mut interpolations)?
+                    {
+                        last_token = *tokens.last().expect("a string was just lexed");
+
+                        last_idx = end;
+                        skip_until = end;
+                        continue;
+                    }
+                }
+
+                match c {
+                    '{' => {
+                        if let Some(depth) = interpolations.last_mut() {
+                            *depth += 1;
+                        }
+                    }
+                    '}' if interpolations.last() == Some(&0) => {
+                        interpolations.pop();
+
+                        let word: &str = unsafe { buf.get_unchecked(idx..idx + 1) };
+                        tokens.push(self.check(Token::CloseInterp(Inner {
+                            loc: idx,
+                            slice: word,
+                        }))?);
+
+                        let end = self
+                            .string_piece(buf, idx, &mut tokens, &mut interpolations)?
+                            .expect("only the start of a string is left unlexed");
+                        last_token = *tokens.last().expect("a string was just lexed");
+
+                        last_idx = end;
+                        skip_until = end;
+                        continue;
+                    }
+                    '}' => {
+                        if let Some(depth) = interpolations.last_mut() {
+                            *depth -= 1;
+                        }
+                    }
+                    // Like the strings they're in, interpolations end at a newline
+                    '\n' | '\r' => interpolations.clear(),
+                    _ => {}
+                }
+
+                if c == '/' {
+                    if let Some(end) = comment_end(bytes, idx) {
+                        let comment: &str = unsafe { buf.get_unchecked(idx..end) };
+                        let token = self.check(Token::comment(idx, comment))?;
+
+                        last_token = token;
+                        tokens.push(token);
+
+                        last_idx = end;
+                        skip_until = end;
+                        continue;
+                    }
+                }
+
+                if c == '\'' {
+                    if let Some(end) = char_end(bytes, idx).or_else(|| label_end(bytes, idx)) {
+                        let quoted: &str = unsafe { buf.get_unchecked(idx..end) };
+                        let token = self.check(Token::from(idx, quoted))?;
+
+                        last_token = token;
+                        tokens.push(token);
+
+                        last_idx = end;
+                        skip_until = end;
+                        continue;
+                    }
+                }
+
+                // Treat `\r\n` as a single newline
+                if c == '\r' && bytes.get(idx + 1) == Some(&b'\n') {
+                    let word: &str = unsafe { buf.get_unchecked(idx..idx + 2) };
+                    let token = self.check(Token::from(idx, word))?;
+
+                    last_token = token;
+                    tokens.push(token);
+
+                    last_idx = idx + 2;
+                    skip_until = idx + 2;
+                    continue;
+                }
+
+                let word: &str = unsafe { buf.get_unchecked(idx..idx + 1) };
+
+                // Store the boundary token
+                let token = self.check(Token::from(idx, word))?;
+
+                // Look behind to see if this is a two character boundary token
+                match token {
+                    Token::GreaterThan(_) => {
+                        let token = match last_token {
+                            Token::Minus(_) => {
+                                tokens.pop();
+                                Token::from(idx - 1, "->")
+                            }
+                            Token::Pipe(_) => {
+                                tokens.pop();
+                                Token::from(idx - 1, "|>")
+                            }
+                            Token::Equals(_) => {
+                                tokens.pop();
+                                Token::from(idx - 1, "=>")
+                            }
+                            _ => token,
+                        };
+
+                        last_token = token;
+                        tokens.push(token);
+                    }
+                    Token::Pipe(_) => {
+                        // `|` is also used between patterns, the parser tells the two apart
+                        let token = match last_token {
+                            Token::Pipe(_) => {
+                                tokens.pop();
+                                Token::from(idx - 1, "||")
+                            }
+                            _ => token,
+                        };
+
+                        last_token = token;
+                        tokens.push(token);
+                    }
+                    Token::Colon(_) => {
+                        let token = match last_token {
+                            Token::Colon(_) => {
+                                tokens.pop();
+                                Token::from(idx - 1, "::")
+                            }
+                            _ => token,
+                        };
+
+                        last_token = token;
+                        tokens.push(token);
+                    }
+                    Token::Dot(_) => {
+                        // Dots are matched greedily, so `....` is `...` followed by `.`
+                        let token = match last_token {
+                            Token::Dot(_) => {
+                                tokens.pop();
+                                Token::from(idx - 1, "..")
+                            }
+                            Token::DotDot(_) => {
+                                tokens.pop();
+                                Token::from(idx - 2, "...")
+                            }
+                            _ => token,
+                        };
+
+                        last_token = token;
+                        tokens.push(token);
+                    }
+                    Token::Equals(_) => {
+                        let token = match last_token {
+                            Token::Plus(_)
+                            | Token::Minus(_)
+                            | Token::Star(_)
+                            | Token::Slash(_)
+                            | Token::Percent(_) => {
+                                tokens.pop();
+                                Token::from(idx - 1, unsafe { buf.get_unchecked(idx - 1..idx + 1) })
+                            }
+                            // TODO: Comparison operators
+                            _ => token,
+                        };
+
+                        last_token = token;
+                        tokens.push(token);
+                    }
+                    _ => {
+                        last_token = token;
+                        tokens.push(token);
+                    }
+                }
+
+                last_idx = idx + 1;
+            }
+        }
+
+        // The input might not end on a boundary
+        if last_idx < bytes.len() {
+            let word: &str = unsafe { buf.get_unchecked(last_idx..bytes.len()) };
+            let token = join_float(&mut tokens, buf, last_idx, word);
+
+            tokens.push(self.check(token)?);
+        }
+
+        Ok(tokens)
+    }
+
+    /// Lex the text of a string from the `"` or `}` at `idx` up to its closing quote or next
+    /// `${`, returning the index to carry on lexing from. A string without interpolations is a
+    /// single `StringLiteral`.
+    ///
+    /// An unterminated string is left for the caller to lex as an unknown `"`, and `None` is
+    /// returned. After an interpolation, the rest of the line is unknown instead.
+    fn string_piece(
+        &mut self,
+        buf: &'a str,
+        idx: usize,
+        tokens: &mut Vec<Token<'a>>,
+        interpolations: &mut Vec<u32>,
+    ) -> Result<Option<usize>, TokenizationError> {
+        let bytes = buf.as_bytes();
+        let opening = bytes[idx] == b'"';
+        let start = if opening { idx } else { idx + 1 };
+
+        let Some(piece) = string_piece_end(bytes, idx + 1) else {
+            if opening {
+                return Ok(None);
+            }
+
+            let end = bytes[start..]
+                .iter()
+                .position(|byte| matches!(byte, b'\n' | b'\r'))
+                .map_or(bytes.len(), |offset| start + offset);
+            if end > start {
+                let rest: &str = unsafe { buf.get_unchecked(start..end) };
+                tokens.push(self.check(Token::Unknown(Inner {
+                    loc: start,
+                    slice: rest,
+                }))?);
+            }
+
+            return Ok(Some(end));
+        };
+
+        let end = match piece {
+            StringPiece::Closed(end) => {
+                let inner = Inner {
+                    loc: start,
+                    slice: unsafe { buf.get_unchecked(start..end) },
+                };
+                let token = if opening {
+                    Token::StringLiteral(inner)
+                } else {
+                    Token::InterpolatedStringEnd(inner)
+                };
+
+                tokens.push(self.check(token)?);
+                end
+            }
+            StringPiece::Interpolated(open) => {
+                let inner = Inner {
+                    loc: start,
+                    slice: unsafe { buf.get_unchecked(start..open) },
+                };
+                // Back to back interpolations have no text between them
+                if opening {
+                    tokens.push(self.check(Token::InterpolatedStringStart(inner))?);
+                } else if open > start {
+                    tokens.push(self.check(Token::InterpolatedStringMiddle(inner))?);
+                }
+
+                let word: &str = unsafe { buf.get_unchecked(open..open + 2) };
+                tokens.push(self.check(Token::OpenInterp(Inner {
+                    loc: open,
+                    slice: word,
+                }))?);
+                interpolations.push(0);
+
+                open + 2
+            }
+        };
+
+        Ok(Some(end))
+    }
+
+    fn check(&mut self, token: Token<'a>) -> Result<Token<'a>, TokenizationError> {
+        // A token only moves onto the next line once something follows the newline
+        if self.after_newline {
+            self.line += 1;
+            self.after_newline = false;
+        }
+
+        match token {
+            Token::Newline(_) => self.after_newline = true,
+            Token::BlockComment(inner) | Token::DocBlockComment(inner) => {
+                self.line += inner.slice.bytes().filter(|byte| *byte == b'\n').count() as u32;
+            }
+            Token::Unknown(_) => self.errors += 1,
+            _ => {}
+        }
+
+        match token {
+            Token::Unknown(inner) if self.strict || self.errors >= self.error_limit => {
+                let error = unrecognized_token(inner.loc, inner.slice);
+
+                if self.strict {
+                    Err(error)
+                } else {
+                    Err(TokenizationError::TooManyErrors {
+                        limit: self.error_limit,
+                        span: error.span(),
+                        last_error: Box::new(error),
+                    })
+                }
+            }
+            _ => Ok(token),
+        }
+    }
+}
+
+impl Default for AsciiLexer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The error for the unknown token `slice` at `loc`.
+pub(crate) fn unrecognized_token(loc: usize, slice: &str) -> TokenizationError {
+    // Point at the first character that stopped this from being an identifier
+    let (offset, ch) = slice
+        .char_indices()
+        .find(|(_, c)| !is_identifier_char(*c))
+        .unwrap_or((0, '\0'));
+
+    TokenizationError::UnrecognizedToken {
+        span: (loc + offset, ch.len_utf8()).into(),
+        ch,
+    }
+}
+
+/// If a comment starts at `idx`, find the index just past its end. Line comments stop before the
+/// newline, and an unterminated block comment runs to the end of the input.
+fn comment_end(bytes: &[u8], idx: usize) -> Option<usize> {
+    match bytes.get(idx + 1) {
+        Some(b'/') => Some(
+            bytes[idx..]
+                .iter()
+                .position(|byte| *byte == b'\n')
+                .map_or(bytes.len(), |offset| idx + offset),
+        ),
+        Some(b'*') => Some(
+            bytes[idx + 2..]
+                .windows(2)
+                .position(|window| window == b"*/")
+                .map_or(bytes.len(), |offset| idx + 2 + offset + 2),
+        ),
+        _ => None,
+    }
+}
+
+/// Where the text of a string stops, see [`string_piece_end`].
+enum StringPiece {
+    /// At the index just past the closing quote.
+    Closed(usize),
+    /// At the index of the `${` starting an interpolation.
+    Interpolated(usize),
+}
+
+/// Find where the text of a string starting at `start` stops, where a `\` escapes the character
+/// after it, so `\${` doesn't start an interpolation. Like every token but block comments,
+/// strings end at a newline, which leaves them unterminated.
+fn string_piece_end(bytes: &[u8], start: usize) -> Option<StringPiece> {
+    let mut escaped = false;
+
+    for (offset, byte) in bytes.get(start..)?.iter().enumerate() {
+        match byte {
+            b'\n' | b'\r' => return None,
+            _ if escaped => escaped = false,
+            b'\\' => escaped = true,
+            b'"' => return Some(StringPiece::Closed(start + offset + 1)),
+            b'$' if bytes.get(start + offset + 1) == Some(&b'{') => {
+                return Some(StringPiece::Interpolated(start + offset));
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// If a character like `'a'` or `'\n'` starts at `idx`, find the index just past its closing
+/// quote. Escapes are only checked when the literal is parsed.
+fn char_end(bytes: &[u8], idx: usize) -> Option<usize> {
+    let start = idx + 1;
+
+    let close = match bytes.get(start)? {
+        b'\'' | b'\n' => return None,
+        // Skip the escaped character, then look for the quote, as in `'\u{1F600}'`
+        b'\\' => {
+            start
+                + 2
+                + bytes
+                    .get(start + 2..)?
+                    .iter()
+                    .take_while(|byte| **byte != b'\n')
+                    .position(|byte| *byte == b'\'')?
+        }
+        // A single UTF-8 encoded character
+        first => start + (first.leading_ones() as usize).max(1),
+    };
+
+    (bytes.get(close) == Some(&b'\'')).then_some(close + 1)
+}
+
+/// Lex `word` at `loc`, first joining it with the `1.` before it if that makes a float like
+/// `1.5`. A number after a field access, like the `1` in `tuple.0.1`, is left alone.
+fn join_float<'a>(
+    tokens: &mut Vec<Token<'a>>,
+    buf: &'a str,
+    loc: usize,
+    word: &'a str,
+) -> Token<'a> {
+    let token = Token::from(loc, word);
+
+    if let [before @ .., Token::IntLiteral(whole), Token::Dot(dot)] = tokens.as_slice() {
+        let adjacent = whole.loc + whole.slice.len() == dot.loc && dot.loc + 1 == loc;
+        let after_field = matches!(before.last(), Some(Token::Dot(_)));
+
+        if adjacent && !after_field {
+            let joined = unsafe { buf.get_unchecked(whole.loc..loc + word.len()) };
+
+            if is_float_literal(joined) {
+                let start = whole.loc;
+                tokens.truncate(tokens.len() - 2);
+                return Token::from(start, joined);
+            }
+        }
+    }
+
+    token
+}
+
+/// If a label like `'outer` starts at `idx`, find the index just past its end. A closing quote
+/// after the name makes it a character instead.
+fn label_end(bytes: &[u8], idx: usize) -> Option<usize> {
+    let end = bytes[idx + 1..]
+        .iter()
+        .position(|byte| !(byte.is_ascii_alphanumeric() || *byte == b'_'))
+        .map_or(bytes.len(), |offset| idx + 1 + offset);
+
+    (end > idx + 1 && bytes.get(end) != Some(&b'\'')).then_some(end)
+}
+
+fn is_word_boundary(word: char) -> bool {
+    matches!(
+        word,
+        '\n' | '\r'
+            | ' '
+            | '\t'
+            | '!'
+            | '"'
+            | '#'
+            | '%'
+            | '&'
+            | '\''
+            | '('
+            | ')'
+            | '*'
+            | '+'
+            | ','
+            | '-'
+            | '.'
+            | '/'
+            | ':'
+            | ';'
+            | '<'
+            | '='
+            | '>'
+            | '?'
+            | '['
+            | ']'
+            | '^'
+            | '`'
+            | '{'
+            | '|'
+            | '}'
+            | '~'
+    )
+}
+
+/// Rebuilds the source text covered by `tokens`, copying any gaps between them from `src`.
+///
+/// Any part of a gap past the end of `src` is left out, as is a gap splitting one of its
+/// characters, so tokens lexed from some other text don't cause a panic.
+pub fn reconstruct_source(tokens: &[Token], src: &str) -> String {
+    let mut output = String::with_capacity(src.len());
+    let mut idx = 0;
+
+    for token in tokens {
+        if idx < token.loc() {
+            let gap = idx..token.loc().min(src.len());
+            output.push_str(src.get(gap).unwrap_or_default());
+        }
+
+        output.push_str(token.as_str());
+        idx = token.end();
+    }
+
+    output.push_str(src.get(idx..).unwrap_or_default());
+
+    output
+}
+
+/// Like [`reconstruct_source`], but fails if any part of `src` isn't covered by a token.
+pub fn reconstruct_source_strict(tokens: &[Token], src: &str) -> Result<String, TokenizationError> {
+    let mut idx = 0;
+
+    for token in tokens {
+        if idx != token.loc() {
+            return Err(TokenizationError::Gap {
+                span: (idx, token.loc().saturating_sub(idx)).into(),
+            });
+        }
+
+        idx = token.end();
+    }
+
+    if idx != src.len() {
+        return Err(TokenizationError::Gap {
+            span: (idx, src.len().saturating_sub(idx)).into(),
+        });
+    }
+
+    Ok(reconstruct_source(tokens, src))
+}
+
+/// Writes one line per token giving its 1-based line, type and text, for dumping token streams.
+pub fn to_compact_string(tokens: &[Token], src: &SourceFile) -> String {
+    tokens
+        .iter()
+        .map(|token| {
+            format!(
+                "{}\t{}\t{:?}\n",
+                src.line_of(token.loc()) + 1,
+                token.ty().type_name(),
+                token.as_str()
+            )
+        })
+        .collect()
+}
+
+/// Describes each token with [`Token::to_debug_repr`], one per line.
+pub fn debug_token_stream(tokens: &[Token], src: &SourceFile) -> String {
+    tokens
+        .iter()
+        .map(|token| token.to_debug_repr(src))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Default colors for the classes given by [`token_stream_to_html`], to include in a `<style>`.
+pub const TOKEN_HTML_CSS: &str = "\
+.tok-keyword { color: #af00db; }
+.tok-type { color: #267f99; }
+.tok-identifier { color: #001080; }
+.tok-label { color: #795e26; }
+.tok-operator { color: #000000; }
+.tok-punctuation { color: #383a42; }
+.tok-int-literal, .tok-float-literal { color: #098658; }
+.tok-string-literal, .tok-char-literal { color: #a31515; }
+.tok-bool-literal { color: #0000ff; }
+.tok-comment { color: #008000; font-style: italic; }
+.tok-doc-comment { color: #008000; }
+.tok-unknown { color: #e51400; text-decoration: wavy underline; }
+";
+
+/// Writes `tokens` as HTML, wrapping each in a `<span class="tok-...">` chosen by what
+/// kind of token it is, like `tok-keyword` or `tok-string-literal`. Spaces and newlines, and any
+/// gaps between the tokens or after the last one, are copied from `src` without a `<span>`.
+/// Gaps are left out where [`reconstruct_source`] would leave them out.
+///
+/// See [`TOKEN_HTML_CSS`] for colors to go with the classes.
+pub fn token_stream_to_html(tokens: &[Token], src: &SourceFile) -> String {
+    let text = src.text();
+    let mut output = String::with_capacity(text.len() * 2);
+    let mut idx = 0;
+
+    for token in tokens {
+        if idx < token.loc() {
+            let gap = idx..token.loc().min(text.len());
+            escape_html(text.get(gap).unwrap_or_default(), &mut output);
+        }
+
+        match html_class(token) {
+            Some(class) => {
+                output.push_str("<span class=\"tok-");
+                output.push_str(class);
+                output.push_str("\">");
+                escape_html(token.as_str(), &mut output);
+                output.push_str("</span>");
+            }
+            None => escape_html(token.as_str(), &mut output),
+        }
+        idx = token.end();
+    }
+
+    escape_html(text.get(idx..).unwrap_or_default(), &mut output);
+
+    output
+}
+
+/// The class [`token_stream_to_html`] gives `token`, without the `tok-` prefix, or `None` for
+/// whitespace.
+fn html_class(token: &Token) -> Option<&'static str> {
+    let class = match token {
+        Token::Space(_) | Token::Newline(_) => return None,
+        Token::LineComment(_) | Token::BlockComment(_) => "comment",
+        Token::DocLineComment(_) | Token::DocBlockComment(_) => "doc-comment",
+        Token::True(_) | Token::False(_) => "bool-literal",
+        Token::IntLiteral(_) => "int-literal",
+        Token::FloatLiteral(_) => "float-literal",
+        Token::StringLiteral(_)
+        | Token::InterpolatedStringStart(_)
+        | Token::InterpolatedStringMiddle(_)
+        | Token::InterpolatedStringEnd(_) => "string-literal",
+        Token::CharLiteral(_) => "char-literal",
+        Token::Identifier(_) => "identifier",
+        Token::Label(_) => "label",
+        Token::Unknown(_) => "unknown",
+        Token::SemiColon(_) | Token::Comma(_) | Token::Colon(_) => "punctuation",
+        _ if token.is_opening_delimiter() || token.is_closing_delimiter() => "punctuation",
+        _ if token.is_primitive_type() => "type",
+        _ if token.is_keyword() => "keyword",
+        _ => "operator",
+    };
+
+    Some(class)
+}
+
+fn escape_html(text: &str, output: &mut String) {
+    for c in text.chars() {
+        match c {
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '&' => output.push_str("&amp;"),
+            '"' => output.push_str("&quot;"),
+            _ => output.push(c),
+        }
+    }
+}
+
+/// Counts describing a token stream, see [`token_stream_statistics`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TokenStreamStats {
+    pub total: usize,
+    pub by_category: HashMap<TokenCategory, usize>,
+    /// Number of distinct identifier names.
+    pub unique_identifiers: usize,
+    /// The deepest the delimiters were nested, where `(` inside `{` is 2.
+    pub max_nesting: u32,
+    /// Average number of characters per line of the source, not counting line breaks.
+    pub avg_line_length_chars: f64,
+}
+
+/// Measures `tokens` lexed from `src`, to spot inputs complex enough to be slow to compile.
+pub fn token_stream_statistics(tokens: &[Token], src: &SourceFile) -> TokenStreamStats {
+    let mut by_category = HashMap::new();
+    let mut identifiers = HashSet::new();
+    let mut nesting = 0u32;
+    let mut max_nesting = 0;
+
+    for token in tokens {
+        *by_category.entry(token.category()).or_insert(0) += 1;
+
+        match token {
+            Token::Identifier(inner) => {
+                identifiers.insert(inner.slice);
+            }
+            _ if token.is_opening_delimiter() => {
+                nesting += 1;
+                max_nesting = max_nesting.max(nesting);
+            }
+            _ if token.is_closing_delimiter() => nesting = nesting.saturating_sub(1),
+            _ => {}
+        }
+    }
+
+    let (lines, chars) = src.text().lines().fold((0, 0), |(lines, chars), line| {
+        (lines + 1, chars + line.chars().count())
+    });
+
+    TokenStreamStats {
+        total: tokens.len(),
+        by_category,
+        unique_identifiers: identifiers.len(),
+        max_nesting,
+        avg_line_length_chars: if lines == 0 {
+            0.0
+        } else {
+            chars as f64 / lines as f64
+        },
+    }
+}
+
+/// Whether `word` is a decimal float like `1.5`, `1e10` or `1.5e10`. Digits can be separated by
+/// `_`. The exponent has no sign, since `+` and `-` always end a word.
+pub(crate) fn is_float_literal(word: &str) -> bool {
+    let is_digits = |part: &str| {
+        part.starts_with(|c: char| c.is_ascii_digit())
+            && part
+                .bytes()
+                .all(|byte| byte.is_ascii_digit() || byte == b'_')
+    };
+
+    let (mantissa, exponent) = match word.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, Some(exponent)),
+        None => (word, None),
+    };
+    let (whole, fraction) = match mantissa.split_once('.') {
+        Some((whole, fraction)) => (whole, Some(fraction)),
+        None => (mantissa, None),
+    };
+
+    is_digits(whole)
+        && fraction.is_none_or(is_digits)
+        && exponent.is_none_or(is_digits)
+        && (fraction.is_some() || exponent.is_some())
+}
+
+pub fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
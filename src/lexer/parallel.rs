@@ -0,0 +1,143 @@
+use std::fs;
+use std::ops::Range;
+use std::path::Path;
+
+use rayon::prelude::*;
+use rayon::ThreadPoolBuilder;
+
+use crate::token::Token;
+
+use super::error::TokenizationError;
+use super::AsciiLexer;
+
+impl<'a> AsciiLexer {
+    /// Lex `buf` using `num_threads` threads.
+    ///
+    /// The input is split into roughly equal chunks that each end just after a newline. A newline
+    /// always ends the token before it, so the only token that can straddle two chunks is a block
+    /// comment. When a chunk ends inside one, the comment is lexed again along with every chunk it
+    /// runs into, and the separately lexed results for those chunks are thrown away.
+    ///
+    /// Unlike [`AsciiLexer::tokenize`], this doesn't update the lexer's line count or metrics.
+    pub fn tokenize_parallel(
+        &mut self,
+        buf: &'a str,
+        num_threads: usize,
+    ) -> Result<Vec<Token<'a>>, TokenizationError> {
+        let num_threads = num_threads.max(1);
+        let chunks = split_at_lines(buf, num_threads);
+        let strict = self.strict;
+
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build()
+            .map_err(|err| TokenizationError::ThreadPool(err.to_string()))?;
+
+        let mut lexed: Vec<_> = pool.install(|| {
+            chunks
+                .par_iter()
+                .map(|range| {
+                    let mut lexer = AsciiLexer {
+                        strict,
+                        ..AsciiLexer::new()
+                    };
+                    Some(lexer.tokenize_buf(buf, range.clone()))
+                })
+                .collect()
+        });
+
+        let mut tokens = Vec::with_capacity(buf.len() / 4);
+        let mut idx = 0;
+
+        while idx < chunks.len() {
+            let mut chunk_tokens = lexed[idx].take().unwrap()?;
+            let mut end = chunks[idx].end;
+            idx += 1;
+
+            while let Some(comment) = chunk_tokens.last().filter(|token| is_unterminated(token)) {
+                if end == buf.len() {
+                    break;
+                }
+
+                let start = comment.loc();
+                let close = buf[start + 2..]
+                    .find("*/")
+                    .map_or(buf.len(), |offset| start + 2 + offset + 2);
+
+                while idx < chunks.len() && chunks[idx].start < close {
+                    end = chunks[idx].end;
+                    idx += 1;
+                }
+
+                chunk_tokens.pop();
+
+                let mut lexer = AsciiLexer {
+                    strict,
+                    ..AsciiLexer::new()
+                };
+                chunk_tokens.extend(lexer.tokenize_buf(buf, start..end)?);
+            }
+
+            tokens.extend(chunk_tokens);
+        }
+
+        Ok(tokens)
+    }
+
+    /// Read the file at `path` into `buf` and lex it with [`AsciiLexer::tokenize_parallel`].
+    ///
+    /// Tokens borrow their text, so the file is read into a buffer of the caller's, replacing what
+    /// it held, rather than one that would be dropped before the tokens could be used.
+    pub fn tokenize_file_parallel(
+        &mut self,
+        path: &Path,
+        num_threads: usize,
+        buf: &'a mut String,
+    ) -> Result<Vec<Token<'a>>, TokenizationError> {
+        *buf = fs::read_to_string(path).map_err(|source| TokenizationError::ReadFile {
+            path: path.to_owned(),
+            source,
+        })?;
+
+        self.tokenize_parallel(buf, num_threads)
+    }
+}
+
+fn is_unterminated(token: &Token) -> bool {
+    match token {
+        Token::BlockComment(inner) | Token::DocBlockComment(inner) => {
+            inner.slice.len() < 4 || !inner.slice.ends_with("*/")
+        }
+        _ => false,
+    }
+}
+
+/// Split `buf` into at most `count` ranges that each end just after a newline, or at the end of
+/// the input.
+fn split_at_lines(buf: &str, count: usize) -> Vec<Range<usize>> {
+    let target = buf.len() / count;
+    let mut chunks = Vec::with_capacity(count);
+    let mut start = 0;
+
+    for i in 1..count {
+        let from = (i * target).max(start);
+
+        if from >= buf.len() {
+            break;
+        }
+
+        let end = buf.as_bytes()[from..]
+            .iter()
+            .position(|byte| *byte == b'\n')
+            .map_or(buf.len(), |offset| from + offset + 1);
+
+        chunks.push(start..end);
+        start = end;
+    }
+
+    if start < buf.len() {
+        chunks.push(start..buf.len());
+    }
+
+    chunks
+}
@@ -0,0 +1,68 @@
+use crate::span::Span;
+use crate::token::{Token, TokenType};
+
+#[derive(Debug, Clone)]
+pub struct ValidationViolation {
+    pub span: Span,
+    pub message: String,
+}
+
+/// Checks the invariants a finished token stream should hold, returning every violation found.
+///
+/// Ordering, overlap and length problems are lexer bugs, while unknown tokens and unbalanced
+/// delimiters come from the input itself.
+pub fn validation_pass(tokens: &[Token], strict: bool) -> Vec<ValidationViolation> {
+    let mut violations = vec![];
+    let mut open_delimiters: Vec<&Token> = vec![];
+
+    let mut violation = |span: Span, message: String| {
+        violations.push(ValidationViolation { span, message });
+    };
+
+    for (idx, token) in tokens.iter().enumerate() {
+        if let Some(previous) = idx.checked_sub(1).map(|previous| &tokens[previous]) {
+            if previous.loc() > token.loc() {
+                violation(token.span(), format!("{:?} is out of order", token.ty()));
+            } else if previous.overlaps(token) {
+                violation(
+                    token.span(),
+                    format!("{:?} overlaps {:?}", token.ty(), previous.ty()),
+                );
+            }
+        }
+
+        if token.as_str().is_empty() {
+            violation(token.span(), format!("{:?} is empty", token.ty()));
+        }
+
+        if strict && token.ty() == TokenType::Unknown {
+            violation(token.span(), "Unknown token in strict mode".into());
+        }
+
+        if token.is_opening_delimiter() {
+            open_delimiters.push(token);
+        } else if token.is_closing_delimiter() {
+            let expected = open_delimiters
+                .pop()
+                .and_then(Token::expected_closing_delimiter);
+
+            match expected {
+                Some(expected) if expected == token.ty() => {}
+                Some(expected) => violation(
+                    token.span(),
+                    format!("Expected {expected:?}, found {:?}", token.ty()),
+                ),
+                None => violation(
+                    token.span(),
+                    format!("{:?} has no matching open delimiter", token.ty()),
+                ),
+            }
+        }
+    }
+
+    for open in open_delimiters {
+        violation(open.span(), format!("{:?} is never closed", open.ty()));
+    }
+
+    violations
+}
@@ -1,6 +1,17 @@
+pub mod ast;
+pub mod ast_printer;
+pub mod builtins;
+pub mod codegen;
+pub mod compiler;
 pub mod lexer;
+pub mod linter;
 pub mod parse;
+pub mod prettyprint;
+pub mod scan;
+pub mod source;
+pub mod span;
 pub mod token;
+pub mod typeck;
 
 #[cfg(test)]
 mod tests {}
@@ -0,0 +1,226 @@
+use crate::ast::{Module, StmtNode};
+use crate::span::Span;
+
+use super::walk::{walk, BindingKind, Event};
+use super::{Lint, LintDiagnostic};
+
+/// Warns about `let` bindings that are never read. Names starting with `_` are left alone.
+pub struct UnusedVariable;
+
+impl Lint for UnusedVariable {
+    fn name(&self) -> &'static str {
+        "unused_variable"
+    }
+
+    fn check(&self, module: &Module) -> Vec<LintDiagnostic> {
+        struct Binding<'m> {
+            name: &'m str,
+            kind: BindingKind,
+            span: Span,
+            used: bool,
+        }
+
+        let mut scopes: Vec<Vec<Binding>> = vec![];
+        let mut diagnostics = vec![];
+
+        walk(module, &mut |event| match event {
+            Event::EnterScope => scopes.push(vec![]),
+            Event::ExitScope => {
+                let unused = scopes.pop().into_iter().flatten().filter(|binding| {
+                    binding.kind == BindingKind::Let
+                        && !binding.used
+                        && !binding.name.starts_with('_')
+                });
+
+                diagnostics.extend(unused.map(|binding| {
+                    diagnostic(
+                        self,
+                        format!("Variable {:?} is never used", binding.name),
+                        binding.span,
+                        Some(format!(
+                            "if this is intentional, name it `_{}` instead",
+                            binding.name
+                        )),
+                    )
+                }));
+            }
+            Event::Define { name, kind, span } => {
+                if let Some(scope) = scopes.last_mut() {
+                    scope.push(Binding {
+                        name,
+                        kind,
+                        span,
+                        used: false,
+                    });
+                }
+            }
+            Event::Use { name } => {
+                // Shadowing means the most recent binding is the one being used
+                let binding = scopes
+                    .iter_mut()
+                    .rev()
+                    .flat_map(|scope| scope.iter_mut().rev())
+                    .find(|binding| binding.name == name);
+
+                if let Some(binding) = binding {
+                    binding.used = true;
+                }
+            }
+            Event::Block { .. } => {}
+        });
+
+        diagnostics
+    }
+}
+
+/// Warns about blocks with nothing in them, other than the bodies of functions, which are often
+/// left empty on purpose.
+pub struct EmptyBlock;
+
+impl Lint for EmptyBlock {
+    fn name(&self) -> &'static str {
+        "empty_block"
+    }
+
+    fn check(&self, module: &Module) -> Vec<LintDiagnostic> {
+        let mut diagnostics = vec![];
+
+        walk(module, &mut |event| {
+            if let Event::Block {
+                block,
+                fn_body: false,
+            } = event
+            {
+                if block.stmts.is_empty() && block.final_expr.is_none() {
+                    diagnostics.push(diagnostic(
+                        self,
+                        "Empty block".into(),
+                        block.span,
+                        Some("remove it, or fill it in".into()),
+                    ));
+                }
+            }
+        });
+
+        diagnostics
+    }
+}
+
+/// Warns about statements after a `return`, `break` or `continue` in the same block, which can
+/// never run.
+pub struct UnreachableCode;
+
+impl Lint for UnreachableCode {
+    fn name(&self) -> &'static str {
+        "unreachable_code"
+    }
+
+    fn check(&self, module: &Module) -> Vec<LintDiagnostic> {
+        let mut diagnostics = vec![];
+
+        walk(module, &mut |event| {
+            let Event::Block { block, .. } = event else {
+                return;
+            };
+
+            let Some((jump, keyword)) =
+                block
+                    .stmts
+                    .iter()
+                    .enumerate()
+                    .find_map(|(idx, stmt)| match stmt {
+                        StmtNode::Return(_) => Some((idx, "return")),
+                        StmtNode::Break(_) => Some((idx, "break")),
+                        StmtNode::Continue(_) => Some((idx, "continue")),
+                        _ => None,
+                    })
+            else {
+                return;
+            };
+
+            let rest = block.stmts[jump + 1..]
+                .iter()
+                .map(StmtNode::span)
+                .chain(block.final_expr.as_ref().map(|expr| expr.span()));
+
+            if let Some(span) = rest.reduce(|first, last| first.merge(&last)) {
+                diagnostics.push(diagnostic(
+                    self,
+                    "Unreachable code".into(),
+                    span,
+                    Some(format!(
+                        "the `{keyword}` before this always leaves the block"
+                    )),
+                ));
+            }
+        });
+
+        diagnostics
+    }
+}
+
+/// Warns about variables and parameters that aren't named in `snake_case`.
+pub struct SnakeCaseIdentifiers;
+
+impl Lint for SnakeCaseIdentifiers {
+    fn name(&self) -> &'static str {
+        "snake_case_identifiers"
+    }
+
+    fn check(&self, module: &Module) -> Vec<LintDiagnostic> {
+        let mut diagnostics = vec![];
+
+        walk(module, &mut |event| {
+            if let Event::Define { name, span, .. } = event {
+                if !is_snake_case(name) {
+                    diagnostics.push(diagnostic(
+                        self,
+                        format!("Variable {name:?} should have a snake case name"),
+                        span,
+                        Some(format!("rename it to `{}`", to_snake_case(name))),
+                    ));
+                }
+            }
+        });
+
+        diagnostics
+    }
+}
+
+fn diagnostic(
+    lint: &impl Lint,
+    message: String,
+    span: Span,
+    help: Option<String>,
+) -> LintDiagnostic {
+    LintDiagnostic {
+        lint: lint.name(),
+        message,
+        span: span.into(),
+        help,
+    }
+}
+
+fn is_snake_case(name: &str) -> bool {
+    !name
+        .chars()
+        .any(|c| c.is_uppercase() || !(c.is_alphanumeric() || c == '_'))
+}
+
+/// `camelCase` and `PascalCase` to `snake_case`, splitting before each capital letter that
+/// follows a lowercase one or a digit.
+fn to_snake_case(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len() + 4);
+    let mut after_lower = false;
+
+    for c in name.chars() {
+        if c.is_uppercase() && after_lower {
+            snake.push('_');
+        }
+
+        after_lower = c.is_lowercase() || c.is_ascii_digit();
+        snake.extend(c.to_lowercase());
+    }
+
+    snake
+}
@@ -0,0 +1,74 @@
+mod lints;
+pub(crate) mod walk;
+
+use miette::{Diagnostic, SourceSpan};
+use thiserror::Error;
+
+use crate::ast::Module;
+
+pub use self::lints::{EmptyBlock, SnakeCaseIdentifiers, UnreachableCode, UnusedVariable};
+
+/// A check for code that compiles, but is probably a mistake or hard to read.
+pub trait Lint {
+    /// A `snake_case` name for the lint, shown along with what it finds.
+    fn name(&self) -> &'static str;
+
+    fn check(&self, module: &Module) -> Vec<LintDiagnostic>;
+}
+
+/// A warning from a [`Lint`].
+#[derive(Error, Diagnostic, Debug, Clone)]
+#[error("{message}")]
+#[diagnostic(severity(Warning))]
+pub struct LintDiagnostic {
+    /// The [`Lint::name`] of the lint that found this.
+    pub lint: &'static str,
+    pub message: String,
+
+    #[label("{lint}")]
+    pub span: SourceSpan,
+
+    #[help]
+    pub help: Option<String>,
+}
+
+/// Runs a set of lints over modules, starting with all of the built-in ones.
+pub struct LintRunner {
+    pub lints: Vec<Box<dyn Lint>>,
+}
+
+impl LintRunner {
+    pub fn new() -> Self {
+        LintRunner {
+            lints: vec![
+                Box::new(UnusedVariable),
+                Box::new(EmptyBlock),
+                Box::new(UnreachableCode),
+                Box::new(SnakeCaseIdentifiers),
+            ],
+        }
+    }
+
+    pub fn with_lint(mut self, lint: impl Lint + 'static) -> Self {
+        self.lints.push(Box::new(lint));
+        self
+    }
+
+    /// Every lint's diagnostics for `module`, in the order they appear in the source.
+    pub fn run_all(&self, module: &Module) -> Vec<LintDiagnostic> {
+        let mut diagnostics: Vec<_> = self
+            .lints
+            .iter()
+            .flat_map(|lint| lint.check(module))
+            .collect();
+
+        diagnostics.sort_by_key(|diagnostic| diagnostic.span.offset());
+        diagnostics
+    }
+}
+
+impl Default for LintRunner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
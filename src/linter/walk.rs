@@ -0,0 +1,249 @@
+use crate::ast::{
+    Block, ClosureBody, ExprNode, FnDecl, IfExpr, IfOrBlock, ImplItem, Item, Module, Pattern,
+    StmtNode, TraitItem,
+};
+use crate::span::Span;
+
+/// What the lints get to see of a module, in source order.
+pub(crate) enum Event<'m> {
+    Block {
+        block: &'m Block,
+        /// Whether this is the body of a function rather than a block inside one.
+        fn_body: bool,
+    },
+    Define {
+        name: &'m str,
+        kind: BindingKind,
+        span: Span,
+    },
+    /// An identifier reading the value of a name. Assigning to a variable isn't a use of it.
+    Use {
+        name: &'m str,
+    },
+    EnterScope,
+    ExitScope,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BindingKind {
+    Let,
+    Param,
+    /// The variable of a `for x in ...` loop.
+    Loop,
+    Pattern,
+}
+
+/// Call `visit` with every [`Event`] in the bodies of the functions in `module`.
+pub(crate) fn walk<'m>(module: &'m Module, visit: &mut impl FnMut(Event<'m>)) {
+    for item in &module.items {
+        match item {
+            Item::FnDecl(decl) => walk_fn(decl, visit),
+            Item::TraitDecl(decl) => {
+                for item in &decl.items {
+                    if let TraitItem::DefaultFn(decl) = item {
+                        walk_fn(decl, visit);
+                    }
+                }
+            }
+            Item::ImplBlock(block) => {
+                for ImplItem::Fn(decl) in &block.items {
+                    walk_fn(decl, visit);
+                }
+            }
+            Item::StructDecl(_)
+            | Item::EnumDecl(_)
+            | Item::UseDecl(_)
+            | Item::GlobalDecl(_)
+            | Item::TypeAlias(_) => {}
+        }
+    }
+}
+
+pub(crate) fn walk_fn<'m>(decl: &'m FnDecl, visit: &mut impl FnMut(Event<'m>)) {
+    // The parameters of a declaration are never used, as the body is elsewhere
+    if decl.is_extern() {
+        return;
+    }
+
+    visit(Event::EnterScope);
+
+    for param in &decl.params {
+        visit(Event::Define {
+            name: &param.name,
+            kind: BindingKind::Param,
+            span: param.span,
+        });
+    }
+    walk_block(&decl.body, true, visit);
+
+    visit(Event::ExitScope);
+}
+
+fn walk_block<'m>(block: &'m Block, fn_body: bool, visit: &mut impl FnMut(Event<'m>)) {
+    visit(Event::Block { block, fn_body });
+    visit(Event::EnterScope);
+
+    for stmt in &block.stmts {
+        walk_stmt(stmt, visit);
+    }
+
+    if let Some(expr) = &block.final_expr {
+        walk_expr(expr, visit);
+    }
+
+    visit(Event::ExitScope);
+}
+
+fn walk_stmt<'m>(stmt: &'m StmtNode, visit: &mut impl FnMut(Event<'m>)) {
+    match stmt {
+        StmtNode::Expr(expr) => walk_expr(expr, visit),
+        StmtNode::Let(stmt) => {
+            if let Some(init) = &stmt.init {
+                walk_expr(init, visit);
+            }
+
+            visit(Event::Define {
+                name: &stmt.name,
+                kind: BindingKind::Let,
+                span: stmt.span,
+            });
+        }
+        StmtNode::Assign(stmt) => {
+            if !matches!(stmt.target, ExprNode::Ident { .. }) {
+                walk_expr(&stmt.target, visit);
+            }
+            walk_expr(&stmt.value, visit);
+        }
+        StmtNode::While(stmt) => {
+            walk_expr(&stmt.cond, visit);
+            walk_block(&stmt.body, false, visit);
+        }
+        StmtNode::For(stmt) => {
+            visit(Event::EnterScope);
+
+            if let Some(init) = &stmt.init {
+                walk_stmt(init, visit);
+            }
+            if let Some(cond) = &stmt.cond {
+                walk_expr(cond, visit);
+            }
+            if let Some(post) = &stmt.post {
+                walk_stmt(post, visit);
+            }
+            walk_block(&stmt.body, false, visit);
+
+            visit(Event::ExitScope);
+        }
+        StmtNode::ForIn(stmt) => {
+            walk_expr(&stmt.iterable, visit);
+
+            visit(Event::EnterScope);
+            visit(Event::Define {
+                name: &stmt.binding,
+                kind: BindingKind::Loop,
+                span: stmt.span,
+            });
+            walk_block(&stmt.body, false, visit);
+            visit(Event::ExitScope);
+        }
+        StmtNode::Return(stmt) => {
+            if let Some(value) = &stmt.value {
+                walk_expr(value, visit);
+            }
+        }
+        StmtNode::Break(stmt) => {
+            if let Some(value) = &stmt.value {
+                walk_expr(value, visit);
+            }
+        }
+        StmtNode::Continue(_) => {}
+    }
+}
+
+fn walk_expr<'m>(expr: &'m ExprNode, visit: &mut impl FnMut(Event<'m>)) {
+    match expr {
+        ExprNode::Literal { .. } => {}
+        ExprNode::Ident { name, .. } => visit(Event::Use { name }),
+        ExprNode::BinOp { lhs, rhs, .. } => {
+            walk_expr(lhs, visit);
+            walk_expr(rhs, visit);
+        }
+        ExprNode::UnaryOp { operand, .. } => walk_expr(operand, visit),
+        ExprNode::Call { callee, args, .. } => {
+            walk_expr(callee, visit);
+
+            for arg in args {
+                walk_expr(arg, visit);
+            }
+        }
+        ExprNode::Builtin { args, .. } => {
+            for arg in args {
+                walk_expr(arg, visit);
+            }
+        }
+        ExprNode::Index { base, index, .. } => {
+            walk_expr(base, visit);
+            walk_expr(index, visit);
+        }
+        ExprNode::Field { base, .. } => walk_expr(base, visit),
+        ExprNode::If(if_expr) => walk_if(if_expr, visit),
+        ExprNode::Match(match_expr) => {
+            walk_expr(&match_expr.scrutinee, visit);
+
+            for arm in &match_expr.arms {
+                visit(Event::EnterScope);
+
+                define_pattern(&arm.pattern, arm.span, visit);
+                if let Some(guard) = &arm.guard {
+                    walk_expr(guard, visit);
+                }
+                walk_expr(&arm.body, visit);
+
+                visit(Event::ExitScope);
+            }
+        }
+        ExprNode::Closure(closure) => {
+            visit(Event::EnterScope);
+
+            for param in &closure.params {
+                visit(Event::Define {
+                    name: &param.name,
+                    kind: BindingKind::Param,
+                    span: param.span,
+                });
+            }
+
+            match &closure.body {
+                ClosureBody::Expr(expr) => walk_expr(expr, visit),
+                ClosureBody::Block(block) => walk_block(block, false, visit),
+            }
+
+            visit(Event::ExitScope);
+        }
+        ExprNode::Block(block) => walk_block(block, false, visit),
+    }
+}
+
+fn walk_if<'m>(if_expr: &'m IfExpr, visit: &mut impl FnMut(Event<'m>)) {
+    walk_expr(&if_expr.cond, visit);
+    walk_block(&if_expr.then_block, false, visit);
+
+    match if_expr.else_block.as_deref() {
+        Some(IfOrBlock::If(else_if)) => walk_if(else_if, visit),
+        Some(IfOrBlock::Block(block)) => walk_block(block, false, visit),
+        None => {}
+    }
+}
+
+fn define_pattern<'m>(pattern: &'m Pattern, span: Span, visit: &mut impl FnMut(Event<'m>)) {
+    match pattern {
+        Pattern::Literal(_) | Pattern::Wildcard => {}
+        Pattern::Ident(name) => visit(Event::Define {
+            name,
+            kind: BindingKind::Pattern,
+            span,
+        }),
+        // Both sides bind the same names
+        Pattern::Or(lhs, _) => define_pattern(lhs, span, visit),
+    }
+}
@@ -1,47 +1,142 @@
-// #![feature(iter_map_windows)]
+use std::fs;
+use std::path::{Path, PathBuf};
 
-use std::fs::File;
-use std::io::BufReader;
-use std::io::Read;
+use clap::{value_parser, Arg, Command};
+use miette::{IntoDiagnostic, MietteHandlerOpts, NamedSource, Report};
 
-use llvm_compiler::parse::parser::parse;
-use llvm_compiler::parse::parser::Parser;
-use llvm_compiler::{lexer::AsciiLexer, token::Token};
+use llvm_compiler::compiler::{check_source, compile_to_llvm_ir, parse_source};
+use llvm_compiler::lexer::{self, AsciiLexer};
+use llvm_compiler::prettyprint::Prettier;
+use llvm_compiler::source::SourceFile;
 
-pub const READER_CAPACITY: usize = 100_000_000;
+fn cli() -> Command {
+    let file = Arg::new("file")
+        .help("The source file to read")
+        .required(true)
+        .value_parser(value_parser!(PathBuf));
+
+    Command::new("llvm-compiler")
+        .about("Compiles source files to LLVM IR")
+        .subcommand_required(true)
+        .arg(
+            Arg::new("color")
+                .long("color")
+                .help("When to color diagnostics")
+                .value_parser(["auto", "always", "never"])
+                .default_value("auto")
+                .global(true),
+        )
+        .subcommand(
+            Command::new("compile")
+                .about("Compile a file")
+                .arg(file.clone())
+                .arg(
+                    Arg::new("output")
+                        .short('o')
+                        .help("Where to write the output, instead of standard output")
+                        .value_parser(value_parser!(PathBuf)),
+                )
+                .arg(
+                    Arg::new("emit")
+                        .long("emit")
+                        .help("What to output")
+                        .value_parser(["tokens", "ast", "formatted", "ir", "obj"])
+                        .default_value("ir"),
+                ),
+        )
+        .subcommand(
+            Command::new("lex")
+                .about("Print the tokens in a file")
+                .arg(file.clone()),
+        )
+        .subcommand(
+            Command::new("check")
+                .about("Type check a file without generating code")
+                .arg(file),
+        )
+}
 
 fn main() -> miette::Result<()> {
     pretty_env_logger::init();
 
-    let file = File::open("./examples/infix.src").unwrap();
-    let mut reader = BufReader::with_capacity(READER_CAPACITY, file);
+    let matches = cli().get_matches();
+    set_color(matches.get_one::<String>("color").unwrap());
+
+    let (command, matches) = matches.subcommand().unwrap();
+    let path = matches.get_one::<PathBuf>("file").unwrap();
+    let text = fs::read_to_string(path).into_diagnostic()?;
+    let source = SourceFile::new(path.display().to_string(), text);
 
-    let mut lexer = AsciiLexer::new();
-    let mut buf = String::new();
+    // Diagnostics point into the source, so it needs attaching to show them
+    let with_source = |report: Report| {
+        report.with_source_code(NamedSource::new(source.name(), source.text().to_owned()))
+    };
 
-    // Read in as much as we can at once
-    let bytes_read = reader.read_to_string(&mut buf).unwrap(); // TODO: There is some edge
-                                                               // case behaviour here when
-                                                               // a file is too long to
-                                                               // store in memory. This is
-                                                               // currently unhandled.
+    match command {
+        "compile" => {
+            let output = matches.get_one::<PathBuf>("output");
+            let emit = matches.get_one::<String>("emit").unwrap();
 
-    let tokens: Vec<Token<'_>> = lexer.tokenize(&buf);
+            let text = match emit.as_str() {
+                "tokens" => dump_tokens(&source).map_err(with_source)?,
+                "ast" => format!(
+                    "{:#?}\n",
+                    parse_source(&source).map_err(|err| with_source(err.into()))?
+                ),
+                "formatted" => Prettier::new()
+                    .format_module(&parse_source(&source).map_err(|err| with_source(err.into()))?),
+                "ir" => compile_to_llvm_ir(&source).map_err(|err| with_source(err.into()))?,
+                "obj" => {
+                    let output = output.cloned().unwrap_or_else(|| path.with_extension("o"));
+                    return emit_object(&source, &output).map_err(with_source);
+                }
+                _ => unreachable!("clap only accepts known values"),
+            };
 
-    for token in tokens.iter() {
-        print!("{:?}", token);
+            match output {
+                Some(output) => fs::write(output, text).into_diagnostic()?,
+                None => print!("{text}"),
+            }
+        }
+        "lex" => print!("{}", dump_tokens(&source).map_err(with_source)?),
+        "check" => {
+            check_source(&source).map_err(|err| with_source(err.into()))?;
+        }
+        _ => unreachable!("clap only accepts known subcommands"),
     }
 
-    println!("{}", tokens.len());
+    Ok(())
+}
+
+fn set_color(color: &str) {
+    let color = match color {
+        "always" => true,
+        "never" => false,
+        _ => return,
+    };
 
-    println!();
+    miette::set_hook(Box::new(move |_| {
+        Box::new(MietteHandlerOpts::new().color(color).build())
+    }))
+    .expect("the hook is only set once");
+}
 
-    let mut parser = Parser::new(0, &tokens);
+fn dump_tokens(source: &SourceFile) -> miette::Result<String> {
+    let tokens = AsciiLexer::new().tokenize(source.text())?;
 
-    let source = buf.clone();
-    let parsed = parse(&mut parser).map_err(|report| report.with_source_code(source));
+    Ok(lexer::to_compact_string(&tokens, source))
+}
 
-    println!("{:?}", parsed);
+#[cfg(feature = "llvm")]
+fn emit_object(source: &SourceFile, output: &Path) -> miette::Result<()> {
+    Ok(llvm_compiler::compiler::compile_to_object_file(
+        source, output, None,
+    )?)
+}
 
-    Ok(())
+#[cfg(not(feature = "llvm"))]
+fn emit_object(_source: &SourceFile, _output: &Path) -> miette::Result<()> {
+    Err(miette::miette!(
+        "Object files can only be emitted when built with the `llvm` feature"
+    ))
 }
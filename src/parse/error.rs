@@ -1,6 +1,8 @@
 use miette::{Diagnostic, LabeledSpan, SourceSpan};
 use thiserror::Error;
 
+use crate::token::TokenType;
+
 #[derive(Error, Diagnostic, Debug)]
 #[diagnostic()]
 #[error("Unexpected token {found:?}")]
@@ -10,6 +12,148 @@ pub struct UnexpectedToken {
     pub unexpected_span: LabeledSpan,
 }
 
-impl UnexpectedToken {
-    fn expected(self) {}
+#[derive(Error, Diagnostic, Debug)]
+pub enum ParseError {
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    UnexpectedToken(#[from] UnexpectedToken),
+
+    #[error("Expected {expected:?}, found {found:?}")]
+    #[diagnostic(help("matched {matched} token(s) of the sequence before this"))]
+    SequenceMismatch {
+        expected: TokenType,
+        found: Option<String>,
+        matched: usize,
+
+        #[label("expected {expected:?}")]
+        span: SourceSpan,
+    },
+
+    #[error("Expected {inserted:?}, found {found:?}")]
+    #[diagnostic(help("parsing carried on as if it were there"))]
+    InsertedToken {
+        inserted: TokenType,
+        found: TokenType,
+
+        #[label("expected {inserted:?} before this")]
+        span: SourceSpan,
+    },
+
+    #[error("Expected an item, found {found:?}")]
+    #[diagnostic(help(
+        "only declarations, like functions and structs, are allowed at the top level"
+    ))]
+    ExpectedItem {
+        found: Option<String>,
+
+        #[label("expected an item")]
+        span: SourceSpan,
+    },
+
+    #[error("Expected a type, found {found:?}")]
+    ExpectedType {
+        found: Option<String>,
+
+        #[label("expected a type")]
+        span: SourceSpan,
+    },
+
+    #[error("{name:?} is a keyword and can't be used as a name")]
+    ReservedName {
+        name: String,
+
+        #[label("reserved keyword")]
+        span: SourceSpan,
+    },
+
+    #[error("Invalid use path, found {found:?}")]
+    #[diagnostic(help("use paths are identifiers separated by `::`, optionally ending in `*`"))]
+    InvalidUsePath {
+        found: Option<String>,
+
+        #[label("expected a path segment")]
+        span: SourceSpan,
+    },
+
+    #[error("Expected an array size, found {found:?}")]
+    ExpectedArraySize {
+        found: Option<String>,
+
+        #[label("expected a whole number")]
+        span: SourceSpan,
+    },
+
+    #[error("Expected an expression, found {found:?}")]
+    ExpectedExpression {
+        found: Option<String>,
+
+        #[label("expected an expression")]
+        span: SourceSpan,
+    },
+
+    #[error("Expected a pattern, found {found:?}")]
+    #[diagnostic(help("patterns are literals, names to bind or `_`, separated by `|`"))]
+    ExpectedPattern {
+        found: Option<String>,
+
+        #[label("expected a pattern")]
+        span: SourceSpan,
+    },
+
+    #[error("Generic parameter list is empty")]
+    #[diagnostic(help("leave out the `<>` if there are no generic parameters"))]
+    EmptyGenericParams {
+        #[label("expected at least one parameter")]
+        span: SourceSpan,
+    },
+
+    #[error("Integer literal is too large")]
+    IntegerOverflow {
+        #[label("doesn't fit in 128 bits")]
+        span: SourceSpan,
+    },
+
+    #[error("Invalid digit {digit:?} in a base {base} literal")]
+    InvalidDigit {
+        digit: char,
+        base: u32,
+
+        #[label("invalid digit")]
+        span: SourceSpan,
+    },
+
+    #[error("Unknown escape sequence {escape:?}")]
+    #[diagnostic(help(
+        "the escapes are \\n, \\r, \\t, \\0, \\\\, \\', \\\" and \\u{{...}} with up to six hex digits"
+    ))]
+    InvalidEscape {
+        escape: String,
+
+        #[label("unknown escape")]
+        span: SourceSpan,
+    },
+
+    #[error("Character literal must contain exactly one character")]
+    InvalidCharLiteral {
+        count: usize,
+
+        #[label("found {count} characters")]
+        span: SourceSpan,
+    },
+
+    #[error("Expected a block, found {found:?}")]
+    #[diagnostic(help("the bodies of `if`, `else` and `while` must be wrapped in braces"))]
+    MissingBlock {
+        found: Option<String>,
+
+        #[label("expected `{{`")]
+        span: SourceSpan,
+    },
+
+    #[error("Can't assign to this expression")]
+    #[diagnostic(help("only variables, fields and indexed elements can be assigned to"))]
+    InvalidAssignTarget {
+        #[label("not assignable")]
+        span: SourceSpan,
+    },
 }
@@ -0,0 +1,347 @@
+use crate::ast::{
+    BinOpKind, CaptureMode, ClosureBody, ClosureExpr, ClosureParam, ExprNode, IfExpr, IfOrBlock,
+    MatchArm, MatchExpr, UnOpKind,
+};
+use crate::builtins::BuiltinFn;
+use crate::token::{Token, TokenType};
+
+use super::error::ParseError;
+use super::literal::parse_literal;
+use super::parser::Parser;
+
+/// Unary operators bind tighter than every binary operator.
+const UNARY_PRECEDENCE: u8 = u8::MAX;
+
+impl<'a> Parser<'a> {
+    /// Parse an expression, stopping at the first binary operator that binds looser than
+    /// `min_prec`. Pass `0` to parse a whole expression.
+    pub fn parse_expr(&mut self, min_prec: u8) -> Result<ExprNode, ParseError> {
+        let start = self.peek_token().map_or(0, |token| token.loc());
+        let mut lhs = self.parse_unary()?;
+
+        while let Some(operator) = self.peek_token() {
+            let (Some(op), Some(precedence)) = (
+                BinOpKind::from_token(&operator),
+                operator.operator_precedence(),
+            ) else {
+                break;
+            };
+
+            if precedence < min_prec {
+                break;
+            }
+
+            self.consume_token();
+
+            let next_prec = if operator.is_right_associative() {
+                precedence
+            } else {
+                precedence + 1
+            };
+            let rhs = self.parse_expr(next_prec)?;
+
+            lhs = ExprNode::BinOp {
+                op,
+                lhs: Box::new(lhs),
+                rhs: Box::new(rhs),
+                span: self.span_from(start),
+            };
+        }
+
+        Ok(lhs)
+    }
+
+    fn parse_unary(&mut self) -> Result<ExprNode, ParseError> {
+        match self.peek_token() {
+            Some(token) if token.is_unary_operator() => {
+                self.consume_token();
+
+                let op = UnOpKind::from_token(&token).expect("unary operators have a kind");
+                let operand = self.parse_expr(UNARY_PRECEDENCE)?;
+
+                Ok(ExprNode::UnaryOp {
+                    op,
+                    operand: Box::new(operand),
+                    span: self.span_from(token.loc()),
+                })
+            }
+            _ => self.parse_postfix(),
+        }
+    }
+
+    /// Parse a primary expression followed by any calls, indexing and field accesses on it.
+    fn parse_postfix(&mut self) -> Result<ExprNode, ParseError> {
+        let start = self.peek_token().map_or(0, |token| token.loc());
+        let mut expr = self.parse_primary()?;
+
+        loop {
+            expr = match self.peek_token() {
+                Some(Token::OpenBracket(_)) => {
+                    self.consume_token();
+
+                    let mut args = vec![];
+
+                    while !matches!(self.peek_token(), Some(Token::CloseBracket(_))) {
+                        args.push(self.parse_expr(0)?);
+
+                        // A trailing comma is allowed before the closing bracket
+                        if !self.consume_if(TokenType::Comma) {
+                            break;
+                        }
+                    }
+
+                    self.expect_type(TokenType::CloseBracket)?;
+
+                    ExprNode::Call {
+                        callee: Box::new(expr),
+                        args,
+                        span: self.span_from(start),
+                    }
+                }
+                Some(Token::OpenSquare(_)) => {
+                    self.consume_token();
+                    let index = self.parse_expr(0)?;
+                    self.expect_type(TokenType::CloseSquare)?;
+
+                    ExprNode::Index {
+                        base: Box::new(expr),
+                        index: Box::new(index),
+                        span: self.span_from(start),
+                    }
+                }
+                Some(Token::Dot(_)) => {
+                    self.consume_token();
+
+                    // Tuple fields are numbered
+                    let name = match self.peek_token() {
+                        Some(Token::IntLiteral(_)) => self.expect_type(TokenType::IntLiteral)?,
+                        _ => self.expect_name()?,
+                    };
+
+                    ExprNode::Field {
+                        base: Box::new(expr),
+                        name: name.as_str().to_owned(),
+                        span: self.span_from(start),
+                    }
+                }
+                _ => return Ok(expr),
+            };
+        }
+    }
+
+    /// Parse `if cond { ... }`, followed by any number of `else if cond { ... }` and an optional
+    /// `else { ... }`.
+    pub fn parse_if_expr(&mut self) -> Result<IfExpr, ParseError> {
+        let keyword = self.expect_type(TokenType::If)?;
+        let cond = self.parse_expr(0)?;
+        let then_block = self.parse_block()?;
+
+        let else_block = match self.peek_token() {
+            Some(Token::Else(_)) => {
+                self.consume_token();
+
+                match self.peek_token() {
+                    Some(Token::If(_)) => Some(Box::new(IfOrBlock::If(self.parse_if_expr()?))),
+                    _ => Some(Box::new(IfOrBlock::Block(self.parse_block()?))),
+                }
+            }
+            _ => None,
+        };
+
+        Ok(IfExpr {
+            cond: Box::new(cond),
+            then_block,
+            else_block,
+            span: self.span_from(keyword.loc()),
+        })
+    }
+
+    /// Parse `match scrutinee { pattern => body, ... }`. Arms with a block-like body don't need a
+    /// comma after them.
+    pub fn parse_match_expr(&mut self) -> Result<MatchExpr, ParseError> {
+        let keyword = self.expect_type(TokenType::Match)?;
+        let scrutinee = self.parse_expr(0)?;
+        self.expect_type(TokenType::OpenCurly)?;
+
+        let mut arms = vec![];
+
+        while !matches!(self.peek_token(), Some(Token::CloseCurly(_))) {
+            let start = self.peek_token().map_or(0, |token| token.loc());
+            let pattern = self.parse_pattern()?;
+
+            let guard = match self.peek_token() {
+                Some(Token::If(_)) => {
+                    self.consume_token();
+                    Some(self.parse_expr(0)?)
+                }
+                _ => None,
+            };
+
+            self.expect_type(TokenType::FatArrow)?;
+
+            // Like statements, a block-like body isn't the start of a binary expression, so
+            // `{ a } -1 => b` is two arms
+            let body = match self.peek_token() {
+                Some(Token::OpenCurly(_) | Token::If(_) | Token::Match(_)) => {
+                    self.parse_primary()?
+                }
+                _ => self.parse_expr(0)?,
+            };
+            let block_like = body.is_block_like();
+
+            arms.push(MatchArm {
+                pattern,
+                guard,
+                body,
+                span: self.span_from(start),
+            });
+
+            match self.peek_token() {
+                Some(Token::Comma(_)) => self.consume_token(),
+                Some(Token::CloseCurly(_)) => break,
+                _ if block_like => {}
+                _ => {
+                    self.expect_type(TokenType::Comma)?;
+                }
+            }
+        }
+
+        self.expect_type(TokenType::CloseCurly)?;
+
+        Ok(MatchExpr {
+            scrutinee: Box::new(scrutinee),
+            arms,
+            span: self.span_from(keyword.loc()),
+        })
+    }
+
+    /// Parse `|a, b: i32| body` or `|| body`, optionally prefixed with `move`. A body that isn't a
+    /// block extends as far to the right as it can.
+    pub fn parse_closure(&mut self) -> Result<ClosureExpr, ParseError> {
+        let start = self.peek_token().map_or(0, |token| token.loc());
+
+        let capture_mode = match self.peek_token() {
+            Some(Token::Move(_)) => {
+                self.consume_token();
+                CaptureMode::Move
+            }
+            _ => CaptureMode::Infer,
+        };
+
+        let mut params = vec![];
+
+        match self.peek_token() {
+            Some(Token::PipePipe(_)) => self.consume_token(),
+            _ => {
+                self.expect_type(TokenType::Pipe)?;
+
+                while !matches!(self.peek_token(), Some(Token::Pipe(_))) {
+                    let name = self.expect_name()?;
+                    let ty = match self.peek_token() {
+                        Some(Token::Colon(_)) => {
+                            self.consume_token();
+                            Some(self.parse_type_expr()?)
+                        }
+                        _ => None,
+                    };
+
+                    params.push(ClosureParam {
+                        name: name.as_str().to_owned(),
+                        ty,
+                        span: self.span_from(name.loc()),
+                    });
+
+                    match self.peek_token() {
+                        Some(Token::Comma(_)) => self.consume_token(),
+                        _ => break,
+                    }
+                }
+
+                self.expect_type(TokenType::Pipe)?;
+            }
+        }
+
+        let body = match self.peek_token() {
+            Some(Token::OpenCurly(_)) => ClosureBody::Block(self.parse_block()?),
+            _ => ClosureBody::Expr(Box::new(self.parse_expr(0)?)),
+        };
+
+        Ok(ClosureExpr {
+            params,
+            body,
+            capture_mode,
+            span: self.span_from(start),
+        })
+    }
+
+    /// Parse a call to a builtin, where `sizeof` and `alignof` take a type in place of their
+    /// arguments, like `sizeof(i32)`.
+    fn parse_builtin_call(&mut self, builtin: BuiltinFn) -> Result<ExprNode, ParseError> {
+        let keyword = self.next_token().expect("the keyword was peeked");
+        self.expect_type(TokenType::OpenBracket)?;
+
+        let mut args = vec![];
+
+        let builtin = match builtin {
+            BuiltinFn::SizeOf(_) => BuiltinFn::SizeOf(self.parse_type_expr()?),
+            BuiltinFn::AlignOf(_) => BuiltinFn::AlignOf(self.parse_type_expr()?),
+            builtin => {
+                while !matches!(self.peek_token(), Some(Token::CloseBracket(_))) {
+                    args.push(self.parse_expr(0)?);
+
+                    if !self.consume_if(TokenType::Comma) {
+                        break;
+                    }
+                }
+
+                builtin
+            }
+        };
+
+        self.expect_type(TokenType::CloseBracket)?;
+
+        Ok(ExprNode::Builtin {
+            builtin,
+            args,
+            span: self.span_from(keyword.loc()),
+        })
+    }
+
+    fn parse_primary(&mut self) -> Result<ExprNode, ParseError> {
+        match self.peek_token() {
+            Some(Token::If(_)) => return Ok(ExprNode::If(self.parse_if_expr()?)),
+            Some(Token::Match(_)) => return Ok(ExprNode::Match(self.parse_match_expr()?)),
+            Some(Token::Pipe(_) | Token::PipePipe(_) | Token::Move(_)) => {
+                return Ok(ExprNode::Closure(self.parse_closure()?))
+            }
+            Some(Token::OpenCurly(_)) => return Ok(ExprNode::Block(self.parse_block()?)),
+            Some(token) => {
+                if let Some(builtin) = token.keyword_to_builtin_fn() {
+                    return self.parse_builtin_call(builtin);
+                }
+            }
+            None => {}
+        }
+
+        match self.next_token() {
+            Some(token @ Token::Identifier(_)) => Ok(ExprNode::Ident {
+                name: token.as_str().to_owned(),
+                span: token.span(),
+            }),
+            Some(token) if token.is_literal() => Ok(ExprNode::Literal {
+                value: parse_literal(&token)?,
+                span: token.span(),
+            }),
+            Some(Token::OpenBracket(_)) => {
+                let expr = self.parse_expr(0)?;
+                self.expect_type(TokenType::CloseBracket)?;
+
+                Ok(expr)
+            }
+            found => Err(ParseError::ExpectedExpression {
+                found: found.map(|token| token.as_str().to_owned()),
+                span: self.span_of(found),
+            }),
+        }
+    }
+}
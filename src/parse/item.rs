@@ -0,0 +1,548 @@
+use std::path::PathBuf;
+
+use crate::ast::{
+    Block, EnumDecl, EnumVariant, EnumVariantFields, FieldDecl, FnDecl, FnSig, GenericParam,
+    GenericParams, GlobalDecl, ImplBlock, ImplItem, Item, LiteralValue, Module, Param, StructDecl,
+    TraitDecl, TraitItem, TypeAlias, TypeExpr, UseDecl, Visibility,
+};
+use crate::token::{Token, TokenType};
+
+use super::error::ParseError;
+use super::literal::parse_literal;
+use super::parser::{Parser, RecoveryAction};
+
+impl<'a> Parser<'a> {
+    /// Parse every top-level item up to the end of the input. After an error, parsing picks back
+    /// up at the next item, so every item with an error gets reported.
+    pub fn parse_module(&mut self) -> Result<Module, Vec<ParseError>> {
+        let mut items = vec![];
+        let mut errors = vec![];
+
+        while self.peek_token().is_some() {
+            match self.parse_item() {
+                Ok(item) => items.push(item),
+                Err(err) => {
+                    errors.push(err);
+
+                    // Always skip at least one token, as the error might be at the item's keyword.
+                    // `let` isn't a declaration keyword, as it's mostly seen in blocks, but it
+                    // starts globals too
+                    self.synchronize();
+                    while self.peek_token().is_some_and(|token| {
+                        !token.is_declaration_keyword() && !matches!(token, Token::Let(_))
+                    }) {
+                        self.synchronize();
+                    }
+                }
+            }
+        }
+
+        // Recovered mistakes are left in `recoveries` unless there are others to report with them
+        if !errors.is_empty() {
+            errors.extend(self.recoveries.iter().map(RecoveryAction::to_error));
+            return Err(errors);
+        }
+
+        Ok(Module {
+            path: PathBuf::new(),
+            items,
+        })
+    }
+
+    pub fn parse_item(&mut self) -> Result<Item, ParseError> {
+        match self.peek_token() {
+            Some(
+                Token::FunctionDeclaration(_) | Token::Pub(_) | Token::Extern(_) | Token::Unsafe(_),
+            ) => Ok(Item::FnDecl(self.parse_fn_decl()?)),
+            Some(Token::StructDeclaration(_)) => Ok(Item::StructDecl(self.parse_struct_decl()?)),
+            Some(Token::EnumDeclaration(_)) => Ok(Item::EnumDecl(self.parse_enum_decl()?)),
+            Some(Token::TraitDeclaration(_)) => Ok(Item::TraitDecl(self.parse_trait_decl()?)),
+            Some(Token::ImplDeclaration(_)) => Ok(Item::ImplBlock(self.parse_impl_block()?)),
+            Some(Token::UseDeclaration(_)) => Ok(Item::UseDecl(self.parse_use_decl()?)),
+            Some(Token::TypeDeclaration(_)) => Ok(Item::TypeAlias(self.parse_type_alias()?)),
+            Some(Token::Let(_) | Token::Const(_)) => {
+                Ok(Item::GlobalDecl(self.parse_global_decl()?))
+            }
+            found => Err(ParseError::ExpectedItem {
+                found: found.map(|token| token.as_str().to_owned()),
+                span: self.span_of(found),
+            }),
+        }
+    }
+
+    /// Parse `func name<T>(param: Type, ...) -> Type { ... }`, where the generic parameters and the
+    /// return type are optional.
+    ///
+    /// It can be preceded by `pub`, then `extern "ABI"`, then `unsafe`, in that order. An `extern`
+    /// function ends with `;` in place of its body, and takes the C ABI if none is given.
+    pub fn parse_fn_decl(&mut self) -> Result<FnDecl, ParseError> {
+        let start = self.peek_token().map(|token| token.loc());
+
+        let visibility = match self.peek_token() {
+            Some(Token::Pub(_)) => {
+                self.consume_token();
+                Visibility::Public
+            }
+            _ => Visibility::Private,
+        };
+        let abi = match self.peek_token() {
+            Some(Token::Extern(_)) => {
+                self.consume_token();
+                Some(self.parse_abi()?)
+            }
+            _ => None,
+        };
+        let is_unsafe = matches!(self.peek_token(), Some(Token::Unsafe(_)));
+        if is_unsafe {
+            self.consume_token();
+        }
+
+        let sig = self.parse_fn_sig()?;
+        let body = if abi.is_some() {
+            let semicolon = self.expect_type(TokenType::SemiColon)?;
+
+            Block {
+                label: None,
+                stmts: vec![],
+                final_expr: None,
+                span: self.span_from(semicolon.loc()),
+            }
+        } else {
+            self.parse_block()?
+        };
+
+        Ok(FnDecl {
+            visibility,
+            abi,
+            is_unsafe,
+            name: sig.name,
+            generic_params: sig.generic_params,
+            params: sig.params,
+            ret: sig.ret,
+            body,
+            span: self.span_from(start.unwrap_or(sig.span.start)),
+        })
+    }
+
+    /// Parse `let name: Type = value;` or `const name: Type = value;` outside of a function.
+    /// A `let` can be `let mut`, and can leave out its value. Unlike a local, the type is always
+    /// written.
+    pub fn parse_global_decl(&mut self) -> Result<GlobalDecl, ParseError> {
+        let keyword = match self.peek_token() {
+            Some(Token::Const(_)) => self.expect_type(TokenType::Const)?,
+            _ => self.expect_type(TokenType::Let)?,
+        };
+        let is_const = matches!(keyword, Token::Const(_));
+
+        let mutable = !is_const && matches!(self.peek_token(), Some(Token::Mut(_)));
+        if mutable {
+            self.consume_token();
+        }
+
+        let name = self.expect_name()?;
+        self.expect_type(TokenType::Colon)?;
+        let ty = self.parse_type_expr()?;
+
+        // A constant always has a value
+        let init = if is_const || matches!(self.peek_token(), Some(Token::Equals(_))) {
+            self.expect_type(TokenType::Equals)?;
+            Some(self.parse_expr(0)?)
+        } else {
+            None
+        };
+
+        self.expect_type(TokenType::SemiColon)?;
+
+        Ok(GlobalDecl {
+            name: name.as_str().to_owned(),
+            ty,
+            init,
+            mutable,
+            is_const,
+            span: self.span_from(keyword.loc()),
+        })
+    }
+
+    /// The name of the ABI after `extern`, or `C` when there isn't one.
+    fn parse_abi(&mut self) -> Result<String, ParseError> {
+        let Some(token @ Token::StringLiteral(_)) = self.peek_token() else {
+            return Ok("C".to_owned());
+        };
+        self.consume_token();
+
+        match parse_literal(&token)? {
+            LiteralValue::String(abi) => Ok(abi),
+            _ => unreachable!("string literals have string values"),
+        }
+    }
+
+    /// Parse a function declaration up to its body.
+    pub fn parse_fn_sig(&mut self) -> Result<FnSig, ParseError> {
+        let keyword = self.expect_type(TokenType::FunctionDeclaration)?;
+        let name = self.expect_name()?;
+        let generic_params = self.parse_optional_generic_params()?;
+
+        self.expect_type(TokenType::OpenBracket)?;
+
+        let mut params = vec![];
+
+        while !matches!(self.peek_token(), Some(Token::CloseBracket(_))) {
+            let param = self.expect_name()?;
+            self.expect_type(TokenType::Colon)?;
+            let ty = self.parse_type_expr()?;
+
+            params.push(Param {
+                name: param.as_str().to_owned(),
+                ty,
+                span: self.span_from(param.loc()),
+            });
+
+            // A trailing comma is allowed before the closing bracket
+            if !self.consume_if(TokenType::Comma) {
+                break;
+            }
+        }
+
+        self.expect_type(TokenType::CloseBracket)?;
+
+        let ret = match self.peek_token() {
+            Some(Token::Arrow(_)) => {
+                self.consume_token();
+                Some(self.parse_type_expr()?)
+            }
+            _ => None,
+        };
+
+        Ok(FnSig {
+            name: name.as_str().to_owned(),
+            generic_params,
+            params,
+            ret,
+            span: self.span_from(keyword.loc()),
+        })
+    }
+
+    /// Parse `struct Name { field: Type, ... }`, where fields are separated by `,` or `;`.
+    pub fn parse_struct_decl(&mut self) -> Result<StructDecl, ParseError> {
+        let keyword = self.expect_type(TokenType::StructDeclaration)?;
+        let name = self.expect_name()?;
+        let generic_params = self.parse_optional_generic_params()?;
+
+        let fields = self.parse_field_list()?;
+
+        Ok(StructDecl {
+            name: name.as_str().to_owned(),
+            generic_params,
+            fields,
+            span: self.span_from(keyword.loc()),
+        })
+    }
+
+    /// Parse `type Name<T> = Target;`, where the generic parameters are optional.
+    pub fn parse_type_alias(&mut self) -> Result<TypeAlias, ParseError> {
+        let keyword = self.expect_type(TokenType::TypeDeclaration)?;
+        let name = self.expect_name()?;
+        let generic_params = self.parse_optional_generic_params()?;
+
+        self.expect_type(TokenType::Equals)?;
+        let target = self.parse_type_expr()?;
+        self.expect_type(TokenType::SemiColon)?;
+
+        Ok(TypeAlias {
+            name: name.as_str().to_owned(),
+            generic_params,
+            target,
+            span: self.span_from(keyword.loc()),
+        })
+    }
+
+    /// Parse `enum Name<T> { A, B(T), C { field: T } }`, allowing a trailing comma.
+    pub fn parse_enum_decl(&mut self) -> Result<EnumDecl, ParseError> {
+        let keyword = self.expect_type(TokenType::EnumDeclaration)?;
+        let name = self.expect_name()?;
+        let generic_params = self.parse_optional_generic_params()?;
+
+        self.expect_type(TokenType::OpenCurly)?;
+
+        let mut variants = vec![];
+
+        while !matches!(self.peek_token(), Some(Token::CloseCurly(_))) {
+            let variant = self.expect_name()?;
+
+            let fields = match self.peek_token() {
+                Some(Token::OpenBracket(_)) => EnumVariantFields::Tuple(
+                    self.parse_type_list(TokenType::OpenBracket, TokenType::CloseBracket)?,
+                ),
+                Some(Token::OpenCurly(_)) => EnumVariantFields::Struct(self.parse_field_list()?),
+                _ => EnumVariantFields::Unit,
+            };
+
+            variants.push(EnumVariant {
+                name: variant.as_str().to_owned(),
+                fields,
+                span: self.span_from(variant.loc()),
+            });
+
+            if !self.consume_if(TokenType::Comma) {
+                break;
+            }
+        }
+
+        self.expect_type(TokenType::CloseCurly)?;
+
+        Ok(EnumDecl {
+            name: name.as_str().to_owned(),
+            generic_params,
+            variants,
+            span: self.span_from(keyword.loc()),
+        })
+    }
+
+    /// Parse `trait Name<T> { ... }`, containing function signatures ending in `;` and functions
+    /// with default bodies.
+    pub fn parse_trait_decl(&mut self) -> Result<TraitDecl, ParseError> {
+        let keyword = self.expect_type(TokenType::TraitDeclaration)?;
+        let name = self.expect_name()?;
+        let generic_params = self.parse_optional_generic_params()?;
+
+        self.expect_type(TokenType::OpenCurly)?;
+
+        let mut items = vec![];
+
+        while !matches!(self.peek_token(), Some(Token::CloseCurly(_))) {
+            let sig = self.parse_fn_sig()?;
+
+            let item = match self.peek_token() {
+                Some(Token::SemiColon(_)) => {
+                    self.consume_token();
+                    TraitItem::FnSignature(sig)
+                }
+                _ => {
+                    let body = self.parse_block()?;
+
+                    TraitItem::DefaultFn(FnDecl {
+                        visibility: Visibility::Private,
+                        abi: None,
+                        is_unsafe: false,
+                        name: sig.name,
+                        generic_params: sig.generic_params,
+                        params: sig.params,
+                        ret: sig.ret,
+                        body,
+                        span: self.span_from(sig.span.start),
+                    })
+                }
+            };
+
+            items.push(item);
+        }
+
+        self.expect_type(TokenType::CloseCurly)?;
+
+        Ok(TraitDecl {
+            name: name.as_str().to_owned(),
+            generic_params,
+            items,
+            span: self.span_from(keyword.loc()),
+        })
+    }
+
+    /// Parse `impl<T> a::Trait for Type { ... }` or `impl Type { ... }`, where the generic
+    /// parameters are optional.
+    pub fn parse_impl_block(&mut self) -> Result<ImplBlock, ParseError> {
+        let keyword = self.expect_type(TokenType::ImplDeclaration)?;
+        let generic_params = self.parse_optional_generic_params()?;
+
+        // Only a trait impl has a `for` after the first path
+        let start = self.save();
+        let (trait_path, trait_generics) = match self.parse_trait_path() {
+            Some(trait_ref) if matches!(self.peek_token(), Some(Token::For(_))) => {
+                self.consume_token();
+                (Some(trait_ref.0), trait_ref.1)
+            }
+            _ => {
+                self.restore(start);
+                (None, vec![])
+            }
+        };
+
+        let for_type = self.parse_type_expr()?;
+
+        self.expect_type(TokenType::OpenCurly)?;
+
+        let mut items = vec![];
+
+        while !matches!(self.peek_token(), Some(Token::CloseCurly(_))) {
+            items.push(ImplItem::Fn(self.parse_fn_decl()?));
+        }
+
+        self.expect_type(TokenType::CloseCurly)?;
+
+        Ok(ImplBlock {
+            generic_params,
+            trait_path,
+            trait_generics,
+            for_type,
+            items,
+            span: self.span_from(keyword.loc()),
+        })
+    }
+
+    /// Parse identifiers separated by `::` and any generic arguments after them, leaving the
+    /// position unspecified if they aren't there.
+    fn parse_trait_path(&mut self) -> Option<(Vec<String>, Vec<TypeExpr>)> {
+        let mut path = vec![];
+
+        loop {
+            match self.next_token() {
+                Some(segment @ Token::Identifier(_)) => path.push(segment.as_str().to_owned()),
+                _ => return None,
+            }
+
+            match self.peek_token() {
+                Some(Token::PathSep(_)) => self.consume_token(),
+                _ => break,
+            }
+        }
+
+        let generics = match self.peek_token() {
+            Some(Token::LessThan(_)) => self
+                .parse_type_list(TokenType::LessThan, TokenType::GreaterThan)
+                .ok()?,
+            _ => vec![],
+        };
+
+        Some((path, generics))
+    }
+
+    /// Parse `{ name: Type, ... }`, where fields are separated by `,` or `;`.
+    fn parse_field_list(&mut self) -> Result<Vec<FieldDecl>, ParseError> {
+        self.expect_type(TokenType::OpenCurly)?;
+
+        let mut fields = vec![];
+
+        while !matches!(self.peek_token(), Some(Token::CloseCurly(_))) {
+            let field = self.expect_name()?;
+            self.expect_type(TokenType::Colon)?;
+            let ty = self.parse_type_expr()?;
+
+            fields.push(FieldDecl {
+                name: field.as_str().to_owned(),
+                ty,
+                span: self.span_from(field.loc()),
+            });
+
+            match self.peek_token() {
+                Some(Token::Comma(_) | Token::SemiColon(_)) => self.consume_token(),
+                _ => break,
+            }
+        }
+
+        self.expect_type(TokenType::CloseCurly)?;
+
+        Ok(fields)
+    }
+
+    /// Parse `<T: A + B, U>`, allowing a trailing comma. The lexer never joins `>` characters, so
+    /// the list can end in the middle of a `>>`.
+    pub fn parse_generic_params(&mut self) -> Result<GenericParams, ParseError> {
+        let open = self.expect_type(TokenType::LessThan)?;
+        let mut params = vec![];
+
+        while !matches!(self.peek_token(), Some(Token::GreaterThan(_))) {
+            let name = self.expect_name()?;
+            let mut bounds = vec![];
+
+            if let Some(Token::Colon(_)) = self.peek_token() {
+                self.consume_token();
+                bounds.push(self.parse_type_expr()?);
+
+                while let Some(Token::Plus(_)) = self.peek_token() {
+                    self.consume_token();
+                    bounds.push(self.parse_type_expr()?);
+                }
+            }
+
+            params.push(GenericParam {
+                name: name.as_str().to_owned(),
+                bounds,
+                span: self.span_from(name.loc()),
+            });
+
+            match self.peek_token() {
+                Some(Token::Comma(_)) => self.consume_token(),
+                _ => break,
+            }
+        }
+
+        self.expect_type(TokenType::GreaterThan)?;
+
+        if params.is_empty() {
+            return Err(ParseError::EmptyGenericParams {
+                span: self.span_from(open.loc()).into(),
+            });
+        }
+
+        Ok(GenericParams(params))
+    }
+
+    fn parse_optional_generic_params(&mut self) -> Result<GenericParams, ParseError> {
+        match self.peek_token() {
+            Some(Token::LessThan(_)) => self.parse_generic_params(),
+            _ => Ok(GenericParams::default()),
+        }
+    }
+
+    /// Parse `use a::b::c`, `use a::b::c as d` or `use a::b::*`, with an optional `;` after.
+    pub fn parse_use_decl(&mut self) -> Result<UseDecl, ParseError> {
+        let keyword = self.expect_type(TokenType::UseDeclaration)?;
+        let mut path = vec![];
+
+        loop {
+            match self.next_token() {
+                Some(segment @ Token::Identifier(_)) => path.push(segment.as_str().to_owned()),
+                // A glob can only be the last segment
+                Some(segment @ Token::Star(_)) if !path.is_empty() => {
+                    path.push(segment.as_str().to_owned());
+                    break;
+                }
+                found => {
+                    return Err(ParseError::InvalidUsePath {
+                        found: found.map(|token| token.as_str().to_owned()),
+                        span: self.span_of(found),
+                    })
+                }
+            }
+
+            match self.peek_token() {
+                Some(Token::PathSep(_)) => self.consume_token(),
+                _ => break,
+            }
+        }
+
+        let glob = path.last().is_some_and(|segment| segment == "*");
+
+        if let found @ Some(Token::PathSep(_)) = self.peek_token() {
+            return Err(ParseError::InvalidUsePath {
+                found: found.map(|token| token.as_str().to_owned()),
+                span: self.span_of(found),
+            });
+        }
+
+        let alias = match self.peek_token() {
+            Some(Token::As(_)) if !glob => {
+                self.consume_token();
+                Some(self.expect_name()?.as_str().to_owned())
+            }
+            _ => None,
+        };
+
+        let span = self.span_from(keyword.loc());
+
+        if let Some(Token::SemiColon(_)) = self.peek_token() {
+            self.consume_token();
+        }
+
+        Ok(UseDecl { path, alias, span })
+    }
+}
@@ -0,0 +1,145 @@
+use crate::ast::LiteralValue;
+use crate::token::Token;
+
+use super::error::ParseError;
+
+/// Read the value of a literal token.
+///
+/// Integers can be written in hex with `0x`, octal with `0o` or binary with `0b`, and any number
+/// can have `_` between its digits. Strings and characters have their escapes replaced.
+pub fn parse_literal(token: &Token) -> Result<LiteralValue, ParseError> {
+    let text = token.as_str();
+    // Escapes are offset by the opening quote
+    let quoted = || &text[1..text.len() - 1];
+
+    match token {
+        Token::True(_) => Ok(LiteralValue::Bool(true)),
+        Token::False(_) => Ok(LiteralValue::Bool(false)),
+        Token::IntLiteral(_) => parse_int(text, token.loc()),
+        Token::FloatLiteral(_) => match text.replace('_', "").parse() {
+            Ok(value) => Ok(LiteralValue::Float(value)),
+            Err(_) => Err(not_a_literal(token)),
+        },
+        Token::StringLiteral(_) => Ok(LiteralValue::String(unescape(quoted(), token.loc() + 1)?)),
+        Token::CharLiteral(_) => {
+            let value = unescape(quoted(), token.loc() + 1)?;
+            let mut chars = value.chars();
+
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Ok(LiteralValue::Char(c)),
+                _ => Err(ParseError::InvalidCharLiteral {
+                    count: value.chars().count(),
+                    span: token.span().into(),
+                }),
+            }
+        }
+        _ => Err(not_a_literal(token)),
+    }
+}
+
+fn not_a_literal(token: &Token) -> ParseError {
+    ParseError::ExpectedExpression {
+        found: Some(token.as_str().to_owned()),
+        span: token.span().into(),
+    }
+}
+
+/// Integers that don't fit in an `i128` become a [`LiteralValue::UInt`].
+fn parse_int(text: &str, loc: usize) -> Result<LiteralValue, ParseError> {
+    let (base, prefix) = match text.get(..2) {
+        Some("0x") => (16, 2),
+        Some("0o") => (8, 2),
+        Some("0b") => (2, 2),
+        _ => (10, 0),
+    };
+
+    // Without any digits after it, the prefix is just an invalid decimal
+    let (base, prefix) = match text[prefix..].bytes().any(|byte| byte != b'_') {
+        true => (base, prefix),
+        false => (10, 0),
+    };
+
+    let mut value: u128 = 0;
+
+    for (offset, c) in text[prefix..].char_indices() {
+        if c == '_' {
+            continue;
+        }
+
+        let digit = c.to_digit(base).ok_or_else(|| ParseError::InvalidDigit {
+            digit: c,
+            base,
+            span: (loc + prefix + offset, c.len_utf8()).into(),
+        })?;
+
+        value = value
+            .checked_mul(base.into())
+            .and_then(|value| value.checked_add(digit.into()))
+            .ok_or_else(|| ParseError::IntegerOverflow {
+                span: (loc, text.len()).into(),
+            })?;
+    }
+
+    Ok(i128::try_from(value).map_or(LiteralValue::UInt(value), LiteralValue::Int))
+}
+
+/// Replace the escapes in the contents of a string or character literal starting at `loc`.
+fn unescape(text: &str, loc: usize) -> Result<String, ParseError> {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+    let mut offset = 0;
+
+    while let Some(start) = rest.find('\\') {
+        output.push_str(&rest[..start]);
+        let escape = &rest[start..];
+
+        let (c, len) = match escape.as_bytes().get(1) {
+            Some(b'n') => ('\n', 2),
+            Some(b'r') => ('\r', 2),
+            Some(b't') => ('\t', 2),
+            Some(b'0') => ('\0', 2),
+            Some(b'\\') => ('\\', 2),
+            Some(b'\'') => ('\'', 2),
+            Some(b'"') => ('"', 2),
+            Some(b'$') => ('$', 2),
+            Some(b'u') => unicode_escape(escape).ok_or_else(|| {
+                let len = escape.find('}').map_or(2, |close| close + 1);
+                invalid_escape(&escape[..len], loc + offset + start)
+            })?,
+            _ => {
+                let len = escape.chars().take(2).map(char::len_utf8).sum();
+                return Err(invalid_escape(&escape[..len], loc + offset + start));
+            }
+        };
+
+        output.push(c);
+        offset += start + len;
+        rest = &rest[start + len..];
+    }
+
+    output.push_str(rest);
+
+    Ok(output)
+}
+
+/// Read a `\u{1F600}` escape at the start of `escape`, returning the character and its length.
+fn unicode_escape(escape: &str) -> Option<(char, usize)> {
+    let body = escape.strip_prefix("\\u{")?;
+    let close = body.find('}')?;
+    let hex = &body[..close];
+
+    if hex.is_empty() || hex.len() > 6 {
+        return None;
+    }
+
+    let c = u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)?;
+
+    Some((c, "\\u{".len() + close + 1))
+}
+
+fn invalid_escape(escape: &str, loc: usize) -> ParseError {
+    ParseError::InvalidEscape {
+        escape: escape.to_owned(),
+        span: (loc, escape.len()).into(),
+    }
+}
@@ -1,2 +1,10 @@
-mod error;
+pub mod error;
+mod expr;
+mod item;
+mod literal;
 pub mod parser;
+mod pattern;
+mod stmt;
+mod ty;
+
+pub use self::literal::parse_literal;
@@ -1,39 +1,156 @@
-use log::trace;
-use miette::{bail, miette, LabeledSpan};
+use std::borrow::Cow;
+use std::ops::Range;
 
-use crate::token::Token;
+use log::{debug, trace};
+use miette::{bail, miette, LabeledSpan, SourceSpan};
 
-use super::error::UnexpectedToken;
+use crate::span::Span;
+use crate::token::{Token, TokenStream, TokenType};
+
+use super::error::{ParseError, UnexpectedToken};
+
+/// Recovering from a syntax error tries at most this many insertions before the same token, so
+/// that a loop expecting a token the input doesn't have gives up instead of inserting forever.
+const MAX_RECOVERIES_AT_TOKEN: usize = 3;
 
 pub struct Parser<'a> {
     index: usize,
-    tokens: &'a [Token<'a>],
+    /// Only copied once tokens are injected into it.
+    tokens: Cow<'a, [Token<'a>]>,
+    /// Ranges of `tokens` that were injected rather than lexed, in order.
+    injected: Vec<Range<usize>>,
     pub stack: Vec<Token<'a>>,
-    parsed: Vec<Token<'a>>,
+    error_recovery: bool,
+    /// The tokens made up by error recovery so far, in the order they were inserted.
+    pub recoveries: Vec<RecoveryAction>,
+}
+
+/// A token that error recovery acted as if it were in the input, where a different one was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecoveryAction {
+    pub inserted: TokenType,
+    pub instead_of: TokenType,
+    /// The token found instead, which is parsed after the inserted one.
+    pub span: Span,
+}
+
+impl RecoveryAction {
+    /// The syntax error that was recovered from.
+    pub fn to_error(&self) -> ParseError {
+        ParseError::InsertedToken {
+            inserted: self.inserted,
+            found: self.instead_of,
+            span: self.span.into(),
+        }
+    }
 }
 
 impl<'a> Parser<'a> {
     pub fn new(index: usize, tokens: &'a [Token<'a>]) -> Self {
         Parser {
             index,
-            tokens,
+            tokens: Cow::Borrowed(tokens),
+            injected: vec![],
             stack: vec![],
-            parsed: Vec::with_capacity(tokens.len()),
+            error_recovery: false,
+            recoveries: vec![],
+        }
+    }
+
+    /// When a token with a fixed spelling, like `(` or `;`, is expected but another is found, act
+    /// as if the expected one were there and keep parsing, recording it in `recoveries`.
+    ///
+    /// This gives a partial AST for input with small mistakes, such as a missing `;`. Such
+    /// mistakes no longer fail parsing, so their errors have to be taken from `recoveries`.
+    pub fn with_error_recovery(mut self, enabled: bool) -> Self {
+        self.error_recovery = enabled;
+        self
+    }
+
+    /// Make up a token of type `expected` just before the next token, recording that it was
+    /// inserted. The next token is left to be parsed after it.
+    ///
+    /// # Panics
+    ///
+    /// If `expected` has no fixed spelling, like identifiers and literals, or there are no
+    /// tokens left to insert before.
+    pub fn recover_with(&mut self, expected: TokenType) -> Token<'a> {
+        let found = self
+            .peek_token()
+            .expect("tokens are only inserted before another token");
+        let token = Token::new_at(found.loc(), expected)
+            .expect("only tokens with a fixed spelling can be inserted");
+
+        debug!(
+            "inserted {expected:?} before {:?} at {}",
+            found.ty(),
+            found.loc()
+        );
+        self.recoveries.push(RecoveryAction {
+            inserted: expected,
+            instead_of: found.ty(),
+            span: found.span(),
+        });
+
+        token
+    }
+
+    /// Whether error recovery can act as if a token of type `expected` came next.
+    fn can_recover_with(&self, expected: TokenType) -> bool {
+        let Some(found) = self.peek_token() else {
+            return false;
+        };
+        let at_found = self
+            .recoveries
+            .iter()
+            .filter(|recovery| recovery.span.start == found.loc())
+            .count();
+
+        self.error_recovery && expected.spelling().is_some() && at_found < MAX_RECOVERIES_AT_TOKEN
+    }
+
+    /// Insert `tokens` at the current position, so they are parsed before the rest of the input.
+    /// This is how the output of macro expansion gets parsed.
+    pub fn inject_tokens(&mut self, tokens: &[Token<'a>]) {
+        let index = self.index;
+        let len = tokens.len();
+
+        self.tokens
+            .to_mut()
+            .splice(index..index, tokens.iter().copied());
+
+        // Injecting into an earlier expansion makes the new tokens part of it
+        for range in &mut self.injected {
+            if range.start >= index {
+                range.start += len;
+                range.end += len;
+            } else if range.end > index {
+                range.end += len;
+            }
         }
+
+        let at = self.injected.partition_point(|range| range.start < index);
+        if !self.injected.iter().any(|range| range.contains(&index)) {
+            self.injected.insert(at, index..index + len);
+        }
+    }
+
+    /// Whether the next token was injected by [`Parser::inject_tokens`] instead of being lexed,
+    /// so errors can say it came from an expansion.
+    pub fn next_is_injected(&self) -> bool {
+        let next = (self.index..self.tokens.len()).find(|idx| !self.tokens[*idx].is_trivia());
+
+        next.is_some_and(|next| self.injected.iter().any(|range| range.contains(&next)))
     }
 
     pub fn peek_token(&self) -> Option<Token<'a>> {
         let mut index = self.index;
 
         loop {
-            if index >= self.tokens.len() - 1 {
-                return None;
-            }
-
-            let next_token = self.tokens[index];
+            let next_token = *self.tokens.get(index)?;
             index += 1;
 
-            if let Token::Space(_) | Token::Newline(_) = next_token {
+            if next_token.is_trivia() {
                 continue;
             } else {
                 return Some(next_token);
@@ -43,14 +160,10 @@ impl<'a> Parser<'a> {
 
     pub fn next_token(&mut self) -> Option<Token<'a>> {
         loop {
-            if self.index >= self.tokens.len() - 1 {
-                return None;
-            }
-
-            let next_token = self.tokens[self.index];
+            let next_token = *self.tokens.get(self.index)?;
             self.index += 1;
 
-            if let Token::Space(_) | Token::Newline(_) = next_token {
+            if next_token.is_trivia() {
                 continue;
             } else {
                 return Some(next_token);
@@ -63,7 +176,7 @@ impl<'a> Parser<'a> {
             let next_token = self.tokens[self.index];
             self.index += 1;
 
-            if let Token::Space(_) | Token::Newline(_) = next_token {
+            if next_token.is_trivia() {
                 continue;
             } else {
                 break;
@@ -71,6 +184,147 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Consume tokens, trivia included, for as long as they match `pred`.
+    ///
+    /// The first token that doesn't match is left for the next call.
+    pub fn take_while<'p>(
+        &'p mut self,
+        pred: impl Fn(&Token<'a>) -> bool + 'p,
+    ) -> impl Iterator<Item = Token<'a>> + 'p {
+        std::iter::from_fn(move || {
+            let token = self.tokens.get(self.index).filter(|token| pred(token))?;
+            self.index += 1;
+
+            Some(*token)
+        })
+    }
+
+    /// Consume tokens, trivia included, for as long as they are of type `ty`.
+    pub fn take_while_type(&mut self, ty: TokenType) -> impl Iterator<Item = Token<'a>> + '_ {
+        self.take_while(move |token| token.ty() == ty)
+    }
+
+    /// Every token being parsed, trivia and injected tokens included, indexed like
+    /// [`Parser::save`].
+    pub fn tokens(&self) -> TokenStream<'_, 'a> {
+        TokenStream::new(&self.tokens)
+    }
+
+    /// Save the current position, to be handed back to [`Parser::restore`] for backtracking.
+    pub fn save(&self) -> usize {
+        self.index
+    }
+
+    /// Go back to a position from [`Parser::save`], forgetting any recoveries made since.
+    pub fn restore(&mut self, index: usize) {
+        self.index = index;
+
+        let offset = self
+            .tokens
+            .get(index)
+            .map_or(usize::MAX, |token| token.loc());
+        self.recoveries
+            .retain(|recovery| recovery.span.start < offset);
+    }
+
+    /// Skip past the token an error was found at, to where parsing can pick back up: the start of
+    /// the next statement or item, or just after a `;` or `}`.
+    pub fn synchronize(&mut self) {
+        while let Some(token) = self.next_token() {
+            if matches!(token, Token::SemiColon(_) | Token::CloseCurly(_)) {
+                return;
+            }
+
+            match self.peek_token() {
+                Some(next) if next.is_start_of_statement() || next.is_declaration_keyword() => {
+                    return
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Consume tokens matching `types` in order. If any token doesn't match, nothing is consumed,
+    /// unless error recovery can insert the missing token.
+    pub fn expect_sequence(&mut self, types: &[TokenType]) -> Result<Vec<Token<'a>>, ParseError> {
+        let start = self.save();
+        let mut matched = Vec::with_capacity(types.len());
+
+        for expected in types {
+            if self
+                .peek_token()
+                .is_some_and(|token| token.ty() != *expected)
+                && self.can_recover_with(*expected)
+            {
+                matched.push(self.recover_with(*expected));
+                continue;
+            }
+
+            match self.next_token() {
+                Some(token) if token.ty() == *expected => matched.push(token),
+                found => {
+                    let span = self.span_of(found);
+                    self.restore(start);
+
+                    return Err(ParseError::SequenceMismatch {
+                        expected: *expected,
+                        found: found.map(|token| token.as_str().to_owned()),
+                        matched: matched.len(),
+                        span,
+                    });
+                }
+            }
+        }
+
+        Ok(matched)
+    }
+
+    /// Consume a single token of type `ty`, or fail without consuming anything.
+    pub fn expect_type(&mut self, ty: TokenType) -> Result<Token<'a>, ParseError> {
+        self.expect_sequence(&[ty]).map(|tokens| tokens[0])
+    }
+
+    /// Consume the next token if it has type `ty`, returning whether it did. Unlike
+    /// [`Parser::expect_type`], error recovery never inserts it.
+    pub fn consume_if(&mut self, ty: TokenType) -> bool {
+        let matches = self.peek_token().is_some_and(|token| token.ty() == ty);
+        if matches {
+            self.consume_token();
+        }
+
+        matches
+    }
+
+    /// Consume the identifier naming a declaration.
+    pub fn expect_name(&mut self) -> Result<Token<'a>, ParseError> {
+        match self.peek_token() {
+            Some(token) if token.is_keyword() => Err(ParseError::ReservedName {
+                name: token.as_str().to_owned(),
+                span: token.span().into(),
+            }),
+            _ => self.expect_type(TokenType::Identifier),
+        }
+    }
+
+    /// The span from `start` to the end of the most recently consumed token.
+    pub fn span_from(&self, start: usize) -> Span {
+        let end = self.tokens[..self.index]
+            .iter()
+            .rev()
+            .find(|token| !token.is_trivia())
+            .map_or(start, |token| token.end());
+
+        Span::new(start, end.max(start))
+    }
+
+    /// The span of `found` for an error. Errors at the end of input point just past the last token.
+    pub fn span_of(&self, found: Option<Token<'a>>) -> SourceSpan {
+        match found {
+            Some(token) => token.span().into(),
+            None => (self.tokens.last().map_or(0, |token| token.end()), 0).into(),
+        }
+    }
+
     pub fn push(&mut self, token: Token<'a>) {
         self.stack.push(token);
     }
@@ -231,7 +485,7 @@ fn function_declaration(parser: &mut Parser<'_>) -> miette::Result<()> {
     Ok(())
 }
 
-fn expression(parser: &mut Parser<'_>, precedence: u8) -> miette::Result<()> {
+fn expression(parser: &mut Parser<'_>, _precedence: u8) -> miette::Result<()> {
     loop {
         let token = parser.next_token();
 
@@ -0,0 +1,57 @@
+use crate::ast::{LiteralValue, Pattern};
+use crate::token::Token;
+
+use super::error::ParseError;
+use super::literal::parse_literal;
+use super::parser::Parser;
+
+impl<'a> Parser<'a> {
+    /// Parse a pattern, such as `1`, `-1`, `"text"`, `true`, `name`, `_` or `1 | 2`.
+    pub fn parse_pattern(&mut self) -> Result<Pattern, ParseError> {
+        let mut pattern = self.parse_single_pattern()?;
+
+        while let Some(Token::Pipe(_)) = self.peek_token() {
+            self.consume_token();
+            pattern = Pattern::Or(Box::new(pattern), Box::new(self.parse_single_pattern()?));
+        }
+
+        Ok(pattern)
+    }
+
+    fn parse_single_pattern(&mut self) -> Result<Pattern, ParseError> {
+        let start = self.peek_token().map_or(0, |token| token.loc());
+        let negative = matches!(self.peek_token(), Some(Token::Minus(_)));
+        if negative {
+            self.consume_token();
+        }
+
+        match self.next_token() {
+            Some(token @ (Token::IntLiteral(_) | Token::FloatLiteral(_))) if negative => {
+                match parse_literal(&token)? {
+                    LiteralValue::Int(value) => Ok(Pattern::Literal(LiteralValue::Int(-value))),
+                    // `i128::MIN` only fits once it's negative
+                    LiteralValue::UInt(value) if value == i128::MIN.unsigned_abs() => {
+                        Ok(Pattern::Literal(LiteralValue::Int(i128::MIN)))
+                    }
+                    LiteralValue::Float(value) => Ok(Pattern::Literal(LiteralValue::Float(-value))),
+                    _ => Err(ParseError::IntegerOverflow {
+                        span: self.span_from(start).into(),
+                    }),
+                }
+            }
+            Some(token) if token.is_literal() && !negative => {
+                Ok(Pattern::Literal(parse_literal(&token)?))
+            }
+            Some(Token::Identifier(inner)) if !negative && inner.slice == "_" => {
+                Ok(Pattern::Wildcard)
+            }
+            Some(token @ Token::Identifier(_)) if !negative => {
+                Ok(Pattern::Ident(token.as_str().to_owned()))
+            }
+            found => Err(ParseError::ExpectedPattern {
+                found: found.map(|token| token.as_str().to_owned()),
+                span: self.span_of(found),
+            }),
+        }
+    }
+}
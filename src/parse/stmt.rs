@@ -0,0 +1,408 @@
+use crate::ast::{
+    AssignStmt, BinOpKind, Block, BreakStmt, ContinueStmt, ExprNode, ForInStmt, ForStmt, LetStmt,
+    ReturnStmt, StmtNode, WhileStmt,
+};
+use crate::token::{Token, TokenType};
+
+use super::error::ParseError;
+use super::parser::Parser;
+
+impl<'a> Parser<'a> {
+    /// Parse `{ stmt; stmt; expr }`, where the final expression without a `;` is optional.
+    pub fn parse_block(&mut self) -> Result<Block, ParseError> {
+        let open = match self.peek_token() {
+            Some(token @ Token::OpenCurly(_)) => {
+                self.consume_token();
+                token
+            }
+            found => {
+                return Err(ParseError::MissingBlock {
+                    found: found.map(|token| token.as_str().to_owned()),
+                    span: self.span_of(found),
+                })
+            }
+        };
+
+        let mut stmts = vec![];
+        let mut final_expr = None;
+
+        while !matches!(self.peek_token(), Some(Token::CloseCurly(_)) | None) {
+            match self.peek_token() {
+                Some(Token::While(_)) => {
+                    stmts.push(StmtNode::While(self.parse_while_stmt()?));
+                    continue;
+                }
+                Some(Token::For(_)) => {
+                    stmts.push(self.parse_for()?);
+                    continue;
+                }
+                Some(Token::Let(_)) => {
+                    stmts.push(StmtNode::Let(self.parse_let()?));
+                    continue;
+                }
+                Some(Token::Return(_)) => {
+                    stmts.push(StmtNode::Return(self.parse_return()?));
+                    continue;
+                }
+                Some(Token::Break(_)) => {
+                    stmts.push(StmtNode::Break(self.parse_break()?));
+                    continue;
+                }
+                Some(Token::Continue(_)) => {
+                    stmts.push(StmtNode::Continue(self.parse_continue()?));
+                    continue;
+                }
+                Some(label @ Token::Label(_)) => {
+                    self.consume_token();
+                    self.expect_type(TokenType::Colon)?;
+
+                    let label = Some(label.as_str()[1..].to_owned());
+
+                    match self.peek_token() {
+                        Some(Token::While(_)) => {
+                            let stmt = WhileStmt {
+                                label,
+                                ..self.parse_while_stmt()?
+                            };
+                            stmts.push(StmtNode::While(stmt));
+                        }
+                        Some(Token::For(_)) => {
+                            let stmt = match self.parse_for()? {
+                                StmtNode::For(stmt) => StmtNode::For(ForStmt { label, ..stmt }),
+                                StmtNode::ForIn(stmt) => {
+                                    StmtNode::ForIn(ForInStmt { label, ..stmt })
+                                }
+                                stmt => stmt,
+                            };
+                            stmts.push(stmt);
+                        }
+                        _ => {
+                            let block = Block {
+                                label,
+                                ..self.parse_block()?
+                            };
+                            stmts.push(StmtNode::Expr(ExprNode::Block(block)));
+                        }
+                    }
+                    continue;
+                }
+                // Empty statements
+                Some(Token::SemiColon(_)) => {
+                    self.consume_token();
+                    continue;
+                }
+                _ => {}
+            }
+
+            let start = self.peek_token().map_or(0, |token| token.loc());
+            let expr = self.parse_expr(0)?;
+
+            match self.peek_token() {
+                Some(token)
+                    if matches!(token, Token::Equals(_))
+                        || BinOpKind::from_compound_assignment(&token).is_some() =>
+                {
+                    stmts.push(StmtNode::Assign(self.parse_assign(expr, start)?));
+                }
+                Some(Token::SemiColon(_)) => {
+                    self.consume_token();
+                    stmts.push(StmtNode::Expr(expr));
+                }
+                Some(Token::CloseCurly(_)) => final_expr = Some(Box::new(expr)),
+                _ if expr.is_block_like() => stmts.push(StmtNode::Expr(expr)),
+                // Only gets past here when error recovery inserts the `;`
+                _ => {
+                    self.expect_type(TokenType::SemiColon)?;
+                    stmts.push(StmtNode::Expr(expr));
+                }
+            }
+        }
+
+        self.expect_type(TokenType::CloseCurly)?;
+
+        Ok(Block {
+            label: None,
+            stmts,
+            final_expr,
+            span: self.span_from(open.loc()),
+        })
+    }
+
+    /// Parse `while cond { ... }`.
+    pub fn parse_while_stmt(&mut self) -> Result<WhileStmt, ParseError> {
+        let keyword = self.expect_type(TokenType::While)?;
+        let cond = self.parse_expr(0)?;
+        let body = self.parse_block()?;
+
+        Ok(WhileStmt {
+            label: None,
+            cond: Box::new(cond),
+            body,
+            span: self.span_from(keyword.loc()),
+        })
+    }
+
+    /// Parse either kind of `for` loop. It's a `for x in` loop when a binding and `in` follow the
+    /// keyword, and a C-style loop otherwise.
+    pub fn parse_for(&mut self) -> Result<StmtNode, ParseError> {
+        let start = self.save();
+        self.expect_type(TokenType::For)?;
+
+        if matches!(self.peek_token(), Some(Token::Mut(_))) {
+            self.consume_token();
+        }
+        let is_for_in = matches!(self.next_token(), Some(Token::Identifier(_)))
+            && matches!(self.peek_token(), Some(Token::In(_)));
+
+        self.restore(start);
+
+        match is_for_in {
+            true => Ok(StmtNode::ForIn(self.parse_for_in_stmt()?)),
+            false => Ok(StmtNode::For(self.parse_for_stmt()?)),
+        }
+    }
+
+    /// Parse `for (init; cond; post) { ... }`, where the parentheses and the clauses are optional.
+    pub fn parse_for_stmt(&mut self) -> Result<ForStmt, ParseError> {
+        let keyword = self.expect_type(TokenType::For)?;
+
+        let parenthesised = matches!(self.peek_token(), Some(Token::OpenBracket(_)));
+        if parenthesised {
+            self.consume_token();
+        }
+
+        let init = match self.peek_token() {
+            Some(Token::SemiColon(_)) => {
+                self.consume_token();
+                None
+            }
+            Some(Token::Let(_)) => Some(Box::new(StmtNode::Let(self.parse_let()?))),
+            _ => Some(Box::new(self.parse_for_clause(true)?)),
+        };
+
+        let cond = match self.peek_token() {
+            Some(Token::SemiColon(_)) => None,
+            _ => Some(self.parse_expr(0)?),
+        };
+        self.expect_type(TokenType::SemiColon)?;
+
+        let post = match self.peek_token() {
+            Some(Token::CloseBracket(_)) if parenthesised => None,
+            Some(Token::OpenCurly(_)) if !parenthesised => None,
+            _ => Some(Box::new(self.parse_for_clause(false)?)),
+        };
+
+        if parenthesised {
+            self.expect_type(TokenType::CloseBracket)?;
+        }
+
+        let body = self.parse_block()?;
+
+        Ok(ForStmt {
+            label: None,
+            init,
+            cond,
+            post,
+            body,
+            span: self.span_from(keyword.loc()),
+        })
+    }
+
+    /// Parse `for x in iterable { ... }`, where `x` can be `mut`.
+    pub fn parse_for_in_stmt(&mut self) -> Result<ForInStmt, ParseError> {
+        let keyword = self.expect_type(TokenType::For)?;
+
+        let mutable = matches!(self.peek_token(), Some(Token::Mut(_)));
+        if mutable {
+            self.consume_token();
+        }
+
+        let binding = self.expect_name()?;
+        self.expect_type(TokenType::In)?;
+
+        let iterable = self.parse_expr(0)?;
+        let body = self.parse_block()?;
+
+        Ok(ForInStmt {
+            label: None,
+            binding: binding.as_str().to_owned(),
+            mutable,
+            iterable,
+            body,
+            span: self.span_from(keyword.loc()),
+        })
+    }
+
+    /// Parse the expression or assignment that starts or steps a C-style `for` loop. Only the
+    /// first clause is followed by a `;`.
+    fn parse_for_clause(&mut self, needs_semicolon: bool) -> Result<StmtNode, ParseError> {
+        let start = self.peek_token().map_or(0, |token| token.loc());
+        let expr = self.parse_expr(0)?;
+
+        let stmt = match self.peek_token() {
+            Some(token)
+                if matches!(token, Token::Equals(_))
+                    || BinOpKind::from_compound_assignment(&token).is_some() =>
+            {
+                StmtNode::Assign(self.parse_assign_value(expr, start)?)
+            }
+            _ => StmtNode::Expr(expr),
+        };
+
+        if needs_semicolon {
+            self.expect_type(TokenType::SemiColon)?;
+        }
+
+        Ok(stmt)
+    }
+
+    /// Parse `let name: Type = init;`, where `mut`, the type and the initialiser are optional.
+    pub fn parse_let(&mut self) -> Result<LetStmt, ParseError> {
+        let keyword = self.expect_type(TokenType::Let)?;
+
+        let mutable = matches!(self.peek_token(), Some(Token::Mut(_)));
+        if mutable {
+            self.consume_token();
+        }
+
+        let name = self.expect_name()?;
+
+        let ty = match self.peek_token() {
+            Some(Token::Colon(_)) => {
+                self.consume_token();
+                Some(self.parse_type_expr()?)
+            }
+            _ => None,
+        };
+
+        let init = match self.peek_token() {
+            Some(Token::Equals(_)) => {
+                self.consume_token();
+                Some(self.parse_expr(0)?)
+            }
+            _ => None,
+        };
+
+        self.expect_type(TokenType::SemiColon)?;
+
+        Ok(LetStmt {
+            name: name.as_str().to_owned(),
+            ty,
+            init,
+            mutable,
+            span: self.span_from(keyword.loc()),
+        })
+    }
+
+    /// Parse the rest of `target = value;` or `target op= value;`, once `target` starting at
+    /// `start` has been parsed.
+    pub fn parse_assign(
+        &mut self,
+        target: ExprNode,
+        start: usize,
+    ) -> Result<AssignStmt, ParseError> {
+        let stmt = self.parse_assign_value(target, start)?;
+        self.expect_type(TokenType::SemiColon)?;
+
+        Ok(AssignStmt {
+            span: self.span_from(start),
+            ..stmt
+        })
+    }
+
+    /// Parse an assignment without the `;` after it.
+    fn parse_assign_value(
+        &mut self,
+        target: ExprNode,
+        start: usize,
+    ) -> Result<AssignStmt, ParseError> {
+        if !target.is_lvalue() {
+            return Err(ParseError::InvalidAssignTarget {
+                span: self.span_from(start).into(),
+            });
+        }
+
+        let op = match self.next_token() {
+            Some(Token::Equals(_)) => None,
+            Some(token) if BinOpKind::from_compound_assignment(&token).is_some() => {
+                BinOpKind::from_compound_assignment(&token)
+            }
+            found => {
+                return Err(ParseError::SequenceMismatch {
+                    expected: TokenType::Equals,
+                    found: found.map(|token| token.as_str().to_owned()),
+                    matched: 0,
+                    span: self.span_of(found),
+                })
+            }
+        };
+
+        let value = self.parse_expr(0)?;
+
+        Ok(AssignStmt {
+            target,
+            op,
+            value,
+            span: self.span_from(start),
+        })
+    }
+
+    /// Parse `return;` or `return value;`.
+    pub fn parse_return(&mut self) -> Result<ReturnStmt, ParseError> {
+        let keyword = self.expect_type(TokenType::Return)?;
+        let value = self.parse_optional_value()?;
+
+        let span = self.span_from(keyword.loc());
+        self.expect_statement_end()?;
+
+        Ok(ReturnStmt { value, span })
+    }
+
+    /// Parse `break;`, `break 'label;` or `break value;`, or both the label and the value.
+    pub fn parse_break(&mut self) -> Result<BreakStmt, ParseError> {
+        let keyword = self.expect_type(TokenType::Break)?;
+        let label = self.parse_optional_label();
+        let value = self.parse_optional_value()?;
+
+        let span = self.span_from(keyword.loc());
+        self.expect_statement_end()?;
+
+        Ok(BreakStmt { label, value, span })
+    }
+
+    /// Parse `continue;` or `continue 'label;`.
+    pub fn parse_continue(&mut self) -> Result<ContinueStmt, ParseError> {
+        let keyword = self.expect_type(TokenType::Continue)?;
+        let label = self.parse_optional_label();
+
+        let span = self.span_from(keyword.loc());
+        self.expect_statement_end()?;
+
+        Ok(ContinueStmt { label, span })
+    }
+
+    fn parse_optional_label(&mut self) -> Option<String> {
+        match self.peek_token() {
+            Some(label @ Token::Label(_)) => {
+                self.consume_token();
+                Some(label.as_str()[1..].to_owned())
+            }
+            _ => None,
+        }
+    }
+
+    fn parse_optional_value(&mut self) -> Result<Option<ExprNode>, ParseError> {
+        match self.peek_token() {
+            Some(Token::SemiColon(_) | Token::CloseCurly(_)) | None => Ok(None),
+            _ => Ok(Some(self.parse_expr(0)?)),
+        }
+    }
+
+    /// The `;` after a statement can be left off before the end of a block.
+    fn expect_statement_end(&mut self) -> Result<(), ParseError> {
+        match self.peek_token() {
+            Some(Token::CloseCurly(_)) => Ok(()),
+            _ => self.expect_type(TokenType::SemiColon).map(|_| ()),
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use crate::ast::TypeExpr;
+use crate::token::{Token, TokenType};
+
+use super::error::ParseError;
+use super::parser::Parser;
+
+impl<'a> Parser<'a> {
+    /// Parse a type, such as `i32`, `*mut T`, `[T; 10]`, `func(T) -> U`, `(T, U)` or `Vec<T>`.
+    ///
+    /// The lexer never joins `>` characters, so nested generics like `A<B<C>>` need no special
+    /// handling.
+    pub fn parse_type_expr(&mut self) -> Result<TypeExpr, ParseError> {
+        match self.peek_token() {
+            Some(token @ Token::Identifier(_)) => {
+                self.consume_token();
+
+                let generics = match self.peek_token() {
+                    Some(Token::LessThan(_)) => {
+                        self.parse_type_list(TokenType::LessThan, TokenType::GreaterThan)?
+                    }
+                    _ => vec![],
+                };
+
+                Ok(TypeExpr::Named {
+                    name: token.as_str().to_owned(),
+                    generics,
+                })
+            }
+            Some(token) if token.is_primitive_type() => {
+                self.consume_token();
+
+                Ok(TypeExpr::Named {
+                    name: token.as_str().to_owned(),
+                    generics: vec![],
+                })
+            }
+            Some(Token::Star(_)) => {
+                self.consume_token();
+
+                let mutable = matches!(self.peek_token(), Some(Token::Mut(_)));
+                if mutable {
+                    self.consume_token();
+                }
+
+                Ok(TypeExpr::Pointer {
+                    inner: Box::new(self.parse_type_expr()?),
+                    mutable,
+                })
+            }
+            Some(Token::OpenSquare(_)) => {
+                self.consume_token();
+                let element = Box::new(self.parse_type_expr()?);
+
+                let size = match self.peek_token() {
+                    Some(Token::SemiColon(_)) => {
+                        self.consume_token();
+
+                        let found = self.next_token();
+                        let size = found.and_then(|token| token.as_str().parse().ok());
+
+                        if size.is_none() {
+                            return Err(ParseError::ExpectedArraySize {
+                                found: found.map(|token| token.as_str().to_owned()),
+                                span: self.span_of(found),
+                            });
+                        }
+
+                        size
+                    }
+                    _ => None,
+                };
+
+                self.expect_type(TokenType::CloseSquare)?;
+
+                Ok(TypeExpr::Array { element, size })
+            }
+            Some(Token::FunctionDeclaration(_)) => {
+                self.consume_token();
+                let params =
+                    self.parse_type_list(TokenType::OpenBracket, TokenType::CloseBracket)?;
+
+                let ret = match self.peek_token() {
+                    Some(Token::Arrow(_)) => {
+                        self.consume_token();
+                        self.parse_type_expr()?
+                    }
+                    _ => TypeExpr::Named {
+                        name: "void".into(),
+                        generics: vec![],
+                    },
+                };
+
+                Ok(TypeExpr::FnType {
+                    params,
+                    ret: Box::new(ret),
+                })
+            }
+            Some(Token::OpenBracket(_)) => Ok(TypeExpr::Tuple(
+                self.parse_type_list(TokenType::OpenBracket, TokenType::CloseBracket)?,
+            )),
+            Some(Token::Bang(_)) => {
+                self.consume_token();
+                Ok(TypeExpr::Never)
+            }
+            found => Err(ParseError::ExpectedType {
+                found: found.map(|token| token.as_str().to_owned()),
+                span: self.span_of(found),
+            }),
+        }
+    }
+
+    /// Parse types separated by commas between `open` and `close`, allowing a trailing comma.
+    pub(crate) fn parse_type_list(
+        &mut self,
+        open: TokenType,
+        close: TokenType,
+    ) -> Result<Vec<TypeExpr>, ParseError> {
+        self.expect_type(open)?;
+
+        let mut types = vec![];
+
+        while self.peek_token().map(|token| token.ty()) != Some(close) {
+            types.push(self.parse_type_expr()?);
+
+            if !self.consume_if(TokenType::Comma) {
+                break;
+            }
+        }
+
+        self.expect_type(close)?;
+
+        Ok(types)
+    }
+}
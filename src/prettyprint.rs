@@ -0,0 +1,732 @@
+use crate::ast::{
+    BinOpKind, Block, CaptureMode, ClosureBody, ClosureExpr, EnumDecl, EnumVariantFields, ExprNode,
+    FieldDecl, FnDecl, FnSig, GenericParams, GlobalDecl, IfExpr, IfOrBlock, ImplBlock, ImplItem,
+    Item, LiteralValue, MatchExpr, Module, Param, Pattern, StmtNode, StructDecl, TraitDecl,
+    TraitItem, TypeAlias, TypeExpr, UnOpKind, UseDecl, Visibility,
+};
+
+/// Writes a module back out as source code in one canonical style, however it was formatted.
+///
+/// Blocks always span several lines, with their contents indented by `indent_width` spaces.
+/// Function signatures stay on one line unless that would be wider than `max_width`, in which
+/// case each parameter gets a line of its own. Parentheses are only written where they're
+/// needed to parse back to the same module. Comments aren't kept, as the AST doesn't have them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Prettier {
+    pub indent_width: usize,
+    pub max_width: usize,
+}
+
+impl Prettier {
+    pub fn new() -> Self {
+        Prettier {
+            indent_width: 4,
+            max_width: 100,
+        }
+    }
+
+    pub fn with_indent_width(mut self, width: usize) -> Self {
+        self.indent_width = width;
+        self
+    }
+
+    pub fn with_max_width(mut self, width: usize) -> Self {
+        self.max_width = width;
+        self
+    }
+
+    /// The source code for `module`, with a blank line between items other than runs of `use`
+    /// declarations and of globals.
+    pub fn format_module(&self, module: &Module) -> String {
+        let mut output = String::new();
+        let mut previous: Option<&Item> = None;
+
+        for item in &module.items {
+            let grouped = matches!(
+                (previous, item),
+                (Some(Item::UseDecl(_)), Item::UseDecl(_))
+                    | (Some(Item::GlobalDecl(_)), Item::GlobalDecl(_))
+            );
+            if previous.is_some() && !grouped {
+                output.push('\n');
+            }
+
+            output.push_str(&self.item(item, 0));
+            output.push('\n');
+            previous = Some(item);
+        }
+
+        output
+    }
+
+    /// An item indented `depth` levels, without a newline after it. Only the lines after the
+    /// first are indented, as the caller places the first.
+    fn item(&self, item: &Item, depth: usize) -> String {
+        match item {
+            Item::FnDecl(decl) => self.fn_decl(decl, depth),
+            Item::StructDecl(decl) => self.struct_decl(decl, depth),
+            Item::EnumDecl(decl) => self.enum_decl(decl, depth),
+            Item::TraitDecl(decl) => self.trait_decl(decl, depth),
+            Item::ImplBlock(block) => self.impl_block(block, depth),
+            Item::UseDecl(decl) => use_decl(decl),
+            Item::GlobalDecl(decl) => self.global_decl(decl, depth),
+            Item::TypeAlias(alias) => type_alias(alias),
+        }
+    }
+
+    fn fn_decl(&self, decl: &FnDecl, depth: usize) -> String {
+        let mut header = String::new();
+        if decl.visibility == Visibility::Public {
+            header.push_str("pub ");
+        }
+        if let Some(abi) = &decl.abi {
+            header.push_str(&format!("extern {} ", string_literal(abi)));
+        }
+        if decl.is_unsafe {
+            header.push_str("unsafe ");
+        }
+
+        let sig = FnSig {
+            name: decl.name.clone(),
+            generic_params: decl.generic_params.clone(),
+            params: decl.params.clone(),
+            ret: decl.ret.clone(),
+            span: decl.span,
+        };
+
+        match decl.is_extern() {
+            true => format!("{header}{};", self.fn_sig(&sig, depth, header.len() + 1)),
+            false => format!(
+                "{header}{} {}",
+                self.fn_sig(&sig, depth, header.len() + 2),
+                self.block(&decl.body, depth)
+            ),
+        }
+    }
+
+    /// `func name<T>(a: A) -> R`, where `extra` is the width of what goes on the same line.
+    fn fn_sig(&self, sig: &FnSig, depth: usize, extra: usize) -> String {
+        let start = format!("func {}{}", sig.name, generic_params(&sig.generic_params));
+        let ret = match &sig.ret {
+            Some(ret) => format!(" -> {ret}"),
+            None => String::new(),
+        };
+
+        let params: Vec<_> = sig.params.iter().map(param).collect();
+        let line = format!("{start}({}){ret}", params.join(", "));
+
+        if params.is_empty() || self.indent(depth).len() + line.len() + extra <= self.max_width {
+            return line;
+        }
+
+        let mut sig = format!("{start}(\n");
+        for param in params {
+            sig.push_str(&format!("{}{param},\n", self.indent(depth + 1)));
+        }
+        sig.push_str(&format!("{}){ret}", self.indent(depth)));
+
+        sig
+    }
+
+    fn struct_decl(&self, decl: &StructDecl, depth: usize) -> String {
+        let header = format!(
+            "struct {}{}",
+            decl.name,
+            generic_params(&decl.generic_params)
+        );
+        let fields: Vec<_> = decl.fields.iter().map(field).collect();
+
+        self.braced(&header, &fields, ",", depth)
+    }
+
+    fn enum_decl(&self, decl: &EnumDecl, depth: usize) -> String {
+        let header = format!("enum {}{}", decl.name, generic_params(&decl.generic_params));
+        let variants: Vec<_> = decl
+            .variants
+            .iter()
+            .map(|variant| match &variant.fields {
+                EnumVariantFields::Unit => variant.name.clone(),
+                EnumVariantFields::Tuple(types) => {
+                    format!("{}({})", variant.name, type_list(types))
+                }
+                EnumVariantFields::Struct(fields) if fields.is_empty() => {
+                    format!("{} {{}}", variant.name)
+                }
+                EnumVariantFields::Struct(fields) => {
+                    let fields: Vec<_> = fields.iter().map(field).collect();
+                    format!("{} {{ {} }}", variant.name, fields.join(", "))
+                }
+            })
+            .collect();
+
+        self.braced(&header, &variants, ",", depth)
+    }
+
+    fn trait_decl(&self, decl: &TraitDecl, depth: usize) -> String {
+        let header = format!(
+            "trait {}{}",
+            decl.name,
+            generic_params(&decl.generic_params)
+        );
+        let items: Vec<_> = decl
+            .items
+            .iter()
+            .map(|item| match item {
+                TraitItem::FnSignature(sig) => {
+                    (format!("{};", self.fn_sig(sig, depth + 1, 1)), false)
+                }
+                TraitItem::DefaultFn(decl) => (self.fn_decl(decl, depth + 1), true),
+            })
+            .collect();
+
+        self.item_list(&header, &items, depth)
+    }
+
+    fn impl_block(&self, block: &ImplBlock, depth: usize) -> String {
+        let mut header = format!("impl{}", generic_params(&block.generic_params));
+        if let Some(path) = &block.trait_path {
+            header.push_str(&format!(" {}", path.join("::")));
+            if !block.trait_generics.is_empty() {
+                header.push_str(&format!("<{}>", type_list(&block.trait_generics)));
+            }
+            header.push_str(" for");
+        }
+        header.push_str(&format!(" {}", block.for_type));
+
+        let items: Vec<_> = block
+            .items
+            .iter()
+            .map(|ImplItem::Fn(decl)| (self.fn_decl(decl, depth + 1), !decl.is_extern()))
+            .collect();
+
+        self.item_list(&header, &items, depth)
+    }
+
+    fn global_decl(&self, decl: &GlobalDecl, depth: usize) -> String {
+        let keyword = match (decl.is_const, decl.mutable) {
+            (true, _) => "const",
+            (false, true) => "let mut",
+            (false, false) => "let",
+        };
+
+        match &decl.init {
+            Some(init) => format!(
+                "{keyword} {}: {} = {};",
+                decl.name,
+                decl.ty,
+                self.expr(init, depth)
+            ),
+            None => format!("{keyword} {}: {};", decl.name, decl.ty),
+        }
+    }
+
+    /// `header { ... }` with each of `lines` followed by `separator` on a line of its own.
+    fn braced(&self, header: &str, lines: &[String], separator: &str, depth: usize) -> String {
+        if lines.is_empty() {
+            return format!("{header} {{}}");
+        }
+
+        let mut text = format!("{header} {{\n");
+        for line in lines {
+            text.push_str(&format!("{}{line}{separator}\n", self.indent(depth + 1)));
+        }
+        text.push_str(&format!("{}}}", self.indent(depth)));
+
+        text
+    }
+
+    /// `header { ... }` around the functions of a trait or impl, each given with whether it has
+    /// a body. Those that do are kept apart by a blank line.
+    fn item_list(&self, header: &str, items: &[(String, bool)], depth: usize) -> String {
+        if items.is_empty() {
+            return format!("{header} {{}}");
+        }
+
+        let mut text = format!("{header} {{\n");
+        for (idx, (item, has_body)) in items.iter().enumerate() {
+            if idx > 0 && (*has_body || items[idx - 1].1) {
+                text.push('\n');
+            }
+            text.push_str(&format!("{}{item}\n", self.indent(depth + 1)));
+        }
+        text.push_str(&format!("{}}}", self.indent(depth)));
+
+        text
+    }
+
+    fn block(&self, block: &Block, depth: usize) -> String {
+        let label = match &block.label {
+            Some(label) => format!("'{label}: "),
+            None => String::new(),
+        };
+
+        if block.stmts.is_empty() && block.final_expr.is_none() {
+            return format!("{label}{{}}");
+        }
+
+        let mut lines: Vec<String> = vec![];
+        // Whether the last line is a block-like expression, which has no `;` after it
+        let mut after_block_like = false;
+
+        let exprs = block
+            .final_expr
+            .iter()
+            .map(|expr| (self.expr(expr, depth + 1), false));
+        for (line, block_like) in block
+            .stmts
+            .iter()
+            .map(|stmt| self.stmt(stmt, depth + 1))
+            .chain(exprs)
+        {
+            // Anything that could carry on an expression would be parsed as part of it
+            if after_block_like && line.starts_with(['-', '(', '|']) {
+                if let Some(last) = lines.last_mut() {
+                    last.push(';');
+                }
+            }
+
+            lines.push(line);
+            after_block_like = block_like;
+        }
+
+        let mut text = format!("{label}{{\n");
+        for line in lines {
+            text.push_str(&format!("{}{line}\n", self.indent(depth + 1)));
+        }
+        text.push_str(&format!("{}}}", self.indent(depth)));
+
+        text
+    }
+
+    /// A statement, and whether it's an expression ending in a block that needs no `;`.
+    fn stmt(&self, stmt: &StmtNode, depth: usize) -> (String, bool) {
+        let label = |label: &Option<String>| match label {
+            Some(label) => format!("'{label}: "),
+            None => String::new(),
+        };
+
+        let text = match stmt {
+            StmtNode::Expr(expr) if expr.is_block_like() => {
+                return (self.expr(expr, depth), true);
+            }
+            StmtNode::Expr(expr) => format!("{};", self.expr(expr, depth)),
+            StmtNode::While(stmt) => format!(
+                "{}while {} {}",
+                label(&stmt.label),
+                self.expr(&stmt.cond, depth),
+                self.block(&stmt.body, depth)
+            ),
+            StmtNode::For(stmt) => {
+                let clause = |clause: &Option<Box<StmtNode>>| match clause {
+                    Some(clause) => format!(" {}", self.for_clause(clause, depth)),
+                    None => String::new(),
+                };
+                let init = match &stmt.init {
+                    Some(init) => self.for_clause(init, depth),
+                    None => String::new(),
+                };
+                let cond = match &stmt.cond {
+                    Some(cond) => format!(" {}", self.expr(cond, depth)),
+                    None => String::new(),
+                };
+
+                format!(
+                    "{}for ({init};{cond};{}) {}",
+                    label(&stmt.label),
+                    clause(&stmt.post),
+                    self.block(&stmt.body, depth)
+                )
+            }
+            StmtNode::ForIn(stmt) => format!(
+                "{}for {}{} in {} {}",
+                label(&stmt.label),
+                if stmt.mutable { "mut " } else { "" },
+                stmt.binding,
+                self.expr(&stmt.iterable, depth),
+                self.block(&stmt.body, depth)
+            ),
+            StmtNode::Let(_) | StmtNode::Assign(_) => format!("{};", self.for_clause(stmt, depth)),
+            StmtNode::Return(stmt) => match &stmt.value {
+                Some(value) => format!("return {};", self.expr(value, depth)),
+                None => "return;".to_owned(),
+            },
+            StmtNode::Break(stmt) => {
+                let mut text = "break".to_owned();
+                if let Some(label) = &stmt.label {
+                    text.push_str(&format!(" '{label}"));
+                }
+                if let Some(value) = &stmt.value {
+                    text.push_str(&format!(" {}", self.expr(value, depth)));
+                }
+                text.push(';');
+                text
+            }
+            StmtNode::Continue(stmt) => match &stmt.label {
+                Some(label) => format!("continue '{label};"),
+                None => "continue;".to_owned(),
+            },
+        };
+
+        (text, false)
+    }
+
+    /// A statement without the `;` after it, as written in the clauses of a C-style `for` loop.
+    fn for_clause(&self, stmt: &StmtNode, depth: usize) -> String {
+        match stmt {
+            StmtNode::Let(stmt) => {
+                let mut text = "let ".to_owned();
+                if stmt.mutable {
+                    text.push_str("mut ");
+                }
+                text.push_str(&stmt.name);
+                if let Some(ty) = &stmt.ty {
+                    text.push_str(&format!(": {ty}"));
+                }
+                if let Some(init) = &stmt.init {
+                    text.push_str(&format!(" = {}", self.expr(init, depth)));
+                }
+                text
+            }
+            StmtNode::Assign(stmt) => format!(
+                "{} {}= {}",
+                self.expr(&stmt.target, depth),
+                stmt.op.map_or("", bin_op),
+                self.expr(&stmt.value, depth)
+            ),
+            StmtNode::Expr(expr) => self.expr(expr, depth),
+            stmt => self.stmt(stmt, depth).0,
+        }
+    }
+
+    fn expr(&self, expr: &ExprNode, depth: usize) -> String {
+        match expr {
+            ExprNode::Literal { value, .. } => literal(value),
+            ExprNode::Ident { name, .. } => name.clone(),
+            ExprNode::BinOp { op, lhs, rhs, .. } => {
+                let binding = precedence(*op);
+                // Operators are left associative, so only the right operand is grouped when it
+                // binds as tightly
+                let lhs = match lhs.as_ref() {
+                    ExprNode::BinOp { op, .. } if precedence(*op) < binding => {
+                        self.parenthesised(lhs, depth)
+                    }
+                    _ => self.operand(lhs, depth),
+                };
+                let rhs = match rhs.as_ref() {
+                    ExprNode::BinOp { op, .. } if precedence(*op) <= binding => {
+                        self.parenthesised(rhs, depth)
+                    }
+                    _ => self.operand(rhs, depth),
+                };
+
+                format!("{lhs} {} {rhs}", bin_op(*op))
+            }
+            ExprNode::UnaryOp { op, operand, .. } => {
+                let operand = match operand.as_ref() {
+                    ExprNode::BinOp { .. } | ExprNode::UnaryOp { .. } | ExprNode::Closure(_) => {
+                        self.parenthesised(operand, depth)
+                    }
+                    _ => self.expr(operand, depth),
+                };
+
+                format!("{}{operand}", un_op(*op))
+            }
+            ExprNode::Call { callee, args, .. } => {
+                format!(
+                    "{}({})",
+                    self.postfix_base(callee, depth),
+                    self.args(args, depth)
+                )
+            }
+            ExprNode::Builtin { builtin, args, .. } => match builtin.type_arg() {
+                Some(ty) => format!("{}({ty})", builtin.name()),
+                None => format!("{}({})", builtin.name(), self.args(args, depth)),
+            },
+            ExprNode::Index { base, index, .. } => format!(
+                "{}[{}]",
+                self.postfix_base(base, depth),
+                self.expr(index, depth)
+            ),
+            ExprNode::Field { base, name, .. } => {
+                let base = match base.as_ref() {
+                    ExprNode::Literal { .. } => self.parenthesised(base, depth),
+                    _ => self.postfix_base(base, depth),
+                };
+
+                format!("{base}.{name}")
+            }
+            ExprNode::If(if_expr) => self.if_expr(if_expr, depth),
+            ExprNode::Match(match_expr) => self.match_expr(match_expr, depth),
+            ExprNode::Closure(closure) => self.closure(closure, depth),
+            ExprNode::Block(block) => self.block(block, depth),
+        }
+    }
+
+    fn if_expr(&self, if_expr: &IfExpr, depth: usize) -> String {
+        let mut text = format!(
+            "if {} {}",
+            self.expr(&if_expr.cond, depth),
+            self.block(&if_expr.then_block, depth)
+        );
+
+        match if_expr.else_block.as_deref() {
+            Some(IfOrBlock::If(else_if)) => {
+                text.push_str(&format!(" else {}", self.if_expr(else_if, depth)));
+            }
+            Some(IfOrBlock::Block(block)) => {
+                text.push_str(&format!(" else {}", self.block(block, depth)));
+            }
+            None => {}
+        }
+
+        text
+    }
+
+    fn match_expr(&self, match_expr: &MatchExpr, depth: usize) -> String {
+        let scrutinee = self.expr(&match_expr.scrutinee, depth);
+        if match_expr.arms.is_empty() {
+            return format!("match {scrutinee} {{}}");
+        }
+
+        let mut text = format!("match {scrutinee} {{\n");
+        for arm in &match_expr.arms {
+            let guard = match &arm.guard {
+                Some(guard) => format!(" if {}", self.expr(guard, depth + 1)),
+                None => String::new(),
+            };
+            // A body starting with a block would end at the block, like a statement
+            let (body, comma) = match &arm.body {
+                body if body.is_block_like() => (self.expr(body, depth + 1), ""),
+                body if leftmost(body).is_block_like() => {
+                    (self.parenthesised(body, depth + 1), ",")
+                }
+                body => (self.expr(body, depth + 1), ","),
+            };
+
+            text.push_str(&format!(
+                "{}{}{guard} => {body}{comma}\n",
+                self.indent(depth + 1),
+                pattern(&arm.pattern)
+            ));
+        }
+        text.push_str(&format!("{}}}", self.indent(depth)));
+
+        text
+    }
+
+    fn closure(&self, closure: &ClosureExpr, depth: usize) -> String {
+        let capture = match closure.capture_mode {
+            CaptureMode::Move => "move ",
+            CaptureMode::Borrow | CaptureMode::Infer => "",
+        };
+        let params: Vec<_> = closure
+            .params
+            .iter()
+            .map(|param| match &param.ty {
+                Some(ty) => format!("{}: {ty}", param.name),
+                None => param.name.clone(),
+            })
+            .collect();
+        let body = match &closure.body {
+            ClosureBody::Expr(expr) => self.expr(expr, depth),
+            ClosureBody::Block(block) => self.block(block, depth),
+        };
+
+        match params.is_empty() {
+            true => format!("{capture}|| {body}"),
+            false => format!("{capture}|{}| {body}", params.join(", ")),
+        }
+    }
+
+    /// An operand of a binary operator. Closures would take the rest of the expression as their
+    /// body, so they're grouped.
+    fn operand(&self, expr: &ExprNode, depth: usize) -> String {
+        match expr {
+            ExprNode::Closure(_) => self.parenthesised(expr, depth),
+            _ => self.expr(expr, depth),
+        }
+    }
+
+    /// What's called, indexed or has a field taken, which is grouped unless it's a single term.
+    fn postfix_base(&self, expr: &ExprNode, depth: usize) -> String {
+        match expr {
+            ExprNode::BinOp { .. } | ExprNode::UnaryOp { .. } | ExprNode::Closure(_) => {
+                self.parenthesised(expr, depth)
+            }
+            _ => self.expr(expr, depth),
+        }
+    }
+
+    fn parenthesised(&self, expr: &ExprNode, depth: usize) -> String {
+        format!("({})", self.expr(expr, depth))
+    }
+
+    fn args(&self, args: &[ExprNode], depth: usize) -> String {
+        let args: Vec<_> = args.iter().map(|arg| self.expr(arg, depth)).collect();
+        args.join(", ")
+    }
+
+    fn indent(&self, depth: usize) -> String {
+        " ".repeat(depth * self.indent_width)
+    }
+}
+
+impl Default for Prettier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn use_decl(decl: &UseDecl) -> String {
+    match &decl.alias {
+        Some(alias) => format!("use {} as {alias};", decl.path.join("::")),
+        None => format!("use {};", decl.path.join("::")),
+    }
+}
+
+fn type_alias(alias: &TypeAlias) -> String {
+    format!(
+        "type {}{} = {};",
+        alias.name,
+        generic_params(&alias.generic_params),
+        alias.target
+    )
+}
+
+fn generic_params(params: &GenericParams) -> String {
+    if params.is_empty() {
+        return String::new();
+    }
+
+    let params: Vec<_> = params
+        .iter()
+        .map(|param| match param.bounds.is_empty() {
+            true => param.name.clone(),
+            false => {
+                let bounds: Vec<_> = param.bounds.iter().map(TypeExpr::to_string).collect();
+                format!("{}: {}", param.name, bounds.join(" + "))
+            }
+        })
+        .collect();
+
+    format!("<{}>", params.join(", "))
+}
+
+fn param(param: &Param) -> String {
+    format!("{}: {}", param.name, param.ty)
+}
+
+fn field(field: &FieldDecl) -> String {
+    format!("{}: {}", field.name, field.ty)
+}
+
+fn type_list(types: &[TypeExpr]) -> String {
+    let types: Vec<_> = types.iter().map(TypeExpr::to_string).collect();
+    types.join(", ")
+}
+
+fn pattern(pattern: &Pattern) -> String {
+    match pattern {
+        Pattern::Literal(value) => literal(value),
+        Pattern::Ident(name) => name.clone(),
+        Pattern::Wildcard => "_".to_owned(),
+        Pattern::Or(lhs, rhs) => format!("{} | {}", self::pattern(lhs), self::pattern(rhs)),
+    }
+}
+
+/// The expression a binary expression or postfix chain starts with.
+fn leftmost(expr: &ExprNode) -> &ExprNode {
+    match expr {
+        ExprNode::BinOp { lhs, .. } => leftmost(lhs),
+        ExprNode::Call { callee: base, .. }
+        | ExprNode::Index { base, .. }
+        | ExprNode::Field { base, .. } => leftmost(base),
+        _ => expr,
+    }
+}
+
+fn literal(value: &LiteralValue) -> String {
+    match value {
+        LiteralValue::Int(value) => value.to_string(),
+        LiteralValue::UInt(value) => value.to_string(),
+        // Exponents can't be negative, so floats are written out in full
+        LiteralValue::Float(value) => {
+            let text = value.to_string();
+            match text.contains('.') || !value.is_finite() {
+                true => text,
+                false => format!("{text}.0"),
+            }
+        }
+        LiteralValue::String(value) => string_literal(value),
+        LiteralValue::Char(value) => format!("'{}'", escape(&value.to_string(), '\'')),
+        LiteralValue::Bool(value) => value.to_string(),
+    }
+}
+
+fn string_literal(value: &str) -> String {
+    format!("\"{}\"", escape(value, '"'))
+}
+
+/// Escape `text` to go between `quote`s, using the escapes literals can contain.
+fn escape(text: &str, quote: char) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            '\0' => escaped.push_str("\\0"),
+            '\\' => escaped.push_str("\\\\"),
+            c if c == quote => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            c if c.is_control() => escaped.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// How tightly each operator binds, as in [`Token::precedence`].
+///
+/// [`Token::precedence`]: crate::token::Token::precedence
+fn precedence(op: BinOpKind) -> u8 {
+    match op {
+        BinOpKind::Pipe => 3,
+        BinOpKind::Or => 5,
+        BinOpKind::Lt | BinOpKind::Gt => 7,
+        BinOpKind::BitOr => 9,
+        BinOpKind::BitXor => 11,
+        BinOpKind::BitAnd => 13,
+        BinOpKind::Add | BinOpKind::Sub => 15,
+        BinOpKind::Mul | BinOpKind::Div | BinOpKind::Rem => 17,
+    }
+}
+
+fn bin_op(op: BinOpKind) -> &'static str {
+    match op {
+        BinOpKind::Add => "+",
+        BinOpKind::Sub => "-",
+        BinOpKind::Mul => "*",
+        BinOpKind::Div => "/",
+        BinOpKind::Rem => "%",
+        BinOpKind::BitAnd => "&",
+        BinOpKind::BitXor => "^",
+        BinOpKind::BitOr => "|",
+        BinOpKind::Or => "||",
+        BinOpKind::Lt => "<",
+        BinOpKind::Gt => ">",
+        BinOpKind::Pipe => "|>",
+    }
+}
+
+fn un_op(op: UnOpKind) -> &'static str {
+    match op {
+        UnOpKind::Neg => "-",
+        UnOpKind::Not => "!",
+        UnOpKind::BitNot => "~",
+    }
+}
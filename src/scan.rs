@@ -0,0 +1,67 @@
+use crate::token::{Token, TokenType};
+
+/// Given the index of an opening delimiter, find the index of its matching closing delimiter.
+///
+/// Only delimiters of the same kind are counted, so `(` only ever matches `)`. Returns `None` if
+/// `open_idx` isn't an opening delimiter or the stream ends before it is closed.
+pub fn scan_balanced(tokens: &[Token], open_idx: usize) -> Option<usize> {
+    let open_token = tokens.get(open_idx)?;
+    let open = open_token.ty();
+    let close = match open_token {
+        // Generics are delimited too, when that's where the scan starts
+        Token::LessThan(_) => TokenType::GreaterThan,
+        _ => open_token.expected_closing_delimiter()?,
+    };
+
+    let mut depth = 0usize;
+
+    for (idx, token) in tokens.iter().enumerate().skip(open_idx) {
+        let ty = token.ty();
+
+        if ty == open {
+            depth += 1;
+        } else if ty == close {
+            depth -= 1;
+
+            if depth == 0 {
+                return Some(idx);
+            }
+        }
+    }
+
+    None
+}
+
+/// The tokens between the delimiter at `open_idx` and its matching close, exclusive.
+pub fn contents_of<'t, 'a>(tokens: &'t [Token<'a>], open_idx: usize) -> Option<&'t [Token<'a>]> {
+    let close_idx = scan_balanced(tokens, open_idx)?;
+
+    Some(&tokens[open_idx + 1..close_idx])
+}
+
+/// The statement containing the token at `idx`, without the `;` or `}` tokens that bound it.
+///
+/// A statement runs from just after the nearest `;` or `}` before `idx` to just before the nearest
+/// one after it, or the start and end of the stream. Returns `None` if `idx` is out of bounds or is
+/// itself one of those delimiters.
+pub fn surrounding_statement<'t, 'a>(
+    tokens: &'t [Token<'a>],
+    idx: usize,
+) -> Option<&'t [Token<'a>]> {
+    let is_boundary = |token: &Token| matches!(token, Token::SemiColon(_) | Token::CloseCurly(_));
+
+    if is_boundary(tokens.get(idx)?) {
+        return None;
+    }
+
+    let start = tokens[..idx]
+        .iter()
+        .rposition(is_boundary)
+        .map_or(0, |boundary| boundary + 1);
+    let end = tokens[idx..]
+        .iter()
+        .position(is_boundary)
+        .map_or(tokens.len(), |boundary| idx + boundary);
+
+    Some(&tokens[start..end])
+}
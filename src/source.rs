@@ -0,0 +1,237 @@
+use std::fmt::Write;
+
+use crate::token::Token;
+
+/// Lines are cut off after this many characters by [`SourceFile::highlight_range`], so they
+/// don't wrap in a terminal.
+const MAX_LINE_WIDTH: usize = 120;
+
+/// A source text along with the byte offset of each line, for turning offsets into positions.
+#[derive(Debug, Clone)]
+pub struct SourceFile {
+    name: String,
+    text: String,
+    line_starts: Vec<usize>,
+}
+
+impl SourceFile {
+    pub fn new(name: impl Into<String>, text: impl Into<String>) -> Self {
+        let text = text.into();
+        let line_starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(idx, _)| idx + 1))
+            .collect();
+
+        SourceFile {
+            name: name.into(),
+            text,
+            line_starts,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The 0-based line containing the byte `offset`.
+    pub fn line_of(&self, offset: usize) -> usize {
+        self.line_starts.partition_point(|start| *start <= offset) - 1
+    }
+
+    /// The 1-based line and character column of the byte `offset`, as shown to users.
+    pub fn line_col(&self, offset: usize) -> (usize, usize) {
+        let line = self.line_of(offset);
+        let start = self.line_starts[line];
+        let offset = offset.min(self.text.len());
+
+        (line + 1, self.text[start..offset].chars().count() + 1)
+    }
+
+    /// The text of the 0-based `line`, without its line ending.
+    pub fn line_text(&self, line: usize) -> &str {
+        let start = self.line_starts[line];
+        let end = self
+            .line_starts
+            .get(line + 1)
+            .map_or(self.text.len(), |next| next - 1);
+
+        self.text[start..end].trim_end_matches('\r')
+    }
+
+    /// The 0-based line and UTF-16 code unit column of the byte `offset`, as used by LSP.
+    pub fn lsp_position(&self, offset: usize) -> (u32, u32) {
+        let line = self.line_of(offset);
+        let start = self.line_starts[line];
+        let offset = offset.min(self.text.len());
+
+        let character = self.text[start..offset]
+            .chars()
+            .map(char::len_utf16)
+            .sum::<usize>();
+
+        (line as u32, character as u32)
+    }
+
+    /// The byte offset of an LSP position, if it is inside the text and not inside a character.
+    pub fn offset_of_lsp_position(&self, line: u32, character: u32) -> Option<usize> {
+        let start = *self.line_starts.get(line as usize)?;
+        let mut units = 0;
+
+        for (idx, c) in self.text[start..].char_indices() {
+            if units == character as usize {
+                return Some(start + idx);
+            }
+            if units > character as usize || c == '\n' {
+                return None;
+            }
+            units += c.len_utf16();
+        }
+
+        (units == character as usize).then_some(self.text.len())
+    }
+
+    /// The token in `tokens`, sorted by position, whose text contains the byte `offset`. An offset
+    /// between two tokens, where trivia has been left out of `tokens`, finds `None` rather than
+    /// the closest token.
+    pub fn find_token_at_offset<'t, 'a>(
+        tokens: &'t [Token<'a>],
+        offset: usize,
+    ) -> Option<&'t Token<'a>> {
+        let idx = tokens.partition_point(|token| token.loc() <= offset);
+
+        tokens[..idx]
+            .last()
+            .filter(|token| token.span().contains_offset(offset))
+    }
+
+    /// The tokens in `tokens`, sorted by position, that overlap the bytes from `start` up to
+    /// `end`, including those only partly inside it.
+    pub fn find_tokens_in_range<'t, 'a>(
+        tokens: &'t [Token<'a>],
+        start: usize,
+        end: usize,
+    ) -> &'t [Token<'a>] {
+        let first = tokens.partition_point(|token| token.end() <= start);
+        let last = tokens.partition_point(|token| token.loc() < end);
+
+        &tokens[first..last.max(first)]
+    }
+
+    /// The lines containing the bytes from `start` up to `end`, one per line, with that range
+    /// shown in `style`. Lines longer than 120 characters are cut short with `…`.
+    ///
+    /// The range is cut down to the text, and widened to whole characters.
+    pub fn highlight_range(&self, start: usize, end: usize, style: &HighlightStyle) -> String {
+        let mut start = start.min(self.text.len());
+        while !self.text.is_char_boundary(start) {
+            start -= 1;
+        }
+        let mut end = end.clamp(start, self.text.len());
+        while !self.text.is_char_boundary(end) {
+            end += 1;
+        }
+
+        let first = self.line_of(start);
+        // A range ending just after a newline doesn't reach into the next line
+        let last = self.line_of(end.saturating_sub(1).max(start));
+
+        let mut output = String::new();
+
+        for line in first..=last {
+            let line_start = self.line_starts[line];
+            let mut text = self.line_text(line);
+            let mut truncated = false;
+
+            if let Some((cut, _)) = text.char_indices().nth(MAX_LINE_WIDTH - 1) {
+                if text[cut..].chars().nth(1).is_some() {
+                    text = &text[..cut];
+                    truncated = true;
+                }
+            }
+
+            let from = start.saturating_sub(line_start).min(text.len());
+            let to = end.saturating_sub(line_start).min(text.len());
+
+            if line > first {
+                output.push('\n');
+            }
+            output.push_str(&text[..from]);
+            style.paint(&text[from..to], &mut output);
+            output.push_str(&text[to..]);
+            if truncated {
+                output.push('…');
+            }
+
+            // Without color, the range is marked on a line of its own
+            if style.no_color && to > from {
+                let indent = text[..from].chars().count();
+                let width = text[from..to].chars().count();
+                write!(output, "\n{:indent$}{:^<width$}", "", "").unwrap();
+            }
+        }
+
+        output
+    }
+}
+
+/// How [`SourceFile::highlight_range`] shows the range it highlights, using ANSI escape codes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HighlightStyle {
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+    pub bold: bool,
+    pub underline: bool,
+    /// Leave out the escape codes, for output that isn't a terminal like CI logs, and mark the
+    /// range with `^` on the line below instead.
+    pub no_color: bool,
+}
+
+impl HighlightStyle {
+    /// The style without escape codes.
+    pub fn no_color() -> Self {
+        HighlightStyle {
+            no_color: true,
+            ..Self::default()
+        }
+    }
+
+    fn paint(&self, text: &str, output: &mut String) {
+        let mut codes = vec![];
+        if self.bold {
+            codes.push(1);
+        }
+        if self.underline {
+            codes.push(4);
+        }
+        if let Some(fg) = self.fg {
+            codes.push(30 + fg as u8);
+        }
+        if let Some(bg) = self.bg {
+            codes.push(40 + bg as u8);
+        }
+
+        if self.no_color || codes.is_empty() || text.is_empty() {
+            output.push_str(text);
+            return;
+        }
+
+        let codes: Vec<_> = codes.iter().map(u8::to_string).collect();
+        write!(output, "\x1b[{}m{text}\x1b[0m", codes.join(";")).unwrap();
+    }
+}
+
+/// The eight standard terminal colors, numbered as in their escape codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
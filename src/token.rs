@@ -1,38 +1,965 @@
+use std::ops::{Index, RangeBounds};
+use std::str::FromStr;
+
 use log::trace;
 use miette::bail;
 
+use crate::ast::{BinOpKind, LiteralValue, TypeExpr};
+use crate::builtins::BuiltinFn;
+use crate::lexer::error::TokenizationError;
+use crate::lexer::{is_float_literal, is_identifier_char, unrecognized_token};
+use crate::parse::parse_literal;
 use crate::parse::parser::Parser;
+use crate::source::SourceFile;
+use crate::span::Span;
 
 #[derive(Debug, Clone, Copy)]
 pub enum Token<'a> {
     // Operators
     Plus(Inner<'a>),
     Minus(Inner<'a>),
+    Caret(Inner<'a>),
+    Ampersand(Inner<'a>),
+    Star(Inner<'a>),
+    Slash(Inner<'a>),
+    Percent(Inner<'a>),
+    Pipe(Inner<'a>),
+    PipePipe(Inner<'a>),
+    PipeGt(Inner<'a>),
+    Tilde(Inner<'a>),
+    Bang(Inner<'a>),
 
     GreaterThan(Inner<'a>),
+    LessThan(Inner<'a>),
     Equals(Inner<'a>),
+    PlusEquals(Inner<'a>),
+    MinusEquals(Inner<'a>),
+    StarEquals(Inner<'a>),
+    SlashEquals(Inner<'a>),
+    PercentEquals(Inner<'a>),
 
     Colon(Inner<'a>),
+    PathSep(Inner<'a>),
     Comma(Inner<'a>),
     Arrow(Inner<'a>),
+    FatArrow(Inner<'a>),
+    Dot(Inner<'a>),
+    DotDot(Inner<'a>),
+    /// `...`, for variadic parameters. Dots are matched greedily, so `....` is an `Ellipsis`
+    /// followed by a `Dot`, never two `DotDot`s.
+    Ellipsis(Inner<'a>),
 
     // Brackets
     FunctionDeclaration(Inner<'a>),
+    StructDeclaration(Inner<'a>),
+    EnumDeclaration(Inner<'a>),
+    TraitDeclaration(Inner<'a>),
+    ImplDeclaration(Inner<'a>),
+    UseDeclaration(Inner<'a>),
+    TypeDeclaration(Inner<'a>),
+    As(Inner<'a>),
+    Mut(Inner<'a>),
+    If(Inner<'a>),
+    Else(Inner<'a>),
+    While(Inner<'a>),
+    For(Inner<'a>),
+    In(Inner<'a>),
+    Let(Inner<'a>),
+    Const(Inner<'a>),
+    Return(Inner<'a>),
+    Break(Inner<'a>),
+    Continue(Inner<'a>),
+    Match(Inner<'a>),
+    Move(Inner<'a>),
+    Pub(Inner<'a>),
+    Extern(Inner<'a>),
+    Unsafe(Inner<'a>),
+    // Reserved for future use
+    Async(Inner<'a>),
+    Await(Inner<'a>),
+    Yield(Inner<'a>),
+    Typeof(Inner<'a>),
+    // Builtin functions
+    Sizeof(Inner<'a>),
+    Alignof(Inner<'a>),
+    Unreachable(Inner<'a>),
+    Memcpy(Inner<'a>),
+    Memset(Inner<'a>),
     SemiColon(Inner<'a>),
 
     OpenBracket(Inner<'a>),
     CloseBracket(Inner<'a>),
     OpenCurly(Inner<'a>),
     CloseCurly(Inner<'a>),
+    OpenSquare(Inner<'a>),
+    CloseSquare(Inner<'a>),
+
+    // Primitive types
+    I8(Inner<'a>),
+    I16(Inner<'a>),
+    I32(Inner<'a>),
+    I64(Inner<'a>),
+    I128(Inner<'a>),
+    U8(Inner<'a>),
+    U16(Inner<'a>),
+    U32(Inner<'a>),
+    U64(Inner<'a>),
+    F32(Inner<'a>),
+    F64(Inner<'a>),
+    Bool(Inner<'a>),
+    Void(Inner<'a>),
+
+    // Literals
+    True(Inner<'a>),
+    False(Inner<'a>),
+    IntLiteral(Inner<'a>),
+    FloatLiteral(Inner<'a>),
+    StringLiteral(Inner<'a>),
+    CharLiteral(Inner<'a>),
+
+    // String interpolation, as in `"Hello, ${name}!"`
+    /// The text of an interpolated string from its opening quote up to the first `${`.
+    InterpolatedStringStart(Inner<'a>),
+    /// The text between a `}` closing one interpolation and the `${` opening the next.
+    InterpolatedStringMiddle(Inner<'a>),
+    /// The text after the last interpolation, up to and including the closing quote.
+    InterpolatedStringEnd(Inner<'a>),
+    OpenInterp(Inner<'a>),
+    /// The `}` ending an interpolation, which is only lexed as one inside a string.
+    CloseInterp(Inner<'a>),
 
     // Whitespace
     Space(Inner<'a>),
     Newline(Inner<'a>),
 
+    // Comments
+    LineComment(Inner<'a>),
+    DocLineComment(Inner<'a>),
+    BlockComment(Inner<'a>),
+    DocBlockComment(Inner<'a>),
+
+    /// `'name`, naming a loop or block for `break` and `continue`.
+    Label(Inner<'a>),
     Identifier(Inner<'a>),
     Unknown(Inner<'a>),
 }
 
+/// The kind of a [`Token`], without its location or source text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum TokenType {
+    // Operators
+    Plus,
+    Minus,
+    Caret,
+    Ampersand,
+    Star,
+    Slash,
+    Percent,
+    Pipe,
+    PipePipe,
+    PipeGt,
+    Tilde,
+    Bang,
+
+    GreaterThan,
+    LessThan,
+    Equals,
+    PlusEquals,
+    MinusEquals,
+    StarEquals,
+    SlashEquals,
+    PercentEquals,
+
+    Colon,
+    PathSep,
+    Comma,
+    Arrow,
+    FatArrow,
+    Dot,
+    DotDot,
+    Ellipsis,
+
+    // Brackets
+    FunctionDeclaration,
+    StructDeclaration,
+    EnumDeclaration,
+    TraitDeclaration,
+    ImplDeclaration,
+    UseDeclaration,
+    TypeDeclaration,
+    As,
+    Mut,
+    If,
+    Else,
+    While,
+    For,
+    In,
+    Let,
+    Const,
+    Return,
+    Break,
+    Continue,
+    Match,
+    Move,
+    Pub,
+    Extern,
+    Unsafe,
+    // Reserved for future use
+    Async,
+    Await,
+    Yield,
+    Typeof,
+    // Builtin functions
+    Sizeof,
+    Alignof,
+    Unreachable,
+    Memcpy,
+    Memset,
+    SemiColon,
+
+    OpenBracket,
+    CloseBracket,
+    OpenCurly,
+    CloseCurly,
+    OpenSquare,
+    CloseSquare,
+
+    // Primitive types
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    U8,
+    U16,
+    U32,
+    U64,
+    F32,
+    F64,
+    Bool,
+    Void,
+
+    // Literals
+    True,
+    False,
+    IntLiteral,
+    FloatLiteral,
+    StringLiteral,
+    CharLiteral,
+
+    // String interpolation
+    InterpolatedStringStart,
+    InterpolatedStringMiddle,
+    InterpolatedStringEnd,
+    OpenInterp,
+    CloseInterp,
+
+    // Whitespace
+    Space,
+    Newline,
+
+    // Comments
+    LineComment,
+    DocLineComment,
+    BlockComment,
+    DocBlockComment,
+
+    Label,
+    Identifier,
+    Unknown,
+}
+
+// Fails to build when a variant is added without adding it to `ALL_VARIANTS`
+const _: () = assert!(TokenType::ALL_VARIANTS.len() == TokenType::Unknown as usize + 1);
+
+impl TokenType {
+    /// Every token type, in declaration order, so that `ALL_VARIANTS[ty as usize] == ty`.
+    ///
+    /// This has to be kept up to date with the enum by hand. `Unknown` stays the last variant so
+    /// that the length can be checked against it.
+    pub const ALL_VARIANTS: &'static [TokenType] = &[
+        TokenType::Plus,
+        TokenType::Minus,
+        TokenType::Caret,
+        TokenType::Ampersand,
+        TokenType::Star,
+        TokenType::Slash,
+        TokenType::Percent,
+        TokenType::Pipe,
+        TokenType::PipePipe,
+        TokenType::PipeGt,
+        TokenType::Tilde,
+        TokenType::Bang,
+        TokenType::GreaterThan,
+        TokenType::LessThan,
+        TokenType::Equals,
+        TokenType::PlusEquals,
+        TokenType::MinusEquals,
+        TokenType::StarEquals,
+        TokenType::SlashEquals,
+        TokenType::PercentEquals,
+        TokenType::Colon,
+        TokenType::PathSep,
+        TokenType::Comma,
+        TokenType::Arrow,
+        TokenType::FatArrow,
+        TokenType::Dot,
+        TokenType::DotDot,
+        TokenType::Ellipsis,
+        TokenType::FunctionDeclaration,
+        TokenType::StructDeclaration,
+        TokenType::EnumDeclaration,
+        TokenType::TraitDeclaration,
+        TokenType::ImplDeclaration,
+        TokenType::UseDeclaration,
+        TokenType::TypeDeclaration,
+        TokenType::As,
+        TokenType::Mut,
+        TokenType::If,
+        TokenType::Else,
+        TokenType::While,
+        TokenType::For,
+        TokenType::In,
+        TokenType::Let,
+        TokenType::Const,
+        TokenType::Return,
+        TokenType::Break,
+        TokenType::Continue,
+        TokenType::Match,
+        TokenType::Move,
+        TokenType::Pub,
+        TokenType::Extern,
+        TokenType::Unsafe,
+        TokenType::Async,
+        TokenType::Await,
+        TokenType::Yield,
+        TokenType::Typeof,
+        TokenType::Sizeof,
+        TokenType::Alignof,
+        TokenType::Unreachable,
+        TokenType::Memcpy,
+        TokenType::Memset,
+        TokenType::SemiColon,
+        TokenType::OpenBracket,
+        TokenType::CloseBracket,
+        TokenType::OpenCurly,
+        TokenType::CloseCurly,
+        TokenType::OpenSquare,
+        TokenType::CloseSquare,
+        TokenType::I8,
+        TokenType::I16,
+        TokenType::I32,
+        TokenType::I64,
+        TokenType::I128,
+        TokenType::U8,
+        TokenType::U16,
+        TokenType::U32,
+        TokenType::U64,
+        TokenType::F32,
+        TokenType::F64,
+        TokenType::Bool,
+        TokenType::Void,
+        TokenType::True,
+        TokenType::False,
+        TokenType::IntLiteral,
+        TokenType::FloatLiteral,
+        TokenType::StringLiteral,
+        TokenType::CharLiteral,
+        TokenType::InterpolatedStringStart,
+        TokenType::InterpolatedStringMiddle,
+        TokenType::InterpolatedStringEnd,
+        TokenType::OpenInterp,
+        TokenType::CloseInterp,
+        TokenType::Space,
+        TokenType::Newline,
+        TokenType::LineComment,
+        TokenType::DocLineComment,
+        TokenType::BlockComment,
+        TokenType::DocBlockComment,
+        TokenType::Label,
+        TokenType::Identifier,
+        TokenType::Unknown,
+    ];
+
+    pub fn all_variants() -> impl Iterator<Item = TokenType> {
+        Self::ALL_VARIANTS.iter().copied()
+    }
+
+    /// The source text of this token type, if it only has one spelling.
+    pub fn spelling(&self) -> Option<&'static str> {
+        match self {
+            // Operators
+            TokenType::Plus => Some("+"),
+            TokenType::Minus => Some("-"),
+            TokenType::Caret => Some("^"),
+            TokenType::Ampersand => Some("&"),
+            TokenType::Star => Some("*"),
+            TokenType::Slash => Some("/"),
+            TokenType::Percent => Some("%"),
+            TokenType::Pipe => Some("|"),
+            TokenType::PipePipe => Some("||"),
+            TokenType::PipeGt => Some("|>"),
+            TokenType::Tilde => Some("~"),
+            TokenType::Bang => Some("!"),
+
+            TokenType::GreaterThan => Some(">"),
+            TokenType::LessThan => Some("<"),
+            TokenType::Equals => Some("="),
+            TokenType::PlusEquals => Some("+="),
+            TokenType::MinusEquals => Some("-="),
+            TokenType::StarEquals => Some("*="),
+            TokenType::SlashEquals => Some("/="),
+            TokenType::PercentEquals => Some("%="),
+
+            TokenType::Colon => Some(":"),
+            TokenType::PathSep => Some("::"),
+            TokenType::Comma => Some(","),
+            TokenType::Arrow => Some("->"),
+            TokenType::FatArrow => Some("=>"),
+            TokenType::Dot => Some("."),
+            TokenType::DotDot => Some(".."),
+            TokenType::Ellipsis => Some("..."),
+
+            // Brackets
+            TokenType::FunctionDeclaration => Some("func"),
+            TokenType::StructDeclaration => Some("struct"),
+            TokenType::EnumDeclaration => Some("enum"),
+            TokenType::TraitDeclaration => Some("trait"),
+            TokenType::ImplDeclaration => Some("impl"),
+            TokenType::UseDeclaration => Some("use"),
+            TokenType::TypeDeclaration => Some("type"),
+            TokenType::As => Some("as"),
+            TokenType::Mut => Some("mut"),
+            TokenType::If => Some("if"),
+            TokenType::Else => Some("else"),
+            TokenType::While => Some("while"),
+            TokenType::For => Some("for"),
+            TokenType::In => Some("in"),
+            TokenType::Let => Some("let"),
+            TokenType::Const => Some("const"),
+            TokenType::Return => Some("return"),
+            TokenType::Break => Some("break"),
+            TokenType::Continue => Some("continue"),
+            TokenType::Match => Some("match"),
+            TokenType::Move => Some("move"),
+            TokenType::Pub => Some("pub"),
+            TokenType::Extern => Some("extern"),
+            TokenType::Unsafe => Some("unsafe"),
+            TokenType::Async => Some("async"),
+            TokenType::Await => Some("await"),
+            TokenType::Yield => Some("yield"),
+            TokenType::Typeof => Some("typeof"),
+            TokenType::Sizeof => Some("sizeof"),
+            TokenType::Alignof => Some("alignof"),
+            TokenType::Unreachable => Some("unreachable"),
+            TokenType::Memcpy => Some("memcpy"),
+            TokenType::Memset => Some("memset"),
+            TokenType::SemiColon => Some(";"),
+
+            TokenType::OpenBracket => Some("("),
+            TokenType::CloseBracket => Some(")"),
+            TokenType::OpenCurly => Some("{"),
+            TokenType::CloseCurly => Some("}"),
+            TokenType::OpenSquare => Some("["),
+            TokenType::CloseSquare => Some("]"),
+
+            // Primitive types
+            TokenType::I8 => Some("i8"),
+            TokenType::I16 => Some("i16"),
+            TokenType::I32 => Some("i32"),
+            TokenType::I64 => Some("i64"),
+            TokenType::I128 => Some("i128"),
+            TokenType::U8 => Some("u8"),
+            TokenType::U16 => Some("u16"),
+            TokenType::U32 => Some("u32"),
+            TokenType::U64 => Some("u64"),
+            TokenType::F32 => Some("f32"),
+            TokenType::F64 => Some("f64"),
+            TokenType::Bool => Some("bool"),
+            TokenType::Void => Some("void"),
+
+            // Literals
+            TokenType::True => Some("true"),
+            TokenType::False => Some("false"),
+
+            // String interpolation
+            TokenType::OpenInterp => Some("${"),
+
+            // Whitespace
+            TokenType::Space => Some(" "),
+            TokenType::Newline => Some("\n"),
+
+            TokenType::IntLiteral
+            | TokenType::FloatLiteral
+            | TokenType::StringLiteral
+            | TokenType::CharLiteral
+            | TokenType::InterpolatedStringStart
+            | TokenType::InterpolatedStringMiddle
+            | TokenType::InterpolatedStringEnd
+            | TokenType::CloseInterp
+            | TokenType::Identifier
+            | TokenType::Label
+            | TokenType::Unknown
+            | TokenType::LineComment
+            | TokenType::DocLineComment
+            | TokenType::BlockComment
+            | TokenType::DocBlockComment => None,
+        }
+    }
+
+    /// The name of this variant, as it's written in the source of this crate.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            TokenType::Plus => "Plus",
+            TokenType::Minus => "Minus",
+            TokenType::Caret => "Caret",
+            TokenType::Ampersand => "Ampersand",
+            TokenType::Star => "Star",
+            TokenType::Slash => "Slash",
+            TokenType::Percent => "Percent",
+            TokenType::Pipe => "Pipe",
+            TokenType::PipePipe => "PipePipe",
+            TokenType::PipeGt => "PipeGt",
+            TokenType::Tilde => "Tilde",
+            TokenType::Bang => "Bang",
+            TokenType::GreaterThan => "GreaterThan",
+            TokenType::LessThan => "LessThan",
+            TokenType::Equals => "Equals",
+            TokenType::PlusEquals => "PlusEquals",
+            TokenType::MinusEquals => "MinusEquals",
+            TokenType::StarEquals => "StarEquals",
+            TokenType::SlashEquals => "SlashEquals",
+            TokenType::PercentEquals => "PercentEquals",
+            TokenType::Colon => "Colon",
+            TokenType::PathSep => "PathSep",
+            TokenType::Comma => "Comma",
+            TokenType::Arrow => "Arrow",
+            TokenType::FatArrow => "FatArrow",
+            TokenType::Dot => "Dot",
+            TokenType::DotDot => "DotDot",
+            TokenType::Ellipsis => "Ellipsis",
+            TokenType::FunctionDeclaration => "FunctionDeclaration",
+            TokenType::StructDeclaration => "StructDeclaration",
+            TokenType::EnumDeclaration => "EnumDeclaration",
+            TokenType::TraitDeclaration => "TraitDeclaration",
+            TokenType::ImplDeclaration => "ImplDeclaration",
+            TokenType::UseDeclaration => "UseDeclaration",
+            TokenType::TypeDeclaration => "TypeDeclaration",
+            TokenType::As => "As",
+            TokenType::Mut => "Mut",
+            TokenType::If => "If",
+            TokenType::Else => "Else",
+            TokenType::While => "While",
+            TokenType::For => "For",
+            TokenType::In => "In",
+            TokenType::Let => "Let",
+            TokenType::Const => "Const",
+            TokenType::Return => "Return",
+            TokenType::Break => "Break",
+            TokenType::Continue => "Continue",
+            TokenType::Match => "Match",
+            TokenType::Move => "Move",
+            TokenType::Pub => "Pub",
+            TokenType::Extern => "Extern",
+            TokenType::Unsafe => "Unsafe",
+            TokenType::Async => "Async",
+            TokenType::Await => "Await",
+            TokenType::Yield => "Yield",
+            TokenType::Typeof => "Typeof",
+            TokenType::Sizeof => "Sizeof",
+            TokenType::Alignof => "Alignof",
+            TokenType::Unreachable => "Unreachable",
+            TokenType::Memcpy => "Memcpy",
+            TokenType::Memset => "Memset",
+            TokenType::SemiColon => "SemiColon",
+            TokenType::OpenBracket => "OpenBracket",
+            TokenType::CloseBracket => "CloseBracket",
+            TokenType::OpenCurly => "OpenCurly",
+            TokenType::CloseCurly => "CloseCurly",
+            TokenType::OpenSquare => "OpenSquare",
+            TokenType::CloseSquare => "CloseSquare",
+            TokenType::I8 => "I8",
+            TokenType::I16 => "I16",
+            TokenType::I32 => "I32",
+            TokenType::I64 => "I64",
+            TokenType::I128 => "I128",
+            TokenType::U8 => "U8",
+            TokenType::U16 => "U16",
+            TokenType::U32 => "U32",
+            TokenType::U64 => "U64",
+            TokenType::F32 => "F32",
+            TokenType::F64 => "F64",
+            TokenType::Bool => "Bool",
+            TokenType::Void => "Void",
+            TokenType::True => "True",
+            TokenType::False => "False",
+            TokenType::IntLiteral => "IntLiteral",
+            TokenType::FloatLiteral => "FloatLiteral",
+            TokenType::StringLiteral => "StringLiteral",
+            TokenType::CharLiteral => "CharLiteral",
+            TokenType::InterpolatedStringStart => "InterpolatedStringStart",
+            TokenType::InterpolatedStringMiddle => "InterpolatedStringMiddle",
+            TokenType::InterpolatedStringEnd => "InterpolatedStringEnd",
+            TokenType::OpenInterp => "OpenInterp",
+            TokenType::CloseInterp => "CloseInterp",
+            TokenType::Space => "Space",
+            TokenType::Newline => "Newline",
+            TokenType::LineComment => "LineComment",
+            TokenType::DocLineComment => "DocLineComment",
+            TokenType::BlockComment => "BlockComment",
+            TokenType::DocBlockComment => "DocBlockComment",
+            TokenType::Label => "Label",
+            TokenType::Identifier => "Identifier",
+            TokenType::Unknown => "Unknown",
+        }
+    }
+
+    /// Whether this is a binary or unary operator that can appear in an expression. Assignments
+    /// are statements, so `=` and the compound assignments aren't.
+    pub fn is_expression_operator(&self) -> bool {
+        match self {
+            TokenType::Plus
+            | TokenType::Minus
+            | TokenType::Caret
+            | TokenType::Ampersand
+            | TokenType::Star
+            | TokenType::Slash
+            | TokenType::Percent
+            | TokenType::Pipe
+            | TokenType::PipePipe
+            | TokenType::PipeGt
+            | TokenType::Tilde
+            | TokenType::Bang
+            | TokenType::GreaterThan
+            | TokenType::LessThan => true,
+
+            TokenType::Equals
+            | TokenType::PlusEquals
+            | TokenType::MinusEquals
+            | TokenType::StarEquals
+            | TokenType::SlashEquals
+            | TokenType::PercentEquals
+            | TokenType::Colon
+            | TokenType::PathSep
+            | TokenType::Comma
+            | TokenType::Arrow
+            | TokenType::FatArrow
+            | TokenType::Dot
+            | TokenType::DotDot
+            | TokenType::Ellipsis
+            | TokenType::FunctionDeclaration
+            | TokenType::StructDeclaration
+            | TokenType::EnumDeclaration
+            | TokenType::TraitDeclaration
+            | TokenType::ImplDeclaration
+            | TokenType::UseDeclaration
+            | TokenType::TypeDeclaration
+            | TokenType::As
+            | TokenType::Mut
+            | TokenType::If
+            | TokenType::Else
+            | TokenType::While
+            | TokenType::For
+            | TokenType::In
+            | TokenType::Let
+            | TokenType::Const
+            | TokenType::Return
+            | TokenType::Break
+            | TokenType::Continue
+            | TokenType::Match
+            | TokenType::Move
+            | TokenType::Pub
+            | TokenType::Extern
+            | TokenType::Unsafe
+            | TokenType::Async
+            | TokenType::Await
+            | TokenType::Yield
+            | TokenType::Typeof
+            | TokenType::Sizeof
+            | TokenType::Alignof
+            | TokenType::Unreachable
+            | TokenType::Memcpy
+            | TokenType::Memset
+            | TokenType::SemiColon
+            | TokenType::OpenBracket
+            | TokenType::CloseBracket
+            | TokenType::OpenCurly
+            | TokenType::CloseCurly
+            | TokenType::OpenSquare
+            | TokenType::CloseSquare
+            | TokenType::I8
+            | TokenType::I16
+            | TokenType::I32
+            | TokenType::I64
+            | TokenType::I128
+            | TokenType::U8
+            | TokenType::U16
+            | TokenType::U32
+            | TokenType::U64
+            | TokenType::F32
+            | TokenType::F64
+            | TokenType::Bool
+            | TokenType::Void
+            | TokenType::True
+            | TokenType::False
+            | TokenType::IntLiteral
+            | TokenType::FloatLiteral
+            | TokenType::StringLiteral
+            | TokenType::CharLiteral
+            | TokenType::InterpolatedStringStart
+            | TokenType::InterpolatedStringMiddle
+            | TokenType::InterpolatedStringEnd
+            | TokenType::OpenInterp
+            | TokenType::CloseInterp
+            | TokenType::Space
+            | TokenType::Newline
+            | TokenType::LineComment
+            | TokenType::DocLineComment
+            | TokenType::BlockComment
+            | TokenType::DocBlockComment
+            | TokenType::Label
+            | TokenType::Identifier
+            | TokenType::Unknown => false,
+        }
+    }
+
+    /// Whether this keyword starts a statement, or an expression that's usually written as one
+    /// like `if` and `match`.
+    pub fn is_statement_keyword(&self) -> bool {
+        match self {
+            TokenType::If
+            | TokenType::While
+            | TokenType::For
+            | TokenType::Let
+            | TokenType::Return
+            | TokenType::Break
+            | TokenType::Continue
+            | TokenType::Match => true,
+
+            TokenType::Plus
+            | TokenType::Minus
+            | TokenType::Caret
+            | TokenType::Ampersand
+            | TokenType::Star
+            | TokenType::Slash
+            | TokenType::Percent
+            | TokenType::Pipe
+            | TokenType::PipePipe
+            | TokenType::PipeGt
+            | TokenType::Tilde
+            | TokenType::Bang
+            | TokenType::GreaterThan
+            | TokenType::LessThan
+            | TokenType::Equals
+            | TokenType::PlusEquals
+            | TokenType::MinusEquals
+            | TokenType::StarEquals
+            | TokenType::SlashEquals
+            | TokenType::PercentEquals
+            | TokenType::Colon
+            | TokenType::PathSep
+            | TokenType::Comma
+            | TokenType::Arrow
+            | TokenType::FatArrow
+            | TokenType::Dot
+            | TokenType::DotDot
+            | TokenType::Ellipsis
+            | TokenType::FunctionDeclaration
+            | TokenType::StructDeclaration
+            | TokenType::EnumDeclaration
+            | TokenType::TraitDeclaration
+            | TokenType::ImplDeclaration
+            | TokenType::UseDeclaration
+            | TokenType::TypeDeclaration
+            | TokenType::As
+            | TokenType::Mut
+            | TokenType::Else
+            | TokenType::In
+            | TokenType::Move
+            | TokenType::Const
+            | TokenType::Pub
+            | TokenType::Extern
+            | TokenType::Unsafe
+            | TokenType::Async
+            | TokenType::Await
+            | TokenType::Yield
+            | TokenType::Typeof
+            | TokenType::Sizeof
+            | TokenType::Alignof
+            | TokenType::Unreachable
+            | TokenType::Memcpy
+            | TokenType::Memset
+            | TokenType::SemiColon
+            | TokenType::OpenBracket
+            | TokenType::CloseBracket
+            | TokenType::OpenCurly
+            | TokenType::CloseCurly
+            | TokenType::OpenSquare
+            | TokenType::CloseSquare
+            | TokenType::I8
+            | TokenType::I16
+            | TokenType::I32
+            | TokenType::I64
+            | TokenType::I128
+            | TokenType::U8
+            | TokenType::U16
+            | TokenType::U32
+            | TokenType::U64
+            | TokenType::F32
+            | TokenType::F64
+            | TokenType::Bool
+            | TokenType::Void
+            | TokenType::True
+            | TokenType::False
+            | TokenType::IntLiteral
+            | TokenType::FloatLiteral
+            | TokenType::StringLiteral
+            | TokenType::CharLiteral
+            | TokenType::InterpolatedStringStart
+            | TokenType::InterpolatedStringMiddle
+            | TokenType::InterpolatedStringEnd
+            | TokenType::OpenInterp
+            | TokenType::CloseInterp
+            | TokenType::Space
+            | TokenType::Newline
+            | TokenType::LineComment
+            | TokenType::DocLineComment
+            | TokenType::BlockComment
+            | TokenType::DocBlockComment
+            | TokenType::Label
+            | TokenType::Identifier
+            | TokenType::Unknown => false,
+        }
+    }
+
+    /// Whether this keyword names a primitive type.
+    pub fn is_type_keyword(&self) -> bool {
+        matches!(
+            self,
+            TokenType::I8
+                | TokenType::I16
+                | TokenType::I32
+                | TokenType::I64
+                | TokenType::I128
+                | TokenType::U8
+                | TokenType::U16
+                | TokenType::U32
+                | TokenType::U64
+                | TokenType::F32
+                | TokenType::F64
+                | TokenType::Bool
+                | TokenType::Void
+        )
+    }
+
+    /// Whether this keyword is a value, which is only `true` and `false`.
+    pub fn is_value_keyword(&self) -> bool {
+        matches!(self, TokenType::True | TokenType::False)
+    }
+}
+
+impl FromStr for TokenType {
+    type Err = TokenizationError;
+
+    /// Classify `s` as a single token, the way the lexer would. Anything that would lex as
+    /// `Unknown` is an error.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match Token::relex(0, s) {
+            Token::Unknown(inner) => Err(unrecognized_token(inner.loc, inner.slice)),
+            token => Ok(token.ty()),
+        }
+    }
+}
+
+/// The number of the type, where `ALL_VARIANTS[n]` is the type numbered `n`.
+impl From<TokenType> for u8 {
+    fn from(ty: TokenType) -> u8 {
+        ty as u8
+    }
+}
+
+/// The type numbered `n` by `u8::from`, or `Err` for a number past the last type.
+impl TryFrom<u8> for TokenType {
+    type Error = ();
+
+    fn try_from(n: u8) -> Result<Self, ()> {
+        Self::ALL_VARIANTS.get(usize::from(n)).copied().ok_or(())
+    }
+}
+
+/// Builds a [`Token`], either from its source text or from its type.
+///
+/// ```
+/// use llvm_compiler::token;
+///
+/// let plus = token!("+" @ 5);
+/// let also_plus = token!(Plus @ 5);
+/// let ident = token!(Identifier("x") @ 7);
+/// ```
+///
+/// The source text form panics if the text is classified as `Unknown`, and the type form panics
+/// if the type has no fixed spelling (use the `Variant("text")` form for those instead).
+#[macro_export]
+macro_rules! token {
+    ($slice:literal @ $loc:expr) => {{
+        let token = $crate::token::Token::from($loc, $slice);
+        if let $crate::token::Token::Unknown(_) = token {
+            panic!("{:?} is not a valid token", $slice);
+        }
+        token
+    }};
+    ($variant:ident($slice:expr) @ $loc:expr) => {
+        $crate::token::Token::$variant($crate::token::Inner {
+            loc: $loc,
+            slice: $slice,
+        })
+    };
+    ($variant:ident @ $loc:expr) => {
+        $crate::token::Token::new_at($loc, $crate::token::TokenType::$variant)
+            .expect(concat!(stringify!($variant), " has no fixed spelling"))
+    };
+}
+
+/// A space at offset 0, for algorithms that need a placeholder token.
+///
+/// `Space` is trivia, so the parser skips it without it changing anything. `Unknown` would be
+/// the obvious choice of "no token", but it means real input wasn't recognized and gets reported.
+impl Default for Token<'_> {
+    fn default() -> Self {
+        Token::new_at(0, TokenType::Space).expect("spaces have a fixed spelling")
+    }
+}
+
+/// What an `Unknown` token looks like it was meant to be, see [`Token::classify_unknown`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnknownTokenKind {
+    /// Starts like an identifier, but has a character identifiers can't contain.
+    ProbablyIdentifier,
+    ProbablyNumeric,
+    /// Starts with a character used in operators, but isn't one that's known.
+    ProbablyOperator,
+    Garbage(char),
+}
+
+/// The broad kind of a token, see [`Token::category`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenCategory {
+    Keyword,
+    /// A primitive type's name, like `i32`.
+    Type,
+    Identifier,
+    Label,
+    Literal,
+    Operator,
+    /// Delimiters and separators, like `(` and `;`.
+    Punctuation,
+    /// Doc comments too.
+    Comment,
+    Whitespace,
+    Unknown,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct Inner<'a> {
     pub loc: usize,
@@ -45,72 +972,677 @@ impl<'a> Token<'a> {
             // Operators
             "+" => Token::Plus(Inner { loc, slice }),
             "-" => Token::Minus(Inner { loc, slice }),
+            "^" => Token::Caret(Inner { loc, slice }),
+            "&" => Token::Ampersand(Inner { loc, slice }),
+            "*" => Token::Star(Inner { loc, slice }),
+            "/" => Token::Slash(Inner { loc, slice }),
+            "%" => Token::Percent(Inner { loc, slice }),
+            "|" => Token::Pipe(Inner { loc, slice }),
+            "||" => Token::PipePipe(Inner { loc, slice }),
+            "|>" => Token::PipeGt(Inner { loc, slice }),
+            "~" => Token::Tilde(Inner { loc, slice }),
+            "!" => Token::Bang(Inner { loc, slice }),
 
             ">" => Token::GreaterThan(Inner { loc, slice }),
+            "<" => Token::LessThan(Inner { loc, slice }),
             "=" => Token::Equals(Inner { loc, slice }),
+            "+=" => Token::PlusEquals(Inner { loc, slice }),
+            "-=" => Token::MinusEquals(Inner { loc, slice }),
+            "*=" => Token::StarEquals(Inner { loc, slice }),
+            "/=" => Token::SlashEquals(Inner { loc, slice }),
+            "%=" => Token::PercentEquals(Inner { loc, slice }),
 
             ":" => Token::Colon(Inner { loc, slice }),
+            "::" => Token::PathSep(Inner { loc, slice }),
             "," => Token::Comma(Inner { loc, slice }),
             "->" => Token::Arrow(Inner { loc, slice }),
+            "=>" => Token::FatArrow(Inner { loc, slice }),
+            "." => Token::Dot(Inner { loc, slice }),
+            ".." => Token::DotDot(Inner { loc, slice }),
+            "..." => Token::Ellipsis(Inner { loc, slice }),
 
             // Brackets
             ";" => Token::SemiColon(Inner { loc, slice }),
             "func" => Token::FunctionDeclaration(Inner { loc, slice }),
+            "struct" => Token::StructDeclaration(Inner { loc, slice }),
+            "enum" => Token::EnumDeclaration(Inner { loc, slice }),
+            "trait" => Token::TraitDeclaration(Inner { loc, slice }),
+            "impl" => Token::ImplDeclaration(Inner { loc, slice }),
+            "use" => Token::UseDeclaration(Inner { loc, slice }),
+            "type" => Token::TypeDeclaration(Inner { loc, slice }),
+            "as" => Token::As(Inner { loc, slice }),
+            "mut" => Token::Mut(Inner { loc, slice }),
+            "if" => Token::If(Inner { loc, slice }),
+            "else" => Token::Else(Inner { loc, slice }),
+            "while" => Token::While(Inner { loc, slice }),
+            "for" => Token::For(Inner { loc, slice }),
+            "in" => Token::In(Inner { loc, slice }),
+            "let" => Token::Let(Inner { loc, slice }),
+            "const" => Token::Const(Inner { loc, slice }),
+            "return" => Token::Return(Inner { loc, slice }),
+            "break" => Token::Break(Inner { loc, slice }),
+            "continue" => Token::Continue(Inner { loc, slice }),
+            "match" => Token::Match(Inner { loc, slice }),
+            "move" => Token::Move(Inner { loc, slice }),
+            "pub" => Token::Pub(Inner { loc, slice }),
+            "extern" => Token::Extern(Inner { loc, slice }),
+            "unsafe" => Token::Unsafe(Inner { loc, slice }),
+            // Reserved for future use
+            "async" => Token::Async(Inner { loc, slice }),
+            "await" => Token::Await(Inner { loc, slice }),
+            "yield" => Token::Yield(Inner { loc, slice }),
+            "typeof" => Token::Typeof(Inner { loc, slice }),
+            // Builtin functions
+            "sizeof" => Token::Sizeof(Inner { loc, slice }),
+            "alignof" => Token::Alignof(Inner { loc, slice }),
+            "unreachable" => Token::Unreachable(Inner { loc, slice }),
+            "memcpy" => Token::Memcpy(Inner { loc, slice }),
+            "memset" => Token::Memset(Inner { loc, slice }),
 
             "(" => Token::OpenBracket(Inner { loc, slice }),
             ")" => Token::CloseBracket(Inner { loc, slice }),
             "{" => Token::OpenCurly(Inner { loc, slice }),
             "}" => Token::CloseCurly(Inner { loc, slice }),
+            "[" => Token::OpenSquare(Inner { loc, slice }),
+            "]" => Token::CloseSquare(Inner { loc, slice }),
+            "${" => Token::OpenInterp(Inner { loc, slice }),
+
+            // Primitive types
+            "i8" => Token::I8(Inner { loc, slice }),
+            "i16" => Token::I16(Inner { loc, slice }),
+            "i32" => Token::I32(Inner { loc, slice }),
+            "i64" => Token::I64(Inner { loc, slice }),
+            "i128" => Token::I128(Inner { loc, slice }),
+            "u8" => Token::U8(Inner { loc, slice }),
+            "u16" => Token::U16(Inner { loc, slice }),
+            "u32" => Token::U32(Inner { loc, slice }),
+            "u64" => Token::U64(Inner { loc, slice }),
+            "f32" => Token::F32(Inner { loc, slice }),
+            "f64" => Token::F64(Inner { loc, slice }),
+            "bool" => Token::Bool(Inner { loc, slice }),
+            "void" => Token::Void(Inner { loc, slice }),
+
+            // Literals
+            "true" => Token::True(Inner { loc, slice }),
+            "false" => Token::False(Inner { loc, slice }),
 
             // Whitespace
-            " " => Token::Space(Inner { loc, slice }),
-            "\n" => Token::Newline(Inner { loc, slice }),
+            " " | "\t" => Token::Space(Inner { loc, slice }),
+            "\n" | "\r\n" => Token::Newline(Inner { loc, slice }),
 
-            _ => Token::Identifier(Inner { loc, slice }),
+            _ if is_float_literal(slice) => Token::FloatLiteral(Inner { loc, slice }),
+            // Anything else starting with a digit is an integer, which might be a malformed one
+            _ if slice.starts_with(|c: char| c.is_ascii_digit())
+                && slice.chars().all(is_identifier_char) =>
+            {
+                Token::IntLiteral(Inner { loc, slice })
+            }
+            _ if slice.len() >= 2 && slice.starts_with('"') && slice.ends_with('"') => {
+                Token::StringLiteral(Inner { loc, slice })
+            }
+            _ if slice.len() >= 3 && slice.starts_with('\'') && slice.ends_with('\'') => {
+                Token::CharLiteral(Inner { loc, slice })
+            }
+            _ if slice.len() > 1
+                && slice.starts_with('\'')
+                && slice[1..].chars().all(is_identifier_char) =>
+            {
+                Token::Label(Inner { loc, slice })
+            }
+            _ if !slice.is_empty() && slice.chars().all(is_identifier_char) => {
+                Token::Identifier(Inner { loc, slice })
+            }
+            _ => Token::Unknown(Inner { loc, slice }),
         }
     }
 
-    pub fn loc(&self) -> usize {
+    /// Synthesizes a token of type `ty` at `loc`, for tokens that weren't lexed from the source.
+    ///
+    /// Returns `None` for types like identifiers that have no fixed spelling to give the token.
+    pub fn new_at(loc: usize, ty: TokenType) -> Option<Self> {
+        let token = Token::from(loc, ty.spelling()?);
+        debug_assert_eq!(token.ty(), ty);
+
+        Some(token)
+    }
+
+    /// Classifies a comment, including its delimiters, as a line or block comment and whether it
+    /// is documentation. As in Rust, `////` and `/***` start ordinary comments.
+    pub fn comment(loc: usize, slice: &'a str) -> Self {
+        let inner = Inner { loc, slice };
+
+        if slice.starts_with("//") {
+            if slice.starts_with("///") && !slice.starts_with("////") {
+                Token::DocLineComment(inner)
+            } else {
+                Token::LineComment(inner)
+            }
+        } else if slice.starts_with("/**") && !slice.starts_with("/***") && slice != "/**/" {
+            Token::DocBlockComment(inner)
+        } else {
+            Token::BlockComment(inner)
+        }
+    }
+
+    /// Whether this is the same as [`Token::default`]. A space lexed at the very start of the
+    /// input is too.
+    pub fn is_default(&self) -> bool {
+        matches!(self, Token::Space(inner) if inner.loc == 0)
+    }
+
+    pub fn is_trivia(&self) -> bool {
+        matches!(
+            self,
+            Token::Space(_)
+                | Token::Newline(_)
+                | Token::LineComment(_)
+                | Token::DocLineComment(_)
+                | Token::BlockComment(_)
+                | Token::DocBlockComment(_)
+        )
+    }
+
+    pub fn is_primitive_type(&self) -> bool {
+        self.to_llvm_type_str().is_some()
+    }
+
+    pub fn is_literal(&self) -> bool {
+        matches!(
+            self,
+            Token::True(_)
+                | Token::False(_)
+                | Token::IntLiteral(_)
+                | Token::FloatLiteral(_)
+                | Token::StringLiteral(_)
+                | Token::CharLiteral(_)
+        )
+    }
+
+    pub fn is_numeric_literal(&self) -> bool {
+        matches!(self.ty(), TokenType::IntLiteral | TokenType::FloatLiteral)
+    }
+
+    /// The value of an integer literal, read from its text in `src`. `None` for any other token,
+    /// and for integers that are invalid or don't fit in an `i128`.
+    pub fn numeric_value(&self, src: &SourceFile) -> Option<i128> {
+        match self.literal_in(src)? {
+            LiteralValue::Int(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// The value of a float literal, read from its text in `src`, or `None` for any other token.
+    pub fn float_value(&self, src: &SourceFile) -> Option<f64> {
+        match self.literal_in(src)? {
+            LiteralValue::Float(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// The value of a numeric literal with its text taken from `src`.
+    fn literal_in(&self, src: &SourceFile) -> Option<LiteralValue> {
+        let inner = Inner {
+            loc: self.loc(),
+            slice: src.text().get(self.loc()..self.end())?,
+        };
+        let token = match self {
+            Token::IntLiteral(_) => Token::IntLiteral(inner),
+            Token::FloatLiteral(_) => Token::FloatLiteral(inner),
+            _ => return None,
+        };
+
+        parse_literal(&token).ok()
+    }
+
+    /// Keywords that can begin an item, including the modifiers written before `func`.
+    pub fn is_declaration_keyword(&self) -> bool {
+        matches!(
+            self,
+            Token::FunctionDeclaration(_)
+                | Token::Pub(_)
+                | Token::Extern(_)
+                | Token::Unsafe(_)
+                | Token::StructDeclaration(_)
+                | Token::EnumDeclaration(_)
+                | Token::TraitDeclaration(_)
+                | Token::ImplDeclaration(_)
+                | Token::UseDeclaration(_)
+                | Token::TypeDeclaration(_)
+                | Token::Const(_)
+        )
+    }
+
+    /// Words with a fixed meaning, which can't be used as names.
+    pub fn is_keyword(&self) -> bool {
+        self.is_declaration_keyword()
+            || self.is_primitive_type()
+            || self.ty().is_value_keyword()
+            || matches!(
+                self,
+                Token::As(_)
+                    | Token::Mut(_)
+                    | Token::If(_)
+                    | Token::Else(_)
+                    | Token::While(_)
+                    | Token::For(_)
+                    | Token::In(_)
+                    | Token::Let(_)
+                    | Token::Return(_)
+                    | Token::Break(_)
+                    | Token::Continue(_)
+                    | Token::Match(_)
+                    | Token::Move(_)
+            )
+            || self.is_reserved_keyword()
+            || self.keyword_to_builtin_fn().is_some()
+    }
+
+    /// Keywords reserved for features that don't exist yet, like `async` functions and generators,
+    /// so that programs using them as names don't break once the features arrive.
+    pub fn is_reserved_keyword(&self) -> bool {
+        matches!(
+            self,
+            Token::Async(_) | Token::Await(_) | Token::Yield(_) | Token::Typeof(_)
+        )
+    }
+
+    /// The builtin function called by this keyword, or `None` for any other token. `typeof` has
+    /// none, as it would only exist at compile time.
+    ///
+    /// `sizeof` and `alignof` are given `void`, as the type they take is written after them.
+    pub fn keyword_to_builtin_fn(&self) -> Option<BuiltinFn> {
+        match self {
+            Token::Sizeof(_) => Some(BuiltinFn::SizeOf(TypeExpr::void())),
+            Token::Alignof(_) => Some(BuiltinFn::AlignOf(TypeExpr::void())),
+            Token::Unreachable(_) => Some(BuiltinFn::Unreachable),
+            Token::Memcpy(_) => Some(BuiltinFn::Memcpy),
+            Token::Memset(_) => Some(BuiltinFn::Memset),
+            _ => None,
+        }
+    }
+
+    pub fn inner(&self) -> &Inner<'a> {
         match self {
             Token::Plus(inner)
             | Token::Minus(inner)
+            | Token::Caret(inner)
+            | Token::Ampersand(inner)
+            | Token::Star(inner)
+            | Token::Slash(inner)
+            | Token::Percent(inner)
+            | Token::Pipe(inner)
+            | Token::PipePipe(inner)
+            | Token::PipeGt(inner)
+            | Token::Tilde(inner)
+            | Token::Bang(inner)
             | Token::GreaterThan(inner)
+            | Token::LessThan(inner)
             | Token::Equals(inner)
+            | Token::PlusEquals(inner)
+            | Token::MinusEquals(inner)
+            | Token::StarEquals(inner)
+            | Token::SlashEquals(inner)
+            | Token::PercentEquals(inner)
             | Token::Colon(inner)
+            | Token::PathSep(inner)
             | Token::Comma(inner)
             | Token::Arrow(inner)
+            | Token::FatArrow(inner)
+            | Token::Dot(inner)
+            | Token::DotDot(inner)
+            | Token::Ellipsis(inner)
             | Token::FunctionDeclaration(inner)
+            | Token::StructDeclaration(inner)
+            | Token::EnumDeclaration(inner)
+            | Token::TraitDeclaration(inner)
+            | Token::ImplDeclaration(inner)
+            | Token::UseDeclaration(inner)
+            | Token::TypeDeclaration(inner)
+            | Token::As(inner)
+            | Token::Mut(inner)
+            | Token::If(inner)
+            | Token::Else(inner)
+            | Token::While(inner)
+            | Token::For(inner)
+            | Token::In(inner)
+            | Token::Let(inner)
+            | Token::Const(inner)
+            | Token::Return(inner)
+            | Token::Break(inner)
+            | Token::Continue(inner)
+            | Token::Match(inner)
+            | Token::Move(inner)
+            | Token::Pub(inner)
+            | Token::Extern(inner)
+            | Token::Unsafe(inner)
+            | Token::Async(inner)
+            | Token::Await(inner)
+            | Token::Yield(inner)
+            | Token::Typeof(inner)
+            | Token::Sizeof(inner)
+            | Token::Alignof(inner)
+            | Token::Unreachable(inner)
+            | Token::Memcpy(inner)
+            | Token::Memset(inner)
             | Token::SemiColon(inner)
             | Token::OpenBracket(inner)
             | Token::CloseBracket(inner)
             | Token::OpenCurly(inner)
             | Token::CloseCurly(inner)
+            | Token::OpenSquare(inner)
+            | Token::CloseSquare(inner)
+            | Token::I8(inner)
+            | Token::I16(inner)
+            | Token::I32(inner)
+            | Token::I64(inner)
+            | Token::I128(inner)
+            | Token::U8(inner)
+            | Token::U16(inner)
+            | Token::U32(inner)
+            | Token::U64(inner)
+            | Token::F32(inner)
+            | Token::F64(inner)
+            | Token::Bool(inner)
+            | Token::Void(inner)
+            | Token::True(inner)
+            | Token::False(inner)
+            | Token::IntLiteral(inner)
+            | Token::FloatLiteral(inner)
+            | Token::StringLiteral(inner)
+            | Token::CharLiteral(inner)
+            | Token::InterpolatedStringStart(inner)
+            | Token::InterpolatedStringMiddle(inner)
+            | Token::InterpolatedStringEnd(inner)
+            | Token::OpenInterp(inner)
+            | Token::CloseInterp(inner)
+            | Token::Label(inner)
             | Token::Space(inner)
             | Token::Newline(inner)
+            | Token::LineComment(inner)
+            | Token::DocLineComment(inner)
+            | Token::BlockComment(inner)
+            | Token::DocBlockComment(inner)
             | Token::Identifier(inner)
-            | Token::Unknown(inner) => inner.loc,
+            | Token::Unknown(inner) => inner,
         }
     }
 
-    pub fn as_str(&self) -> &str {
+    fn inner_mut(&mut self) -> &mut Inner<'a> {
         match self {
             Token::Plus(inner)
             | Token::Minus(inner)
+            | Token::Caret(inner)
+            | Token::Ampersand(inner)
+            | Token::Star(inner)
+            | Token::Slash(inner)
+            | Token::Percent(inner)
+            | Token::Pipe(inner)
+            | Token::PipePipe(inner)
+            | Token::PipeGt(inner)
+            | Token::Tilde(inner)
+            | Token::Bang(inner)
             | Token::GreaterThan(inner)
+            | Token::LessThan(inner)
             | Token::Equals(inner)
+            | Token::PlusEquals(inner)
+            | Token::MinusEquals(inner)
+            | Token::StarEquals(inner)
+            | Token::SlashEquals(inner)
+            | Token::PercentEquals(inner)
             | Token::Colon(inner)
+            | Token::PathSep(inner)
             | Token::Comma(inner)
             | Token::Arrow(inner)
+            | Token::FatArrow(inner)
+            | Token::Dot(inner)
+            | Token::DotDot(inner)
+            | Token::Ellipsis(inner)
             | Token::FunctionDeclaration(inner)
+            | Token::StructDeclaration(inner)
+            | Token::EnumDeclaration(inner)
+            | Token::TraitDeclaration(inner)
+            | Token::ImplDeclaration(inner)
+            | Token::UseDeclaration(inner)
+            | Token::TypeDeclaration(inner)
+            | Token::As(inner)
+            | Token::Mut(inner)
+            | Token::If(inner)
+            | Token::Else(inner)
+            | Token::While(inner)
+            | Token::For(inner)
+            | Token::In(inner)
+            | Token::Let(inner)
+            | Token::Const(inner)
+            | Token::Return(inner)
+            | Token::Break(inner)
+            | Token::Continue(inner)
+            | Token::Match(inner)
+            | Token::Move(inner)
+            | Token::Pub(inner)
+            | Token::Extern(inner)
+            | Token::Unsafe(inner)
+            | Token::Async(inner)
+            | Token::Await(inner)
+            | Token::Yield(inner)
+            | Token::Typeof(inner)
+            | Token::Sizeof(inner)
+            | Token::Alignof(inner)
+            | Token::Unreachable(inner)
+            | Token::Memcpy(inner)
+            | Token::Memset(inner)
             | Token::SemiColon(inner)
             | Token::OpenBracket(inner)
             | Token::CloseBracket(inner)
             | Token::OpenCurly(inner)
             | Token::CloseCurly(inner)
+            | Token::OpenSquare(inner)
+            | Token::CloseSquare(inner)
+            | Token::I8(inner)
+            | Token::I16(inner)
+            | Token::I32(inner)
+            | Token::I64(inner)
+            | Token::I128(inner)
+            | Token::U8(inner)
+            | Token::U16(inner)
+            | Token::U32(inner)
+            | Token::U64(inner)
+            | Token::F32(inner)
+            | Token::F64(inner)
+            | Token::Bool(inner)
+            | Token::Void(inner)
+            | Token::True(inner)
+            | Token::False(inner)
+            | Token::IntLiteral(inner)
+            | Token::FloatLiteral(inner)
+            | Token::StringLiteral(inner)
+            | Token::CharLiteral(inner)
+            | Token::InterpolatedStringStart(inner)
+            | Token::InterpolatedStringMiddle(inner)
+            | Token::InterpolatedStringEnd(inner)
+            | Token::OpenInterp(inner)
+            | Token::CloseInterp(inner)
+            | Token::Label(inner)
             | Token::Space(inner)
             | Token::Newline(inner)
+            | Token::LineComment(inner)
+            | Token::DocLineComment(inner)
+            | Token::BlockComment(inner)
+            | Token::DocBlockComment(inner)
             | Token::Identifier(inner)
-            | Token::Unknown(inner) => inner.slice,
+            | Token::Unknown(inner) => inner,
+        }
+    }
+
+    /// A copy of this token moved to start at `new_loc`, like a token copied out of a macro
+    /// definition to where the macro is used.
+    #[must_use]
+    pub fn with_loc(&self, new_loc: usize) -> Token<'a> {
+        let mut token = *self;
+        token.inner_mut().loc = new_loc;
+        token
+    }
+
+    /// A copy of this token moved to `span`, which must be as long as its text as that doesn't
+    /// change.
+    #[must_use]
+    pub fn with_span(&self, span: Span) -> Token<'a> {
+        debug_assert_eq!(span.len(), self.as_str().len());
+        self.with_loc(span.start)
+    }
+
+    pub fn ty(&self) -> TokenType {
+        match self {
+            Token::Plus(_) => TokenType::Plus,
+            Token::Minus(_) => TokenType::Minus,
+            Token::Caret(_) => TokenType::Caret,
+            Token::Ampersand(_) => TokenType::Ampersand,
+            Token::Star(_) => TokenType::Star,
+            Token::Slash(_) => TokenType::Slash,
+            Token::Percent(_) => TokenType::Percent,
+            Token::Pipe(_) => TokenType::Pipe,
+            Token::PipePipe(_) => TokenType::PipePipe,
+            Token::PipeGt(_) => TokenType::PipeGt,
+            Token::Tilde(_) => TokenType::Tilde,
+            Token::Bang(_) => TokenType::Bang,
+            Token::GreaterThan(_) => TokenType::GreaterThan,
+            Token::LessThan(_) => TokenType::LessThan,
+            Token::Equals(_) => TokenType::Equals,
+            Token::PlusEquals(_) => TokenType::PlusEquals,
+            Token::MinusEquals(_) => TokenType::MinusEquals,
+            Token::StarEquals(_) => TokenType::StarEquals,
+            Token::SlashEquals(_) => TokenType::SlashEquals,
+            Token::PercentEquals(_) => TokenType::PercentEquals,
+            Token::Colon(_) => TokenType::Colon,
+            Token::PathSep(_) => TokenType::PathSep,
+            Token::Comma(_) => TokenType::Comma,
+            Token::Arrow(_) => TokenType::Arrow,
+            Token::FatArrow(_) => TokenType::FatArrow,
+            Token::Dot(_) => TokenType::Dot,
+            Token::DotDot(_) => TokenType::DotDot,
+            Token::Ellipsis(_) => TokenType::Ellipsis,
+            Token::FunctionDeclaration(_) => TokenType::FunctionDeclaration,
+            Token::StructDeclaration(_) => TokenType::StructDeclaration,
+            Token::EnumDeclaration(_) => TokenType::EnumDeclaration,
+            Token::TraitDeclaration(_) => TokenType::TraitDeclaration,
+            Token::ImplDeclaration(_) => TokenType::ImplDeclaration,
+            Token::UseDeclaration(_) => TokenType::UseDeclaration,
+            Token::TypeDeclaration(_) => TokenType::TypeDeclaration,
+            Token::As(_) => TokenType::As,
+            Token::Mut(_) => TokenType::Mut,
+            Token::If(_) => TokenType::If,
+            Token::Else(_) => TokenType::Else,
+            Token::While(_) => TokenType::While,
+            Token::For(_) => TokenType::For,
+            Token::In(_) => TokenType::In,
+            Token::Let(_) => TokenType::Let,
+            Token::Const(_) => TokenType::Const,
+            Token::Return(_) => TokenType::Return,
+            Token::Break(_) => TokenType::Break,
+            Token::Continue(_) => TokenType::Continue,
+            Token::Match(_) => TokenType::Match,
+            Token::Move(_) => TokenType::Move,
+            Token::Pub(_) => TokenType::Pub,
+            Token::Extern(_) => TokenType::Extern,
+            Token::Unsafe(_) => TokenType::Unsafe,
+            Token::Async(_) => TokenType::Async,
+            Token::Await(_) => TokenType::Await,
+            Token::Yield(_) => TokenType::Yield,
+            Token::Typeof(_) => TokenType::Typeof,
+            Token::Sizeof(_) => TokenType::Sizeof,
+            Token::Alignof(_) => TokenType::Alignof,
+            Token::Unreachable(_) => TokenType::Unreachable,
+            Token::Memcpy(_) => TokenType::Memcpy,
+            Token::Memset(_) => TokenType::Memset,
+            Token::SemiColon(_) => TokenType::SemiColon,
+            Token::OpenBracket(_) => TokenType::OpenBracket,
+            Token::CloseBracket(_) => TokenType::CloseBracket,
+            Token::OpenCurly(_) => TokenType::OpenCurly,
+            Token::CloseCurly(_) => TokenType::CloseCurly,
+            Token::OpenSquare(_) => TokenType::OpenSquare,
+            Token::CloseSquare(_) => TokenType::CloseSquare,
+            Token::I8(_) => TokenType::I8,
+            Token::I16(_) => TokenType::I16,
+            Token::I32(_) => TokenType::I32,
+            Token::I64(_) => TokenType::I64,
+            Token::I128(_) => TokenType::I128,
+            Token::U8(_) => TokenType::U8,
+            Token::U16(_) => TokenType::U16,
+            Token::U32(_) => TokenType::U32,
+            Token::U64(_) => TokenType::U64,
+            Token::F32(_) => TokenType::F32,
+            Token::F64(_) => TokenType::F64,
+            Token::Bool(_) => TokenType::Bool,
+            Token::Void(_) => TokenType::Void,
+            Token::True(_) => TokenType::True,
+            Token::False(_) => TokenType::False,
+            Token::IntLiteral(_) => TokenType::IntLiteral,
+            Token::FloatLiteral(_) => TokenType::FloatLiteral,
+            Token::StringLiteral(_) => TokenType::StringLiteral,
+            Token::CharLiteral(_) => TokenType::CharLiteral,
+            Token::InterpolatedStringStart(_) => TokenType::InterpolatedStringStart,
+            Token::InterpolatedStringMiddle(_) => TokenType::InterpolatedStringMiddle,
+            Token::InterpolatedStringEnd(_) => TokenType::InterpolatedStringEnd,
+            Token::OpenInterp(_) => TokenType::OpenInterp,
+            Token::CloseInterp(_) => TokenType::CloseInterp,
+            Token::Label(_) => TokenType::Label,
+            Token::Space(_) => TokenType::Space,
+            Token::Newline(_) => TokenType::Newline,
+            Token::LineComment(_) => TokenType::LineComment,
+            Token::DocLineComment(_) => TokenType::DocLineComment,
+            Token::BlockComment(_) => TokenType::BlockComment,
+            Token::DocBlockComment(_) => TokenType::DocBlockComment,
+            Token::Identifier(_) => TokenType::Identifier,
+            Token::Unknown(_) => TokenType::Unknown,
+        }
+    }
+
+    pub fn loc(&self) -> usize {
+        self.inner().loc
+    }
+
+    pub fn as_str(&self) -> &'a str {
+        self.inner().slice
+    }
+
+    /// Byte offset one past the end of this token.
+    pub fn end(&self) -> usize {
+        self.loc() + self.as_str().len()
+    }
+
+    pub fn span(&self) -> Span {
+        Span::new(self.loc(), self.end())
+    }
+
+    /// Whether this token ends at or before the start of `other`, so touching tokens count.
+    pub fn before(&self, other: &Token) -> bool {
+        self.end() <= other.loc()
+    }
+
+    /// Whether the tokens share a byte. Tokens that only touch don't overlap, and neither does a
+    /// zero-length token at either end of another.
+    pub fn overlaps(&self, other: &Token) -> bool {
+        self.loc() < other.end() && other.loc() < self.end()
+    }
+
+    /// Whether one token ends exactly where the other starts.
+    pub fn adjacent_to(&self, other: &Token) -> bool {
+        self.end() == other.loc() || other.end() == self.loc()
+    }
+
+    /// The LLVM IR type for a primitive type keyword.
+    pub fn to_llvm_type_str(&self) -> Option<&'static str> {
+        match self {
+            // LLVM integers have no signedness, that lives in the instructions instead
+            Token::I8(_) | Token::U8(_) => Some("i8"),
+            Token::I16(_) | Token::U16(_) => Some("i16"),
+            Token::I32(_) | Token::U32(_) => Some("i32"),
+            Token::I64(_) | Token::U64(_) => Some("i64"),
+            Token::I128(_) => Some("i128"),
+            Token::F32(_) => Some("float"),
+            Token::F64(_) => Some("double"),
+            Token::Bool(_) => Some("i1"),
+            Token::Void(_) => Some("void"),
+            _ => None,
         }
     }
 
@@ -119,42 +1651,472 @@ impl<'a> Token<'a> {
             // Operators
             Token::Equals(_) => (2, 1),
 
-            Token::Plus(_) => (3, 4),
+            Token::PipeGt(_) => (3, 4),
+            Token::PipePipe(_) => (5, 6),
+            Token::GreaterThan(_) | Token::LessThan(_) => (7, 8),
+            Token::Pipe(_) => (9, 10),
+            Token::Caret(_) => (11, 12),
+            Token::Ampersand(_) => (13, 14),
+
+            Token::Plus(_) | Token::Minus(_) => (15, 16),
+            Token::Star(_) | Token::Slash(_) | Token::Percent(_) => (17, 18),
 
-            Token::Colon(_) => (3, 4),
+            Token::Colon(_) => (15, 16),
             Token::Comma(_) => (2, 1),
 
             _ => (0, 0),
         }
     }
 
+    /// How tightly a binary operator binds its left operand, or `None` for anything else.
+    pub fn operator_precedence(&self) -> Option<u8> {
+        BinOpKind::from_token(self).map(|_| self.precedence().0)
+    }
+
+    /// Right associative operators bind their right operand tighter than their left.
+    pub fn is_right_associative(&self) -> bool {
+        let (left, right) = self.precedence();
+        left > right
+    }
+
+    /// Whether this token can be used as a prefix operator. `-` is also a binary operator.
+    pub fn is_unary_operator(&self) -> bool {
+        matches!(self, Token::Minus(_) | Token::Tilde(_) | Token::Bang(_))
+    }
+
+    /// Whether an expression can begin with this token. Blocks, `if` and `match` are expressions
+    /// too, as are closures starting with `|`, `||` or `move`.
+    pub fn is_start_of_expression(&self) -> bool {
+        self.is_literal()
+            || self.is_unary_operator()
+            || matches!(
+                self,
+                Token::Identifier(_)
+                    | Token::OpenBracket(_)
+                    | Token::OpenCurly(_)
+                    | Token::If(_)
+                    | Token::Match(_)
+                    | Token::Pipe(_)
+                    | Token::PipePipe(_)
+                    | Token::Move(_)
+            )
+            || self.keyword_to_builtin_fn().is_some()
+    }
+
+    /// See [`TokenType::is_expression_operator`].
+    pub fn is_expression_operator(&self) -> bool {
+        self.ty().is_expression_operator()
+    }
+
+    /// See [`TokenType::is_statement_keyword`].
+    pub fn is_statement_keyword(&self) -> bool {
+        self.ty().is_statement_keyword()
+    }
+
+    /// Whether a statement can begin with this token, including a loop's label and any token
+    /// that starts an expression statement.
+    pub fn is_start_of_statement(&self) -> bool {
+        self.is_start_of_expression()
+            || matches!(
+                self,
+                Token::Let(_)
+                    | Token::Return(_)
+                    | Token::Break(_)
+                    | Token::Continue(_)
+                    | Token::While(_)
+                    | Token::For(_)
+                    | Token::Label(_)
+            )
+    }
+
+    /// The delimiter that closes this one, if it's an opening delimiter. `<` isn't included, as
+    /// it's only a delimiter when it starts generics.
+    pub fn expected_closing_delimiter(&self) -> Option<TokenType> {
+        match self {
+            Token::OpenBracket(_) => Some(TokenType::CloseBracket),
+            Token::OpenCurly(_) => Some(TokenType::CloseCurly),
+            Token::OpenSquare(_) => Some(TokenType::CloseSquare),
+            Token::OpenInterp(_) => Some(TokenType::CloseInterp),
+            _ => None,
+        }
+    }
+
+    /// The delimiter that this one closes, if it's a closing delimiter.
+    pub fn expected_opening_delimiter(&self) -> Option<TokenType> {
+        match self {
+            Token::CloseBracket(_) => Some(TokenType::OpenBracket),
+            Token::CloseCurly(_) => Some(TokenType::OpenCurly),
+            Token::CloseSquare(_) => Some(TokenType::OpenSquare),
+            Token::CloseInterp(_) => Some(TokenType::OpenInterp),
+            _ => None,
+        }
+    }
+
+    pub fn is_opening_delimiter(&self) -> bool {
+        self.expected_closing_delimiter().is_some()
+    }
+
+    pub fn is_closing_delimiter(&self) -> bool {
+        self.expected_opening_delimiter().is_some()
+    }
+
+    /// Guess what an `Unknown` token was meant to be from its first character, or `None` for any
+    /// other token.
+    pub fn classify_unknown(&self) -> Option<UnknownTokenKind> {
+        let Token::Unknown(inner) = self else {
+            return None;
+        };
+
+        let kind = match inner.slice.chars().next()? {
+            c if c.is_ascii_digit() => UnknownTokenKind::ProbablyNumeric,
+            c if is_identifier_char(c) => UnknownTokenKind::ProbablyIdentifier,
+            '+' | '-' | '^' | '*' | '/' | '%' | '|' | '~' | '!' | '>' | '<' | '=' | ':' | ','
+            | '.' | '&' => UnknownTokenKind::ProbablyOperator,
+            c => UnknownTokenKind::Garbage(c),
+        };
+
+        Some(kind)
+    }
+
+    pub fn category(&self) -> TokenCategory {
+        match self {
+            Token::Space(_) | Token::Newline(_) => TokenCategory::Whitespace,
+            _ if self.is_trivia() => TokenCategory::Comment,
+            _ if self.is_literal() => TokenCategory::Literal,
+            Token::InterpolatedStringStart(_)
+            | Token::InterpolatedStringMiddle(_)
+            | Token::InterpolatedStringEnd(_) => TokenCategory::Literal,
+            Token::Identifier(_) => TokenCategory::Identifier,
+            Token::Label(_) => TokenCategory::Label,
+            Token::Unknown(_) => TokenCategory::Unknown,
+            Token::SemiColon(_) | Token::Comma(_) | Token::Colon(_) => TokenCategory::Punctuation,
+            _ if self.is_opening_delimiter() || self.is_closing_delimiter() => {
+                TokenCategory::Punctuation
+            }
+            _ if self.is_primitive_type() => TokenCategory::Type,
+            _ if self.is_keyword() => TokenCategory::Keyword,
+            _ => TokenCategory::Operator,
+        }
+    }
+
     pub fn parse(&self, parser: &mut Parser<'a>) -> miette::Result<()> {
         match &self {
             Token::Plus(_)
             | Token::Minus(_)
+            | Token::Star(_)
+            | Token::Slash(_)
+            | Token::Percent(_)
+            | Token::Caret(_)
+            | Token::Ampersand(_)
+            | Token::Pipe(_)
+            | Token::PipePipe(_)
+            | Token::PipeGt(_)
             | Token::Equals(_)
             | Token::GreaterThan(_)
+            | Token::LessThan(_)
             | Token::Colon(_)
             | Token::Comma(_)
-            | Token::Arrow(_) => {
+            | Token::Arrow(_)
+            | Token::FatArrow(_)
+            | Token::Dot(_)
+            | Token::DotDot(_) => {
                 binary_operator(*self, parser)?;
             }
             Token::FunctionDeclaration(_)
+            | Token::StructDeclaration(_)
+            | Token::EnumDeclaration(_)
+            | Token::TraitDeclaration(_)
+            | Token::ImplDeclaration(_)
+            | Token::UseDeclaration(_)
+            | Token::TypeDeclaration(_)
+            | Token::As(_)
+            | Token::Mut(_)
+            | Token::If(_)
+            | Token::Else(_)
+            | Token::While(_)
+            | Token::For(_)
+            | Token::In(_)
+            | Token::Let(_)
+            | Token::Const(_)
+            | Token::Return(_)
+            | Token::Break(_)
+            | Token::Continue(_)
+            | Token::Match(_)
+            | Token::Move(_)
+            | Token::Pub(_)
+            | Token::Extern(_)
+            | Token::Unsafe(_)
+            | Token::Async(_)
+            | Token::Await(_)
+            | Token::Yield(_)
+            | Token::Typeof(_)
+            | Token::Sizeof(_)
+            | Token::Alignof(_)
+            | Token::Unreachable(_)
+            | Token::Memcpy(_)
+            | Token::Memset(_)
             | Token::SemiColon(_)
             | Token::OpenBracket(_)
             | Token::CloseBracket(_)
             | Token::OpenCurly(_)
-            | Token::CloseCurly(_) => {}
-            Token::Identifier(_) => {
+            | Token::CloseCurly(_)
+            | Token::OpenSquare(_)
+            | Token::CloseSquare(_)
+            | Token::Tilde(_)
+            | Token::Bang(_)
+            | Token::Ellipsis(_)
+            | Token::PercentEquals(_)
+            | Token::SlashEquals(_)
+            | Token::StarEquals(_)
+            | Token::MinusEquals(_)
+            | Token::PlusEquals(_)
+            | Token::PathSep(_)
+            | Token::InterpolatedStringStart(_)
+            | Token::InterpolatedStringMiddle(_)
+            | Token::InterpolatedStringEnd(_)
+            | Token::OpenInterp(_)
+            | Token::CloseInterp(_)
+            | Token::Label(_) => {}
+            Token::Identifier(_)
+            | Token::I8(_)
+            | Token::I16(_)
+            | Token::I32(_)
+            | Token::I64(_)
+            | Token::I128(_)
+            | Token::U8(_)
+            | Token::U16(_)
+            | Token::U32(_)
+            | Token::U64(_)
+            | Token::F32(_)
+            | Token::F64(_)
+            | Token::Bool(_)
+            | Token::Void(_)
+            | Token::True(_)
+            | Token::False(_)
+            | Token::IntLiteral(_)
+            | Token::FloatLiteral(_)
+            | Token::StringLiteral(_)
+            | Token::CharLiteral(_) => {
                 trace!("Parsing identifier {:?}", self);
                 parser.push(*self);
             }
-            Token::Newline(_) | Token::Space(_) => {}
+            Token::Newline(_)
+            | Token::Space(_)
+            | Token::LineComment(_)
+            | Token::DocLineComment(_)
+            | Token::BlockComment(_)
+            | Token::DocBlockComment(_) => {}
             Token::Unknown(_) => {}
         }
 
         Ok(())
     }
+
+    /// Whether the token at `idx` is the first thing on its line, with only spaces and tabs before
+    /// it.
+    pub fn is_at_line_start(tokens: &[Token], idx: usize) -> bool {
+        tokens[..idx]
+            .iter()
+            .rev()
+            .take_while(|token| !matches!(token, Token::Newline(_)))
+            .all(|token| matches!(token, Token::Space(_)))
+    }
+
+    /// The number of columns of whitespace that start the line the token at `idx` is on, with tabs
+    /// moving to the next multiple of `tab_width`. A `tab_width` of 0 is taken as 1.
+    pub fn indentation_level(tokens: &[Token], idx: usize, tab_width: u8) -> u32 {
+        let tab_width = u32::from(tab_width.max(1));
+        let line_start = tokens[..idx]
+            .iter()
+            .rposition(|token| matches!(token, Token::Newline(_)))
+            .map_or(0, |newline| newline + 1);
+
+        tokens[line_start..]
+            .iter()
+            .take_while(|token| matches!(token, Token::Space(_)))
+            .flat_map(|token| token.as_str().chars())
+            .fold(0, |column, c| match c {
+                '\t' => (column / tab_width + 1) * tab_width,
+                _ => column + 1,
+            })
+    }
+
+    /// Describes the token with its position in `src`, like
+    /// `Token { ty: Plus, text: "+", line: 3, col: 7 }`.
+    pub fn to_debug_repr(&self, src: &SourceFile) -> String {
+        let (line, col) = src.line_col(self.loc());
+
+        format!(
+            "Token {{ ty: {}, text: {:?}, line: {line}, col: {col} }}",
+            self.ty().type_name(),
+            self.as_str()
+        )
+    }
+
+    /// Rebuilds a token packed with [`pack_u64`] from the source it was lexed from. There's no
+    /// `From<u64>` for tokens, as they borrow their text and can only be rebuilt from the source.
+    ///
+    /// Returns `None` if the packed range doesn't fit `src`, or no longer lexes as the packed type.
+    pub fn from_packed(packed: u64, src: &'a str) -> Option<Self> {
+        let (loc, len, ty) = unpack_u64(packed);
+        let (loc, len) = (loc as usize, len as usize);
+        let slice = src.get(loc..loc + len)?;
+        let token = Token::relex(loc, slice);
+
+        (token.ty() as u8 == ty).then_some(token)
+    }
+
+    /// Describes the token as LSP does, with 0-based lines and UTF-16 columns.
+    #[cfg(feature = "serde")]
+    pub fn to_json_value(&self, src: &SourceFile) -> serde_json::Value {
+        let position = |offset| {
+            let (line, character) = src.lsp_position(offset);
+            serde_json::json!({ "line": line, "character": character })
+        };
+
+        serde_json::json!({
+            "type": self.ty().type_name(),
+            "text": self.as_str(),
+            "range": {
+                "start": position(self.loc()),
+                "end": position(self.end()),
+            },
+        })
+    }
+
+    /// Reads back a token described by [`Token::to_json_value`].
+    ///
+    /// Returns `None` if the range isn't inside `src`, or no longer lexes as the described type.
+    #[cfg(feature = "serde")]
+    pub fn from_json_value(value: &serde_json::Value, src: &'a SourceFile) -> Option<Self> {
+        let offset = |position: &serde_json::Value| {
+            let line = position.get("line")?.as_u64()?;
+            let character = position.get("character")?.as_u64()?;
+            src.offset_of_lsp_position(line.try_into().ok()?, character.try_into().ok()?)
+        };
+
+        let range = value.get("range")?;
+        let start = offset(range.get("start")?)?;
+        let end = offset(range.get("end")?)?;
+
+        let token = Token::relex(start, src.text().get(start..end)?);
+
+        (token.ty().type_name() == value.get("type")?.as_str()?).then_some(token)
+    }
+
+    /// Lexes `slice` back into the single token it was taken from.
+    fn relex(loc: usize, slice: &'a str) -> Self {
+        if slice.starts_with("//") || slice.starts_with("/*") {
+            Token::comment(loc, slice)
+        } else {
+            Token::from(loc, slice)
+        }
+    }
+}
+
+/// Packs a token into a `u64` as `loc: 32 | len: 24 | ty: 8`, from most to least significant.
+///
+/// Tokens borrow their text, so the length is kept to recover it from the source on unpacking.
+pub const fn pack_u64(loc: u32, len: u32, ty: u8) -> u64 {
+    debug_assert!(len <= MAX_PACKED_LEN, "token too long to pack");
+    ((loc as u64) << 32) | (((len & MAX_PACKED_LEN) as u64) << 8) | ty as u64
+}
+
+/// The length of the longest token [`pack_u64`] can hold.
+pub const MAX_PACKED_LEN: u32 = 0xFF_FFFF;
+
+pub const fn unpack_u64(packed: u64) -> (u32, u32, u8) {
+    (
+        (packed >> 32) as u32,
+        ((packed >> 8) & 0xFF_FFFF) as u32,
+        (packed & 0xFF) as u8,
+    )
+}
+
+/// Packs a token with [`pack_u64`], failing if it starts past `u32::MAX` or is longer than
+/// [`MAX_PACKED_LEN`], rather than packing a different token.
+impl TryFrom<Token<'_>> for u64 {
+    type Error = TokenizationError;
+
+    fn try_from(token: Token<'_>) -> Result<Self, Self::Error> {
+        let too_large = || TokenizationError::TooLargeToPack {
+            span: token.span().into(),
+        };
+
+        let loc = u32::try_from(token.loc()).map_err(|_| too_large())?;
+        let len = u32::try_from(token.as_str().len())
+            .ok()
+            .filter(|len| *len <= MAX_PACKED_LEN)
+            .ok_or_else(too_large)?;
+
+        Ok(pack_u64(loc, len, token.ty() as u8))
+    }
+}
+
+/// A borrowed run of tokens, which can be indexed and sliced into smaller streams without
+/// copying any tokens.
+#[derive(Debug, Clone, Copy)]
+pub struct TokenStream<'t, 'a> {
+    tokens: &'t [Token<'a>],
+}
+
+impl<'t, 'a> TokenStream<'t, 'a> {
+    pub fn new(tokens: &'t [Token<'a>]) -> Self {
+        TokenStream { tokens }
+    }
+
+    pub fn len(&self) -> usize {
+        self.tokens.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    pub fn get(&self, idx: usize) -> Option<&'t Token<'a>> {
+        self.tokens.get(idx)
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'t, Token<'a>> {
+        self.tokens.iter()
+    }
+
+    pub fn as_slice(&self) -> &'t [Token<'a>] {
+        self.tokens
+    }
+
+    /// The tokens in `range` of this stream, which is indexed from the start of this stream
+    /// rather than the one it was sliced from. Panics if `range` is out of bounds, like slicing.
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> TokenStream<'t, 'a> {
+        let bounds = (range.start_bound().cloned(), range.end_bound().cloned());
+
+        TokenStream {
+            tokens: &self.tokens[bounds],
+        }
+    }
+}
+
+impl<'a> Index<usize> for TokenStream<'_, 'a> {
+    type Output = Token<'a>;
+
+    fn index(&self, idx: usize) -> &Token<'a> {
+        &self.tokens[idx]
+    }
+}
+
+impl<'t, 'a> From<&'t [Token<'a>]> for TokenStream<'t, 'a> {
+    fn from(tokens: &'t [Token<'a>]) -> Self {
+        TokenStream::new(tokens)
+    }
+}
+
+impl<'t, 'a> IntoIterator for TokenStream<'t, 'a> {
+    type Item = &'t Token<'a>;
+    type IntoIter = std::slice::Iter<'t, Token<'a>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.tokens.iter()
+    }
 }
 
 fn binary_operator<'a>(operator: Token<'a>, parser: &mut Parser<'a>) -> miette::Result<()> {
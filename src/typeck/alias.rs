@@ -0,0 +1,108 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::ast::mutator::{walk_type_mut, AstMutator};
+use crate::ast::visitor::{walk_type, AstVisitor};
+use crate::ast::{Item, Module, TypeAlias, TypeExpr};
+
+use super::error::TypeError;
+
+/// `module` with each use of a type alias replaced by the type it stands for, given the
+/// alias's type arguments, and the aliases themselves left out.
+///
+/// Aliases standing for a type that uses them again, directly or through other aliases, are
+/// reported, as they would never finish expanding.
+pub fn expand_type_aliases(module: &Module) -> Result<Module, Vec<TypeError>> {
+    let aliases: HashMap<_, _> = module
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            Item::TypeAlias(alias) => Some((alias.name.as_str(), alias)),
+            _ => None,
+        })
+        .collect();
+
+    let errors: Vec<_> = module
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            Item::TypeAlias(alias) if is_cyclic(alias, &aliases) => {
+                Some(TypeError::CyclicTypeAlias {
+                    name: alias.name.clone(),
+                    span: alias.span.into(),
+                })
+            }
+            _ => None,
+        })
+        .collect();
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let mut expanded = module.clone();
+    expanded
+        .items
+        .retain(|item| !matches!(item, Item::TypeAlias(_)));
+    ExpandAliases { aliases }.visit_module_mut(&mut expanded);
+
+    Ok(expanded)
+}
+
+/// Whether expanding `alias` leads back to it.
+fn is_cyclic(alias: &TypeAlias, aliases: &HashMap<&str, &TypeAlias>) -> bool {
+    let mut pending = vec![alias];
+    let mut seen = HashSet::new();
+
+    while let Some(current) = pending.pop() {
+        let mut names = NamedTypes::default();
+        names.visit_type(&current.target);
+
+        for name in names.0 {
+            if name == alias.name {
+                return true;
+            }
+            if let Some(used) = aliases.get(name.as_str()) {
+                if seen.insert(name) {
+                    pending.push(used);
+                }
+            }
+        }
+    }
+
+    false
+}
+
+/// The names of the named types in a type, including its generic arguments.
+#[derive(Default)]
+struct NamedTypes(Vec<String>);
+
+impl AstVisitor for NamedTypes {
+    fn visit_type(&mut self, n: &TypeExpr) {
+        if let TypeExpr::Named { name, .. } = n {
+            self.0.push(name.clone());
+        }
+
+        walk_type(self, n);
+    }
+}
+
+struct ExpandAliases<'a> {
+    aliases: HashMap<&'a str, &'a TypeAlias>,
+}
+
+impl AstMutator for ExpandAliases<'_> {
+    fn visit_type_mut(&mut self, n: &mut TypeExpr) {
+        walk_type_mut(self, n);
+
+        let TypeExpr::Named { name, generics } = n else {
+            return;
+        };
+        let Some(alias) = self.aliases.get(name.as_str()) else {
+            return;
+        };
+
+        *n = alias.target.substitute(&alias.generic_params, generics);
+        // What the alias stands for can use other aliases
+        self.visit_type_mut(n);
+    }
+}
@@ -0,0 +1,142 @@
+use miette::{Diagnostic, SourceSpan};
+use thiserror::Error;
+
+use crate::ast::TypeExpr;
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum TypeError {
+    #[error("Expected {expected}, found {found}")]
+    TypeMismatch {
+        expected: TypeExpr,
+        found: TypeExpr,
+
+        #[label("this is {found}")]
+        span: SourceSpan,
+    },
+
+    #[error("Cannot find {name:?} in this scope")]
+    UndefinedVariable {
+        name: String,
+
+        #[label("not found")]
+        span: SourceSpan,
+    },
+
+    #[error("Expected {expected} argument(s), found {found}")]
+    ArgumentCountMismatch {
+        expected: usize,
+        found: usize,
+
+        #[label("called with {found} argument(s)")]
+        span: SourceSpan,
+    },
+
+    #[error("Only functions can be called")]
+    NotCallable {
+        #[label("not a function")]
+        span: SourceSpan,
+    },
+
+    #[error("{ty} has no field {name:?}")]
+    UnknownField {
+        ty: TypeExpr,
+        name: String,
+
+        #[label("unknown field")]
+        span: SourceSpan,
+    },
+
+    #[error("{ty} can't be indexed")]
+    NotIndexable {
+        ty: TypeExpr,
+
+        #[label("not an array")]
+        span: SourceSpan,
+    },
+
+    #[error("{ty} can't be iterated over")]
+    NotIterable {
+        ty: TypeExpr,
+
+        #[label("not an array of known length")]
+        span: SourceSpan,
+    },
+
+    #[error("Can't infer the type of {name:?}")]
+    #[diagnostic(help("give it a type, or a value to take the type from"))]
+    CannotInfer {
+        name: String,
+
+        #[label("needs a type")]
+        span: SourceSpan,
+    },
+
+    #[error("The type alias {name:?} stands for a type using itself")]
+    #[diagnostic(help(
+        "an alias can't be used by the type it stands for, even through other aliases"
+    ))]
+    CyclicTypeAlias {
+        name: String,
+
+        #[label("never finishes expanding")]
+        span: SourceSpan,
+    },
+
+    #[error("The value of the global {name:?} isn't a constant")]
+    #[diagnostic(help("globals can only start out as a literal, which numbers can negate"))]
+    NotConstant {
+        name: String,
+
+        #[label("not a literal")]
+        span: SourceSpan,
+    },
+
+    #[error("Can't infer the type argument {param} of {callee:?}")]
+    #[diagnostic(help("only type parameters used by a parameter's type can be inferred"))]
+    CannotInferTypeArgument {
+        param: String,
+        callee: String,
+
+        #[label("{param} unknown in this call")]
+        span: SourceSpan,
+    },
+}
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum NameError {
+    #[error("Cannot find {name:?} in this scope")]
+    Undefined {
+        name: String,
+
+        #[label("not found")]
+        span: SourceSpan,
+    },
+
+    #[error("{name:?} is already defined in this scope")]
+    AlreadyDefined {
+        name: String,
+
+        #[label("first defined here")]
+        first: SourceSpan,
+
+        #[label("defined again here")]
+        second: SourceSpan,
+    },
+
+    #[error("Use of undeclared label '{name}")]
+    UndefinedLabel {
+        name: String,
+
+        #[label("no enclosing loop or block has this label")]
+        span: SourceSpan,
+    },
+
+    #[error("`{keyword}` outside of a loop")]
+    #[diagnostic(help("only loops can be continued, and only loops and labelled blocks broken"))]
+    OutsideLoop {
+        keyword: &'static str,
+
+        #[label("not inside a loop")]
+        span: SourceSpan,
+    },
+}
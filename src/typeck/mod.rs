@@ -0,0 +1,1244 @@
+mod alias;
+pub mod error;
+mod mono;
+mod resolve;
+pub mod symbols;
+pub mod typed;
+
+use std::collections::{HashMap, HashSet};
+
+use crate::ast::{
+    BinOpKind, Block, ClosureBody, ClosureExpr, ExprNode, FnDecl, ForInStmt, ForStmt,
+    GenericParams, GlobalDecl, IfExpr, IfOrBlock, Item, LiteralValue, MatchArm, MatchExpr, Module,
+    Param, Pattern, StmtNode, StructDecl, TypeExpr, UnOpKind,
+};
+use crate::builtins::BuiltinRegistry;
+use crate::codegen::GlobalVar;
+use crate::span::Span;
+
+pub use self::alias::expand_type_aliases;
+use self::error::{NameError, TypeError};
+pub use self::mono::{monomorphize, MonoModule, Monomorphizer};
+pub use self::resolve::resolve_names;
+use self::symbols::{SymbolInfo, SymbolKind, SymbolTable};
+use self::typed::{
+    TypedBlock, TypedExpr, TypedExprKind, TypedFn, TypedMatchArm, TypedModule, TypedStmt,
+};
+
+/// Define every function, global, struct, enum and trait in `module` in the module scope of `syms`, so
+/// they can be used before the point they are declared.
+pub fn declare_items(module: &Module, syms: &mut SymbolTable) -> Result<(), Vec<NameError>> {
+    let mut errors = vec![];
+
+    for item in &module.items {
+        let (name, info) = match item {
+            Item::FnDecl(decl) => (
+                &decl.name,
+                SymbolInfo {
+                    kind: SymbolKind::Function,
+                    ty: TypeExpr::FnType {
+                        params: decl.params.iter().map(|param| param.ty.clone()).collect(),
+                        ret: Box::new(decl.ret.clone().unwrap_or_else(TypeExpr::void)),
+                    },
+                    def_span: decl.span,
+                },
+            ),
+            Item::GlobalDecl(decl) => (
+                &decl.name,
+                SymbolInfo {
+                    kind: SymbolKind::Variable {
+                        mutable: decl.mutable,
+                    },
+                    ty: decl.ty.clone(),
+                    def_span: decl.span,
+                },
+            ),
+            Item::StructDecl(decl) => (
+                &decl.name,
+                SymbolInfo {
+                    kind: SymbolKind::Struct,
+                    ty: TypeExpr::named(&decl.name),
+                    def_span: decl.span,
+                },
+            ),
+            Item::EnumDecl(decl) => (
+                &decl.name,
+                SymbolInfo {
+                    kind: SymbolKind::Enum,
+                    ty: TypeExpr::named(&decl.name),
+                    def_span: decl.span,
+                },
+            ),
+            Item::TraitDecl(decl) => (
+                &decl.name,
+                SymbolInfo {
+                    kind: SymbolKind::Trait,
+                    ty: TypeExpr::named(&decl.name),
+                    def_span: decl.span,
+                },
+            ),
+            // Methods are looked up through the type they're implemented for, and aliases are
+            // replaced before type checking
+            Item::ImplBlock(_) | Item::UseDecl(_) | Item::TypeAlias(_) => continue,
+        };
+
+        if let Err(err) = syms.define(name, info) {
+            errors.push(err);
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Checks that every expression in `module` is used at a type it can have, given the items
+/// declared in `syms`.
+///
+/// Integer literals take the integer type expected of them, or `i64` if nothing is expected. A
+/// local initialised with one and no type of its own takes the type it's first used at instead.
+///
+/// The bodies of generic functions are only checked once [`monomorphize`] has given them the type
+/// arguments they're called with. Calls to them infer those type arguments from the arguments.
+///
+/// Type aliases are expected to have been replaced already, by [`expand_type_aliases`].
+pub fn type_check(module: &Module, syms: &SymbolTable) -> Result<TypedModule, Vec<TypeError>> {
+    let mut structs = vec![];
+    let mut generic_fns = vec![];
+
+    for item in &module.items {
+        match item {
+            Item::StructDecl(decl) => structs.push(decl.clone()),
+            Item::FnDecl(decl) if !decl.generic_params.is_empty() => generic_fns.push(decl.clone()),
+            _ => {}
+        }
+    }
+
+    let mut checker = Checker::new(syms.clone(), &structs, &generic_fns);
+
+    let globals = module
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            Item::GlobalDecl(decl) => checker.check_global(decl),
+            _ => None,
+        })
+        .collect();
+
+    let functions = module
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            Item::FnDecl(decl) if decl.generic_params.is_empty() => Some(checker.check_fn(decl)),
+            _ => None,
+        })
+        .collect();
+
+    if checker.errors.is_empty() {
+        Ok(TypedModule {
+            path: module.path.clone(),
+            structs,
+            globals,
+            functions,
+            generic_fns,
+        })
+    } else {
+        Err(checker.errors)
+    }
+}
+
+struct Checker {
+    syms: SymbolTable,
+    structs: HashMap<String, StructDecl>,
+    generic_fns: HashMap<String, FnDecl>,
+    builtins: BuiltinRegistry,
+    /// The return type of the function being checked.
+    ret: TypeExpr,
+    /// The `let`s without a type whose integer literal defaulted to `i64`, by their span.
+    defaulted: HashSet<Span>,
+    /// The types those `let`s were later used at, which they're given when checked again.
+    inferred: HashMap<Span, TypeExpr>,
+    errors: Vec<TypeError>,
+}
+
+impl Checker {
+    fn new(syms: SymbolTable, structs: &[StructDecl], generic_fns: &[FnDecl]) -> Self {
+        Checker {
+            syms,
+            structs: structs
+                .iter()
+                .map(|decl| (decl.name.clone(), decl.clone()))
+                .collect(),
+            generic_fns: generic_fns
+                .iter()
+                .map(|decl| (decl.name.clone(), decl.clone()))
+                .collect(),
+            builtins: BuiltinRegistry::new(),
+            ret: TypeExpr::void(),
+            defaulted: HashSet::new(),
+            inferred: HashMap::new(),
+            errors: vec![],
+        }
+    }
+
+    /// Check `decl`, again for as long as that finds out the types of more of its locals.
+    fn check_fn(&mut self, decl: &FnDecl) -> TypedFn {
+        let errors = self.errors.len();
+        // Generic functions are checked once per instance, which can use their locals differently
+        self.inferred.clear();
+
+        loop {
+            let inferred = self.inferred.len();
+            self.defaulted.clear();
+
+            let function = self.check_fn_once(decl);
+            if self.inferred.len() == inferred {
+                return function;
+            }
+
+            // The errors may only have come from using a local at the wrong type
+            self.errors.truncate(errors);
+        }
+    }
+
+    fn check_fn_once(&mut self, decl: &FnDecl) -> TypedFn {
+        let ret = decl.ret.clone().unwrap_or_else(TypeExpr::void);
+        self.ret = ret.clone();
+
+        self.syms.enter_scope();
+
+        for param in &decl.params {
+            // Repeated parameter names are left for name resolution to report
+            let _ = self.syms.define(
+                &param.name,
+                SymbolInfo {
+                    kind: SymbolKind::Parameter,
+                    ty: param.ty.clone(),
+                    def_span: param.span,
+                },
+            );
+        }
+
+        let body = self.check_block(&decl.body, Some(&ret));
+
+        if let Some(final_expr) = &body.final_expr {
+            if let Err(err) = expect_type(&ret, final_expr) {
+                self.errors.push(err);
+            }
+        }
+
+        self.syms.exit_scope();
+
+        TypedFn {
+            abi: decl.abi.clone(),
+            name: decl.name.clone(),
+            params: decl.params.clone(),
+            ret,
+            body,
+            span: decl.span,
+        }
+    }
+
+    /// The global declared by `decl`, with its value worked out. `None` once an error in it has
+    /// been reported.
+    fn check_global(&mut self, decl: &GlobalDecl) -> Option<GlobalVar> {
+        let init = match &decl.init {
+            Some(init) => {
+                let checked = self.expect_expr(init, &decl.ty).and_then(|init| {
+                    constant_value(&init).ok_or_else(|| TypeError::NotConstant {
+                        name: decl.name.clone(),
+                        span: init.span.into(),
+                    })
+                });
+
+                match checked {
+                    Ok(value) => Some(value),
+                    Err(err) => {
+                        self.errors.push(err);
+                        return None;
+                    }
+                }
+            }
+            None => None,
+        };
+
+        Some(GlobalVar {
+            name: decl.name.clone(),
+            ty: decl.ty.clone(),
+            init,
+            mutable: decl.mutable,
+        })
+    }
+
+    /// Statements that fail to check are reported and left out, so one mistake doesn't hide the
+    /// rest of the block.
+    fn check_block(&mut self, block: &Block, expected: Option<&TypeExpr>) -> TypedBlock {
+        self.syms.enter_scope();
+
+        let mut stmts = Vec::with_capacity(block.stmts.len());
+
+        for stmt in &block.stmts {
+            match self.check_stmt(stmt) {
+                Ok(stmt) => stmts.push(stmt),
+                Err(err) => self.errors.push(err),
+            }
+        }
+
+        let final_expr =
+            block
+                .final_expr
+                .as_ref()
+                .and_then(|expr| match self.check_expr(expr, expected) {
+                    // Only the block can see the local it ends in, to give it the expected type
+                    Ok(typed) => match expected {
+                        Some(ty)
+                            if expect_type(ty, &typed).is_err()
+                                && self.infer_from_use(expr, ty) =>
+                        {
+                            Some(Box::new(TypedExpr {
+                                ty: ty.clone(),
+                                ..typed
+                            }))
+                        }
+                        _ => Some(Box::new(typed)),
+                    },
+                    Err(err) => {
+                        self.errors.push(err);
+                        None
+                    }
+                });
+
+        self.syms.exit_scope();
+
+        // Control never reaches the end of a block that jumps away
+        let diverges = matches!(
+            stmts.last(),
+            Some(TypedStmt::Return { .. } | TypedStmt::Break { .. } | TypedStmt::Continue { .. })
+        );
+
+        let ty = match &final_expr {
+            Some(expr) => expr.ty.clone(),
+            None if diverges => TypeExpr::Never,
+            None => TypeExpr::void(),
+        };
+
+        TypedBlock {
+            label: block.label.clone(),
+            stmts,
+            ty,
+            final_expr,
+            span: block.span,
+        }
+    }
+
+    fn check_stmt(&mut self, stmt: &StmtNode) -> Result<TypedStmt, TypeError> {
+        match stmt {
+            StmtNode::Expr(expr) => Ok(TypedStmt::Expr(self.check_expr(expr, None)?)),
+            StmtNode::Let(stmt) => {
+                let inferred = self.inferred.get(&stmt.span).cloned();
+                let init = match (stmt.ty.as_ref().or(inferred.as_ref()), &stmt.init) {
+                    (Some(ty), Some(init)) => Some(self.expect_expr(init, ty)?),
+                    (None, Some(init)) => {
+                        if is_integer_literal(init) {
+                            self.defaulted.insert(stmt.span);
+                        }
+                        Some(self.check_expr(init, None)?)
+                    }
+                    (_, None) => None,
+                };
+
+                let ty = match (&stmt.ty, &init) {
+                    (Some(ty), _) => ty.clone(),
+                    (None, Some(init)) => init.ty.clone(),
+                    (None, None) => {
+                        return Err(TypeError::CannotInfer {
+                            name: stmt.name.clone(),
+                            span: stmt.span.into(),
+                        })
+                    }
+                };
+
+                self.syms.redefine(
+                    &stmt.name,
+                    SymbolInfo {
+                        kind: SymbolKind::Variable {
+                            mutable: stmt.mutable,
+                        },
+                        ty: ty.clone(),
+                        def_span: stmt.span,
+                    },
+                );
+
+                Ok(TypedStmt::Let {
+                    name: stmt.name.clone(),
+                    ty,
+                    init,
+                    mutable: stmt.mutable,
+                    span: stmt.span,
+                })
+            }
+            StmtNode::Assign(stmt) => {
+                let mut target = self.check_expr(&stmt.target, None)?;
+                let value = self.expect_operand(&stmt.target, &mut target, &stmt.value)?;
+
+                Ok(TypedStmt::Assign {
+                    target,
+                    op: stmt.op,
+                    value,
+                    span: stmt.span,
+                })
+            }
+            StmtNode::While(stmt) => Ok(TypedStmt::While {
+                label: stmt.label.clone(),
+                cond: self.expect_expr(&stmt.cond, &TypeExpr::named("bool"))?,
+                body: self.check_block(&stmt.body, None),
+                span: stmt.span,
+            }),
+            StmtNode::For(stmt) => {
+                // Anything declared by `init` is only visible inside the loop
+                self.syms.enter_scope();
+                let stmt = self.check_for(stmt);
+                self.syms.exit_scope();
+
+                stmt
+            }
+            StmtNode::ForIn(stmt) => {
+                self.syms.enter_scope();
+                let stmt = self.check_for_in(stmt);
+                self.syms.exit_scope();
+
+                stmt
+            }
+            StmtNode::Return(stmt) => {
+                let ret = self.ret.clone();
+                let value = match &stmt.value {
+                    Some(value) => Some(self.expect_expr(value, &ret)?),
+                    None if ret.is_void() => None,
+                    None => {
+                        return Err(TypeError::TypeMismatch {
+                            expected: ret,
+                            found: TypeExpr::void(),
+                            span: stmt.span.into(),
+                        })
+                    }
+                };
+
+                Ok(TypedStmt::Return {
+                    value,
+                    span: stmt.span,
+                })
+            }
+            StmtNode::Break(stmt) => Ok(TypedStmt::Break {
+                label: stmt.label.clone(),
+                value: match &stmt.value {
+                    Some(value) => Some(self.check_expr(value, None)?),
+                    None => None,
+                },
+                span: stmt.span,
+            }),
+            StmtNode::Continue(stmt) => Ok(TypedStmt::Continue {
+                label: stmt.label.clone(),
+                span: stmt.span,
+            }),
+        }
+    }
+
+    fn check_for(&mut self, stmt: &ForStmt) -> Result<TypedStmt, TypeError> {
+        let init = match &stmt.init {
+            Some(init) => Some(Box::new(self.check_stmt(init)?)),
+            None => None,
+        };
+        let cond = match &stmt.cond {
+            Some(cond) => Some(self.expect_expr(cond, &TypeExpr::named("bool"))?),
+            None => None,
+        };
+        let post = match &stmt.post {
+            Some(post) => Some(Box::new(self.check_stmt(post)?)),
+            None => None,
+        };
+
+        Ok(TypedStmt::For {
+            label: stmt.label.clone(),
+            init,
+            cond,
+            post,
+            body: self.check_block(&stmt.body, None),
+            span: stmt.span,
+        })
+    }
+
+    fn check_for_in(&mut self, stmt: &ForInStmt) -> Result<TypedStmt, TypeError> {
+        let iterable = self.check_expr(&stmt.iterable, None)?;
+
+        let TypeExpr::Array {
+            element,
+            size: Some(_),
+        } = &iterable.ty
+        else {
+            return Err(TypeError::NotIterable {
+                ty: iterable.ty,
+                span: iterable.span.into(),
+            });
+        };
+
+        self.syms.redefine(
+            &stmt.binding,
+            SymbolInfo {
+                kind: SymbolKind::Variable {
+                    mutable: stmt.mutable,
+                },
+                ty: (**element).clone(),
+                def_span: stmt.span,
+            },
+        );
+
+        Ok(TypedStmt::ForIn {
+            label: stmt.label.clone(),
+            binding: stmt.binding.clone(),
+            mutable: stmt.mutable,
+            body: self.check_block(&stmt.body, None),
+            iterable,
+            span: stmt.span,
+        })
+    }
+
+    /// Check `expr` and make sure it has type `expected`.
+    fn expect_expr(
+        &mut self,
+        expr: &ExprNode,
+        expected: &TypeExpr,
+    ) -> Result<TypedExpr, TypeError> {
+        let typed = self.check_expr(expr, Some(expected))?;
+
+        match expect_type(expected, &typed) {
+            Err(_) if self.infer_from_use(expr, expected) => Ok(TypedExpr {
+                ty: expected.clone(),
+                ..typed
+            }),
+            result => result.map(|()| typed),
+        }
+    }
+
+    /// Check `rhs` has the type of `lhs`, the other operand of a binary operator. If `lhs` is a
+    /// local whose type was defaulted, it's `rhs` that decides the type instead.
+    fn expect_operand(
+        &mut self,
+        lhs: &ExprNode,
+        lhs_typed: &mut TypedExpr,
+        rhs: &ExprNode,
+    ) -> Result<TypedExpr, TypeError> {
+        let typed = self.check_expr(rhs, Some(&lhs_typed.ty))?;
+
+        match expect_type(&lhs_typed.ty, &typed) {
+            Err(_) if self.infer_from_use(lhs, &typed.ty) => {
+                lhs_typed.ty = typed.ty.clone();
+                Ok(typed)
+            }
+            Err(_) if self.infer_from_use(rhs, &lhs_typed.ty) => Ok(TypedExpr {
+                ty: lhs_typed.ty.clone(),
+                ..typed
+            }),
+            result => result.map(|()| typed),
+        }
+    }
+
+    /// Note that `expr` is used at the integer type `ty`, if it names a local whose type was
+    /// defaulted. Returns whether it does.
+    fn infer_from_use(&mut self, expr: &ExprNode, ty: &TypeExpr) -> bool {
+        let ExprNode::Ident { name, .. } = expr else {
+            return false;
+        };
+        let Some(def_span) = self.syms.lookup(name).map(|info| info.def_span) else {
+            return false;
+        };
+
+        if !ty.is_integer() || !self.defaulted.remove(&def_span) {
+            return false;
+        }
+
+        self.inferred.insert(def_span, ty.clone());
+        true
+    }
+
+    /// Find the type of `expr`. `expected` is only a hint, used to give literals their type.
+    fn check_expr(
+        &mut self,
+        expr: &ExprNode,
+        expected: Option<&TypeExpr>,
+    ) -> Result<TypedExpr, TypeError> {
+        let span = expr.span();
+
+        let (kind, ty) = match expr {
+            ExprNode::Literal { value, .. } => {
+                let ty = literal_type(value, expected);
+
+                (TypedExprKind::Literal(value.clone()), ty)
+            }
+            ExprNode::Ident { name, span } => {
+                let info = self
+                    .syms
+                    .lookup(name)
+                    .ok_or_else(|| TypeError::UndefinedVariable {
+                        name: name.clone(),
+                        span: (*span).into(),
+                    })?;
+
+                (TypedExprKind::Ident(name.clone()), info.ty.clone())
+            }
+            ExprNode::BinOp { op, lhs, rhs, .. } => match op {
+                BinOpKind::Pipe => return self.check_call(rhs, &[lhs], expected, span),
+                BinOpKind::Or => {
+                    let bool = TypeExpr::named("bool");
+                    let lhs = self.expect_expr(lhs, &bool)?;
+                    let rhs = self.expect_expr(rhs, &bool)?;
+
+                    (binary(*op, lhs, rhs), bool)
+                }
+                BinOpKind::Lt | BinOpKind::Gt => {
+                    let mut lhs_typed = self.check_expr(lhs, None)?;
+                    let rhs = self.expect_operand(lhs, &mut lhs_typed, rhs)?;
+
+                    (binary(*op, lhs_typed, rhs), TypeExpr::named("bool"))
+                }
+                _ => {
+                    let mut lhs_typed = self.check_expr(lhs, expected)?;
+                    let rhs = self.expect_operand(lhs, &mut lhs_typed, rhs)?;
+                    let ty = lhs_typed.ty.clone();
+
+                    (binary(*op, lhs_typed, rhs), ty)
+                }
+            },
+            ExprNode::UnaryOp { op, operand, .. } => {
+                let operand = match op {
+                    UnOpKind::Not => self.expect_expr(operand, &TypeExpr::named("bool"))?,
+                    UnOpKind::Neg | UnOpKind::BitNot => self.check_expr(operand, expected)?,
+                };
+                let ty = operand.ty.clone();
+
+                (
+                    TypedExprKind::UnaryOp {
+                        op: *op,
+                        operand: Box::new(operand),
+                    },
+                    ty,
+                )
+            }
+            ExprNode::Call { callee, args, .. } => {
+                let args: Vec<_> = args.iter().collect();
+                return self.check_call(callee, &args, expected, span);
+            }
+            ExprNode::Builtin { builtin, args, .. } => {
+                let signature = self.builtins.signature(builtin).clone();
+
+                if signature.params.len() != args.len() {
+                    return Err(TypeError::ArgumentCountMismatch {
+                        expected: signature.params.len(),
+                        found: args.len(),
+                        span: span.into(),
+                    });
+                }
+
+                let args = args
+                    .iter()
+                    .zip(&signature.params)
+                    .map(|(arg, param)| self.expect_expr(arg, param))
+                    .collect::<Result<_, _>>()?;
+
+                (
+                    TypedExprKind::Builtin {
+                        builtin: builtin.clone(),
+                        args,
+                    },
+                    signature.ret,
+                )
+            }
+            ExprNode::Index { base, index, .. } => {
+                let base = self.check_expr(base, None)?;
+
+                let TypeExpr::Array { element, .. } = &base.ty else {
+                    return Err(TypeError::NotIndexable {
+                        ty: base.ty,
+                        span: base.span.into(),
+                    });
+                };
+                let ty = (**element).clone();
+
+                let index = self.check_expr(index, Some(&TypeExpr::named("i64")))?;
+                if !index.ty.is_integer() {
+                    return Err(TypeError::TypeMismatch {
+                        expected: TypeExpr::named("i64"),
+                        found: index.ty,
+                        span: index.span.into(),
+                    });
+                }
+
+                (
+                    TypedExprKind::Index {
+                        base: Box::new(base),
+                        index: Box::new(index),
+                    },
+                    ty,
+                )
+            }
+            ExprNode::Field { base, name, .. } => {
+                let base = self.check_expr(base, None)?;
+
+                let field = match &base.ty {
+                    TypeExpr::Named { name: ty, .. } => self.structs.get(ty).and_then(|decl| {
+                        decl.fields
+                            .iter()
+                            .position(|field| field.name == *name)
+                            .map(|idx| (idx, decl.fields[idx].ty.clone()))
+                    }),
+                    TypeExpr::Tuple(types) => name
+                        .parse::<usize>()
+                        .ok()
+                        .and_then(|idx| Some((idx, types.get(idx)?.clone()))),
+                    _ => None,
+                };
+
+                let Some((index, ty)) = field else {
+                    return Err(TypeError::UnknownField {
+                        ty: base.ty,
+                        name: name.clone(),
+                        span: span.into(),
+                    });
+                };
+
+                (
+                    TypedExprKind::Field {
+                        base: Box::new(base),
+                        name: name.clone(),
+                        index,
+                    },
+                    ty,
+                )
+            }
+            ExprNode::If(if_expr) => return self.check_if(if_expr, expected),
+            ExprNode::Match(match_expr) => return self.check_match(match_expr, expected),
+            ExprNode::Closure(closure) => return self.check_closure(closure, expected),
+            ExprNode::Block(block) => {
+                let block = self.check_block(block, expected);
+                let ty = block.ty.clone();
+
+                (TypedExprKind::Block(block), ty)
+            }
+        };
+
+        Ok(TypedExpr { kind, ty, span })
+    }
+
+    /// Parameters without a type, and the return type, are taken from `expected` when it's a
+    /// function type. Without one, a `return` in the body can't have a value.
+    fn check_closure(
+        &mut self,
+        closure: &ClosureExpr,
+        expected: Option<&TypeExpr>,
+    ) -> Result<TypedExpr, TypeError> {
+        let (expected_params, expected_ret) = match expected {
+            Some(TypeExpr::FnType { params, ret }) if params.len() == closure.params.len() => {
+                (Some(params), Some(&**ret))
+            }
+            _ => (None, None),
+        };
+
+        let params = closure
+            .params
+            .iter()
+            .enumerate()
+            .map(|(idx, param)| {
+                let ty = param
+                    .ty
+                    .clone()
+                    .or_else(|| expected_params.map(|params| params[idx].clone()))
+                    .ok_or_else(|| TypeError::CannotInfer {
+                        name: param.name.clone(),
+                        span: param.span.into(),
+                    })?;
+
+                Ok(Param {
+                    name: param.name.clone(),
+                    ty,
+                    span: param.span,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // A `return` in the body leaves the closure, not the function around it
+        let outer_ret = std::mem::replace(
+            &mut self.ret,
+            expected_ret.cloned().unwrap_or_else(TypeExpr::void),
+        );
+        self.syms.enter_scope();
+
+        for param in &params {
+            // Repeated parameter names are left for name resolution to report
+            let _ = self.syms.define(
+                &param.name,
+                SymbolInfo {
+                    kind: SymbolKind::Parameter,
+                    ty: param.ty.clone(),
+                    def_span: param.span,
+                },
+            );
+        }
+
+        let body = match &closure.body {
+            ClosureBody::Expr(expr) => self.check_expr(expr, expected_ret),
+            ClosureBody::Block(block) => {
+                let block = self.check_block(block, expected_ret);
+
+                Ok(TypedExpr {
+                    ty: block.ty.clone(),
+                    span: block.span,
+                    kind: TypedExprKind::Block(block),
+                })
+            }
+        };
+
+        self.syms.exit_scope();
+        self.ret = outer_ret;
+
+        let body = body?;
+        if let Some(ret) = expected_ret {
+            expect_type(ret, &body)?;
+        }
+
+        Ok(TypedExpr {
+            ty: TypeExpr::FnType {
+                params: params.iter().map(|param| param.ty.clone()).collect(),
+                ret: Box::new(expected_ret.cloned().unwrap_or_else(|| body.ty.clone())),
+            },
+            kind: TypedExprKind::Closure {
+                params,
+                capture_mode: closure.capture_mode,
+                body: Box::new(body),
+            },
+            span: closure.span,
+        })
+    }
+
+    /// `expected` is only used to infer the type arguments of a generic callee.
+    fn check_call(
+        &mut self,
+        callee: &ExprNode,
+        args: &[&ExprNode],
+        expected: Option<&TypeExpr>,
+        span: Span,
+    ) -> Result<TypedExpr, TypeError> {
+        let ExprNode::Ident { name, .. } = callee else {
+            return Err(TypeError::NotCallable {
+                span: callee.span().into(),
+            });
+        };
+
+        let callee_ty = self.check_expr(callee, None)?.ty;
+
+        // Unless the name has been shadowed by a local
+        let is_function = self
+            .syms
+            .lookup(name)
+            .is_some_and(|info| info.kind == SymbolKind::Function);
+        if is_function {
+            if let Some(decl) = self.generic_fns.get(name).cloned() {
+                return self.check_generic_call(&decl, args, expected, span);
+            }
+        }
+
+        let TypeExpr::FnType { params, ret } = callee_ty else {
+            return Err(TypeError::NotCallable {
+                span: callee.span().into(),
+            });
+        };
+
+        if params.len() != args.len() {
+            return Err(TypeError::ArgumentCountMismatch {
+                expected: params.len(),
+                found: args.len(),
+                span: span.into(),
+            });
+        }
+
+        let args = args
+            .iter()
+            .zip(&params)
+            .map(|(arg, param)| self.expect_expr(arg, param))
+            .collect::<Result<_, _>>()?;
+
+        Ok(TypedExpr {
+            kind: TypedExprKind::Call {
+                callee: name.clone(),
+                args,
+                type_args: vec![],
+            },
+            ty: *ret,
+            span,
+        })
+    }
+
+    /// Check a call to the generic function `decl`, taking each type argument from the type
+    /// expected of the call if its return type uses it, or else the first argument whose
+    /// parameter does.
+    fn check_generic_call(
+        &mut self,
+        decl: &FnDecl,
+        args: &[&ExprNode],
+        expected: Option<&TypeExpr>,
+        span: Span,
+    ) -> Result<TypedExpr, TypeError> {
+        if decl.params.len() != args.len() {
+            return Err(TypeError::ArgumentCountMismatch {
+                expected: decl.params.len(),
+                found: args.len(),
+                span: span.into(),
+            });
+        }
+
+        let mut bindings = vec![None; decl.generic_params.0.len()];
+        if let (Some(ret), Some(expected)) = (&decl.ret, expected) {
+            bind_type_args(&decl.generic_params, ret, expected, &mut bindings);
+        }
+
+        let mut typed_args = Vec::with_capacity(args.len());
+
+        for (arg, param) in args.iter().zip(&decl.params) {
+            // Give literals the type of a parameter already known, like the `0` of `max(x, 0)`
+            let hint = param.ty.substitute(
+                &decl.generic_params,
+                &bound_or_unbound(&decl.generic_params, &bindings),
+            );
+            let typed = self.check_expr(arg, Some(&hint))?;
+            bind_type_args(&decl.generic_params, &param.ty, &typed.ty, &mut bindings);
+
+            typed_args.push(typed);
+        }
+
+        let type_args = decl
+            .generic_params
+            .iter()
+            .zip(bindings)
+            .map(|(param, binding)| {
+                binding.ok_or_else(|| TypeError::CannotInferTypeArgument {
+                    param: param.name.clone(),
+                    callee: decl.name.clone(),
+                    span: span.into(),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for (arg, param) in typed_args.iter().zip(&decl.params) {
+            expect_type(&param.ty.substitute(&decl.generic_params, &type_args), arg)?;
+        }
+
+        let ret = decl.ret.clone().unwrap_or_else(TypeExpr::void);
+
+        Ok(TypedExpr {
+            ty: ret.substitute(&decl.generic_params, &type_args),
+            kind: TypedExprKind::Call {
+                callee: decl.name.clone(),
+                args: typed_args,
+                type_args,
+            },
+            span,
+        })
+    }
+
+    fn check_if(
+        &mut self,
+        if_expr: &IfExpr,
+        expected: Option<&TypeExpr>,
+    ) -> Result<TypedExpr, TypeError> {
+        let cond = self.expect_expr(&if_expr.cond, &TypeExpr::named("bool"))?;
+        let then_block = self.check_block(&if_expr.then_block, expected);
+
+        // A `then` branch that never finishes says nothing about the type of the `if`
+        let then_ty = match then_block.ty {
+            TypeExpr::Never => None,
+            _ => Some(&then_block.ty),
+        };
+        let else_expected = then_ty.or(expected);
+
+        let else_block = match if_expr.else_block.as_deref() {
+            Some(IfOrBlock::If(else_if)) => Some(self.check_if(else_if, else_expected)?),
+            Some(IfOrBlock::Block(block)) => {
+                let block = self.check_block(block, else_expected);
+
+                Some(TypedExpr {
+                    ty: block.ty.clone(),
+                    span: block.span,
+                    kind: TypedExprKind::Block(block),
+                })
+            }
+            None => None,
+        };
+
+        // Without an else branch there is no value when the condition is false
+        let ty = match &else_block {
+            Some(else_block) => match then_ty {
+                Some(then_ty) => {
+                    expect_type(then_ty, else_block)?;
+                    then_ty.clone()
+                }
+                None => else_block.ty.clone(),
+            },
+            None => TypeExpr::void(),
+        };
+
+        Ok(TypedExpr {
+            kind: TypedExprKind::If {
+                cond: Box::new(cond),
+                then_block,
+                else_block: else_block.map(Box::new),
+            },
+            ty,
+            span: if_expr.span,
+        })
+    }
+
+    /// Every arm must have the same type, which is the type of the match. A match without any
+    /// arms never produces a value.
+    fn check_match(
+        &mut self,
+        match_expr: &MatchExpr,
+        expected: Option<&TypeExpr>,
+    ) -> Result<TypedExpr, TypeError> {
+        let scrutinee = self.check_expr(&match_expr.scrutinee, None)?;
+        let mut ty: Option<TypeExpr> = None;
+        let mut arms = Vec::with_capacity(match_expr.arms.len());
+
+        for arm in &match_expr.arms {
+            // Bindings from the pattern are only visible in the guard and body
+            self.syms.enter_scope();
+            let arm = self.check_arm(arm, &scrutinee.ty, ty.as_ref().or(expected));
+            self.syms.exit_scope();
+            let arm = arm?;
+
+            match &ty {
+                Some(ty) => expect_type(ty, &arm.body)?,
+                None if arm.body.ty != TypeExpr::Never => ty = Some(arm.body.ty.clone()),
+                None => {}
+            }
+
+            arms.push(arm);
+        }
+
+        Ok(TypedExpr {
+            kind: TypedExprKind::Match {
+                scrutinee: Box::new(scrutinee),
+                arms,
+            },
+            ty: ty.unwrap_or(TypeExpr::Never),
+            span: match_expr.span,
+        })
+    }
+
+    fn check_arm(
+        &mut self,
+        arm: &MatchArm,
+        scrutinee: &TypeExpr,
+        expected: Option<&TypeExpr>,
+    ) -> Result<TypedMatchArm, TypeError> {
+        self.check_pattern(&arm.pattern, scrutinee, arm.span)?;
+
+        let guard = match &arm.guard {
+            Some(guard) => Some(self.expect_expr(guard, &TypeExpr::named("bool"))?),
+            None => None,
+        };
+
+        Ok(TypedMatchArm {
+            pattern: arm.pattern.clone(),
+            guard,
+            body: self.check_expr(&arm.body, expected)?,
+            span: arm.span,
+        })
+    }
+
+    /// Make sure `pattern` can match a value of type `ty`, defining the names it binds.
+    fn check_pattern(
+        &mut self,
+        pattern: &Pattern,
+        ty: &TypeExpr,
+        span: Span,
+    ) -> Result<(), TypeError> {
+        match pattern {
+            Pattern::Literal(value) => match literal_type(value, Some(ty)) {
+                found if found == *ty => Ok(()),
+                found => Err(TypeError::TypeMismatch {
+                    expected: ty.clone(),
+                    found,
+                    span: span.into(),
+                }),
+            },
+            Pattern::Wildcard => Ok(()),
+            Pattern::Ident(name) => {
+                self.syms.redefine(
+                    name,
+                    SymbolInfo {
+                        kind: SymbolKind::Variable { mutable: false },
+                        ty: ty.clone(),
+                        def_span: span,
+                    },
+                );
+
+                Ok(())
+            }
+            Pattern::Or(lhs, rhs) => {
+                self.check_pattern(lhs, ty, span)?;
+                self.check_pattern(rhs, ty, span)
+            }
+        }
+    }
+}
+
+/// The type of a literal. Numbers take the type they're `expected` to have if they can, falling
+/// back to `i64` or `f64`.
+/// The value of `expr` if it's a literal, or a negated number literal.
+fn constant_value(expr: &TypedExpr) -> Option<LiteralValue> {
+    match &expr.kind {
+        TypedExprKind::Literal(value) => Some(value.clone()),
+        TypedExprKind::UnaryOp {
+            op: UnOpKind::Neg,
+            operand,
+        } => match &operand.kind {
+            TypedExprKind::Literal(LiteralValue::Int(value)) => Some(LiteralValue::Int(-value)),
+            TypedExprKind::Literal(LiteralValue::Float(value)) => Some(LiteralValue::Float(-value)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Whether `expr` is an integer literal, or the negation of one.
+fn is_integer_literal(expr: &ExprNode) -> bool {
+    match expr {
+        ExprNode::Literal {
+            value: LiteralValue::Int(_) | LiteralValue::UInt(_),
+            ..
+        } => true,
+        ExprNode::UnaryOp {
+            op: UnOpKind::Neg,
+            operand,
+            ..
+        } => is_integer_literal(operand),
+        _ => false,
+    }
+}
+
+fn literal_type(value: &LiteralValue, expected: Option<&TypeExpr>) -> TypeExpr {
+    match value {
+        LiteralValue::Int(_) | LiteralValue::UInt(_) => match expected {
+            Some(ty) if ty.is_integer() => ty.clone(),
+            _ => TypeExpr::named("i64"),
+        },
+        LiteralValue::Float(_) => match expected {
+            Some(ty) if ty.is_float() => ty.clone(),
+            _ => TypeExpr::named("f64"),
+        },
+        LiteralValue::String(_) => TypeExpr::Pointer {
+            inner: Box::new(TypeExpr::named("u8")),
+            mutable: false,
+        },
+        LiteralValue::Char(_) => TypeExpr::named("u32"),
+        LiteralValue::Bool(_) => TypeExpr::named("bool"),
+    }
+}
+
+/// Bind the generic `params` used by `param_ty` to the part of `arg_ty` in the same place. Those
+/// already bound are kept, and any disagreement is left for checking the arguments to report.
+fn bind_type_args(
+    params: &GenericParams,
+    param_ty: &TypeExpr,
+    arg_ty: &TypeExpr,
+    bindings: &mut [Option<TypeExpr>],
+) {
+    let mut bind_all = |param_types: &[TypeExpr], arg_types: &[TypeExpr]| {
+        if param_types.len() == arg_types.len() {
+            for (param_ty, arg_ty) in param_types.iter().zip(arg_types) {
+                bind_type_args(params, param_ty, arg_ty, bindings);
+            }
+        }
+    };
+
+    match (param_ty, arg_ty) {
+        // `!` can be passed for any type, so it says nothing about which
+        (_, TypeExpr::Never) => {}
+        (TypeExpr::Named { name, generics }, _) if generics.is_empty() => {
+            if let Some(idx) = params.iter().position(|param| param.name == *name) {
+                bindings[idx].get_or_insert_with(|| arg_ty.clone());
+            }
+        }
+        (
+            TypeExpr::Named { name, generics },
+            TypeExpr::Named {
+                name: arg_name,
+                generics: arg_generics,
+            },
+        ) if name == arg_name => bind_all(generics, arg_generics),
+        (
+            TypeExpr::Pointer { inner, .. },
+            TypeExpr::Pointer {
+                inner: arg_inner, ..
+            },
+        ) => {
+            bind_type_args(params, inner, arg_inner, bindings);
+        }
+        (
+            TypeExpr::Array { element, .. },
+            TypeExpr::Array {
+                element: arg_element,
+                ..
+            },
+        ) => bind_type_args(params, element, arg_element, bindings),
+        (
+            TypeExpr::FnType {
+                params: fn_params,
+                ret,
+            },
+            TypeExpr::FnType {
+                params: arg_params,
+                ret: arg_ret,
+            },
+        ) => {
+            bind_all(fn_params, arg_params);
+            bind_type_args(params, ret, arg_ret, bindings);
+        }
+        (TypeExpr::Tuple(types), TypeExpr::Tuple(arg_types)) => bind_all(types, arg_types),
+        _ => {}
+    }
+}
+
+/// The type arguments bound so far, with each parameter that isn't bound standing for itself.
+fn bound_or_unbound(params: &GenericParams, bindings: &[Option<TypeExpr>]) -> Vec<TypeExpr> {
+    params
+        .iter()
+        .zip(bindings)
+        .map(|(param, binding)| {
+            binding
+                .clone()
+                .unwrap_or_else(|| TypeExpr::named(&param.name))
+        })
+        .collect()
+}
+
+fn binary(op: BinOpKind, lhs: TypedExpr, rhs: TypedExpr) -> TypedExprKind {
+    TypedExprKind::BinOp {
+        op,
+        lhs: Box::new(lhs),
+        rhs: Box::new(rhs),
+    }
+}
+
+/// Expressions that never finish, like a block ending in `return`, fit any type.
+fn expect_type(expected: &TypeExpr, found: &TypedExpr) -> Result<(), TypeError> {
+    if found.ty == *expected || found.ty == TypeExpr::Never {
+        Ok(())
+    } else {
+        Err(TypeError::TypeMismatch {
+            expected: expected.clone(),
+            found: found.ty.clone(),
+            span: found.span.into(),
+        })
+    }
+}
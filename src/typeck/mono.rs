@@ -0,0 +1,335 @@
+use std::collections::{HashMap, VecDeque};
+use std::mem;
+use std::path::PathBuf;
+
+use crate::ast::mutator::AstMutator;
+use crate::ast::{FnDecl, GenericParams, StructDecl, TypeExpr};
+use crate::codegen::{GlobalVar, MangleScheme};
+use crate::span::Span;
+
+use super::error::TypeError;
+use super::symbols::{SymbolInfo, SymbolKind, SymbolTable};
+use super::typed::{TypedBlock, TypedExpr, TypedExprKind, TypedFn, TypedModule, TypedStmt};
+use super::Checker;
+
+/// A [`TypedModule`] after [`monomorphize`], where each generic function has been replaced by an
+/// instance for every set of type arguments it's called with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonoModule {
+    pub path: PathBuf,
+    pub structs: Vec<StructDecl>,
+    pub globals: Vec<GlobalVar>,
+    /// Every function, none of them generic, with the instances after the functions that were
+    /// declared.
+    pub functions: Vec<TypedFn>,
+}
+
+impl From<MonoModule> for TypedModule {
+    fn from(module: MonoModule) -> Self {
+        TypedModule {
+            path: module.path,
+            structs: module.structs,
+            globals: module.globals,
+            functions: module.functions,
+            generic_fns: vec![],
+        }
+    }
+}
+
+/// Instantiates the generic functions of a module with the type arguments inferred at each call
+/// to them, starting from the functions that aren't generic.
+///
+/// Each instance is named by the [`MangleScheme`], and type checked as the function it becomes,
+/// which is where any mistake in a generic function's body is found.
+#[derive(Debug, Default)]
+pub struct Monomorphizer {
+    /// The instances of the last module monomorphized, by the name of the generic function and the
+    /// type arguments it was given.
+    instances: HashMap<(String, Vec<TypeExpr>), FnDecl>,
+    scheme: MangleScheme,
+}
+
+/// A generic function and the type arguments to instantiate it with.
+type InstanceKey = (String, Vec<TypeExpr>);
+
+impl Monomorphizer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_mangle_scheme(scheme: MangleScheme) -> Self {
+        Monomorphizer {
+            instances: HashMap::new(),
+            scheme,
+        }
+    }
+
+    pub fn instances(&self) -> &HashMap<(String, Vec<TypeExpr>), FnDecl> {
+        &self.instances
+    }
+
+    pub fn monomorphize(&mut self, module: &TypedModule) -> Result<MonoModule, Vec<TypeError>> {
+        self.instances.clear();
+
+        let generic_fns: HashMap<&str, &FnDecl> = module
+            .generic_fns
+            .iter()
+            .map(|decl| (decl.name.as_str(), decl))
+            .collect();
+        let mut checker =
+            Checker::new(module_symbols(module), &module.structs, &module.generic_fns);
+
+        let mut functions = module.functions.clone();
+        let mut pending = VecDeque::new();
+
+        for function in &mut functions {
+            self.name_instances(&mut function.body, &generic_fns, &mut pending);
+        }
+
+        // Instances can call generic functions themselves, so they're checked as they're made
+        while let Some(key) = pending.pop_front() {
+            if self.instances.contains_key(&key) {
+                continue;
+            }
+
+            let instance = instantiate(generic_fns[key.0.as_str()], &key.1, &self.scheme);
+            let mut function = checker.check_fn(&instance);
+            self.name_instances(&mut function.body, &generic_fns, &mut pending);
+
+            self.instances.insert(key, instance);
+            functions.push(function);
+        }
+
+        if checker.errors.is_empty() {
+            Ok(MonoModule {
+                path: module.path.clone(),
+                structs: module.structs.clone(),
+                globals: module.globals.clone(),
+                functions,
+            })
+        } else {
+            Err(checker.errors)
+        }
+    }
+
+    /// Point each generic call in `body` at the instance it needs, queueing it in `pending`.
+    fn name_instances(
+        &self,
+        body: &mut TypedBlock,
+        generic_fns: &HashMap<&str, &FnDecl>,
+        pending: &mut VecDeque<InstanceKey>,
+    ) {
+        for_each_call_in_block(body, &mut |callee, type_args| {
+            if type_args.is_empty() {
+                return;
+            }
+
+            let type_args = mem::take(type_args);
+            let name = self.scheme.mangle(generic_fns[callee.as_str()], &type_args);
+
+            pending.push_back((mem::replace(callee, name), type_args));
+        });
+    }
+}
+
+/// Replace the generic functions of `module` with the instances its calls need.
+pub fn monomorphize(module: &TypedModule) -> Result<MonoModule, Vec<TypeError>> {
+    Monomorphizer::new().monomorphize(module)
+}
+
+/// The module scope the bodies of `module` were checked in, as far as it can be known from what
+/// type checking kept.
+fn module_symbols(module: &TypedModule) -> SymbolTable {
+    let mut syms = SymbolTable::new();
+
+    let functions = module.functions.iter().map(|function| {
+        let params = function.params.iter().map(|param| param.ty.clone());
+        (
+            &function.name,
+            params.collect(),
+            function.ret.clone(),
+            function.span,
+        )
+    });
+    let generic_fns = module.generic_fns.iter().map(|decl| {
+        let params = decl.params.iter().map(|param| param.ty.clone());
+        let ret = decl.ret.clone().unwrap_or_else(TypeExpr::void);
+        (&decl.name, params.collect(), ret, decl.span)
+    });
+
+    for (name, params, ret, def_span) in functions.chain(generic_fns) {
+        syms.redefine(
+            name,
+            SymbolInfo {
+                kind: SymbolKind::Function,
+                ty: TypeExpr::FnType {
+                    params,
+                    ret: Box::new(ret),
+                },
+                def_span,
+            },
+        );
+    }
+    for decl in &module.structs {
+        syms.redefine(
+            &decl.name,
+            SymbolInfo {
+                kind: SymbolKind::Struct,
+                ty: TypeExpr::named(&decl.name),
+                def_span: decl.span,
+            },
+        );
+    }
+    for global in &module.globals {
+        syms.redefine(
+            &global.name,
+            SymbolInfo {
+                kind: SymbolKind::Variable {
+                    mutable: global.mutable,
+                },
+                ty: global.ty.clone(),
+                def_span: Span::default(),
+            },
+        );
+    }
+
+    syms
+}
+
+/// The generic function `decl` with `type_args` put in place of its type parameters.
+fn instantiate(decl: &FnDecl, type_args: &[TypeExpr], scheme: &MangleScheme) -> FnDecl {
+    let mut instance = decl.clone();
+    instance.name = scheme.mangle(decl, type_args);
+
+    let params = mem::take(&mut instance.generic_params);
+    SubstituteTypes {
+        params: &params,
+        args: type_args,
+    }
+    .visit_fn_decl_mut(&mut instance);
+
+    instance
+}
+
+struct SubstituteTypes<'a> {
+    params: &'a GenericParams,
+    args: &'a [TypeExpr],
+}
+
+impl AstMutator for SubstituteTypes<'_> {
+    fn visit_type_mut(&mut self, n: &mut TypeExpr) {
+        *n = n.substitute(self.params, self.args);
+    }
+}
+
+type CallFn<'f> = dyn FnMut(&mut String, &mut Vec<TypeExpr>) + 'f;
+
+/// Call `f` with the callee and type arguments of every call in `block`.
+fn for_each_call_in_block(block: &mut TypedBlock, f: &mut CallFn) {
+    for stmt in &mut block.stmts {
+        for_each_call_in_stmt(stmt, f);
+    }
+    if let Some(expr) = &mut block.final_expr {
+        for_each_call(expr, f);
+    }
+}
+
+fn for_each_call_in_stmt(stmt: &mut TypedStmt, f: &mut CallFn) {
+    match stmt {
+        TypedStmt::Expr(expr) => for_each_call(expr, f),
+        TypedStmt::Let { init, .. } => {
+            if let Some(init) = init {
+                for_each_call(init, f);
+            }
+        }
+        TypedStmt::Assign { target, value, .. } => {
+            for_each_call(target, f);
+            for_each_call(value, f);
+        }
+        TypedStmt::While { cond, body, .. } => {
+            for_each_call(cond, f);
+            for_each_call_in_block(body, f);
+        }
+        TypedStmt::For {
+            init,
+            cond,
+            post,
+            body,
+            ..
+        } => {
+            if let Some(init) = init {
+                for_each_call_in_stmt(init, f);
+            }
+            if let Some(cond) = cond {
+                for_each_call(cond, f);
+            }
+            if let Some(post) = post {
+                for_each_call_in_stmt(post, f);
+            }
+            for_each_call_in_block(body, f);
+        }
+        TypedStmt::ForIn { iterable, body, .. } => {
+            for_each_call(iterable, f);
+            for_each_call_in_block(body, f);
+        }
+        TypedStmt::Return { value, .. } | TypedStmt::Break { value, .. } => {
+            if let Some(value) = value {
+                for_each_call(value, f);
+            }
+        }
+        TypedStmt::Continue { .. } => {}
+    }
+}
+
+fn for_each_call(expr: &mut TypedExpr, f: &mut CallFn) {
+    match &mut expr.kind {
+        TypedExprKind::Literal(_) | TypedExprKind::Ident(_) => {}
+        TypedExprKind::BinOp { lhs, rhs, .. } => {
+            for_each_call(lhs, f);
+            for_each_call(rhs, f);
+        }
+        TypedExprKind::UnaryOp { operand, .. } => for_each_call(operand, f),
+        TypedExprKind::Call {
+            callee,
+            args,
+            type_args,
+        } => {
+            for arg in args {
+                for_each_call(arg, f);
+            }
+            f(callee, type_args);
+        }
+        TypedExprKind::Builtin { args, .. } => {
+            for arg in args {
+                for_each_call(arg, f);
+            }
+        }
+        TypedExprKind::Index { base, index } => {
+            for_each_call(base, f);
+            for_each_call(index, f);
+        }
+        TypedExprKind::Field { base, .. } => for_each_call(base, f),
+        TypedExprKind::If {
+            cond,
+            then_block,
+            else_block,
+        } => {
+            for_each_call(cond, f);
+            for_each_call_in_block(then_block, f);
+            if let Some(else_block) = else_block {
+                for_each_call(else_block, f);
+            }
+        }
+        TypedExprKind::Match { scrutinee, arms } => {
+            for_each_call(scrutinee, f);
+            for arm in arms {
+                if let Some(guard) = &mut arm.guard {
+                    for_each_call(guard, f);
+                }
+                for_each_call(&mut arm.body, f);
+            }
+        }
+        TypedExprKind::Closure { body, .. } => for_each_call(body, f),
+        TypedExprKind::Block(block) => for_each_call_in_block(block, f),
+    }
+}
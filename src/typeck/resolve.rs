@@ -0,0 +1,344 @@
+use crate::ast::{
+    Block, ClosureBody, ExprNode, FnDecl, IfExpr, IfOrBlock, ImplItem, Item, Module, Pattern,
+    StmtNode, TraitItem, TypeExpr,
+};
+use crate::span::Span;
+
+use super::declare_items;
+use super::error::NameError;
+use super::symbols::{SymbolInfo, SymbolKind, SymbolTable};
+
+/// Declare the items in `module`, then resolve every identifier in it to the definition it refers
+/// to, recording each in `syms`. The labels of `break` and `continue` are checked here too.
+///
+/// Every `let` starts a new scope, so an identifier keeps referring to the variable it saw even
+/// once a later `let` shadows it.
+pub fn resolve_names(module: &Module, syms: &mut SymbolTable) -> Result<(), Vec<NameError>> {
+    let errors = declare_items(module, syms).err().unwrap_or_default();
+
+    let mut resolver = Resolver {
+        syms,
+        labels: vec![],
+        errors,
+    };
+
+    for item in &module.items {
+        match item {
+            Item::FnDecl(decl) => resolver.resolve_fn(decl),
+            Item::TraitDecl(decl) => {
+                for item in &decl.items {
+                    if let TraitItem::DefaultFn(decl) = item {
+                        resolver.resolve_fn(decl);
+                    }
+                }
+            }
+            Item::ImplBlock(block) => {
+                for ImplItem::Fn(decl) in &block.items {
+                    resolver.resolve_fn(decl);
+                }
+            }
+            Item::GlobalDecl(decl) => {
+                if let Some(init) = &decl.init {
+                    resolver.resolve_expr(init);
+                }
+            }
+            Item::StructDecl(_) | Item::EnumDecl(_) | Item::UseDecl(_) | Item::TypeAlias(_) => {}
+        }
+    }
+
+    if resolver.errors.is_empty() {
+        Ok(())
+    } else {
+        Err(resolver.errors)
+    }
+}
+
+struct Resolver<'s> {
+    syms: &'s mut SymbolTable,
+    /// The loops and labelled blocks around the current point, innermost last. Loops are `true`.
+    labels: Vec<(Option<String>, bool)>,
+    errors: Vec<NameError>,
+}
+
+impl Resolver<'_> {
+    fn resolve_fn(&mut self, decl: &FnDecl) {
+        self.syms.enter_scope();
+
+        for param in &decl.params {
+            self.define(
+                &param.name,
+                SymbolKind::Parameter,
+                param.ty.clone(),
+                param.span,
+            );
+        }
+        self.resolve_block(&decl.body);
+
+        self.syms.exit_scope();
+    }
+
+    fn resolve_block(&mut self, block: &Block) {
+        if block.label.is_some() {
+            self.labels.push((block.label.clone(), false));
+        }
+
+        self.scoped(|resolver| {
+            for stmt in &block.stmts {
+                resolver.resolve_stmt(stmt);
+            }
+
+            if let Some(expr) = &block.final_expr {
+                resolver.resolve_expr(expr);
+            }
+        });
+
+        if block.label.is_some() {
+            self.labels.pop();
+        }
+    }
+
+    fn resolve_stmt(&mut self, stmt: &StmtNode) {
+        match stmt {
+            StmtNode::Expr(expr) => self.resolve_expr(expr),
+            StmtNode::Let(stmt) => {
+                // The initialiser can't see the variable it initialises
+                if let Some(init) = &stmt.init {
+                    self.resolve_expr(init);
+                }
+
+                // Left to the enclosing block to exit
+                self.syms.enter_scope();
+                self.define(
+                    &stmt.name,
+                    SymbolKind::Variable {
+                        mutable: stmt.mutable,
+                    },
+                    stmt.ty.clone().unwrap_or_else(unknown_type),
+                    stmt.span,
+                );
+            }
+            StmtNode::Assign(stmt) => {
+                self.resolve_expr(&stmt.target);
+                self.resolve_expr(&stmt.value);
+            }
+            StmtNode::While(stmt) => {
+                self.resolve_expr(&stmt.cond);
+                self.resolve_loop_body(stmt.label.as_ref(), &stmt.body);
+            }
+            StmtNode::For(stmt) => self.scoped(|resolver| {
+                if let Some(init) = &stmt.init {
+                    resolver.resolve_stmt(init);
+                }
+                if let Some(cond) = &stmt.cond {
+                    resolver.resolve_expr(cond);
+                }
+                if let Some(post) = &stmt.post {
+                    resolver.resolve_stmt(post);
+                }
+
+                resolver.resolve_loop_body(stmt.label.as_ref(), &stmt.body);
+            }),
+            StmtNode::ForIn(stmt) => {
+                self.resolve_expr(&stmt.iterable);
+
+                self.scoped(|resolver| {
+                    resolver.define(
+                        &stmt.binding,
+                        SymbolKind::Variable {
+                            mutable: stmt.mutable,
+                        },
+                        unknown_type(),
+                        stmt.span,
+                    );
+                    resolver.resolve_loop_body(stmt.label.as_ref(), &stmt.body);
+                });
+            }
+            StmtNode::Return(stmt) => {
+                if let Some(value) = &stmt.value {
+                    self.resolve_expr(value);
+                }
+            }
+            StmtNode::Break(stmt) => {
+                self.check_label(stmt.label.as_deref(), "break", stmt.span);
+
+                if let Some(value) = &stmt.value {
+                    self.resolve_expr(value);
+                }
+            }
+            StmtNode::Continue(stmt) => {
+                self.check_label(stmt.label.as_deref(), "continue", stmt.span);
+            }
+        }
+    }
+
+    fn resolve_loop_body(&mut self, label: Option<&String>, body: &Block) {
+        self.labels.push((label.cloned(), true));
+        self.resolve_block(body);
+        self.labels.pop();
+    }
+
+    fn resolve_expr(&mut self, expr: &ExprNode) {
+        match expr {
+            ExprNode::Literal { .. } => {}
+            ExprNode::Ident { name, span } => match self.syms.lookup_id(name) {
+                Some(id) => self.syms.record_resolution(*span, id),
+                None => self.errors.push(NameError::Undefined {
+                    name: name.clone(),
+                    span: (*span).into(),
+                }),
+            },
+            ExprNode::BinOp { lhs, rhs, .. } => {
+                self.resolve_expr(lhs);
+                self.resolve_expr(rhs);
+            }
+            ExprNode::UnaryOp { operand, .. } => self.resolve_expr(operand),
+            ExprNode::Call { callee, args, .. } => {
+                self.resolve_expr(callee);
+
+                for arg in args {
+                    self.resolve_expr(arg);
+                }
+            }
+            ExprNode::Builtin { args, .. } => {
+                for arg in args {
+                    self.resolve_expr(arg);
+                }
+            }
+            ExprNode::Index { base, index, .. } => {
+                self.resolve_expr(base);
+                self.resolve_expr(index);
+            }
+            // Field names are looked up in the type of `base` during type checking
+            ExprNode::Field { base, .. } => self.resolve_expr(base),
+            ExprNode::If(if_expr) => self.resolve_if(if_expr),
+            ExprNode::Match(match_expr) => {
+                self.resolve_expr(&match_expr.scrutinee);
+
+                for arm in &match_expr.arms {
+                    self.scoped(|resolver| {
+                        resolver.bind_pattern(&arm.pattern, arm.span);
+
+                        if let Some(guard) = &arm.guard {
+                            resolver.resolve_expr(guard);
+                        }
+                        resolver.resolve_expr(&arm.body);
+                    });
+                }
+            }
+            ExprNode::Closure(closure) => {
+                // Loops around the closure can't be broken out of from inside it
+                let labels = std::mem::take(&mut self.labels);
+
+                self.scoped(|resolver| {
+                    for param in &closure.params {
+                        let ty = param.ty.clone().unwrap_or_else(unknown_type);
+                        resolver.define(&param.name, SymbolKind::Parameter, ty, param.span);
+                    }
+
+                    match &closure.body {
+                        ClosureBody::Expr(expr) => resolver.resolve_expr(expr),
+                        ClosureBody::Block(block) => resolver.resolve_block(block),
+                    }
+                });
+
+                self.labels = labels;
+            }
+            ExprNode::Block(block) => self.resolve_block(block),
+        }
+    }
+
+    fn resolve_if(&mut self, if_expr: &IfExpr) {
+        self.resolve_expr(&if_expr.cond);
+        self.resolve_block(&if_expr.then_block);
+
+        match if_expr.else_block.as_deref() {
+            Some(IfOrBlock::If(else_if)) => self.resolve_if(else_if),
+            Some(IfOrBlock::Block(block)) => self.resolve_block(block),
+            None => {}
+        }
+    }
+
+    fn bind_pattern(&mut self, pattern: &Pattern, span: Span) {
+        match pattern {
+            Pattern::Literal(_) | Pattern::Wildcard => {}
+            // Both sides of an `|` bind the same names
+            Pattern::Ident(name) => self.syms.redefine(
+                name,
+                SymbolInfo {
+                    kind: SymbolKind::Variable { mutable: false },
+                    ty: unknown_type(),
+                    def_span: span,
+                },
+            ),
+            Pattern::Or(lhs, rhs) => {
+                self.bind_pattern(lhs, span);
+                self.bind_pattern(rhs, span);
+            }
+        }
+    }
+
+    /// Make sure a `break` or `continue` with `label` has a loop or block to jump to.
+    fn check_label(&mut self, label: Option<&str>, keyword: &'static str, span: Span) {
+        let is_continue = keyword == "continue";
+        // An unlabelled `break` only leaves loops, and blocks can never be continued
+        let mut targets = self
+            .labels
+            .iter()
+            .filter(|(_, is_loop)| *is_loop || (label.is_some() && !is_continue));
+
+        let found = match label {
+            Some(label) => targets.any(|(name, _)| name.as_deref() == Some(label)),
+            None => targets.next().is_some(),
+        };
+
+        if found {
+            return;
+        }
+
+        let names_block = label.is_some_and(|label| {
+            self.labels
+                .iter()
+                .any(|(name, _)| name.as_deref() == Some(label))
+        });
+
+        self.errors.push(match label {
+            Some(label) if !names_block => NameError::UndefinedLabel {
+                name: label.to_owned(),
+                span: span.into(),
+            },
+            _ => NameError::OutsideLoop {
+                keyword,
+                span: span.into(),
+            },
+        });
+    }
+
+    fn define(&mut self, name: &str, kind: SymbolKind, ty: TypeExpr, span: Span) {
+        let info = SymbolInfo {
+            kind,
+            ty,
+            def_span: span,
+        };
+
+        if let Err(err) = self.syms.define(name, info) {
+            self.errors.push(err);
+        }
+    }
+
+    /// Run `resolve` in a new scope, exiting it and any scopes started by `let`s inside it after.
+    fn scoped(&mut self, resolve: impl FnOnce(&mut Self)) {
+        let outer = self.syms.current_scope();
+
+        self.syms.enter_scope();
+        resolve(self);
+
+        while self.syms.current_scope() != outer {
+            self.syms.exit_scope();
+        }
+    }
+}
+
+/// Variables without a type annotation only get their type during type checking.
+fn unknown_type() -> TypeExpr {
+    TypeExpr::named("_")
+}
@@ -0,0 +1,161 @@
+use std::collections::HashMap;
+
+use crate::ast::TypeExpr;
+use crate::span::Span;
+
+use super::error::NameError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolKind {
+    Variable { mutable: bool },
+    Parameter,
+    Function,
+    Struct,
+    Enum,
+    Trait,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolInfo {
+    pub kind: SymbolKind,
+    pub ty: TypeExpr,
+    pub def_span: Span,
+}
+
+/// Where a name was defined, as found by [`resolve_names`](super::resolve_names).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NameId {
+    pub scope: usize,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Scope {
+    symbols: HashMap<String, SymbolInfo>,
+    parent: Option<usize>,
+}
+
+impl Scope {
+    pub fn get(&self, name: &str) -> Option<&SymbolInfo> {
+        self.symbols.get(name)
+    }
+
+    pub fn parent(&self) -> Option<usize> {
+        self.parent
+    }
+}
+
+/// Every scope seen so far, kept after they are exited so later passes can look back into them.
+#[derive(Debug, Clone)]
+pub struct SymbolTable {
+    scopes: Vec<Scope>,
+    current: usize,
+    /// The definition each identifier refers to, by the span of the identifier.
+    resolutions: HashMap<Span, NameId>,
+}
+
+impl SymbolTable {
+    /// A table holding only the outermost, module level scope.
+    pub fn new() -> Self {
+        SymbolTable {
+            scopes: vec![Scope::default()],
+            current: 0,
+            resolutions: HashMap::new(),
+        }
+    }
+
+    /// Start a scope nested in the current one, returning its index.
+    pub fn enter_scope(&mut self) -> usize {
+        self.scopes.push(Scope {
+            symbols: HashMap::new(),
+            parent: Some(self.current),
+        });
+        self.current = self.scopes.len() - 1;
+
+        self.current
+    }
+
+    /// Go back to the parent of the current scope. The module scope is never exited.
+    pub fn exit_scope(&mut self) {
+        if let Some(parent) = self.scopes[self.current].parent {
+            self.current = parent;
+        }
+    }
+
+    pub fn current_scope(&self) -> usize {
+        self.current
+    }
+
+    pub fn scope(&self, idx: usize) -> Option<&Scope> {
+        self.scopes.get(idx)
+    }
+
+    /// Define `name` in the current scope. Shadowing a name from an outer scope is allowed, but
+    /// defining it twice in the same scope isn't.
+    pub fn define(&mut self, name: impl Into<String>, info: SymbolInfo) -> Result<(), NameError> {
+        let name = name.into();
+        let symbols = &mut self.scopes[self.current].symbols;
+
+        if let Some(first) = symbols.get(&name) {
+            return Err(NameError::AlreadyDefined {
+                first: first.def_span.into(),
+                second: info.def_span.into(),
+                name,
+            });
+        }
+
+        symbols.insert(name, info);
+
+        Ok(())
+    }
+
+    /// Define `name` in the current scope, replacing any earlier definition there, as `let` does.
+    pub fn redefine(&mut self, name: impl Into<String>, info: SymbolInfo) {
+        self.scopes[self.current].symbols.insert(name.into(), info);
+    }
+
+    /// Find the innermost definition of `name` visible from the current scope.
+    pub fn lookup(&self, name: &str) -> Option<&SymbolInfo> {
+        self.lookup_id(name).and_then(|id| self.get(&id))
+    }
+
+    /// Find which scope the innermost definition of `name` visible from the current scope is in.
+    pub fn lookup_id(&self, name: &str) -> Option<NameId> {
+        let mut scope = Some(self.current);
+
+        while let Some(idx) = scope {
+            let current = &self.scopes[idx];
+
+            if current.symbols.contains_key(name) {
+                return Some(NameId {
+                    scope: idx,
+                    name: name.to_owned(),
+                });
+            }
+
+            scope = current.parent;
+        }
+
+        None
+    }
+
+    pub fn get(&self, id: &NameId) -> Option<&SymbolInfo> {
+        self.scopes.get(id.scope)?.get(&id.name)
+    }
+
+    /// Remember that the identifier at `span` refers to `id`.
+    pub fn record_resolution(&mut self, span: Span, id: NameId) {
+        self.resolutions.insert(span, id);
+    }
+
+    /// The definition the identifier at `span` was resolved to.
+    pub fn resolution(&self, span: Span) -> Option<&NameId> {
+        self.resolutions.get(&span)
+    }
+}
+
+impl Default for SymbolTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
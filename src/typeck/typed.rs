@@ -0,0 +1,162 @@
+use std::path::PathBuf;
+
+use crate::ast::{
+    BinOpKind, CaptureMode, FnDecl, LiteralValue, Param, Pattern, StructDecl, TypeExpr, UnOpKind,
+};
+use crate::builtins::BuiltinFn;
+use crate::codegen::GlobalVar;
+use crate::span::Span;
+
+/// A [`Module`](crate::ast::Module) after type checking, where every expression knows its type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypedModule {
+    pub path: PathBuf,
+    pub structs: Vec<StructDecl>,
+    pub globals: Vec<GlobalVar>,
+    pub functions: Vec<TypedFn>,
+    /// Left unchecked until [`monomorphize`](super::monomorphize) instantiates them.
+    pub generic_fns: Vec<FnDecl>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypedFn {
+    /// Set for an `extern` function, which only has a declaration.
+    pub abi: Option<String>,
+    pub name: String,
+    pub params: Vec<Param>,
+    pub ret: TypeExpr,
+    pub body: TypedBlock,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypedBlock {
+    pub label: Option<String>,
+    pub stmts: Vec<TypedStmt>,
+    pub final_expr: Option<Box<TypedExpr>>,
+    /// The type of `final_expr`, `!` when the block ends by jumping away, or `void` otherwise.
+    pub ty: TypeExpr,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypedStmt {
+    Expr(TypedExpr),
+    Let {
+        name: String,
+        ty: TypeExpr,
+        init: Option<TypedExpr>,
+        mutable: bool,
+        span: Span,
+    },
+    Assign {
+        target: TypedExpr,
+        op: Option<BinOpKind>,
+        value: TypedExpr,
+        span: Span,
+    },
+    While {
+        label: Option<String>,
+        cond: TypedExpr,
+        body: TypedBlock,
+        span: Span,
+    },
+    For {
+        label: Option<String>,
+        init: Option<Box<TypedStmt>>,
+        cond: Option<TypedExpr>,
+        post: Option<Box<TypedStmt>>,
+        body: TypedBlock,
+        span: Span,
+    },
+    /// `binding` has the element type of `iterable`.
+    ForIn {
+        label: Option<String>,
+        binding: String,
+        mutable: bool,
+        iterable: TypedExpr,
+        body: TypedBlock,
+        span: Span,
+    },
+    Return {
+        value: Option<TypedExpr>,
+        span: Span,
+    },
+    Break {
+        label: Option<String>,
+        value: Option<TypedExpr>,
+        span: Span,
+    },
+    Continue {
+        label: Option<String>,
+        span: Span,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypedExpr {
+    pub kind: TypedExprKind,
+    pub ty: TypeExpr,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypedExprKind {
+    Literal(LiteralValue),
+    Ident(String),
+    BinOp {
+        op: BinOpKind,
+        lhs: Box<TypedExpr>,
+        rhs: Box<TypedExpr>,
+    },
+    UnaryOp {
+        op: UnOpKind,
+        operand: Box<TypedExpr>,
+    },
+    /// Calls are always to a named function. `a |> f` is checked as `f(a)`.
+    Call {
+        callee: String,
+        args: Vec<TypedExpr>,
+        /// The type arguments inferred for a generic callee, until it's monomorphized.
+        type_args: Vec<TypeExpr>,
+    },
+    Builtin {
+        builtin: BuiltinFn,
+        args: Vec<TypedExpr>,
+    },
+    Index {
+        base: Box<TypedExpr>,
+        index: Box<TypedExpr>,
+    },
+    /// `index` is the position of the field in its struct or tuple.
+    Field {
+        base: Box<TypedExpr>,
+        name: String,
+        index: usize,
+    },
+    /// The else branch is another `If` or a `Block`.
+    If {
+        cond: Box<TypedExpr>,
+        then_block: TypedBlock,
+        else_block: Option<Box<TypedExpr>>,
+    },
+    Match {
+        scrutinee: Box<TypedExpr>,
+        arms: Vec<TypedMatchArm>,
+    },
+    /// Every parameter has a type by now, and a block body has become a `Block` expression.
+    Closure {
+        params: Vec<Param>,
+        capture_mode: CaptureMode,
+        body: Box<TypedExpr>,
+    },
+    Block(TypedBlock),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypedMatchArm {
+    pub pattern: Pattern,
+    pub guard: Option<TypedExpr>,
+    pub body: TypedExpr,
+    pub span: Span,
+}
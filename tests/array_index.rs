@@ -0,0 +1,42 @@
+//! Checks that array elements are reached with `getelementptr`, with optional bounds checks.
+
+use llvm_compiler::codegen::emitter::LlvmEmitter;
+use llvm_compiler::compiler::check_source;
+use llvm_compiler::source::SourceFile;
+
+const NTH: &str = "func nth(xs: [i32; 3], i: i32) -> i32 { xs[i] }\n";
+
+fn compile(bounds_checking: bool) -> String {
+    let module = check_source(&SourceFile::new("nth.src", NTH)).unwrap();
+
+    let mut emitter = LlvmEmitter::new().with_bounds_checking(bounds_checking);
+    emitter.emit_module(&module).unwrap();
+    emitter.finish()
+}
+
+#[test]
+fn indices_are_widened_to_i64() {
+    let ir = compile(false);
+
+    assert!(
+        ir.contains(
+            "%tmp1 = sext i32 %tmp0 to i64
+  %tmp2 = getelementptr inbounds [3 x i32], [3 x i32]* %xs.addr, i64 0, i64 %tmp1
+"
+        ),
+        "{ir}"
+    );
+    assert!(!ir.contains("panic_out_of_bounds"), "{ir}");
+}
+
+#[test]
+fn bounds_checks_panic_before_the_access() {
+    let ir = compile(true);
+
+    assert!(ir.contains("%tmp2 = icmp ult i64 %tmp1, 3\n"), "{ir}");
+    assert!(
+        ir.contains("call void @panic_out_of_bounds()\n  unreachable\n"),
+        "{ir}"
+    );
+    assert!(ir.contains("declare void @panic_out_of_bounds()"), "{ir}");
+}
@@ -0,0 +1,53 @@
+//! Checks the S-expressions printed for an AST.
+
+use llvm_compiler::ast::{ExprNode, Item, StmtNode};
+use llvm_compiler::ast_printer::{print_sexp, AstNode};
+use llvm_compiler::compiler::parse_source;
+use llvm_compiler::source::SourceFile;
+
+#[test]
+fn functions_print_as_sexps() {
+    let source = SourceFile::new(
+        "ast_printer.src",
+        "func add(a: i32, b: i32) -> i32 { return a + b; }
+func main() { let mut x: *u8 = \"a\\\"b\"; 'outer: while x < 2.0 { x += 1; break 'outer; } f(x) }
+",
+    );
+    let module = parse_source(&source).unwrap();
+
+    assert_eq!(
+        print_sexp(&module.items[0]),
+        "(fn add (params (a i32) (b i32)) (ret i32) (return (+ (ident a) (ident b))))"
+    );
+    assert_eq!(
+        print_sexp(&module.items[1]),
+        "(fn main (params) (let (mut x) (type (ptr u8)) \"a\\\"b\") \
+         (while (label outer) (< (ident x) 2.0) (+= (ident x) 1) (break (label outer))) \
+         (call (ident f) (ident x)))"
+    );
+}
+
+#[test]
+fn nodes_give_the_source_they_cover() {
+    let text = "func f() { let x = a + b; }\nfunc g() {}\n";
+    let source = SourceFile::new("span.src", text);
+    let module = parse_source(&source).unwrap();
+    let slice = |node: &dyn AstNode| &text[node.span().start..node.span().end];
+
+    assert_eq!(slice(&module), "func f() { let x = a + b; }\nfunc g() {}");
+    assert_eq!(slice(&module.items[1]), "func g() {}");
+
+    let Item::FnDecl(decl) = &module.items[0] else {
+        panic!("expected a function");
+    };
+    let StmtNode::Let(stmt) = &decl.body.stmts[0] else {
+        panic!("expected a let");
+    };
+    let init = Box::new(stmt.init.clone().unwrap());
+    assert_eq!(slice(&init), "a + b");
+
+    let ExprNode::BinOp { rhs, .. } = &*init else {
+        panic!("expected a binary operation");
+    };
+    assert_eq!(slice(rhs), "b");
+}
@@ -0,0 +1,61 @@
+//! Checks how `let` bindings and assignments are parsed.
+
+use llvm_compiler::ast::{BinOpKind, StmtNode};
+use llvm_compiler::ast_printer::print_sexp;
+use llvm_compiler::lexer::AsciiLexer;
+use llvm_compiler::parse::error::ParseError;
+use llvm_compiler::parse::parser::Parser;
+
+fn parse_stmts(src: &str) -> Result<Vec<StmtNode>, ParseError> {
+    let mut tokens = AsciiLexer::new().tokenize(src).unwrap();
+    tokens.retain(|token| !token.is_trivia());
+
+    Parser::new(0, &tokens)
+        .parse_block()
+        .map(|block| block.stmts)
+}
+
+#[test]
+fn lets_may_leave_out_the_type_and_initialiser() {
+    let stmts = parse_stmts("{ let x; let mut y: i32; let z = 1; }").unwrap();
+
+    let [StmtNode::Let(x), StmtNode::Let(y), StmtNode::Let(z)] = &stmts[..] else {
+        panic!("expected three lets, got {stmts:?}");
+    };
+    assert_eq!(x.name, "x");
+    assert!(!x.mutable && x.ty.is_none() && x.init.is_none());
+    assert!(y.mutable && y.ty.is_some() && y.init.is_none());
+    assert_eq!(
+        z.init.as_ref().map(|init| print_sexp(init)),
+        Some("1".to_owned())
+    );
+}
+
+#[test]
+fn lets_need_a_semicolon() {
+    assert!(matches!(
+        parse_stmts("{ let x = 1 }"),
+        Err(ParseError::SequenceMismatch { .. })
+    ));
+}
+
+#[test]
+fn fields_and_indices_can_be_assigned() {
+    let stmts = parse_stmts("{ a.b = 1; xs[0] += 2; }").unwrap();
+
+    let [StmtNode::Assign(field), StmtNode::Assign(index)] = &stmts[..] else {
+        panic!("expected two assignments, got {stmts:?}");
+    };
+    assert_eq!(print_sexp(&field.target), "(field (ident a) b)");
+    assert_eq!(field.op, None);
+    assert_eq!(print_sexp(&index.target), "(index (ident xs) 0)");
+    assert_eq!(index.op, Some(BinOpKind::Add));
+}
+
+#[test]
+fn calls_cannot_be_assigned() {
+    assert!(matches!(
+        parse_stmts("{ f() = 1; }"),
+        Err(ParseError::InvalidAssignTarget { .. })
+    ));
+}
@@ -0,0 +1,52 @@
+//! Checks how blocks split into statements and a final expression.
+
+use llvm_compiler::ast::{Block, StmtNode};
+use llvm_compiler::ast_printer::print_sexp;
+use llvm_compiler::lexer::AsciiLexer;
+use llvm_compiler::parse::parser::Parser;
+
+fn parse_block(src: &str) -> Block {
+    let mut tokens = AsciiLexer::new().tokenize(src).unwrap();
+    tokens.retain(|token| !token.is_trivia());
+
+    Parser::new(0, &tokens).parse_block().unwrap()
+}
+
+#[test]
+fn a_trailing_expression_is_the_value() {
+    let block = parse_block("{ f(); x + 1 }");
+
+    assert_eq!(block.stmts.len(), 1);
+    assert!(matches!(block.stmts[0], StmtNode::Expr(_)));
+    assert_eq!(
+        block.final_expr.as_deref().map(|expr| print_sexp(expr)),
+        Some("(+ (ident x) 1)".to_owned())
+    );
+}
+
+#[test]
+fn a_semicolon_makes_the_expression_a_statement() {
+    let block = parse_block("{ f(); x + 1; }");
+
+    assert_eq!(block.stmts.len(), 2);
+    assert!(block.final_expr.is_none());
+}
+
+#[test]
+fn empty_blocks_have_nothing() {
+    let block = parse_block("{ ; }");
+
+    assert!(block.stmts.is_empty());
+    assert!(block.final_expr.is_none());
+}
+
+#[test]
+fn block_like_expressions_need_no_semicolon() {
+    let block = parse_block("{ if a { b(); } c }");
+
+    assert_eq!(block.stmts.len(), 1);
+    assert_eq!(
+        block.final_expr.as_deref().map(|expr| print_sexp(expr)),
+        Some("(ident c)".to_owned())
+    );
+}
@@ -0,0 +1,104 @@
+//! Checks that builtin keywords are called like functions and emitted as LLVM intrinsics,
+//! constants or instructions.
+
+use llvm_compiler::ast::TypeExpr;
+use llvm_compiler::builtins::{BuiltinFn, BuiltinRegistry};
+use llvm_compiler::compiler::error::CompilerError;
+use llvm_compiler::compiler::{check_source, compile_to_llvm_ir};
+use llvm_compiler::lexer::AsciiLexer;
+use llvm_compiler::source::SourceFile;
+use llvm_compiler::typeck::error::TypeError;
+
+#[test]
+fn keywords_map_to_builtins() {
+    let tokens = AsciiLexer::new()
+        .tokenize("sizeof alignof typeof unreachable memcpy memset sizes")
+        .unwrap();
+    let builtins: Vec<_> = tokens
+        .iter()
+        .filter(|token| !token.is_trivia())
+        .map(|token| token.keyword_to_builtin_fn())
+        .collect();
+
+    assert_eq!(
+        builtins,
+        [
+            Some(BuiltinFn::SizeOf(TypeExpr::void())),
+            Some(BuiltinFn::AlignOf(TypeExpr::void())),
+            None,
+            Some(BuiltinFn::Unreachable),
+            Some(BuiltinFn::Memcpy),
+            Some(BuiltinFn::Memset),
+            None,
+        ]
+    );
+}
+
+#[test]
+fn signatures_ignore_the_type_given() {
+    let registry = BuiltinRegistry::new();
+
+    assert_eq!(
+        registry.signature(&BuiltinFn::SizeOf(TypeExpr::named("i32"))),
+        registry.signature(&BuiltinFn::SizeOf(TypeExpr::void()))
+    );
+    assert_eq!(
+        registry.signature(&BuiltinFn::Memcpy).intrinsic,
+        Some("llvm.memcpy.p0i8.p0i8.i64")
+    );
+    assert_eq!(registry.signature(&BuiltinFn::Memset).params.len(), 3);
+}
+
+#[test]
+fn arguments_are_checked_against_the_signature() {
+    let source = SourceFile::new(
+        "bad.src",
+        "func clear(dst: *mut u8) { memset(dst, 0); }
+func copy(dst: *mut u8, src: *u8) { memcpy(src, dst, 1); }
+",
+    );
+    let Err(CompilerError::Types { errors }) = check_source(&source) else {
+        panic!("expected type errors");
+    };
+
+    assert!(
+        matches!(
+            &errors[..],
+            [
+                TypeError::ArgumentCountMismatch {
+                    expected: 3,
+                    found: 2,
+                    ..
+                },
+                TypeError::TypeMismatch { .. },
+            ]
+        ),
+        "{errors:?}"
+    );
+}
+
+#[test]
+fn builtins_are_emitted_without_calls() {
+    let source = SourceFile::new(
+        "builtins.src",
+        "func copy(dst: *mut u8, src: *u8) {
+    memcpy(dst, src, sizeof(i64));
+    memcpy(dst, src, alignof(i32));
+}
+func never() -> i32 { unreachable() }
+",
+    );
+    let ir = compile_to_llvm_ir(&source).unwrap();
+
+    assert!(
+        ir.contains("ptrtoint (i64* getelementptr (i64, i64* null, i32 1) to i64)"),
+        "{ir}"
+    );
+    assert_eq!(
+        ir.matches("declare void @llvm.memcpy.p0i8.p0i8.i64(")
+            .count(),
+        1,
+        "{ir}"
+    );
+    assert!(ir.contains("entry:\n  unreachable\n}"), "{ir}");
+}
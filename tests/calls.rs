@@ -0,0 +1,30 @@
+//! Checks that calls pass their arguments with their types, and declare unknown callees.
+
+use llvm_compiler::compiler::compile_to_llvm_ir;
+use llvm_compiler::source::SourceFile;
+
+fn compile(text: &str) -> String {
+    compile_to_llvm_ir(&SourceFile::new("calls.src", text)).unwrap()
+}
+
+#[test]
+fn arguments_are_typed() {
+    let ir = compile(
+        "func add(a: i32, b: i32) -> i32 { return a + b; }
+func main() -> i32 { return add(1, 2); }
+",
+    );
+
+    assert!(
+        ir.contains("%tmp0 = call i32 @add(i32 1, i32 2)\n  ret i32 %tmp0"),
+        "{ir}"
+    );
+}
+
+#[test]
+fn void_calls_have_no_result() {
+    let ir = compile("func tick() {}\nfunc main() { tick(); }\n");
+
+    assert!(ir.contains("  call void @tick()\n"), "{ir}");
+    assert!(!ir.contains("= call void"), "{ir}");
+}
@@ -0,0 +1,42 @@
+//! Checks that type checked modules are lowered to LLVM IR, one `define` per function.
+
+use llvm_compiler::codegen::emitter::LlvmEmitter;
+use llvm_compiler::compiler::{check_source, compile_to_llvm_ir};
+use llvm_compiler::source::SourceFile;
+
+#[test]
+fn functions_are_defined_with_their_instructions() {
+    let source = SourceFile::new(
+        "add.src",
+        "func add(a: i32, b: i32) -> i32 { return a + b; }",
+    );
+    let ir = compile_to_llvm_ir(&source).unwrap();
+
+    assert!(
+        ir.contains("define i32 @add(i32 %a, i32 %b) {\nentry:\n"),
+        "{ir}"
+    );
+    assert!(
+        ir.contains("= add i32 %tmp0, %tmp1\n  ret i32 %tmp2\n}"),
+        "{ir}"
+    );
+}
+
+#[test]
+fn the_emitter_can_be_driven_directly() {
+    let source = SourceFile::new("main.src", "func main() {}\nfunc two() -> i32 { 2 }\n");
+    let module = check_source(&source).unwrap();
+
+    let mut emitter = LlvmEmitter::new();
+    emitter.emit_module(&module).unwrap();
+    let ir = emitter.finish();
+
+    assert!(
+        ir.contains("define void @main() {\nentry:\n  ret void\n}"),
+        "{ir}"
+    );
+    assert!(
+        ir.contains("define i32 @two() {\nentry:\n  ret i32 2\n}"),
+        "{ir}"
+    );
+}
@@ -0,0 +1,113 @@
+//! Checks the basic blocks and branches emitted for `if` expressions and `while` loops.
+
+use llvm_compiler::compiler::compile_to_llvm_ir;
+use llvm_compiler::source::SourceFile;
+
+fn compile(text: &str) -> String {
+    compile_to_llvm_ir(&SourceFile::new("control_flow.src", text)).unwrap()
+}
+
+/// The IR from the first `define` on, leaving out the module header.
+fn definitions(ir: &str) -> &str {
+    &ir[ir.find("define").unwrap()..]
+}
+
+#[test]
+fn ifs_without_else_fall_through_to_the_merge_block() {
+    let ir = compile("func f(a: i32) -> i32 {\n    if a > 0 { return 1; }\n    a\n}\n");
+
+    assert!(
+        ir.contains("br i1 %tmp1, label %then0, label %merge1\nthen0:\n  ret i32 1\nmerge1:\n"),
+        "{ir}"
+    );
+}
+
+#[test]
+fn ifs_whose_branches_all_return_end_in_unreachable() {
+    let ir = compile(
+        "func f(a: i32) -> i32 {
+    if a > 0 { return 1; }
+    if a < 0 { return 2; } else { return 3; }
+}
+",
+    );
+
+    assert_eq!(
+        definitions(&ir),
+        "define i32 @f(i32 %a) {
+entry:
+  %a.addr = alloca i32
+  store i32 %a, i32* %a.addr
+  %tmp0 = load i32, i32* %a.addr
+  %tmp1 = icmp sgt i32 %tmp0, 0
+  br i1 %tmp1, label %then0, label %merge1
+then0:
+  ret i32 1
+merge1:
+  %tmp2 = load i32, i32* %a.addr
+  %tmp3 = icmp slt i32 %tmp2, 0
+  br i1 %tmp3, label %then2, label %else4
+then2:
+  ret i32 2
+else4:
+  ret i32 3
+merge3:
+  unreachable
+}
+"
+    );
+}
+
+#[test]
+fn while_loops_check_the_condition_before_each_iteration() {
+    let ir = compile(
+        "func count(n: i32) -> i32 {
+    let mut i: i32 = 0;
+    while i < n { i += 1; }
+    i
+}
+",
+    );
+
+    assert!(
+        ir.contains("  br label %loop_header0\nloop_header0:\n"),
+        "{ir}"
+    );
+    assert!(
+        ir.contains("br i1 %tmp2, label %loop_body1, label %loop_exit2\nloop_body1:\n"),
+        "{ir}"
+    );
+    assert!(
+        ir.contains("  br label %loop_header0\nloop_exit2:\n  %tmp5 = load i32, i32* %i.addr\n  ret i32 %tmp5\n"),
+        "{ir}"
+    );
+}
+
+/// `llvm-as` only comes with an LLVM installation, which the `llvm` feature needs anyway.
+#[cfg(feature = "llvm")]
+#[test]
+fn returning_branches_assemble() {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    let ir = compile(
+        "func f(a: i32) -> i32 {
+    if a < 0 { return 2; } else { return 3; }
+}
+",
+    );
+
+    let mut llvm_as = Command::new("llvm-as")
+        .args(["-o", "/dev/null"])
+        .stdin(Stdio::piped())
+        .spawn()
+        .unwrap();
+    llvm_as
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(ir.as_bytes())
+        .unwrap();
+
+    assert!(llvm_as.wait().unwrap().success(), "{ir}");
+}
@@ -0,0 +1,36 @@
+//! Checks the line the lexer reports it's on.
+
+use llvm_compiler::lexer::AsciiLexer;
+
+fn last_line(src: &str) -> u32 {
+    let mut lexer = AsciiLexer::new();
+    lexer.tokenize(src).unwrap();
+    lexer.current_line()
+}
+
+#[test]
+fn lines_are_counted_once_per_newline() {
+    assert_eq!(last_line("a\nb\nc\nd\ne"), 5);
+    assert_eq!(last_line("a\r\nb\r\nc\r\nd\r\ne"), 5);
+    assert_eq!(last_line("a\r\nb\nc\r\nd\ne"), 5);
+}
+
+#[test]
+fn a_trailing_newline_doesnt_start_a_line() {
+    assert_eq!(last_line("a\nb\nc\nd\ne\n"), 5);
+    assert_eq!(last_line("a\r\nb\r\nc\r\nd\r\ne\r\n"), 5);
+}
+
+#[test]
+fn newlines_in_block_comments_are_counted() {
+    assert_eq!(last_line("/* a\nb\nc */\nd\ne"), 5);
+}
+
+#[test]
+fn each_tokenize_starts_again_at_line_one() {
+    let mut lexer = AsciiLexer::new();
+    lexer.tokenize("a\nb\nc").unwrap();
+    lexer.tokenize("x").unwrap();
+
+    assert_eq!(lexer.current_line(), 1);
+}
@@ -0,0 +1,57 @@
+//! Checks that dead code elimination keeps exactly the functions reachable from the entry point.
+
+use llvm_compiler::ast::{Item, Module};
+use llvm_compiler::compiler::error::CompilerWarning;
+use llvm_compiler::compiler::{eliminate_dead_code, parse_source};
+use llvm_compiler::source::SourceFile;
+use miette::SourceSpan;
+
+const SRC: &str = "func main() -> i32 { return foo(1); }
+func foo(x: i32) -> i32 { return even(x); }
+func even(x: i32) -> i32 { if x > 0 { return odd(x - 1); } return 1; }
+func odd(x: i32) -> i32 { if x > 0 { return even(x - 1); } return 0; }
+func bar() -> i32 { return foo(2); }
+";
+
+fn parse() -> Module {
+    parse_source(&SourceFile::new("dead_code.src", SRC)).unwrap()
+}
+
+fn function_names(module: &Module) -> Vec<String> {
+    module
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            Item::FnDecl(decl) => Some(decl.name.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn uncalled_functions_are_removed() {
+    let (module, _) = eliminate_dead_code(&parse(), "main");
+
+    assert_eq!(function_names(&module), ["main", "foo", "even", "odd"]);
+}
+
+#[test]
+fn removed_functions_are_warned_about_where_they_are() {
+    let (_, warnings) = eliminate_dead_code(&parse(), "main");
+
+    let [CompilerWarning::UnusedFunction { name, span }] = &warnings[..] else {
+        panic!("expected one warning, got {warnings:?}");
+    };
+    assert_eq!(name, "bar");
+    let bar = "func bar() -> i32 { return foo(2); }";
+    assert_eq!(*span, SourceSpan::from((SRC.find(bar).unwrap(), bar.len())));
+}
+
+#[test]
+fn nothing_is_removed_without_an_entry_point() {
+    let module = parse();
+    let (eliminated, warnings) = eliminate_dead_code(&module, "start");
+
+    assert_eq!(eliminated, module);
+    assert!(warnings.is_empty());
+}
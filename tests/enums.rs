@@ -0,0 +1,68 @@
+//! Checks how enum declarations and their variants are parsed.
+
+use llvm_compiler::ast::{EnumDecl, EnumVariantFields, TypeExpr};
+use llvm_compiler::lexer::AsciiLexer;
+use llvm_compiler::parse::error::ParseError;
+use llvm_compiler::parse::parser::Parser;
+
+fn parse_enum(src: &str) -> Result<EnumDecl, ParseError> {
+    let mut tokens = AsciiLexer::new().tokenize(src).unwrap();
+    tokens.retain(|token| !token.is_trivia());
+
+    Parser::new(0, &tokens).parse_enum_decl()
+}
+
+#[test]
+fn variants_can_be_units_tuples_or_structs() {
+    let decl = parse_enum("enum Shape { Empty, Circle(f64), Rect { w: f64, h: f64 } }").unwrap();
+
+    assert_eq!(decl.name, "Shape");
+    let names: Vec<_> = decl.variants.iter().map(|variant| &variant.name).collect();
+    assert_eq!(names, ["Empty", "Circle", "Rect"]);
+
+    assert_eq!(decl.variants[0].fields, EnumVariantFields::Unit);
+    assert_eq!(
+        decl.variants[1].fields,
+        EnumVariantFields::Tuple(vec![TypeExpr::named("f64")])
+    );
+    let EnumVariantFields::Struct(fields) = &decl.variants[2].fields else {
+        panic!("expected struct fields");
+    };
+    assert_eq!(fields.len(), 2);
+}
+
+#[test]
+fn tuple_variants_can_hold_several_types() {
+    let decl = parse_enum("enum Pair<T> { Both(T, i32), Neither }").unwrap();
+
+    assert!(!decl.generic_params.is_empty());
+    assert!(matches!(
+        &decl.variants[0].fields,
+        EnumVariantFields::Tuple(types) if types.len() == 2
+    ));
+}
+
+#[test]
+fn the_last_comma_is_optional() {
+    let with = parse_enum("enum Dir { Up, Down, }").unwrap();
+    let without = parse_enum("enum Dir { Up, Down }").unwrap();
+
+    assert_eq!(with.variants.len(), 2);
+    let names = |decl: &EnumDecl| -> Vec<String> {
+        decl.variants
+            .iter()
+            .map(|variant| variant.name.clone())
+            .collect()
+    };
+    assert_eq!(names(&with), names(&without));
+}
+
+#[test]
+fn enums_can_be_empty() {
+    assert!(parse_enum("enum Never {}").unwrap().variants.is_empty());
+}
+
+#[test]
+fn variants_need_commas_between_them() {
+    assert!(parse_enum("enum Dir { Up Down }").is_err());
+}
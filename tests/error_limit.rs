@@ -0,0 +1,63 @@
+//! Checks that a lexer with an error limit gives up on reaching it.
+
+use llvm_compiler::lexer::error::TokenizationError;
+use llvm_compiler::lexer::AsciiLexer;
+use miette::{GraphicalReportHandler, GraphicalTheme, Report};
+
+const FIVE_ERRORS: &str = "a @ b @ c @ d @ e @";
+
+#[test]
+fn lexing_stops_at_the_limit() {
+    let mut lexer = AsciiLexer::new().with_error_limit(3);
+    let error = lexer.tokenize(FIVE_ERRORS).unwrap_err();
+
+    let TokenizationError::TooManyErrors {
+        limit, last_error, ..
+    } = &error
+    else {
+        panic!("expected too many errors, got {error:?}");
+    };
+    assert_eq!(*limit, 3);
+    assert_eq!(last_error.span().offset(), 10);
+    assert_eq!(error.span(), last_error.span());
+    assert_eq!(lexer.metrics().errors_encountered, 3);
+}
+
+#[test]
+fn errors_under_the_limit_are_unknown_tokens() {
+    let mut lexer = AsciiLexer::new().with_error_limit(6);
+    let tokens = lexer.tokenize(FIVE_ERRORS).unwrap();
+
+    assert_eq!(
+        tokens
+            .iter()
+            .filter(|token| token.classify_unknown().is_some())
+            .count(),
+        5
+    );
+    assert_eq!(lexer.metrics().errors_encountered, 5);
+}
+
+#[test]
+fn the_report_points_at_the_last_error() {
+    let error = AsciiLexer::new()
+        .with_error_limit(3)
+        .tokenize(FIVE_ERRORS)
+        .unwrap_err();
+    let report = Report::new(error).with_source_code(FIVE_ERRORS);
+
+    let mut rendered = String::new();
+    GraphicalReportHandler::new_themed(GraphicalTheme::unicode_nocolor())
+        .render_report(&mut rendered, report.as_ref())
+        .unwrap();
+
+    assert!(
+        rendered.contains("Giving up after 3 unrecognized token(s)"),
+        "{rendered}"
+    );
+    assert!(rendered.contains("stopped at this error"), "{rendered}");
+    assert!(
+        rendered.contains("Unrecognized character '@'"),
+        "{rendered}"
+    );
+}
@@ -0,0 +1,57 @@
+//! Checks how expressions are parsed, printing them as S-expressions to compare.
+
+use llvm_compiler::ast_printer::print_sexp;
+use llvm_compiler::lexer::AsciiLexer;
+use llvm_compiler::parse::error::ParseError;
+use llvm_compiler::parse::parser::Parser;
+
+fn parse_expr(src: &str) -> Result<String, ParseError> {
+    let mut tokens = AsciiLexer::new().tokenize(src).unwrap();
+    tokens.retain(|token| !token.is_trivia());
+
+    Parser::new(0, &tokens)
+        .parse_expr(0)
+        .map(|expr| print_sexp(&expr))
+}
+
+#[test]
+fn binary_operators_bind_by_precedence() {
+    assert_eq!(
+        parse_expr("a + b * c").unwrap(),
+        "(+ (ident a) (* (ident b) (ident c)))"
+    );
+    assert_eq!(
+        parse_expr("a * b + c").unwrap(),
+        "(+ (* (ident a) (ident b)) (ident c))"
+    );
+    assert_eq!(
+        parse_expr("a - b - c").unwrap(),
+        "(- (- (ident a) (ident b)) (ident c))"
+    );
+}
+
+#[test]
+fn method_calls_are_calls_of_fields() {
+    assert_eq!(
+        parse_expr("foo.bar(1, 2)").unwrap(),
+        "(call (field (ident foo) bar) 1 2)"
+    );
+    assert_eq!(
+        parse_expr("xs[i + 1]").unwrap(),
+        "(index (ident xs) (+ (ident i) 1))"
+    );
+}
+
+#[test]
+fn unary_operators_bind_tightest() {
+    assert_eq!(parse_expr("-x").unwrap(), "(- (ident x))");
+    assert_eq!(parse_expr("-x * y").unwrap(), "(* (- (ident x)) (ident y))");
+}
+
+#[test]
+fn missing_operands_are_errors() {
+    assert!(matches!(
+        parse_expr("a +"),
+        Err(ParseError::ExpectedExpression { .. })
+    ));
+}
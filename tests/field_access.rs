@@ -0,0 +1,43 @@
+//! Checks that struct fields are reached with `getelementptr`, by their index in the struct.
+
+use llvm_compiler::ast::Item;
+use llvm_compiler::codegen::emitter::LlvmEmitter;
+use llvm_compiler::codegen::error::CodegenError;
+use llvm_compiler::compiler::{compile_to_llvm_ir, parse_source};
+use llvm_compiler::source::SourceFile;
+use llvm_compiler::span::Span;
+
+const POINT: &str = "struct Point { x: i32, y: i32 }\n";
+
+#[test]
+fn fields_are_indexed_in_declaration_order() {
+    let text = format!("{POINT}func get_y(p: Point) -> i32 {{ p.y }}\n");
+    let ir = compile_to_llvm_ir(&SourceFile::new("fields.src", text)).unwrap();
+
+    assert!(ir.contains("%Point = type { i32, i32 }\n"), "{ir}");
+    assert!(
+        ir.contains("getelementptr inbounds %Point, %Point* %p.addr, i32 0, i32 1\n"),
+        "{ir}"
+    );
+}
+
+#[test]
+fn unknown_fields_are_errors() {
+    let module = parse_source(&SourceFile::new("fields.src", POINT)).unwrap();
+    let Item::StructDecl(point) = &module.items[0] else {
+        panic!("expected a struct");
+    };
+
+    let mut emitter = LlvmEmitter::new();
+    assert_eq!(
+        emitter
+            .emit_field_access("%p", point, "x", Span::default())
+            .unwrap(),
+        "%tmp0"
+    );
+    assert!(matches!(
+        emitter.emit_field_access("%p", point, "z", Span::default()),
+        Err(CodegenError::UnknownField { struct_name, field_name, .. })
+            if struct_name == "Point" && field_name == "z"
+    ));
+}
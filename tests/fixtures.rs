@@ -0,0 +1,51 @@
+//! Lexes every `tests/fixtures/*.src` file and compares the tokens against the golden
+//! `.tokens` file next to it. Missing goldens are written out, and `UPDATE_GOLDENS=1` rewrites
+//! all of them.
+
+use std::fs;
+use std::path::Path;
+
+use llvm_compiler::lexer::{to_compact_string, AsciiLexer};
+use llvm_compiler::source::SourceFile;
+
+#[test]
+fn fixtures_match_goldens() {
+    let update = std::env::var_os("UPDATE_GOLDENS").is_some_and(|value| value == "1");
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+
+    let mut sources: Vec<_> = fs::read_dir(&fixtures)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "src"))
+        .collect();
+    sources.sort();
+
+    assert!(!sources.is_empty(), "no fixtures found in {fixtures:?}");
+
+    let mut mismatches = Vec::new();
+
+    for path in sources {
+        let source = SourceFile::new(
+            path.display().to_string(),
+            fs::read_to_string(&path).unwrap(),
+        );
+        let tokens = AsciiLexer::new().tokenize(source.text()).unwrap();
+        let actual = to_compact_string(&tokens, &source);
+
+        let golden = path.with_extension("tokens");
+
+        match fs::read_to_string(&golden) {
+            Ok(expected) if !update => {
+                if expected != actual {
+                    mismatches.push(golden);
+                }
+            }
+            _ => fs::write(&golden, actual).unwrap(),
+        }
+    }
+
+    assert!(
+        mismatches.is_empty(),
+        "tokens differ from the goldens in {mismatches:?}, rerun with UPDATE_GOLDENS=1 to accept them"
+    );
+}
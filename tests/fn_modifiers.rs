@@ -0,0 +1,64 @@
+//! Checks that `pub`, `extern` and `unsafe` are parsed on function declarations, and that
+//! `extern "C"` functions are declared rather than defined.
+
+use llvm_compiler::ast::{FnDecl, Item, Visibility};
+use llvm_compiler::compiler::{compile_to_llvm_ir, parse_source};
+use llvm_compiler::source::SourceFile;
+
+fn parse_fn(text: &str) -> FnDecl {
+    let module = parse_source(&SourceFile::new("modifiers.src", text)).unwrap();
+
+    match module.items.into_iter().next() {
+        Some(Item::FnDecl(decl)) => decl,
+        item => panic!("expected a function, found {item:?}"),
+    }
+}
+
+#[test]
+fn pub_makes_a_function_public() {
+    let decl = parse_fn("pub func foo() {}");
+
+    assert_eq!(decl.visibility, Visibility::Public);
+    assert_eq!(decl.abi, None);
+    assert!(!decl.is_unsafe);
+    assert_eq!(decl.span.start, 0);
+}
+
+#[test]
+fn extern_gives_an_abi_and_no_body() {
+    let decl = parse_fn("extern \"C\" func bar(s: *u8) -> i32;");
+    assert_eq!(decl.visibility, Visibility::Private);
+    assert_eq!(decl.abi.as_deref(), Some("C"));
+    assert!(decl.is_extern());
+    assert!(decl.body.stmts.is_empty());
+
+    let decl = parse_fn("extern func bar();");
+    assert_eq!(decl.abi.as_deref(), Some("C"));
+}
+
+#[test]
+fn unsafe_combines_with_the_other_modifiers() {
+    let decl = parse_fn("unsafe func baz() {}");
+    assert!(decl.is_unsafe);
+    assert_eq!(decl.visibility, Visibility::Private);
+
+    let decl = parse_fn("pub extern \"Rust\" unsafe func baz();");
+    assert_eq!(decl.visibility, Visibility::Public);
+    assert_eq!(decl.abi.as_deref(), Some("Rust"));
+    assert!(decl.is_unsafe);
+}
+
+#[test]
+fn extern_c_functions_are_declared() {
+    let source = SourceFile::new(
+        "puts.src",
+        "extern \"C\" func puts(s: *u8) -> i32;
+pub func main() -> i32 { puts(\"hi\"); return 0; }
+",
+    );
+    let ir = compile_to_llvm_ir(&source).unwrap();
+
+    assert!(ir.contains("declare i32 @puts(i8*)"), "{ir}");
+    assert!(!ir.contains("define i32 @puts"), "{ir}");
+    assert!(ir.contains("define i32 @main()"), "{ir}");
+}
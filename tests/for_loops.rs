@@ -0,0 +1,84 @@
+//! Checks how C-style `for` loops and `for ... in` loops are parsed.
+
+use llvm_compiler::ast::{Block, StmtNode};
+use llvm_compiler::ast_printer::print_sexp;
+use llvm_compiler::lexer::AsciiLexer;
+use llvm_compiler::parse::parser::Parser;
+
+fn parse_for(src: &str) -> StmtNode {
+    let mut tokens = AsciiLexer::new().tokenize(src).unwrap();
+    tokens.retain(|token| !token.is_trivia());
+
+    Parser::new(0, &tokens).parse_for().unwrap()
+}
+
+fn parse_block(src: &str) -> Block {
+    let mut tokens = AsciiLexer::new().tokenize(src).unwrap();
+    tokens.retain(|token| !token.is_trivia());
+
+    Parser::new(0, &tokens).parse_block().unwrap()
+}
+
+#[test]
+fn every_clause_is_optional() {
+    let StmtNode::For(stmt) = parse_for("for ;; { f(); }") else {
+        panic!("expected a C-style for loop");
+    };
+
+    assert!(stmt.init.is_none() && stmt.cond.is_none() && stmt.post.is_none());
+    assert_eq!(stmt.body.stmts.len(), 1);
+}
+
+#[test]
+fn clauses_can_be_lets_and_assignments() {
+    let StmtNode::For(stmt) = parse_for("for let mut i = 0; i < 10; i += 1 {}") else {
+        panic!("expected a C-style for loop");
+    };
+
+    assert!(matches!(stmt.init.as_deref(), Some(StmtNode::Let(init)) if init.mutable));
+    assert_eq!(
+        stmt.cond.as_ref().map(|cond| print_sexp(cond)),
+        Some("(< (ident i) 10)".to_owned())
+    );
+    assert!(matches!(stmt.post.as_deref(), Some(StmtNode::Assign(_))));
+}
+
+#[test]
+fn clauses_can_be_parenthesised() {
+    let plain = parse_for("for i = 0; i < 3; i += 1 {}");
+    let parenthesised = parse_for("for (i = 0; i < 3; i += 1) {}");
+
+    assert!(matches!(plain, StmtNode::For(_)));
+    assert_eq!(print_sexp(&plain), print_sexp(&parenthesised));
+
+    let StmtNode::For(empty) = parse_for("for (;;) {}") else {
+        panic!("expected a C-style for loop");
+    };
+    assert!(empty.init.is_none() && empty.cond.is_none() && empty.post.is_none());
+}
+
+#[test]
+fn for_in_loops_bind_a_name() {
+    let StmtNode::ForIn(stmt) = parse_for("for x in xs { f(x); }") else {
+        panic!("expected a for in loop");
+    };
+    assert_eq!(stmt.binding, "x");
+    assert!(!stmt.mutable);
+    assert_eq!(print_sexp(&stmt.iterable), "(ident xs)");
+
+    let StmtNode::ForIn(stmt) = parse_for("for mut x in xs {}") else {
+        panic!("expected a for in loop");
+    };
+    assert!(stmt.mutable);
+}
+
+#[test]
+fn loops_in_blocks_can_be_labelled() {
+    let block = parse_block("{ 'rows: for ;; {} 'cells: for c in cs {} }");
+
+    let [StmtNode::For(rows), StmtNode::ForIn(cells)] = &block.stmts[..] else {
+        panic!("expected two loops, got {:?}", block.stmts);
+    };
+    assert_eq!(rows.label.as_deref(), Some("rows"));
+    assert_eq!(cells.label.as_deref(), Some("cells"));
+}
@@ -0,0 +1,63 @@
+//! Checks that top-level `let` and `const` declarations become LLVM globals.
+
+use llvm_compiler::ast_printer::print_sexp;
+use llvm_compiler::compiler::error::CompilerError;
+use llvm_compiler::compiler::{check_source, compile_to_llvm_ir, parse_source};
+use llvm_compiler::source::SourceFile;
+use llvm_compiler::typeck::error::TypeError;
+
+fn compile(text: &str) -> String {
+    compile_to_llvm_ir(&SourceFile::new("globals.src", text)).unwrap()
+}
+
+#[test]
+fn constants_are_emitted_before_functions() {
+    let ir = compile("const PI: f64 = 3.14159;\nfunc main() -> f64 { PI }\n");
+
+    let global = ir
+        .find("@PI = constant double 0x400921F9F01B866E\n")
+        .unwrap();
+    assert!(global < ir.find("define").unwrap());
+    assert!(ir.contains("load double, double* @PI"));
+}
+
+#[test]
+fn mutable_globals_start_at_zero_without_a_value() {
+    let ir = compile("let mut count: i32;\nlet limit: i32 = -3;\nfunc main() { count = limit; }\n");
+
+    assert!(ir.contains("@count = global i32 zeroinitializer\n"));
+    assert!(ir.contains("@limit = constant i32 -3\n"));
+    assert!(ir.contains("store i32 %tmp0, i32* @count"));
+}
+
+#[test]
+fn locals_shadow_globals() {
+    let ir = compile("let x: i64 = 1;\nfunc main() -> i64 { let x: i64 = 2; x }\n");
+
+    assert!(!ir.contains("load i64, i64* @x"));
+}
+
+#[test]
+fn globals_print_like_lets() {
+    let source = SourceFile::new("globals.src", "const N: u8 = 4;\nlet mut s: *u8;\n");
+    let module = parse_source(&source).unwrap();
+
+    assert_eq!(print_sexp(&module.items[0]), "(const N (type u8) 4)");
+    assert_eq!(
+        print_sexp(&module.items[1]),
+        "(let (mut s) (type (ptr u8)))"
+    );
+}
+
+#[test]
+fn values_must_be_constants() {
+    let source = SourceFile::new(
+        "globals.src",
+        "func f() -> i32 { 1 }\nconst N: i32 = f();\n",
+    );
+
+    let Err(CompilerError::Types { errors }) = check_source(&source) else {
+        panic!("expected a type error");
+    };
+    assert!(matches!(&errors[..], [TypeError::NotConstant { name, .. }] if name == "N"));
+}
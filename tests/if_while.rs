@@ -0,0 +1,73 @@
+//! Checks how `if` expressions and `while` loops are parsed.
+
+use llvm_compiler::ast::{IfOrBlock, WhileStmt};
+use llvm_compiler::ast_printer::print_sexp;
+use llvm_compiler::lexer::AsciiLexer;
+use llvm_compiler::parse::error::ParseError;
+use llvm_compiler::parse::parser::Parser;
+
+fn with_parser<T>(src: &str, parse: impl FnOnce(&mut Parser) -> T) -> T {
+    let mut tokens = AsciiLexer::new().tokenize(src).unwrap();
+    tokens.retain(|token| !token.is_trivia());
+
+    parse(&mut Parser::new(0, &tokens))
+}
+
+#[test]
+fn simple_ifs_have_no_else() {
+    let expr = with_parser("if x > 0 { y }", |parser| parser.parse_if_expr()).unwrap();
+
+    assert_eq!(print_sexp(&*expr.cond), "(> (ident x) 0)");
+    assert_eq!(print_sexp(&expr.then_block), "(block (ident y))");
+    assert!(expr.else_block.is_none());
+}
+
+#[test]
+fn else_takes_a_block() {
+    let expr = with_parser("if x { 1 } else { 2 }", |parser| parser.parse_if_expr()).unwrap();
+
+    let Some(else_block) = expr.else_block else {
+        panic!("expected an else block");
+    };
+    assert!(matches!(*else_block, IfOrBlock::Block(_)));
+}
+
+#[test]
+fn else_if_chains_nest() {
+    let expr = with_parser("if a { 1 } else if b { 2 } else { 3 }", |parser| {
+        parser.parse_if_expr()
+    })
+    .unwrap();
+
+    let Some(IfOrBlock::If(nested)) = expr.else_block.as_deref() else {
+        panic!("expected an else if");
+    };
+    assert_eq!(print_sexp(&*nested.cond), "(ident b)");
+    assert!(matches!(
+        nested.else_block.as_deref(),
+        Some(IfOrBlock::Block(_))
+    ));
+}
+
+#[test]
+fn bodies_need_braces() {
+    assert!(matches!(
+        with_parser("if x foo()", |parser| parser.parse_if_expr()),
+        Err(ParseError::MissingBlock { found: Some(found), .. }) if found == "foo"
+    ));
+    assert!(matches!(
+        with_parser("while x y = 1;", |parser| parser.parse_while_stmt()),
+        Err(ParseError::MissingBlock { .. })
+    ));
+}
+
+#[test]
+fn while_loops_have_a_condition_and_body() {
+    let WhileStmt { cond, body, .. } = with_parser("while i < 10 { i += 1; }", |parser| {
+        parser.parse_while_stmt()
+    })
+    .unwrap();
+
+    assert_eq!(print_sexp(&*cond), "(< (ident i) 10)");
+    assert_eq!(body.stmts.len(), 1);
+}
@@ -0,0 +1,56 @@
+//! Checks how the indentation in front of a token is measured, with spaces and tabs.
+
+use llvm_compiler::lexer::AsciiLexer;
+use llvm_compiler::token::{Token, TokenType};
+
+/// The tokens of `src`, and the index of the first `let` among them.
+fn lex_to_let(src: &str) -> (Vec<Token<'_>>, usize) {
+    let tokens = AsciiLexer::new().tokenize(src).unwrap();
+    let idx = tokens
+        .iter()
+        .position(|token| token.ty() == TokenType::Let)
+        .unwrap();
+
+    (tokens, idx)
+}
+
+#[test]
+fn tabs_are_whitespace() {
+    let tokens = AsciiLexer::new().tokenize("\n\tlet").unwrap();
+    let types: Vec<_> = tokens.iter().map(|token| token.ty()).collect();
+
+    assert_eq!(
+        types,
+        [TokenType::Newline, TokenType::Space, TokenType::Let]
+    );
+    assert_eq!(tokens[1].as_str(), "\t");
+}
+
+#[test]
+fn tab_indented_tokens_start_their_line() {
+    let (tokens, idx) = lex_to_let("func f() {\n\t\tlet x = 1;\n}");
+    assert!(Token::is_at_line_start(&tokens, idx));
+
+    let (tokens, idx) = lex_to_let("x;\tlet y = 1;");
+    assert!(!Token::is_at_line_start(&tokens, idx));
+}
+
+#[test]
+fn tabs_move_to_the_next_tab_stop() {
+    let (tokens, idx) = lex_to_let("\n\tlet");
+    assert_eq!(Token::indentation_level(&tokens, idx, 4), 4);
+
+    let (tokens, idx) = lex_to_let("\n  \t let");
+    assert_eq!(Token::indentation_level(&tokens, idx, 4), 5);
+    assert_eq!(Token::indentation_level(&tokens, idx, 8), 9);
+
+    let (tokens, idx) = lex_to_let("\n    let");
+    assert_eq!(Token::indentation_level(&tokens, idx, 4), 4);
+}
+
+#[test]
+fn zero_width_tabs_count_as_one_column() {
+    let (tokens, idx) = lex_to_let("\t\tlet");
+
+    assert_eq!(Token::indentation_level(&tokens, idx, 0), 2);
+}
@@ -0,0 +1,105 @@
+//! Checks the types given to `if` expressions whose branches diverge, and to locals initialised
+//! with integer literals.
+
+use llvm_compiler::ast::TypeExpr;
+use llvm_compiler::compiler::check_source;
+use llvm_compiler::source::SourceFile;
+use llvm_compiler::typeck::typed::{TypedModule, TypedStmt};
+
+fn check(text: &str) -> TypedModule {
+    check_source(&SourceFile::new("inference.src", text)).unwrap()
+}
+
+/// The type of each `let` at the top of the body of the first function.
+fn let_types(module: &TypedModule) -> Vec<(String, TypeExpr)> {
+    module.functions[0]
+        .body
+        .stmts
+        .iter()
+        .filter_map(|stmt| match stmt {
+            TypedStmt::Let { name, ty, .. } => Some((name.clone(), ty.clone())),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn ifs_take_the_type_of_the_branch_that_finishes() {
+    let module = check(
+        "func f(c: bool) -> i32 {
+    let v = if c { return 5; } else { 2 };
+    let w: i32 = if c { 3 } else { return 4; };
+    w
+}
+",
+    );
+
+    assert_eq!(
+        let_types(&module),
+        [
+            ("v".to_owned(), TypeExpr::named("i64")),
+            ("w".to_owned(), TypeExpr::named("i32")),
+        ]
+    );
+}
+
+#[test]
+fn literal_locals_take_the_type_they_are_compared_at() {
+    let module = check(
+        "func count(n: i32) -> i32 {
+    let mut i = 0;
+    while i < n { i += 1; }
+    i
+}
+",
+    );
+
+    assert_eq!(
+        let_types(&module),
+        [("i".to_owned(), TypeExpr::named("i32"))]
+    );
+}
+
+#[test]
+fn literal_locals_take_the_type_they_are_passed_at() {
+    let module = check(
+        "func main() -> u8 {
+    let a = 1;
+    let b = -2;
+    let c = 3;
+    take(a, b);
+    c
+}
+func take(a: u16, b: i8) {}
+",
+    );
+
+    assert_eq!(
+        let_types(&module),
+        [
+            ("a".to_owned(), TypeExpr::named("u16")),
+            ("b".to_owned(), TypeExpr::named("i8")),
+            ("c".to_owned(), TypeExpr::named("u8")),
+        ]
+    );
+}
+
+#[test]
+fn unused_literal_locals_default_to_i64() {
+    let module = check("func main() {\n    let x = 1;\n}\n");
+
+    assert_eq!(
+        let_types(&module),
+        [("x".to_owned(), TypeExpr::named("i64"))]
+    );
+}
+
+#[test]
+fn typed_locals_still_mismatch() {
+    let source = SourceFile::new(
+        "inference.src",
+        "func f(n: i32) -> bool {\n    let i: i64 = 0;\n    i < n\n}\n",
+    );
+
+    assert!(check_source(&source).is_err());
+}
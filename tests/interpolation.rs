@@ -0,0 +1,110 @@
+//! Checks how strings with `${…}` interpolations in them are lexed.
+
+use llvm_compiler::lexer::AsciiLexer;
+use llvm_compiler::token::TokenType;
+
+/// The type and text of every token in `src` other than spaces.
+fn lex(src: &str) -> Vec<(TokenType, &str)> {
+    AsciiLexer::new()
+        .tokenize(src)
+        .unwrap()
+        .into_iter()
+        .filter(|token| token.ty() != TokenType::Space)
+        .map(|token| (token.ty(), token.as_str()))
+        .collect()
+}
+
+#[test]
+fn interpolated_names_are_lexed_between_the_string_pieces() {
+    assert_eq!(
+        lex(r#""Hello, ${name}!""#),
+        [
+            (TokenType::InterpolatedStringStart, "\"Hello, "),
+            (TokenType::OpenInterp, "${"),
+            (TokenType::Identifier, "name"),
+            (TokenType::CloseInterp, "}"),
+            (TokenType::InterpolatedStringEnd, "!\""),
+        ]
+    );
+}
+
+#[test]
+fn interpolated_expressions_are_lexed_normally() {
+    assert_eq!(
+        lex(r#""${a + b}""#),
+        [
+            (TokenType::InterpolatedStringStart, "\""),
+            (TokenType::OpenInterp, "${"),
+            (TokenType::Identifier, "a"),
+            (TokenType::Plus, "+"),
+            (TokenType::Identifier, "b"),
+            (TokenType::CloseInterp, "}"),
+            (TokenType::InterpolatedStringEnd, "\""),
+        ]
+    );
+}
+
+#[test]
+fn text_between_interpolations_is_a_middle_piece() {
+    assert_eq!(
+        lex(r#""${x}, ${y}${z}""#),
+        [
+            (TokenType::InterpolatedStringStart, "\""),
+            (TokenType::OpenInterp, "${"),
+            (TokenType::Identifier, "x"),
+            (TokenType::CloseInterp, "}"),
+            (TokenType::InterpolatedStringMiddle, ", "),
+            (TokenType::OpenInterp, "${"),
+            (TokenType::Identifier, "y"),
+            (TokenType::CloseInterp, "}"),
+            (TokenType::OpenInterp, "${"),
+            (TokenType::Identifier, "z"),
+            (TokenType::CloseInterp, "}"),
+            (TokenType::InterpolatedStringEnd, "\""),
+        ]
+    );
+}
+
+#[test]
+fn braces_and_strings_nest_inside_interpolations() {
+    assert_eq!(
+        lex(r#""${ { "in ${x}" } }""#),
+        [
+            (TokenType::InterpolatedStringStart, "\""),
+            (TokenType::OpenInterp, "${"),
+            (TokenType::OpenCurly, "{"),
+            (TokenType::InterpolatedStringStart, "\"in "),
+            (TokenType::OpenInterp, "${"),
+            (TokenType::Identifier, "x"),
+            (TokenType::CloseInterp, "}"),
+            (TokenType::InterpolatedStringEnd, "\""),
+            (TokenType::CloseCurly, "}"),
+            (TokenType::CloseInterp, "}"),
+            (TokenType::InterpolatedStringEnd, "\""),
+        ]
+    );
+}
+
+#[test]
+fn strings_without_interpolations_are_single_literals() {
+    assert_eq!(
+        lex(r#""cost: $5 \${x}""#),
+        [(TokenType::StringLiteral, r#""cost: $5 \${x}""#)]
+    );
+}
+
+#[test]
+fn unterminated_interpolated_strings_end_at_the_newline() {
+    assert_eq!(
+        lex("\"${x} left open\n}"),
+        [
+            (TokenType::InterpolatedStringStart, "\""),
+            (TokenType::OpenInterp, "${"),
+            (TokenType::Identifier, "x"),
+            (TokenType::CloseInterp, "}"),
+            (TokenType::Unknown, " left open"),
+            (TokenType::Newline, "\n"),
+            (TokenType::CloseCurly, "}"),
+        ]
+    );
+}
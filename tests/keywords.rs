@@ -0,0 +1,78 @@
+//! Checks how keywords are classified.
+
+use llvm_compiler::compiler::error::CompilerError;
+use llvm_compiler::compiler::parse_source;
+use llvm_compiler::parse::error::ParseError;
+use llvm_compiler::source::SourceFile;
+use llvm_compiler::token::{Token, TokenType};
+
+const KEYWORDS: &[&str] = &[
+    "func", "struct", "enum", "trait", "impl", "use", "as", "mut", "if", "else", "while", "for",
+    "in", "let", "return", "break", "continue", "match", "move", "async", "await", "yield", "i8",
+    "i16", "i32", "i64", "i128", "u8", "u16", "u32", "u64", "f32", "f64", "bool", "void", "true",
+    "false",
+];
+
+#[test]
+fn keywords_have_at_most_one_kind() {
+    for keyword in KEYWORDS {
+        let ty: TokenType = keyword.parse().unwrap();
+        assert!(Token::from(0, keyword).is_keyword(), "{keyword}");
+
+        let kinds = [
+            ty.is_statement_keyword(),
+            ty.is_type_keyword(),
+            ty.is_value_keyword(),
+        ];
+        assert!(
+            kinds.iter().filter(|kind| **kind).count() <= 1,
+            "{keyword} is {kinds:?}"
+        );
+    }
+}
+
+#[test]
+fn operators_are_not_keywords() {
+    for ty in [TokenType::Plus, TokenType::Bang, TokenType::PipeGt] {
+        assert!(ty.is_expression_operator());
+        assert!(!ty.is_statement_keyword());
+    }
+
+    assert!(!TokenType::Equals.is_expression_operator());
+    assert!(!TokenType::PlusEquals.is_expression_operator());
+}
+
+#[test]
+fn literals_other_than_booleans_are_not_keywords() {
+    for literal in ["42", "1.5", "\"text\"", "'c'"] {
+        assert!(!Token::from(0, literal).is_keyword(), "{literal}");
+    }
+}
+
+#[test]
+fn literals_are_not_reserved_names() {
+    let source = SourceFile::new("names.src", "struct 42 {}\n");
+    let Err(CompilerError::Parse { errors }) = parse_source(&source) else {
+        panic!("expected a syntax error");
+    };
+
+    assert!(
+        matches!(
+            errors[..],
+            [ParseError::SequenceMismatch {
+                expected: TokenType::Identifier,
+                ..
+            }]
+        ),
+        "{errors:#?}"
+    );
+
+    let source = SourceFile::new("names.src", "struct true {}\n");
+    let Err(CompilerError::Parse { errors }) = parse_source(&source) else {
+        panic!("expected a syntax error");
+    };
+    assert!(
+        matches!(errors[..], [ParseError::ReservedName { .. }]),
+        "{errors:#?}"
+    );
+}
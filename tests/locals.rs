@@ -0,0 +1,56 @@
+//! Checks that locals live in stack slots, made in the entry block and used through `load` and
+//! `store`.
+
+use llvm_compiler::compiler::compile_to_llvm_ir;
+use llvm_compiler::source::SourceFile;
+
+fn compile(text: &str) -> String {
+    compile_to_llvm_ir(&SourceFile::new("locals.src", text)).unwrap()
+}
+
+#[test]
+fn each_local_gets_a_slot() {
+    let ir = compile("func main() -> i64 {\n    let a: i32 = 1;\n    let b: i64 = 2;\n    b\n}\n");
+
+    assert!(
+        ir.contains(
+            "entry:
+  %a.addr = alloca i32
+  %b.addr = alloca i64
+  store i32 1, i32* %a.addr
+  store i64 2, i64* %b.addr
+  %tmp0 = load i64, i64* %b.addr
+  ret i64 %tmp0
+"
+        ),
+        "{ir}"
+    );
+}
+
+#[test]
+fn slots_are_all_made_before_the_first_instruction() {
+    let ir = compile(
+        "func main() -> i32 {
+    let mut a: i32 = 1;
+    while a < 3 { let b: i32 = a; a = b + 1; }
+    a
+}
+",
+    );
+
+    assert!(
+        ir.contains("  %a.addr = alloca i32\n  %b.addr = alloca i32\n  store i32 1"),
+        "{ir}"
+    );
+}
+
+#[test]
+fn shadowed_locals_get_slots_of_their_own() {
+    let ir = compile("func main() -> i32 {\n    let a: i32 = 1;\n    let a: i32 = a;\n    a\n}\n");
+
+    assert!(ir.contains("%a.addr1 = alloca i32"), "{ir}");
+    assert!(
+        ir.contains("%tmp0 = load i32, i32* %a.addr\n  store i32 %tmp0, i32* %a.addr1"),
+        "{ir}"
+    );
+}
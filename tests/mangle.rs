@@ -0,0 +1,67 @@
+//! Checks the names given to instances of generic functions.
+
+use llvm_compiler::ast::{Item, TypeExpr};
+use llvm_compiler::codegen::{MangleScheme, MangleStyle};
+use llvm_compiler::compiler::parse_source;
+use llvm_compiler::source::SourceFile;
+
+fn mangle(style: MangleStyle, source: &str, type_args: &[TypeExpr]) -> String {
+    let module = parse_source(&SourceFile::new("mangle.src", source)).unwrap();
+    let Item::FnDecl(decl) = &module.items[0] else {
+        panic!("expected a function");
+    };
+
+    MangleScheme::new(style).mangle(decl, type_args)
+}
+
+fn pointer(inner: TypeExpr) -> TypeExpr {
+    TypeExpr::Pointer {
+        inner: Box::new(inner),
+        mutable: false,
+    }
+}
+
+#[test]
+fn simple_names_append_the_type_arguments() {
+    let max = "func max<T>(a: T, b: T) -> T { return a; }";
+
+    assert_eq!(
+        mangle(MangleStyle::Simple, max, &[TypeExpr::named("i32")]),
+        "max__i32"
+    );
+    assert_eq!(
+        mangle(MangleStyle::Simple, max, &[TypeExpr::named("f64")]),
+        "max__f64"
+    );
+    assert_eq!(
+        mangle(MangleStyle::Simple, max, &[pointer(TypeExpr::named("u8"))]),
+        "max__ptr_u8"
+    );
+    assert_eq!(mangle(MangleStyle::Simple, "func main() {}", &[]), "main");
+}
+
+#[test]
+fn itanium_names_encode_the_signature() {
+    let max = "func max<T>(a: T, b: T) -> T { return a; }";
+    let first = "func first<T, U>(p: *T, u: U) { }";
+    let args = [TypeExpr::named("u8"), TypeExpr::named("Point")];
+
+    assert_eq!(
+        mangle(MangleStyle::Itanium, max, &[TypeExpr::named("i32")]),
+        "_Z3maxIiEiii"
+    );
+    assert_eq!(
+        mangle(MangleStyle::Itanium, first, &args),
+        "_Z5firstIh5PointEvPKh5Point"
+    );
+    assert_eq!(mangle(MangleStyle::Itanium, "func main() {}", &[]), "main");
+}
+
+#[test]
+fn custom_names_are_used_for_every_function() {
+    let style = MangleStyle::Custom(Box::new(|decl, args| {
+        format!("{}.{}", decl.name, args.len())
+    }));
+
+    assert_eq!(mangle(style, "func main() {}", &[]), "main.0");
+}
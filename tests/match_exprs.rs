@@ -0,0 +1,61 @@
+//! Checks how `match` expressions and their arms are parsed.
+
+use llvm_compiler::ast::{LiteralValue, MatchExpr, Pattern};
+use llvm_compiler::ast_printer::print_sexp;
+use llvm_compiler::lexer::AsciiLexer;
+use llvm_compiler::parse::error::ParseError;
+use llvm_compiler::parse::parser::Parser;
+
+fn parse_match(src: &str) -> Result<MatchExpr, ParseError> {
+    let mut tokens = AsciiLexer::new().tokenize(src).unwrap();
+    tokens.retain(|token| !token.is_trivia());
+
+    Parser::new(0, &tokens).parse_match_expr()
+}
+
+#[test]
+fn arms_can_have_guards() {
+    let expr = parse_match("match x { n if n > 0 => 1, _ => 0 }").unwrap();
+
+    assert_eq!(expr.arms.len(), 2);
+    assert_eq!(expr.arms[0].pattern, Pattern::Ident("n".into()));
+    assert_eq!(
+        expr.arms[0].guard.as_ref().map(|guard| print_sexp(guard)),
+        Some("(> (ident n) 0)".to_owned())
+    );
+    assert_eq!(expr.arms[1].pattern, Pattern::Wildcard);
+    assert!(expr.arms[1].guard.is_none());
+}
+
+#[test]
+fn the_last_comma_is_optional() {
+    let with = parse_match("match x { 1 => a, 2 | 3 => b, }").unwrap();
+    let without = parse_match("match x { 1 => a, 2 | 3 => b }").unwrap();
+
+    assert_eq!(with.arms, without.arms);
+    assert_eq!(with.arms[0].pattern, Pattern::Literal(LiteralValue::Int(1)));
+    assert!(matches!(with.arms[1].pattern, Pattern::Or(..)));
+}
+
+#[test]
+fn block_bodies_need_no_comma() {
+    let expr = parse_match("match x { 1 => { a } _ => b }").unwrap();
+
+    assert_eq!(expr.arms.len(), 2);
+}
+
+#[test]
+fn other_bodies_need_a_comma() {
+    assert!(matches!(
+        parse_match("match x { 1 => a 2 => b }"),
+        Err(ParseError::SequenceMismatch { .. })
+    ));
+}
+
+#[test]
+fn matches_can_be_empty() {
+    let expr = parse_match("match x {}").unwrap();
+
+    assert_eq!(print_sexp(&*expr.scrutinee), "(ident x)");
+    assert!(expr.arms.is_empty());
+}
@@ -0,0 +1,60 @@
+//! Checks that a module collects its top-level items in order.
+
+use llvm_compiler::ast::Item;
+use llvm_compiler::compiler::error::CompilerError;
+use llvm_compiler::compiler::parse_source;
+use llvm_compiler::parse::error::ParseError;
+use llvm_compiler::source::SourceFile;
+
+#[test]
+fn functions_are_collected_in_order() {
+    let source = SourceFile::new(
+        "two.src",
+        "func first() -> i32 { return 1; }\nfunc second(x: i32) { }\n",
+    );
+    let module = parse_source(&source).unwrap();
+
+    let names: Vec<_> = module
+        .items
+        .iter()
+        .map(|item| match item {
+            Item::FnDecl(f) => f.name.as_str(),
+            other => panic!("expected a function, got {other:?}"),
+        })
+        .collect();
+    assert_eq!(names, ["first", "second"]);
+    assert_eq!(module.path.to_str(), Some("two.src"));
+}
+
+#[test]
+fn every_kind_of_item_is_dispatched() {
+    let source = SourceFile::new(
+        "items.src",
+        "use std::io;\nstruct Point { x: i32 }\nfunc main() { }\n",
+    );
+    let module = parse_source(&source).unwrap();
+
+    assert!(matches!(
+        module.items.as_slice(),
+        [Item::UseDecl(_), Item::StructDecl(_), Item::FnDecl(_)]
+    ));
+}
+
+#[test]
+fn empty_sources_have_no_items() {
+    let module = parse_source(&SourceFile::new("empty.src", "")).unwrap();
+    assert!(module.items.is_empty());
+}
+
+#[test]
+fn anything_else_at_the_top_level_is_an_error() {
+    let Err(CompilerError::Parse { errors }) = parse_source(&SourceFile::new("bad.src", "1 + 2"))
+    else {
+        panic!("expected a syntax error");
+    };
+
+    assert!(
+        matches!(errors[0], ParseError::ExpectedItem { .. }),
+        "{errors:?}"
+    );
+}
@@ -0,0 +1,96 @@
+//! Checks that generic functions are instantiated once for each set of type arguments they're
+//! called with.
+
+use llvm_compiler::compiler::error::CompilerError;
+use llvm_compiler::compiler::{check_source, compile_to_llvm_ir, parse_source};
+use llvm_compiler::source::SourceFile;
+use llvm_compiler::typeck::error::TypeError;
+use llvm_compiler::typeck::symbols::SymbolTable;
+use llvm_compiler::typeck::{resolve_names, type_check, Monomorphizer};
+
+const GENERIC_MAX: &str = "func max<T>(a: T, b: T) -> T {
+    if a > b { a } else { b }
+}
+
+func main() -> i32 {
+    let x: i32 = max(1, 2);
+    let y: f64 = max(1.5, 0.5);
+    let z: i32 = max(x, 3);
+    return z;
+}
+";
+
+#[test]
+fn each_set_of_type_arguments_is_instantiated_once() {
+    let module = parse_source(&SourceFile::new("max.src", GENERIC_MAX)).unwrap();
+    let mut syms = SymbolTable::new();
+    resolve_names(&module, &mut syms).unwrap();
+    let typed = type_check(&module, &syms).unwrap();
+
+    let mut monomorphizer = Monomorphizer::new();
+    let mono = monomorphizer.monomorphize(&typed).unwrap();
+
+    let mut keys: Vec<_> = monomorphizer.instances().keys().cloned().collect();
+    keys.sort_by_key(|(_, args)| args[0].to_string());
+    assert_eq!(keys.len(), 2);
+    assert_eq!(keys[0].1[0].to_string(), "f64");
+    assert_eq!(keys[1].1[0].to_string(), "i32");
+
+    let names: Vec<_> = mono.functions.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(names, ["main", "max__i32", "max__f64"]);
+}
+
+#[test]
+fn instances_call_other_instances() {
+    let source = SourceFile::new(
+        "nested.src",
+        "func id<T>(x: T) -> T { x }
+func twice<U>(x: U) -> U { id(id(x)) }
+func main() -> i32 { twice(7) + twice(1) }
+",
+    );
+    let typed = check_source(&source).unwrap();
+
+    let names: Vec<_> = typed.functions.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(names, ["main", "twice__i32", "id__i32"]);
+    assert!(typed.generic_fns.is_empty());
+}
+
+#[test]
+fn generic_functions_compile_to_concrete_ones() {
+    let ir = compile_to_llvm_ir(&SourceFile::new("max.src", GENERIC_MAX)).unwrap();
+
+    assert!(ir.contains("define i32 @max__i32(i32 "), "{ir}");
+    assert!(ir.contains("define double @max__f64(double "), "{ir}");
+    assert!(!ir.contains("@max("), "{ir}");
+}
+
+#[test]
+fn mistakes_in_generic_bodies_are_found_in_instances() {
+    let source = SourceFile::new(
+        "bad.src",
+        "func first<T>(x: T) -> T { return true; }
+func main() -> i32 { first(1) }
+",
+    );
+
+    assert!(check_source(&source).is_err());
+}
+
+#[test]
+fn type_arguments_must_be_inferrable() {
+    let source = SourceFile::new(
+        "unused.src",
+        "func make<T>() -> i32 { 0 }
+func main() -> i32 { make() }
+",
+    );
+    let Err(CompilerError::Types { errors }) = check_source(&source) else {
+        panic!("expected a type error");
+    };
+
+    assert!(
+        matches!(&errors[..], [TypeError::CannotInferTypeArgument { param, .. }] if param == "T"),
+        "{errors:?}"
+    );
+}
@@ -0,0 +1,28 @@
+//! Checks that a mutator can replace expressions anywhere in a module.
+
+use llvm_compiler::ast::mutator::{AstMutator, ConstantFoldMutator};
+use llvm_compiler::ast_printer::print_sexp;
+use llvm_compiler::compiler::parse_source;
+use llvm_compiler::source::SourceFile;
+
+#[test]
+fn constant_operations_are_folded() {
+    let source = SourceFile::new(
+        "mutator.src",
+        "func main(x: i32) -> i32 {
+    let y = (2 + 3) * x;
+    if 1 < 2 { return y; }
+    return (10 / 0) + (2 + 3) * (4 - 1);
+}
+",
+    );
+    let mut module = parse_source(&source).unwrap();
+
+    ConstantFoldMutator.visit_module_mut(&mut module);
+
+    assert_eq!(
+        print_sexp(&module.items[0]),
+        "(fn main (params (x i32)) (ret i32) (let y (* 5 (ident x))) \
+         (expr (if true (block (return (ident y))))) (return (+ (/ 10 0) 15)))"
+    );
+}
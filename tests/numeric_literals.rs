@@ -0,0 +1,35 @@
+//! Checks reading the values of numeric literal tokens.
+
+use llvm_compiler::lexer::AsciiLexer;
+use llvm_compiler::source::SourceFile;
+
+#[test]
+fn numeric_literals_give_their_values() {
+    let source = SourceFile::new(
+        "numbers.src",
+        "0x1F 1_000 2.5 1e3 x 340282366920938463463374607431768211456",
+    );
+    let tokens = AsciiLexer::new().tokenize(source.text()).unwrap();
+    let tokens: Vec<_> = tokens
+        .into_iter()
+        .filter(|token| !token.is_trivia())
+        .collect();
+
+    let numeric: Vec<_> = tokens
+        .iter()
+        .map(|token| token.is_numeric_literal())
+        .collect();
+    assert_eq!(numeric, [true, true, true, true, false, true]);
+
+    let ints: Vec<_> = tokens
+        .iter()
+        .map(|token| token.numeric_value(&source))
+        .collect();
+    assert_eq!(ints, [Some(31), Some(1000), None, None, None, None]);
+
+    let floats: Vec<_> = tokens
+        .iter()
+        .map(|token| token.float_value(&source))
+        .collect();
+    assert_eq!(floats, [None, None, Some(2.5), Some(1000.0), None, None]);
+}
@@ -0,0 +1,36 @@
+//! Checks that modules compile to native object files. Like the `llvm` feature itself, this
+//! needs `llc` on `PATH`.
+#![cfg(feature = "llvm")]
+
+use std::fs;
+
+use llvm_compiler::codegen::error::CodegenError;
+use llvm_compiler::compiler::compile_to_object_file;
+use llvm_compiler::compiler::error::CompilerError;
+use llvm_compiler::source::SourceFile;
+
+fn source() -> SourceFile {
+    SourceFile::new("main.src", "func main() -> i32 { return 0; }\n")
+}
+
+#[test]
+fn object_files_are_written_for_the_host() {
+    let output = std::env::temp_dir().join(format!("object_file_{}.o", std::process::id()));
+
+    compile_to_object_file(&source(), &output, None).unwrap();
+
+    let written = fs::metadata(&output).unwrap().len();
+    fs::remove_file(&output).unwrap();
+    assert!(written > 0);
+}
+
+#[test]
+fn unknown_targets_are_unsupported() {
+    let output = std::env::temp_dir().join(format!("object_file_{}_bad.o", std::process::id()));
+
+    assert!(matches!(
+        compile_to_object_file(&source(), &output, Some("nonsense-unknown-none")),
+        Err(CompilerError::Codegen(CodegenError::UnsupportedTarget(triple)))
+            if triple == "nonsense-unknown-none"
+    ));
+}
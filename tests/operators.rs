@@ -0,0 +1,71 @@
+//! Checks how operators are lexed, and how tightly they bind.
+
+use llvm_compiler::ast_printer::print_sexp;
+use llvm_compiler::compiler::parse_source;
+use llvm_compiler::lexer::AsciiLexer;
+use llvm_compiler::source::SourceFile;
+use llvm_compiler::token::{Token, TokenType};
+
+/// The type and text of every token in `src`.
+fn lex(src: &str) -> Vec<(TokenType, &str)> {
+    AsciiLexer::new()
+        .tokenize(src)
+        .unwrap()
+        .into_iter()
+        .map(|token| (token.ty(), token.as_str()))
+        .collect()
+}
+
+#[test]
+fn dots_are_matched_longest_first() {
+    assert_eq!(lex("..."), [(TokenType::Ellipsis, "...")]);
+    assert_eq!(lex(".."), [(TokenType::DotDot, "..")]);
+    assert_eq!(lex("."), [(TokenType::Dot, ".")]);
+}
+
+#[test]
+fn four_dots_are_an_ellipsis_then_a_dot() {
+    assert_eq!(
+        lex("...."),
+        [(TokenType::Ellipsis, "..."), (TokenType::Dot, ".")]
+    );
+}
+
+#[test]
+fn bitwise_operators_are_lexed_on_their_own() {
+    assert_eq!(
+        lex("~x"),
+        [(TokenType::Tilde, "~"), (TokenType::Identifier, "x")]
+    );
+    assert_eq!(
+        lex("a^b"),
+        [
+            (TokenType::Identifier, "a"),
+            (TokenType::Caret, "^"),
+            (TokenType::Identifier, "b"),
+        ]
+    );
+}
+
+#[test]
+fn only_tilde_and_bang_are_unary() {
+    assert!(Token::from(0, "~").is_unary_operator());
+    assert!(Token::from(0, "!").is_unary_operator());
+    assert!(!Token::from(0, "^").is_unary_operator());
+    assert!(!Token::from(0, "&").is_unary_operator());
+}
+
+#[test]
+fn and_binds_tighter_than_xor_which_binds_tighter_than_or() {
+    let precedence = |op| Token::from(0, op).operator_precedence().unwrap();
+    assert!(precedence("&") > precedence("^"));
+    assert!(precedence("^") > precedence("|"));
+
+    let source = SourceFile::new("bits.src", "func f() -> i32 { return a | b ^ c & d; }");
+    let module = parse_source(&source).unwrap();
+    let sexp = print_sexp(&module);
+    assert!(
+        sexp.contains("(return (bit-or (ident a) (^ (ident b) (& (ident c) (ident d)))))"),
+        "{sexp}"
+    );
+}
@@ -0,0 +1,38 @@
+//! Checks that the IR we emit goes through LLVM's optimizer. Like the `llvm` feature itself,
+//! this needs `opt` on `PATH`.
+#![cfg(feature = "llvm")]
+
+use llvm_compiler::codegen::error::OptimizationError;
+use llvm_compiler::codegen::optimize::{optimize_llvm_ir, OptLevel};
+use llvm_compiler::compiler::compile_to_llvm_ir;
+use llvm_compiler::source::SourceFile;
+
+#[test]
+fn stack_slots_are_promoted_to_registers() {
+    let source = SourceFile::new(
+        "add.src",
+        "func add(a: i32, b: i32) -> i32 {\n    let sum: i32 = a + b;\n    sum\n}\n",
+    );
+    let ir = compile_to_llvm_ir(&source).unwrap();
+    assert!(ir.contains("alloca"), "{ir}");
+
+    let optimized = optimize_llvm_ir(&ir, OptLevel::O1).unwrap();
+
+    assert!(!optimized.contains("alloca"), "{optimized}");
+    assert!(optimized.contains("ret i32 %tmp2"), "{optimized}");
+}
+
+#[test]
+fn nothing_changes_at_o0() {
+    let optimized = optimize_llvm_ir("define void @f() {\n  ret void\n}\n", OptLevel::O0).unwrap();
+
+    assert!(optimized.contains("define void @f()"), "{optimized}");
+}
+
+#[test]
+fn invalid_ir_is_rejected() {
+    assert!(matches!(
+        optimize_llvm_ir("define i32 @f() {\n  ret i32\n}\n", OptLevel::O2),
+        Err(OptimizationError::Llvm(message)) if message.contains("error")
+    ));
+}
@@ -0,0 +1,52 @@
+//! Checks that tokens survive being packed into a `u64` and rebuilt from their source.
+
+use llvm_compiler::lexer::error::TokenizationError;
+use llvm_compiler::lexer::AsciiLexer;
+use llvm_compiler::token;
+use llvm_compiler::token::{pack_u64, unpack_u64, Token, TokenType, MAX_PACKED_LEN};
+
+#[test]
+fn lexed_tokens_round_trip_through_u64() {
+    let src = "func main() -> i32 {\n    let s = \"hi\"; // done\n    return 0x1F;\n}\n";
+    let tokens = AsciiLexer::new().tokenize(src).unwrap();
+
+    for token in tokens {
+        let packed = u64::try_from(token).unwrap();
+        let unpacked = Token::from_packed(packed, src).unwrap();
+
+        assert_eq!(unpacked.ty(), token.ty());
+        assert_eq!(unpacked.span(), token.span());
+    }
+}
+
+#[test]
+fn packing_is_the_inverse_of_unpacking() {
+    const PACKED: u64 = pack_u64(1234, MAX_PACKED_LEN, TokenType::Unknown as u8);
+
+    assert_eq!(
+        unpack_u64(PACKED),
+        (1234, MAX_PACKED_LEN, TokenType::Unknown as u8)
+    );
+}
+
+#[test]
+fn tokens_that_do_not_fit_are_not_packed() {
+    let long = "x".repeat(MAX_PACKED_LEN as usize + 1);
+    let too_long: Token = token!(Identifier(&long) @ 0);
+    assert!(matches!(
+        u64::try_from(too_long),
+        Err(TokenizationError::TooLargeToPack { .. })
+    ));
+
+    let too_far: Token = token!(Identifier("x") @ u32::MAX as usize + 1);
+    assert!(u64::try_from(too_far).is_err());
+}
+
+#[test]
+fn tokens_only_unpack_from_the_same_source() {
+    let packed = u64::try_from(token!("+" @ 1)).unwrap();
+
+    assert!(Token::from_packed(packed, "a+b").is_some());
+    assert!(Token::from_packed(packed, "a-b").is_none());
+    assert!(Token::from_packed(packed, "a").is_none());
+}
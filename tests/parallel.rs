@@ -0,0 +1,60 @@
+//! Checks that lexing in parallel gives the same tokens as lexing on one thread.
+#![cfg(feature = "parallel")]
+
+use std::path::Path;
+
+use llvm_compiler::lexer::error::TokenizationError;
+use llvm_compiler::lexer::AsciiLexer;
+use llvm_compiler::span::Span;
+use llvm_compiler::token::{Token, TokenType};
+
+fn spans(tokens: &[Token]) -> Vec<(TokenType, Span)> {
+    tokens
+        .iter()
+        .map(|token| (token.ty(), token.span()))
+        .collect()
+}
+
+#[test]
+fn files_lex_the_same_on_several_threads() {
+    let path = Path::new("examples/example_1000_lines.src");
+    let src = std::fs::read_to_string(path).unwrap();
+    let expected = AsciiLexer::new().tokenize(&src).unwrap();
+
+    for threads in [1, 3, 8] {
+        let mut buf = String::new();
+        let tokens = AsciiLexer::new()
+            .tokenize_file_parallel(path, threads, &mut buf)
+            .unwrap();
+
+        let spans: Vec<_> = tokens
+            .iter()
+            .map(|token| (token.ty(), token.span()))
+            .collect();
+        let expected: Vec<_> = expected
+            .iter()
+            .map(|token| (token.ty(), token.span()))
+            .collect();
+        assert_eq!(spans, expected, "{threads} threads");
+    }
+}
+
+#[test]
+fn block_comments_across_chunks_are_lexed_whole() {
+    let src = format!("a\n/*{}*/\nb\n", "\nline".repeat(100));
+    let tokens = AsciiLexer::new().tokenize_parallel(&src, 8).unwrap();
+
+    assert_eq!(
+        spans(&tokens),
+        spans(&AsciiLexer::new().tokenize(&src).unwrap())
+    );
+}
+
+#[test]
+fn missing_files_are_errors() {
+    let mut buf = String::new();
+    let result =
+        AsciiLexer::new().tokenize_file_parallel(Path::new("examples/missing.src"), 2, &mut buf);
+
+    assert!(matches!(result, Err(TokenizationError::ReadFile { .. })));
+}
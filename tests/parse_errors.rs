@@ -0,0 +1,129 @@
+//! Checks that the parser recovers from syntax errors, reporting every broken item at once.
+
+use llvm_compiler::ast_printer::print_sexp;
+use llvm_compiler::compiler::error::CompilerError;
+use llvm_compiler::compiler::parse_source;
+use llvm_compiler::lexer::AsciiLexer;
+use llvm_compiler::parse::error::ParseError;
+use llvm_compiler::parse::parser::{Parser, RecoveryAction};
+use llvm_compiler::source::SourceFile;
+use llvm_compiler::token::TokenType;
+
+#[test]
+fn every_broken_item_is_reported() {
+    let source = SourceFile::new(
+        "errors.src",
+        "func first() -> i32 { let = 1; return 1; }
+struct Point { x: i32, y }
+func second() { return 2 + ; }
+func fine() -> i32 { return 3; }
+",
+    );
+
+    let Err(CompilerError::Parse { errors }) = parse_source(&source) else {
+        panic!("expected syntax errors");
+    };
+
+    assert_eq!(errors.len(), 3, "{errors:#?}");
+}
+
+#[test]
+fn parsing_picks_back_up_at_globals() {
+    let source = SourceFile::new(
+        "errors.src",
+        "struct Point { x: i32, y }
+let mut count: i32 = ;
+func fine() -> i32 { return 3; }
+",
+    );
+
+    let Err(CompilerError::Parse { errors }) = parse_source(&source) else {
+        panic!("expected syntax errors");
+    };
+
+    // Skipping to the next item keyword would have passed over the broken `let`
+    assert_eq!(errors.len(), 2, "{errors:#?}");
+}
+
+fn parse_with_recovery(
+    text: &str,
+) -> (
+    Result<llvm_compiler::ast::Module, Vec<ParseError>>,
+    Vec<RecoveryAction>,
+) {
+    let mut tokens = AsciiLexer::new().tokenize(text).unwrap();
+    tokens.retain(|token| !token.is_trivia());
+
+    let mut parser = Parser::new(0, &tokens).with_error_recovery(true);
+    let module = parser.parse_module();
+
+    (module, parser.recoveries)
+}
+
+#[test]
+fn recovery_inserts_missing_tokens() {
+    let (module, recoveries) = parse_with_recovery(
+        "func first) -> i32 { let x = 1 return x; }
+func second() -> i32 { return 2; }
+",
+    );
+
+    let module = module.expect("recovered mistakes shouldn't fail parsing");
+    assert_eq!(module.items.len(), 2);
+
+    let inserted: Vec<_> = recoveries
+        .iter()
+        .map(|recovery| (recovery.inserted, recovery.instead_of))
+        .collect();
+    assert_eq!(
+        inserted,
+        [
+            (TokenType::OpenBracket, TokenType::CloseBracket),
+            (TokenType::SemiColon, TokenType::Return),
+        ]
+    );
+    assert!(matches!(
+        recoveries[0].to_error(),
+        ParseError::InsertedToken {
+            inserted: TokenType::OpenBracket,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn statements_missing_a_semicolon_are_kept() {
+    let (module, recoveries) = parse_with_recovery("func main() { foo() bar(); }\n");
+
+    let module = module.expect("a missing `;` can be inserted");
+    assert_eq!(recoveries.len(), 1);
+
+    let sexp = print_sexp(&module);
+    assert!(
+        sexp.contains("(expr (call (ident foo))) (expr (call (ident bar)))"),
+        "{sexp}"
+    );
+}
+
+#[test]
+fn recovery_reports_mistakes_alongside_other_errors() {
+    let (module, recoveries) = parse_with_recovery(
+        "func first() -> i32 { let x = 1 return x; }
+func second() { let = 2; }
+",
+    );
+
+    let errors = module.expect_err("a missing name can't be inserted");
+    assert_eq!(recoveries.len(), 1);
+    assert_eq!(errors.len(), 2, "{errors:#?}");
+}
+
+#[test]
+fn recovery_is_off_by_default() {
+    let source = SourceFile::new("missing.src", "func first) -> i32 { return 1; }\n");
+
+    let Err(CompilerError::Parse { errors }) = parse_source(&source) else {
+        panic!("expected a syntax error");
+    };
+    assert_eq!(errors.len(), 1, "{errors:#?}");
+}
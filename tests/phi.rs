@@ -0,0 +1,54 @@
+//! Checks that the values of `if` expressions are merged with `phi` nodes.
+
+use llvm_compiler::ast::TypeExpr;
+use llvm_compiler::codegen::PhiNode;
+use llvm_compiler::compiler::compile_to_llvm_ir;
+use llvm_compiler::source::SourceFile;
+
+fn compile(text: &str) -> String {
+    compile_to_llvm_ir(&SourceFile::new("phi.src", text)).unwrap()
+}
+
+#[test]
+fn phis_come_from_the_blocks_that_reach_them() {
+    let ir = compile(
+        "func pick(a: i32, b: i32) -> i32 {
+    if a < b { if b < 10 { b } else { 10 } } else { a }
+}
+",
+    );
+
+    assert!(
+        ir.contains("phi i32 [ %tmp5, %then3 ], [ 10, %else5 ]"),
+        "{ir}"
+    );
+    // The outer `then` ends in the block where the inner `if` meets again
+    assert!(
+        ir.contains("phi i32 [ %tmp6, %merge4 ], [ %tmp7, %else2 ]"),
+        "{ir}"
+    );
+    assert!(!ir.contains("if.result"), "{ir}");
+}
+
+#[test]
+fn branches_that_return_dont_reach_the_phi() {
+    let ir = compile("func f(a: i32) -> i32 {\n    if a < 0 { a } else { return 0; }\n}\n");
+
+    assert!(ir.contains("phi i32 [ %tmp2, %then0 ]\n"), "{ir}");
+}
+
+#[test]
+fn phi_instructions_list_each_incoming_value() {
+    let ty = TypeExpr::Named {
+        name: "bool".into(),
+        generics: vec![],
+    };
+    let mut phi = PhiNode::new("%tmp2", ty);
+    phi.add_incoming("true", "then0");
+    phi.add_incoming("%tmp1", "else2");
+
+    assert_eq!(
+        phi.instruction(),
+        "%tmp2 = phi i1 [ true, %then0 ], [ %tmp1, %else2 ]"
+    );
+}
@@ -0,0 +1,107 @@
+//! Checks that modules are formatted canonically and parse back to the same module.
+
+use llvm_compiler::ast_printer::print_sexp;
+use llvm_compiler::compiler::parse_source;
+use llvm_compiler::prettyprint::Prettier;
+use llvm_compiler::source::SourceFile;
+
+fn format(text: &str, prettier: Prettier) -> String {
+    let module = parse_source(&SourceFile::new("pretty.src", text)).unwrap();
+    prettier.format_module(&module)
+}
+
+/// Format `text`, checking the result parses to the same module and formats the same again.
+fn round_trip(text: &str) -> String {
+    let before = parse_source(&SourceFile::new("pretty.src", text)).unwrap();
+    let formatted = Prettier::new().format_module(&before);
+
+    let after = parse_source(&SourceFile::new("pretty.src", formatted.as_str()))
+        .unwrap_or_else(|err| panic!("{err:?} in\n{formatted}"));
+    assert_eq!(print_sexp(&before), print_sexp(&after), "\n{formatted}");
+    assert_eq!(Prettier::new().format_module(&after), formatted);
+
+    formatted
+}
+
+#[test]
+fn functions_are_laid_out_canonically() {
+    let formatted = format(
+        "func   max(a:i32,b :i32)->i32{if a>b{a}else{b}}
+pub func log(  msg: *u8 ) { print(msg)  ; if msg < 1 {return;} }",
+        Prettier::new(),
+    );
+
+    assert_eq!(
+        formatted,
+        "func max(a: i32, b: i32) -> i32 {
+    if a > b {
+        a
+    } else {
+        b
+    }
+}
+
+pub func log(msg: *u8) {
+    print(msg);
+    if msg < 1 {
+        return;
+    }
+}
+"
+    );
+}
+
+#[test]
+fn long_signatures_put_each_parameter_on_its_own_line() {
+    let formatted = format(
+        "func blend(first: *u8, second: *u8, amount: f64) -> u8 { 0 }",
+        Prettier::new().with_max_width(40).with_indent_width(2),
+    );
+
+    assert_eq!(
+        formatted,
+        "func blend(
+  first: *u8,
+  second: *u8,
+  amount: f64,
+) -> u8 {
+  0
+}
+"
+    );
+}
+
+#[test]
+fn parentheses_are_kept_only_where_needed() {
+    let formatted = round_trip(
+        "func f(a: i32, b: i32) -> i32 { ((a + b)) * (a - (b - 1)) + (-(a * b)) + ((a * b) - b) }",
+    );
+
+    assert!(formatted.contains("(a + b) * (a - (b - 1)) + -(a * b) + (a * b - b)"));
+}
+
+#[test]
+fn every_item_parses_back_the_same() {
+    round_trip(
+        r#"use std::io::print; use std::mem as m;
+const LIMIT: u32 = 10; let mut count: i64;
+type Pair<T> = (T, T);
+struct Point<T: Show + Copy> { x: T; y: T }
+enum Shape { Dot, Circle(f64), Rect { w: f64, h: f64 }, }
+trait Show { func show(self: *u8) -> *u8; func name() -> *u8 { "show\n\"me\"" } }
+impl<T> Show<T> for Point<T> { pub func show(self: *u8) -> *u8 { self } }
+extern "C" func puts(s: *u8) -> i32;
+unsafe func raw(p: *mut u8) { memset(p, 0, sizeof([u8; 4])); }
+func control(n: i64) -> i64 {
+    let f = move |x: i64| x + 1; let g = || 'c';
+    for (let mut i = 0; i < n; i += 1) { continue; }
+    for (;;) { break; }
+    'outer: for mut x in xs { if x > 2.5 { break 'outer x; } else if x < 1e20 { -x; } }
+    'done: { match n { 0 | -1 => 1, m if m > 5 => { m } _ => n |> f, } }
+    while !done { count = count - 1; }
+    if n < 0 { n; }; -n;
+    (f)(n).field[2] + (|x| x)(1)
+}
+"#,
+    );
+}
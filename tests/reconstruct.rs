@@ -0,0 +1,70 @@
+//! Checks that lexing loses nothing, by rebuilding each source in a corpus from its tokens.
+
+use std::fs;
+
+use llvm_compiler::lexer::error::TokenizationError;
+use llvm_compiler::lexer::{reconstruct_source, reconstruct_source_strict, AsciiLexer};
+use llvm_compiler::token;
+
+const SNIPPETS: &[&str] = &[
+    "",
+    " ",
+    "x",
+    "func main() -> i32 { return 0; }",
+    "let x = 1.5e3 + 0x1F\r\n",
+    "\"unterminated\nlet 'outer: while true { break 'outer; }",
+    "/* unterminated block comment",
+    "/// doc\nfunc f() {}\n",
+    "let s = \"Hello, ${name}!\" |> print",
+    "let c = '\\n'; let d = 'é'; // ünïcödé",
+    "a....b ...c ::d => e -> f || g",
+    "@ # ` $ ?",
+];
+
+/// The snippets above, and every source in the fixtures and examples.
+fn corpus() -> Vec<String> {
+    let mut sources: Vec<String> = SNIPPETS.iter().map(|src| src.to_string()).collect();
+
+    for dir in ["tests/fixtures", "examples"] {
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().is_some_and(|ext| ext == "src") {
+                sources.push(fs::read_to_string(path).unwrap());
+            }
+        }
+    }
+
+    sources
+}
+
+#[test]
+fn every_source_round_trips() {
+    for src in corpus() {
+        let tokens = AsciiLexer::new().tokenize(&src).unwrap();
+
+        assert_eq!(reconstruct_source(&tokens, &src), src);
+        assert_eq!(reconstruct_source_strict(&tokens, &src).unwrap(), src);
+    }
+}
+
+#[test]
+fn gaps_are_filled_from_the_source() {
+    let src = "a + b";
+    let tokens = [token!(Identifier("a") @ 0), token!(Identifier("b") @ 4)];
+
+    assert_eq!(reconstruct_source(&tokens, src), src);
+    assert!(matches!(
+        reconstruct_source_strict(&tokens, src),
+        Err(TokenizationError::Gap { .. })
+    ));
+}
+
+#[test]
+fn tokens_from_other_text_do_not_panic() {
+    let tokens = [token!(Identifier("far") @ 100)];
+    assert_eq!(reconstruct_source(&tokens, "é"), "éfar");
+
+    // `1` is inside the `é`, which takes two bytes
+    let tokens = [token!(Identifier("x") @ 1)];
+    assert_eq!(reconstruct_source(&tokens, "é"), "x");
+}
@@ -0,0 +1,70 @@
+//! Checks finding the statement around a token.
+
+use llvm_compiler::lexer::AsciiLexer;
+use llvm_compiler::scan::surrounding_statement;
+use llvm_compiler::token::Token;
+
+fn lex(src: &str) -> Vec<Token<'_>> {
+    let mut tokens = AsciiLexer::new().tokenize(src).unwrap();
+    tokens.retain(|token| !token.is_trivia());
+    tokens
+}
+
+/// The text of the statement around the first token spelled `word`.
+fn statement_around<'a>(tokens: &[Token<'a>], word: &str) -> Option<Vec<&'a str>> {
+    let idx = tokens.iter().position(|token| token.as_str() == word)?;
+
+    surrounding_statement(tokens, idx)
+        .map(|statement| statement.iter().map(Token::as_str).collect())
+}
+
+const STATEMENTS: &str = "let a = 1; b = a + 2; return b";
+
+#[test]
+fn the_first_statement_starts_the_stream() {
+    let tokens = lex(STATEMENTS);
+
+    assert_eq!(
+        statement_around(&tokens, "let"),
+        Some(vec!["let", "a", "=", "1"])
+    );
+    assert_eq!(
+        statement_around(&tokens, "1"),
+        Some(vec!["let", "a", "=", "1"])
+    );
+}
+
+#[test]
+fn middle_statements_run_between_semicolons() {
+    let tokens = lex(STATEMENTS);
+
+    assert_eq!(
+        statement_around(&tokens, "+"),
+        Some(vec!["b", "=", "a", "+", "2"])
+    );
+}
+
+#[test]
+fn the_last_statement_ends_the_stream() {
+    let tokens = lex(STATEMENTS);
+
+    assert_eq!(
+        statement_around(&tokens, "return"),
+        Some(vec!["return", "b"])
+    );
+}
+
+#[test]
+fn statements_also_end_at_closing_braces() {
+    let tokens = lex("if x { y(); } z()");
+
+    assert_eq!(statement_around(&tokens, "z"), Some(vec!["z", "(", ")"]));
+}
+
+#[test]
+fn boundaries_have_no_statement() {
+    let tokens = lex(STATEMENTS);
+
+    assert_eq!(statement_around(&tokens, ";"), None);
+    assert!(surrounding_statement(&tokens, tokens.len()).is_none());
+}
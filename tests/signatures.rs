@@ -0,0 +1,56 @@
+//! Checks that functions called without being defined in the module are declared.
+
+use llvm_compiler::ast::TypeExpr;
+use llvm_compiler::codegen::{emit_llvm_ir, FnSignature, SignatureRegistry};
+use llvm_compiler::compiler::check_source;
+use llvm_compiler::source::SourceFile;
+
+const SOURCE: &str = "extern func puts(s: *u8) -> i32;
+func twice(x: i64) -> i64 { return x + x; }
+func main() -> i64 { puts(\"hi\"); twice(1) + twice(2) }
+";
+
+#[test]
+fn registry_has_every_function() {
+    let module = check_source(&SourceFile::new("signatures.src", SOURCE)).unwrap();
+    let registry = SignatureRegistry::from_module(&module);
+
+    assert_eq!(registry.len(), 3);
+    assert_eq!(
+        registry.get("twice"),
+        Some(&FnSignature {
+            name: "twice".into(),
+            params: vec![TypeExpr::named("i64")],
+            ret: TypeExpr::named("i64"),
+            variadic: false,
+        })
+    );
+}
+
+#[test]
+fn missing_functions_are_declared_once() {
+    let mut module = check_source(&SourceFile::new("signatures.src", SOURCE)).unwrap();
+
+    let ir = emit_llvm_ir(&module).unwrap();
+    assert_eq!(ir.matches("declare").count(), 1);
+
+    module.functions.retain(|function| function.name != "twice");
+    let ir = emit_llvm_ir(&module).unwrap();
+    assert_eq!(ir.matches("declare i64 @twice(i64)").count(), 1);
+    assert_eq!(ir.matches("declare").count(), 2);
+}
+
+#[test]
+fn variadic_signatures_end_in_an_ellipsis() {
+    let signature = FnSignature {
+        name: "printf".into(),
+        params: vec![TypeExpr::Pointer {
+            inner: Box::new(TypeExpr::named("u8")),
+            mutable: false,
+        }],
+        ret: TypeExpr::named("i32"),
+        variadic: true,
+    };
+
+    assert_eq!(signature.declaration(), "declare i32 @printf(i8*, ...)");
+}
@@ -0,0 +1,94 @@
+//! Checks finding tokens by their position in the source, and highlighting ranges of it.
+
+use llvm_compiler::lexer::AsciiLexer;
+use llvm_compiler::source::{Color, HighlightStyle, SourceFile};
+use llvm_compiler::token::Token;
+
+#[test]
+fn tokens_are_found_by_offset() {
+    let text = "let value = 12;";
+    let all = AsciiLexer::new().tokenize(text).unwrap();
+    let tokens: Vec<Token> = all.into_iter().filter(|token| !token.is_trivia()).collect();
+
+    let found = |offset| SourceFile::find_token_at_offset(&tokens, offset).map(Token::as_str);
+    assert_eq!(found(0), Some("let"));
+    assert_eq!(found(6), Some("value"));
+    assert_eq!(found(3), None);
+    assert_eq!(found(14), Some(";"));
+    assert_eq!(found(15), None);
+
+    let in_range: Vec<_> = SourceFile::find_tokens_in_range(&tokens, 5, 13)
+        .iter()
+        .map(Token::as_str)
+        .collect();
+    assert_eq!(in_range, ["value", "=", "12"]);
+    assert!(SourceFile::find_tokens_in_range(&tokens, 3, 4).is_empty());
+}
+
+#[test]
+fn ranges_are_highlighted_on_each_line() {
+    let source = SourceFile::new("highlight.src", "let a = 1;\nlet bc = a + 2;\n");
+    let style = HighlightStyle {
+        fg: Some(Color::Red),
+        bold: true,
+        ..HighlightStyle::default()
+    };
+
+    assert_eq!(
+        source.highlight_range(4, 5, &style),
+        "let \x1b[1;31ma\x1b[0m = 1;"
+    );
+    assert_eq!(
+        source.highlight_range(8, 17, &style),
+        "let a = \x1b[1;31m1;\x1b[0m\n\x1b[1;31mlet bc\x1b[0m = a + 2;"
+    );
+    // Ending at the start of a line doesn't include it
+    assert_eq!(
+        source.highlight_range(4, 11, &HighlightStyle::no_color()),
+        "let a = 1;\n    ^^^^^^"
+    );
+}
+
+#[test]
+fn long_lines_are_truncated() {
+    let text = format!("let x = {};", "1".repeat(200));
+    let source = SourceFile::new("long.src", text);
+    let style = HighlightStyle {
+        underline: true,
+        ..HighlightStyle::default()
+    };
+
+    let highlighted = source.highlight_range(0, 3, &style);
+    assert!(highlighted.starts_with("\x1b[4mlet\x1b[0m x = 111"));
+    assert!(highlighted.ends_with('…'));
+    assert_eq!(highlighted.chars().count(), 120 + "\x1b[4m\x1b[0m".len());
+}
+
+#[test]
+fn ranges_outside_the_text_are_cut_down() {
+    let source = SourceFile::new("short.src", "let a = 1;\n");
+    let style = HighlightStyle::no_color();
+
+    assert_eq!(source.highlight_range(100, 200, &style), "");
+    assert_eq!(
+        source.highlight_range(8, 200, &style),
+        "let a = 1;\n        ^^"
+    );
+    assert_eq!(source.highlight_range(9, 2, &style), "let a = 1;");
+}
+
+#[test]
+fn ranges_are_widened_to_whole_characters() {
+    // `é` takes bytes 9 and 10
+    let source = SourceFile::new("unicode.src", "let s = 'é';\n");
+    let style = HighlightStyle::no_color();
+
+    assert_eq!(
+        source.highlight_range(10, 11, &style),
+        "let s = 'é';\n         ^"
+    );
+    assert_eq!(
+        source.highlight_range(9, 10, &style),
+        "let s = 'é';\n         ^"
+    );
+}
@@ -0,0 +1,81 @@
+//! Checks the properties of spans, over every span with small offsets.
+
+use llvm_compiler::span::Span;
+
+/// Every span starting and ending within `0..=6`.
+fn spans() -> Vec<Span> {
+    (0..=6)
+        .flat_map(|start| (start..=6).map(move |end| Span::new(start, end)))
+        .collect()
+}
+
+#[test]
+fn contains_offset_excludes_the_end() {
+    for span in spans() {
+        for offset in 0..=7 {
+            assert_eq!(
+                span.contains_offset(offset),
+                (span.start..span.end).contains(&offset),
+                "{span:?} {offset}"
+            );
+        }
+
+        assert!(!span.contains_offset(span.end), "{span:?}");
+        assert_eq!(
+            span.contains_offset(span.start),
+            !span.is_empty(),
+            "{span:?}"
+        );
+    }
+}
+
+#[test]
+fn merge_is_commutative_and_covers_both() {
+    for a in spans() {
+        for b in spans() {
+            let merged = a.merge(&b);
+
+            assert_eq!(merged, b.merge(&a), "{a:?} {b:?}");
+            assert_eq!(merged.start, a.start.min(b.start));
+            assert_eq!(merged.end, a.end.max(b.end));
+        }
+
+        assert_eq!(a.merge(&a), a);
+    }
+}
+
+#[test]
+fn merge_is_associative() {
+    let spans = spans();
+
+    for a in &spans {
+        for b in &spans {
+            for c in &spans {
+                assert_eq!(a.merge(b).merge(c), a.merge(&b.merge(c)));
+            }
+        }
+    }
+}
+
+#[test]
+fn adjacency_is_symmetric_and_only_at_the_ends() {
+    for a in spans() {
+        for b in spans() {
+            assert_eq!(a.is_adjacent_to(&b), b.is_adjacent_to(&a), "{a:?} {b:?}");
+            assert_eq!(
+                a.is_adjacent_to(&b),
+                a.end == b.start || b.end == a.start,
+                "{a:?} {b:?}"
+            );
+
+            // Adjacent spans merge into one exactly as long as both
+            if a.is_adjacent_to(&b) && !a.is_empty() && !b.is_empty() {
+                assert_eq!(a.merge(&b).len(), a.len() + b.len(), "{a:?} {b:?}");
+            }
+        }
+    }
+
+    assert!(Span::new(0, 3).is_adjacent_to(&Span::new(3, 5)));
+    assert!(!Span::new(0, 3).is_adjacent_to(&Span::new(4, 5)));
+    assert!(!Span::new(0, 4).is_adjacent_to(&Span::new(3, 5)));
+}
@@ -0,0 +1,37 @@
+//! Checks that string literals are stored once per module as globals.
+
+use llvm_compiler::codegen::string_table::StringTable;
+use llvm_compiler::compiler::compile_to_llvm_ir;
+use llvm_compiler::source::SourceFile;
+
+#[test]
+fn identical_strings_share_a_global() {
+    let mut table = StringTable::new();
+
+    assert_eq!(table.intern("hello"), "@str.0");
+    assert_eq!(table.intern("world"), "@str.1");
+    assert_eq!(table.intern("hello"), "@str.0");
+    assert_eq!(table.len(), 2);
+}
+
+#[test]
+fn string_literals_are_emitted_as_globals() {
+    let source = SourceFile::new(
+        "strings.src",
+        "func first() -> *u8 { return \"hi\"; }
+func second() -> *u8 { let s: *u8 = \"say \\\"hi\\\"\\n\"; return \"hi\"; }
+",
+    );
+    let ir = compile_to_llvm_ir(&source).unwrap();
+
+    assert!(
+        ir.contains("@str.0 = private unnamed_addr constant [3 x i8] c\"hi\\00\""),
+        "{ir}"
+    );
+    assert!(
+        ir.contains("@str.1 = private unnamed_addr constant [10 x i8] c\"say \\22hi\\22\\0A\\00\""),
+        "{ir}"
+    );
+    assert_eq!(ir.matches("@str.0 =").count(), 1);
+    assert_eq!(ir.matches("[3 x i8]* @str.0").count(), 2);
+}
@@ -0,0 +1,54 @@
+//! Checks how struct declarations are parsed.
+
+use llvm_compiler::ast::{StructDecl, TypeExpr};
+use llvm_compiler::lexer::AsciiLexer;
+use llvm_compiler::parse::error::ParseError;
+use llvm_compiler::parse::parser::Parser;
+
+fn parse_struct(src: &str) -> Result<StructDecl, ParseError> {
+    let mut tokens = AsciiLexer::new().tokenize(src).unwrap();
+    tokens.retain(|token| !token.is_trivia());
+
+    Parser::new(0, &tokens).parse_struct_decl()
+}
+
+fn named(name: &str) -> TypeExpr {
+    TypeExpr::Named {
+        name: name.to_owned(),
+        generics: vec![],
+    }
+}
+
+#[test]
+fn fields_have_names_and_types() {
+    let decl = parse_struct("struct Point { x: f64, y: f64 }").unwrap();
+
+    assert_eq!(decl.name, "Point");
+    let fields: Vec<_> = decl
+        .fields
+        .iter()
+        .map(|field| (field.name.as_str(), &field.ty))
+        .collect();
+    assert_eq!(fields, [("x", &named("f64")), ("y", &named("f64"))]);
+}
+
+#[test]
+fn fields_can_be_separated_by_semicolons_and_end_with_either() {
+    let decl = parse_struct("struct Pair { a: i32; b: bool, }").unwrap();
+    assert_eq!(decl.fields.len(), 2);
+
+    let decl = parse_struct("struct Unit { }").unwrap();
+    assert!(decl.fields.is_empty());
+}
+
+#[test]
+fn keywords_are_not_struct_or_field_names() {
+    assert!(matches!(
+        parse_struct("struct while { }"),
+        Err(ParseError::ReservedName { name, .. }) if name == "while"
+    ));
+    assert!(matches!(
+        parse_struct("struct S { if: i32 }"),
+        Err(ParseError::ReservedName { name, .. }) if name == "if"
+    ));
+}
@@ -0,0 +1,86 @@
+//! Checks that a target's data layout is emitted and used to give sizes and alignments.
+
+use llvm_compiler::codegen::emitter::LlvmEmitter;
+use llvm_compiler::codegen::{DataLayout, Target};
+use llvm_compiler::compiler::check_source;
+use llvm_compiler::source::SourceFile;
+
+fn emit(text: &str, emitter: LlvmEmitter) -> String {
+    let module = check_source(&SourceFile::new("target.src", text)).unwrap();
+    let mut emitter = emitter;
+    emitter.emit_module(&module).unwrap();
+
+    emitter.finish()
+}
+
+const SIZES: &str = "struct Pair { a: u8, b: *u8 }
+let mut counter: i64;
+func pair_size() -> u64 { sizeof(Pair) }
+func pair_align() -> u64 { alignof(Pair) }
+";
+
+#[test]
+fn targets_are_written_at_the_top() {
+    let ir = emit(
+        SIZES,
+        LlvmEmitter::new().with_target(Target::X86_64_LINUX_GNU),
+    );
+
+    let layout = ir
+        .find(&format!(
+            "target datalayout = \"{}\"\n",
+            Target::X86_64_LINUX_GNU.data_layout
+        ))
+        .unwrap();
+    let triple = ir
+        .find("target triple = \"x86_64-unknown-linux-gnu\"\n")
+        .unwrap();
+    assert!(layout < triple && triple < ir.find("%Pair").unwrap());
+}
+
+#[test]
+fn sizes_come_from_the_layout() {
+    let ir = emit(
+        SIZES,
+        LlvmEmitter::new().with_target(Target::X86_64_LINUX_GNU),
+    );
+    assert!(ir.contains("ret i64 16"), "{ir}");
+    assert!(ir.contains("ret i64 8"), "{ir}");
+    assert!(ir.contains("@counter = global i64 zeroinitializer, align 8\n"));
+
+    let ir = emit(
+        SIZES,
+        LlvmEmitter::new().with_target(Target::WASM32_UNKNOWN_UNKNOWN),
+    );
+    assert!(ir.contains("ret i64 8"), "{ir}");
+    assert!(ir.contains("ret i64 4"), "{ir}");
+}
+
+#[test]
+fn sizes_are_left_to_llvm_without_a_layout() {
+    let ir = emit(SIZES, LlvmEmitter::new());
+
+    assert!(!ir.contains("target datalayout"));
+    assert!(ir.contains("getelementptr (%Pair, %Pair* null, i32 1)"));
+}
+
+#[test]
+fn layouts_fall_back_to_llvm_defaults() {
+    let layout = DataLayout::new("e-i64:64-n8:16:32:64");
+    let i64 = llvm_compiler::ast::TypeExpr::Named {
+        name: "i64".into(),
+        generics: vec![],
+    };
+    let i128 = llvm_compiler::ast::TypeExpr::Named {
+        name: "i128".into(),
+        generics: vec![],
+    };
+
+    assert_eq!(layout.align_of(&i64, &Default::default()), Some(8));
+    assert_eq!(layout.align_of(&i128, &Default::default()), Some(8));
+    assert_eq!(layout.size_of(&i128, &Default::default()), Some(16));
+    assert_eq!(
+        Target::from_triple("aarch64-apple-darwin"),
+        Some(Target::AARCH64_APPLE_DARWIN)
+    );
+}
@@ -0,0 +1,69 @@
+//! Checks the HTML written for token streams.
+
+use llvm_compiler::lexer::{token_stream_to_html, AsciiLexer, TOKEN_HTML_CSS};
+use llvm_compiler::source::SourceFile;
+use llvm_compiler::token;
+
+fn to_html(text: &str) -> String {
+    let tokens = AsciiLexer::new().tokenize(text).unwrap();
+    token_stream_to_html(&tokens, &SourceFile::new("test", text))
+}
+
+#[test]
+fn tokens_are_wrapped_by_kind() {
+    assert_eq!(
+        to_html("let x = \"a\";\n"),
+        "<span class=\"tok-keyword\">let</span> <span class=\"tok-identifier\">x</span> \
+         <span class=\"tok-operator\">=</span> \
+         <span class=\"tok-string-literal\">&quot;a&quot;</span>\
+         <span class=\"tok-punctuation\">;</span>\n"
+    );
+}
+
+#[test]
+fn token_text_is_escaped() {
+    let html = to_html("a < b && c > 1");
+
+    assert!(html.contains("<span class=\"tok-operator\">&lt;</span>"));
+    assert!(html.contains("<span class=\"tok-operator\">&gt;</span>"));
+    assert!(!html.contains("&&"));
+    assert!(html.contains("<span class=\"tok-int-literal\">1</span>"));
+}
+
+#[test]
+fn gaps_are_copied_from_the_source() {
+    let source = SourceFile::new("test", "a + b // <end>");
+    let tokens = [token!(Identifier("a") @ 0), token!(Identifier("b") @ 4)];
+
+    assert_eq!(
+        token_stream_to_html(&tokens, &source),
+        "<span class=\"tok-identifier\">a</span> + \
+         <span class=\"tok-identifier\">b</span> // &lt;end&gt;"
+    );
+}
+
+#[test]
+fn tokens_from_other_text_do_not_panic() {
+    let tokens = [token!(Identifier("far") @ 100)];
+    assert_eq!(
+        token_stream_to_html(&tokens, &SourceFile::new("test", "é")),
+        "é<span class=\"tok-identifier\">far</span>"
+    );
+
+    // `1` is inside the `é`, which takes two bytes
+    let tokens = [token!(Identifier("x") @ 1)];
+    assert_eq!(
+        token_stream_to_html(&tokens, &SourceFile::new("test", "é")),
+        "<span class=\"tok-identifier\">x</span>"
+    );
+}
+
+#[test]
+fn every_class_has_a_color() {
+    let html = to_html("func f(x: i32) -> bool { // c\n true }");
+
+    for class in html.split("class=\"").skip(1) {
+        let class = &class[..class.find('"').unwrap()];
+        assert!(TOKEN_HTML_CSS.contains(&format!(".{class}")), "{class}");
+    }
+}
@@ -0,0 +1,60 @@
+//! Checks where tokens are relative to each other, at the ends of their ranges.
+
+use llvm_compiler::token;
+use llvm_compiler::token::Token;
+
+/// An identifier covering `start..end`.
+fn ident(start: usize, end: usize) -> Token<'static> {
+    const NAME: &str = "abcdefghij";
+    token!(Identifier(&NAME[..end - start]) @ start)
+}
+
+#[test]
+fn touching_tokens_are_adjacent_without_overlapping() {
+    let (a, b) = (ident(0, 3), ident(3, 5));
+
+    assert!(a.before(&b));
+    assert!(!b.before(&a));
+    assert!(a.adjacent_to(&b) && b.adjacent_to(&a));
+    assert!(!a.overlaps(&b) && !b.overlaps(&a));
+}
+
+#[test]
+fn overlapping_tokens_are_neither_before_nor_adjacent() {
+    let (a, b) = (ident(0, 4), ident(3, 6));
+
+    assert!(!a.before(&b) && !b.before(&a));
+    assert!(!a.adjacent_to(&b));
+    assert!(a.overlaps(&b) && b.overlaps(&a));
+    assert!(a.overlaps(&a));
+
+    let inner = ident(1, 2);
+    assert!(a.overlaps(&inner) && inner.overlaps(&a));
+}
+
+#[test]
+fn disjoint_tokens_are_only_before() {
+    let (a, b) = (ident(0, 2), ident(4, 6));
+
+    assert!(a.before(&b));
+    assert!(!b.before(&a));
+    assert!(!a.adjacent_to(&b));
+    assert!(!a.overlaps(&b));
+}
+
+#[test]
+fn zero_length_tokens_touch_at_either_end() {
+    let token = ident(2, 5);
+    let (at_start, inside, at_end) = (ident(2, 2), ident(3, 3), ident(5, 5));
+
+    assert!(at_start.before(&token) && at_start.adjacent_to(&token));
+    assert!(token.before(&at_end) && token.adjacent_to(&at_end));
+    assert!(!at_start.overlaps(&token) && !at_end.overlaps(&token));
+
+    assert!(inside.overlaps(&token));
+    assert!(!inside.adjacent_to(&token));
+
+    // An empty token is before, and adjacent to, itself
+    assert!(inside.before(&inside) && inside.adjacent_to(&inside));
+    assert!(!inside.overlaps(&inside));
+}
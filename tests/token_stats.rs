@@ -0,0 +1,34 @@
+//! Checks the statistics gathered over token streams.
+
+use llvm_compiler::lexer::{token_stream_statistics, AsciiLexer};
+use llvm_compiler::source::SourceFile;
+use llvm_compiler::token::TokenCategory;
+
+#[test]
+fn statistics_count_tokens_names_and_nesting() {
+    let source = SourceFile::new(
+        "stats.src",
+        "func f(x: i32) -> i32 {\n    return g((x));\n}\n",
+    );
+    let tokens = AsciiLexer::new().tokenize(source.text()).unwrap();
+    let stats = token_stream_statistics(&tokens, &source);
+
+    assert_eq!(stats.total, tokens.len());
+    assert_eq!(stats.by_category.values().sum::<usize>(), tokens.len());
+    assert_eq!(stats.by_category[&TokenCategory::Type], 2);
+    assert_eq!(stats.by_category[&TokenCategory::Keyword], 2);
+    assert_eq!(stats.unique_identifiers, 3);
+    assert_eq!(stats.max_nesting, 3);
+    assert_eq!(stats.avg_line_length_chars, (23 + 18 + 1) as f64 / 3.0);
+}
+
+#[test]
+fn statistics_of_nothing_are_zero() {
+    let source = SourceFile::new("empty.src", "");
+    let stats = token_stream_statistics(&[], &source);
+
+    assert_eq!(stats.total, 0);
+    assert!(stats.by_category.is_empty());
+    assert_eq!(stats.max_nesting, 0);
+    assert_eq!(stats.avg_line_length_chars, 0.0);
+}
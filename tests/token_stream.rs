@@ -0,0 +1,22 @@
+//! Checks indexing and slicing token streams.
+
+use llvm_compiler::lexer::AsciiLexer;
+use llvm_compiler::token::{Token, TokenStream};
+
+#[test]
+fn sub_streams_index_from_their_own_start() {
+    let tokens = AsciiLexer::new().tokenize("a+b*c").unwrap();
+    let stream = TokenStream::from(tokens.as_slice());
+
+    assert_eq!(stream[2].as_str(), "b");
+
+    let rest = stream.slice(2..);
+    assert_eq!(rest.len(), 3);
+    assert_eq!(rest[0].as_str(), "b");
+
+    let middle = rest.slice(..=1);
+    let text: Vec<_> = middle.iter().map(Token::as_str).collect();
+    assert_eq!(text, ["b", "*"]);
+    assert!(middle.slice(2..).is_empty());
+    assert!(middle.get(2).is_none());
+}
@@ -0,0 +1,38 @@
+//! Checks that `TokenType::ALL_VARIANTS` lists every token type once, in declaration order, and
+//! that types convert to and from their numbers.
+
+use std::collections::HashSet;
+
+use llvm_compiler::token::TokenType;
+
+#[test]
+fn all_variants_are_listed_in_order() {
+    for (idx, ty) in TokenType::all_variants().enumerate() {
+        assert_eq!(ty as usize, idx, "{ty:?} is out of place");
+    }
+
+    let names: HashSet<_> = TokenType::all_variants().map(|ty| ty.type_name()).collect();
+    assert_eq!(names.len(), TokenType::ALL_VARIANTS.len());
+}
+
+#[test]
+fn spellings_parse_back_to_their_type() {
+    for ty in TokenType::all_variants() {
+        let Some(spelling) = ty.spelling() else {
+            continue;
+        };
+
+        assert_eq!(spelling.parse::<TokenType>().ok(), Some(ty), "{spelling:?}");
+    }
+}
+
+#[test]
+fn types_convert_to_and_from_bytes() {
+    for ty in TokenType::all_variants() {
+        assert_eq!(TokenType::try_from(u8::from(ty)), Ok(ty));
+    }
+
+    let past_the_end = u8::try_from(TokenType::ALL_VARIANTS.len()).unwrap();
+    assert_eq!(TokenType::try_from(past_the_end), Err(()));
+    assert_eq!(TokenType::try_from(u8::MAX), Err(()));
+}
@@ -0,0 +1,83 @@
+//! Checks how trait declarations and impl blocks are parsed.
+
+use llvm_compiler::ast::{ImplBlock, ImplItem, TraitDecl, TraitItem, TypeExpr};
+use llvm_compiler::lexer::AsciiLexer;
+use llvm_compiler::parse::error::ParseError;
+use llvm_compiler::parse::parser::Parser;
+
+fn with_parser<T>(src: &str, parse: impl FnOnce(&mut Parser) -> T) -> T {
+    let mut tokens = AsciiLexer::new().tokenize(src).unwrap();
+    tokens.retain(|token| !token.is_trivia());
+
+    parse(&mut Parser::new(0, &tokens))
+}
+
+fn parse_trait(src: &str) -> Result<TraitDecl, ParseError> {
+    with_parser(src, |parser| parser.parse_trait_decl())
+}
+
+fn parse_impl(src: &str) -> Result<ImplBlock, ParseError> {
+    with_parser(src, |parser| parser.parse_impl_block())
+}
+
+#[test]
+fn traits_hold_signatures_and_default_functions() {
+    let decl = parse_trait(
+        "trait Shape {
+    func area(self: Self) -> f64;
+    func describe(self: Self) -> i32 { 0 }
+}",
+    )
+    .unwrap();
+
+    assert_eq!(decl.name, "Shape");
+    let [TraitItem::FnSignature(sig), TraitItem::DefaultFn(default)] = &decl.items[..] else {
+        panic!("expected a signature then a default, got {:?}", decl.items);
+    };
+    assert_eq!(sig.name, "area");
+    assert_eq!(sig.ret, Some(TypeExpr::named("f64")));
+    assert_eq!(default.name, "describe");
+    assert!(default.body.final_expr.is_some());
+}
+
+#[test]
+fn traits_can_be_generic_and_empty() {
+    let decl = parse_trait("trait From<T> {}").unwrap();
+
+    assert!(!decl.generic_params.is_empty());
+    assert!(decl.items.is_empty());
+}
+
+#[test]
+fn inherent_impls_have_no_trait() {
+    let block = parse_impl("impl Point { func zero() -> i32 { 0 } }").unwrap();
+
+    assert_eq!(block.trait_path, None);
+    assert_eq!(block.for_type, TypeExpr::named("Point"));
+    let [ImplItem::Fn(decl)] = &block.items[..] else {
+        panic!("expected one function");
+    };
+    assert_eq!(decl.name, "zero");
+}
+
+#[test]
+fn trait_impls_name_the_trait_and_type() {
+    let block = parse_impl("impl<T> shapes::Show<T> for Wrapper<T> {}").unwrap();
+
+    assert!(!block.generic_params.is_empty());
+    assert_eq!(
+        block.trait_path,
+        Some(vec!["shapes".to_owned(), "Show".to_owned()])
+    );
+    assert_eq!(block.trait_generics.len(), 1);
+    assert!(matches!(
+        &block.for_type,
+        TypeExpr::Named { name, generics } if name == "Wrapper" && generics.len() == 1
+    ));
+}
+
+#[test]
+fn trait_bodies_only_hold_functions() {
+    assert!(parse_trait("trait Shape { let x = 1; }").is_err());
+    assert!(parse_impl("impl Point { let x = 1; }").is_err());
+}
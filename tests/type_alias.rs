@@ -0,0 +1,87 @@
+//! Checks parsing type aliases and replacing them with the types they stand for.
+
+use llvm_compiler::ast::{Item, TypeExpr};
+use llvm_compiler::ast_printer::print_sexp;
+use llvm_compiler::compiler::error::CompilerError;
+use llvm_compiler::compiler::{check_source, compile_to_llvm_ir, parse_source};
+use llvm_compiler::source::SourceFile;
+use llvm_compiler::typeck::error::TypeError;
+
+#[test]
+fn aliases_parse_with_their_target() {
+    let source = SourceFile::new(
+        "alias.src",
+        "type Bytes = [u8; 1024];\ntype Ptr<T> = *mut T;\n",
+    );
+    let module = parse_source(&source).unwrap();
+
+    let Item::TypeAlias(alias) = &module.items[0] else {
+        panic!("expected a type alias");
+    };
+    assert_eq!(alias.name, "Bytes");
+    assert!(alias.generic_params.is_empty());
+    assert_eq!(
+        alias.target,
+        TypeExpr::Array {
+            element: Box::new(TypeExpr::named("u8")),
+            size: Some(1024),
+        }
+    );
+
+    assert_eq!(
+        print_sexp(&module.items[1]),
+        "(type-alias Ptr (generics T) (ptr-mut T))"
+    );
+}
+
+#[test]
+fn aliases_are_replaced_before_type_checking() {
+    let source = SourceFile::new(
+        "alias.src",
+        "type Int = i32;
+type Row<T> = [T; 2];
+type Grid<T> = [Row<T>; 3];
+func pick(grid: Grid<Int>, i: i64) -> Int { grid[i][1] }
+func main() -> u64 { sizeof(Grid<Int>) }
+",
+    );
+    let module = check_source(&source).unwrap();
+
+    let first = &module.functions[0];
+    let row = TypeExpr::Array {
+        element: Box::new(TypeExpr::named("i32")),
+        size: Some(2),
+    };
+    assert_eq!(
+        first.params[0].ty,
+        TypeExpr::Array {
+            element: Box::new(row),
+            size: Some(3),
+        }
+    );
+    assert_eq!(first.ret, TypeExpr::named("i32"));
+
+    let ir = compile_to_llvm_ir(&source).unwrap();
+    assert!(!ir.contains("Int"));
+    assert!(ir.contains("getelementptr ([3 x [2 x i32]], [3 x [2 x i32]]* null, i32 1)"));
+}
+
+#[test]
+fn aliases_using_themselves_are_reported() {
+    let source = SourceFile::new(
+        "alias.src",
+        "type A = *B;\ntype B = [A; 2];\ntype C = A;\nfunc main() {}\n",
+    );
+
+    let Err(CompilerError::Types { errors }) = check_source(&source) else {
+        panic!("expected type errors");
+    };
+    let names: Vec<_> = errors
+        .iter()
+        .map(|err| match err {
+            TypeError::CyclicTypeAlias { name, .. } => name.as_str(),
+            err => panic!("unexpected error {err:?}"),
+        })
+        .collect();
+    assert_eq!(names, ["A", "B"]);
+}
@@ -0,0 +1,92 @@
+//! Checks how type expressions are parsed.
+
+use llvm_compiler::ast::TypeExpr;
+use llvm_compiler::lexer::AsciiLexer;
+use llvm_compiler::parse::error::ParseError;
+use llvm_compiler::parse::parser::Parser;
+
+fn parse_type(src: &str) -> Result<TypeExpr, ParseError> {
+    let mut tokens = AsciiLexer::new().tokenize(src).unwrap();
+    tokens.retain(|token| !token.is_trivia());
+
+    Parser::new(0, &tokens).parse_type_expr()
+}
+
+fn named(name: &str, generics: Vec<TypeExpr>) -> TypeExpr {
+    TypeExpr::Named {
+        name: name.to_owned(),
+        generics,
+    }
+}
+
+#[test]
+fn pointers_can_be_mutable() {
+    assert_eq!(
+        parse_type("*mut i32").unwrap(),
+        TypeExpr::Pointer {
+            inner: Box::new(named("i32", vec![])),
+            mutable: true,
+        }
+    );
+    assert!(matches!(
+        parse_type("*u8").unwrap(),
+        TypeExpr::Pointer { mutable: false, .. }
+    ));
+}
+
+#[test]
+fn arrays_have_an_optional_size() {
+    assert_eq!(
+        parse_type("[i32; 10]").unwrap(),
+        TypeExpr::Array {
+            element: Box::new(named("i32", vec![])),
+            size: Some(10),
+        }
+    );
+    assert!(matches!(
+        parse_type("[i32]").unwrap(),
+        TypeExpr::Array { size: None, .. }
+    ));
+    assert!(matches!(
+        parse_type("[i32; n]"),
+        Err(ParseError::ExpectedArraySize { .. })
+    ));
+}
+
+#[test]
+fn function_types_have_params_and_a_return_type() {
+    assert_eq!(
+        parse_type("func(i32) -> bool").unwrap(),
+        TypeExpr::FnType {
+            params: vec![named("i32", vec![])],
+            ret: Box::new(named("bool", vec![])),
+        }
+    );
+}
+
+#[test]
+fn generics_nest_without_splitting_closing_angles() {
+    assert_eq!(
+        parse_type("Vec<HashMap<String, i32>>").unwrap(),
+        named(
+            "Vec",
+            vec![named(
+                "HashMap",
+                vec![named("String", vec![]), named("i32", vec![])]
+            )]
+        )
+    );
+}
+
+#[test]
+fn tuples_and_never() {
+    assert_eq!(
+        parse_type("(i32, bool)").unwrap(),
+        TypeExpr::Tuple(vec![named("i32", vec![]), named("bool", vec![])])
+    );
+    assert_eq!(parse_type("!").unwrap(), TypeExpr::Never);
+    assert!(matches!(
+        parse_type("1"),
+        Err(ParseError::ExpectedType { .. })
+    ));
+}
@@ -0,0 +1,52 @@
+//! Checks how `use` declarations are parsed.
+
+use llvm_compiler::ast::UseDecl;
+use llvm_compiler::lexer::AsciiLexer;
+use llvm_compiler::parse::error::ParseError;
+use llvm_compiler::parse::parser::Parser;
+
+fn parse_use(src: &str) -> Result<UseDecl, ParseError> {
+    let mut tokens = AsciiLexer::new().tokenize(src).unwrap();
+    tokens.retain(|token| !token.is_trivia());
+
+    Parser::new(0, &tokens).parse_use_decl()
+}
+
+#[test]
+fn plain_paths_are_split_into_segments() {
+    let decl = parse_use("use std::io::File;").unwrap();
+
+    assert_eq!(decl.path, ["std", "io", "File"]);
+    assert_eq!(decl.alias, None);
+}
+
+#[test]
+fn aliases_follow_as() {
+    let decl = parse_use("use std::io::File as F;").unwrap();
+
+    assert_eq!(decl.path, ["std", "io", "File"]);
+    assert_eq!(decl.alias.as_deref(), Some("F"));
+}
+
+#[test]
+fn globs_end_the_path() {
+    let decl = parse_use("use std::io::*;").unwrap();
+    assert_eq!(decl.path, ["std", "io", "*"]);
+
+    assert!(matches!(
+        parse_use("use std::*::io;"),
+        Err(ParseError::InvalidUsePath { found: Some(found), .. }) if found == "::"
+    ));
+}
+
+#[test]
+fn paths_only_hold_names() {
+    assert!(matches!(
+        parse_use("use std::1;"),
+        Err(ParseError::InvalidUsePath { found: Some(found), .. }) if found == "1"
+    ));
+    assert!(matches!(
+        parse_use("use *;"),
+        Err(ParseError::InvalidUsePath { .. })
+    ));
+}
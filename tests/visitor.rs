@@ -0,0 +1,30 @@
+//! Checks that a visitor overriding a couple of methods still reaches every nested node.
+
+use llvm_compiler::ast::visitor::{AstVisitor, UnusedVarVisitor};
+use llvm_compiler::compiler::parse_source;
+use llvm_compiler::source::SourceFile;
+
+#[test]
+fn unused_let_bindings_are_found() {
+    let source = SourceFile::new(
+        "visitor.src",
+        "func main() -> i32 {
+    let used = 1;
+    let unused = 2;
+    let _ignored = 3;
+    while used > 0 {
+        let in_loop = |x: i32| if x > 0 { used } else { 0 };
+        let also_unused = in_loop(used);
+    }
+    match used { 1 => { let in_arm = 4; 1 }, _ => 0 }
+}
+",
+    );
+    let module = parse_source(&source).unwrap();
+
+    let mut visitor = UnusedVarVisitor::new();
+    visitor.visit_module(&module);
+
+    let names: Vec<_> = visitor.unused().into_iter().map(|(name, _)| name).collect();
+    assert_eq!(names, ["unused", "also_unused", "in_arm"]);
+}
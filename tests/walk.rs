@@ -0,0 +1,75 @@
+//! Checks that the closure-based walks reach the same nodes as a visitor, in pre-order.
+
+use llvm_compiler::ast::walk::{walk_exprs, walk_module, walk_stmts};
+use llvm_compiler::ast::{ExprNode, Module, StmtNode};
+use llvm_compiler::ast_printer::print_sexp;
+use llvm_compiler::compiler::parse_source;
+use llvm_compiler::source::SourceFile;
+
+fn parse(src: &str) -> Module {
+    parse_source(&SourceFile::new("walk.src", src)).unwrap()
+}
+
+#[test]
+fn nodes_are_walked_before_their_children() {
+    let module = parse("func f(x: i32) -> i32 { return -x; }");
+
+    let mut nodes = Vec::new();
+    walk_module(&module, &mut |node| nodes.push(print_sexp(node)));
+
+    assert_eq!(nodes[0], print_sexp(&module));
+    assert_eq!(nodes[1], print_sexp(&module.items[0]));
+    assert_eq!(
+        nodes[nodes.len() - 2..],
+        ["(- (ident x))".to_owned(), "(ident x)".to_owned()]
+    );
+}
+
+#[test]
+fn expressions_are_walked_inside_closures_and_loops() {
+    let module = parse(
+        "func main() -> i32 {
+    let add = |a: i32| f(a);
+    while g() > 0 {
+        h(add(1));
+    }
+    return 0;
+}
+",
+    );
+
+    let mut calls = Vec::new();
+    walk_exprs(&module, &mut |expr| {
+        if let ExprNode::Call { .. } = expr {
+            calls.push(print_sexp(expr));
+        }
+    });
+
+    assert_eq!(calls.len(), 4);
+    assert!(calls[2].starts_with("(call (ident h)"));
+}
+
+#[test]
+fn statements_are_walked_inside_nested_blocks() {
+    let module = parse(
+        "func main() -> i32 {
+    let x = 1;
+    if x > 0 {
+        let y = 2;
+    }
+    return x;
+}
+",
+    );
+
+    let mut lets = Vec::new();
+    let mut returns = 0;
+    walk_stmts(&module, &mut |stmt| match stmt {
+        StmtNode::Let(stmt) => lets.push(stmt.name.clone()),
+        StmtNode::Return(_) => returns += 1,
+        _ => {}
+    });
+
+    assert_eq!(lets, ["x", "y"]);
+    assert_eq!(returns, 1);
+}
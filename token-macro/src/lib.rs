@@ -17,8 +17,8 @@ pub fn token_derive(_tokens: TokenStream) -> TokenStream {
 
     let mut word_idents: Vec<proc_macro2::TokenStream> = vec![];
     let mut words: Vec<proc_macro2::TokenStream> = vec![];
-    let mut char_idents: Vec<proc_macro2::TokenStream> = vec![];
-    let mut chars: Vec<proc_macro2::TokenStream> = vec![];
+    let _char_idents: Vec<proc_macro2::TokenStream> = vec![];
+    let _chars: Vec<proc_macro2::TokenStream> = vec![];
 
     let mut pairs: Vec<proc_macro2::TokenStream> = vec![];
 